@@ -1,4 +1,5 @@
 #[derive(Debug, Clone, serde::Deserialize)]
+#[serde(default)]
 pub struct AppliationContextProperties {
     allow_override: bool,
 }
@@ -0,0 +1,263 @@
+use crate::util::path_matcher::PathMatcher;
+
+/// 跨域配置属性
+///
+/// 对应 `next.server.http.cors` 前缀。
+///
+/// # Cors Properties
+///
+/// Bound to the `next.server.http.cors` prefix, the keys of [`CorsConfiguration`] are
+/// the defaults applied to `/**`, `mappings` configure single paths and inherit
+/// every key they don't set from the defaults.
+///
+/// Without this section and without a `CorsConfigurer` no cors layer is installed, cross
+/// origin requests then get no `Access-Control-*` headers.
+///
+/// ```yaml
+/// next:
+///   server:
+///     http:
+///       cors:
+///         allowed_origins: ["https://*.example.com"]
+///         allow_credentials: true
+///         mappings:
+///           - path: /public/**
+///             allowed_origins: ["*"]
+///             allow_credentials: false
+/// ```
+#[derive(Debug, Clone, Default, serde::Deserialize)]
+pub struct CorsProperties {
+    #[serde(flatten)]
+    defaults: CorsConfiguration,
+
+    /// 按路径配置的跨域规则，按顺序匹配
+    ///
+    /// Per path configurations, the first matching path wins.
+    mappings: Option<Vec<CorsMapping>>,
+}
+
+impl CorsProperties {
+    pub fn defaults(&self) -> &CorsConfiguration {
+        &self.defaults
+    }
+
+    pub fn mappings(&self) -> &[CorsMapping] {
+        self.mappings.as_deref().unwrap_or_default()
+    }
+}
+
+/// A path pattern together with its configuration, see [`PathMatcher`] for the pattern syntax.
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct CorsMapping {
+    path: String,
+
+    #[serde(flatten)]
+    configuration: CorsConfiguration,
+}
+
+impl CorsMapping {
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    pub fn configuration(&self) -> &CorsConfiguration {
+        &self.configuration
+    }
+}
+
+/// 跨域配置
+///
+/// # Cors Configuration
+///
+/// Origins are compared ignoring case, a pattern may contain `*`, e.g. `https://*.example.com`,
+/// a single `*` allows every origin but cannot be combined with `allow_credentials`.
+///
+/// Nothing is allowed until `allowed_origins` is set, the other keys default to
+/// methods `GET, HEAD, POST`, every request header, no exposed headers,
+/// no credentials and a max age of `1800` seconds.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Deserialize)]
+pub struct CorsConfiguration {
+    allowed_origins: Option<Vec<String>>,
+    allowed_methods: Option<Vec<String>>,
+    allowed_headers: Option<Vec<String>>,
+    exposed_headers: Option<Vec<String>>,
+    allow_credentials: Option<bool>,
+
+    /// from_secs
+    max_age: Option<u64>,
+}
+
+impl CorsConfiguration {
+    const DEFAULT_METHODS: &'static [&'static str] = &["GET", "HEAD", "POST"];
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_allowed_origins<T: ToString>(mut self, origins: impl IntoIterator<Item = T>) -> Self {
+        self.allowed_origins = Some(origins.into_iter().map(|item| item.to_string()).collect());
+        self
+    }
+
+    pub fn with_allowed_methods<T: ToString>(mut self, methods: impl IntoIterator<Item = T>) -> Self {
+        self.allowed_methods = Some(methods.into_iter().map(|item| item.to_string()).collect());
+        self
+    }
+
+    pub fn with_allowed_headers<T: ToString>(mut self, headers: impl IntoIterator<Item = T>) -> Self {
+        self.allowed_headers = Some(headers.into_iter().map(|item| item.to_string()).collect());
+        self
+    }
+
+    pub fn with_exposed_headers<T: ToString>(mut self, headers: impl IntoIterator<Item = T>) -> Self {
+        self.exposed_headers = Some(headers.into_iter().map(|item| item.to_string()).collect());
+        self
+    }
+
+    pub fn with_allow_credentials(mut self, allow_credentials: bool) -> Self {
+        self.allow_credentials = Some(allow_credentials);
+        self
+    }
+
+    pub fn with_max_age(mut self, max_age: u64) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// Fill the keys that are not set with the ones of `defaults`.
+    pub fn merge(&self, defaults: &CorsConfiguration) -> Self {
+        Self {
+            allowed_origins: self.allowed_origins.clone().or_else(|| defaults.allowed_origins.clone()),
+            allowed_methods: self.allowed_methods.clone().or_else(|| defaults.allowed_methods.clone()),
+            allowed_headers: self.allowed_headers.clone().or_else(|| defaults.allowed_headers.clone()),
+            exposed_headers: self.exposed_headers.clone().or_else(|| defaults.exposed_headers.clone()),
+            allow_credentials: self.allow_credentials.or(defaults.allow_credentials),
+            max_age: self.max_age.or(defaults.max_age),
+        }
+    }
+
+    pub fn allowed_origins(&self) -> &[String] {
+        self.allowed_origins.as_deref().unwrap_or_default()
+    }
+
+    pub fn allowed_methods(&self) -> Vec<&str> {
+        match self.allowed_methods.as_ref() {
+            Some(methods) => methods.iter().map(String::as_str).collect(),
+            None => Self::DEFAULT_METHODS.to_vec(),
+        }
+    }
+
+    pub fn allowed_headers(&self) -> Vec<&str> {
+        match self.allowed_headers.as_ref() {
+            Some(headers) => headers.iter().map(String::as_str).collect(),
+            None => vec!["*"],
+        }
+    }
+
+    pub fn exposed_headers(&self) -> &[String] {
+        self.exposed_headers.as_deref().unwrap_or_default()
+    }
+
+    pub fn allow_credentials(&self) -> bool {
+        self.allow_credentials.unwrap_or(false)
+    }
+
+    pub fn max_age(&self) -> u64 {
+        self.max_age.unwrap_or(1800)
+    }
+
+    /// Returns the value of `Access-Control-Allow-Origin` for the request origin,
+    /// `None` if the origin is not allowed.
+    pub fn check_origin(&self, origin: &str) -> Option<String> {
+        let origin = origin.trim_end_matches('/');
+        let lowercase = origin.to_ascii_lowercase();
+
+        for pattern in self.allowed_origins() {
+            let pattern = pattern.trim_end_matches('/').to_ascii_lowercase();
+            if pattern == "*" {
+                if self.allow_credentials() {
+                    continue;
+                }
+                return Some("*".to_string());
+            }
+            if PathMatcher::wildcard_match(&pattern, &lowercase) {
+                return Some(origin.to_string());
+            }
+        }
+        None
+    }
+
+    /// Whether the method, e.g. of `Access-Control-Request-Method`, is allowed.
+    pub fn check_method(&self, method: &str) -> bool {
+        self.allowed_methods()
+            .iter()
+            .any(|allowed| *allowed == "*" || allowed.eq_ignore_ascii_case(method))
+    }
+
+    /// Returns the requested headers when all of them are allowed, `None` otherwise.
+    pub fn check_headers<'a>(&self, headers: &[&'a str]) -> Option<Vec<&'a str>> {
+        let allowed = self.allowed_headers();
+        if allowed.contains(&"*") {
+            return Some(headers.to_vec());
+        }
+
+        headers
+            .iter()
+            .all(|header| allowed.iter().any(|item| item.eq_ignore_ascii_case(header)))
+            .then(|| headers.to_vec())
+    }
+
+    /// Check the combination of the keys.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.allow_credentials() && self.allowed_origins().iter().any(|origin| origin == "*") {
+            return Err(
+                "allowed_origins cannot contain '*' when allow_credentials is true, list the origins or use a pattern like 'https://*.example.com'"
+                    .to_string(),
+            );
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_origin() {
+        let configuration = CorsConfiguration::new()
+            .with_allowed_origins(["https://*.example.com", "http://localhost:8080"])
+            .with_allow_credentials(true);
+
+        assert_eq!(
+            configuration.check_origin("https://App.Example.com"),
+            Some("https://App.Example.com".to_string())
+        );
+        assert!(configuration.check_origin("http://localhost:8080").is_some());
+        assert!(configuration.check_origin("https://example.com.evil.io").is_none());
+        assert!(configuration.validate().is_ok());
+
+        let any = CorsConfiguration::new().with_allowed_origins(["*"]);
+        assert_eq!(any.check_origin("https://evil.io"), Some("*".to_string()));
+        assert!(any.clone().with_allow_credentials(true).validate().is_err());
+        assert!(CorsConfiguration::new().check_origin("https://example.com").is_none());
+    }
+
+    #[test]
+    fn test_merge_and_defaults() {
+        let properties: CorsProperties = serde_yaml::from_str(
+            "{ allowed_origins: ['https://example.com'], max_age: 60, mappings: [{ path: /api/**, allowed_methods: ['*'] }] }",
+        )
+        .unwrap();
+
+        let mapping = properties.mappings()[0].configuration().merge(properties.defaults());
+        assert_eq!(mapping.allowed_origins(), &["https://example.com".to_string()]);
+        assert_eq!(mapping.max_age(), 60);
+        assert!(mapping.check_method("DELETE"));
+        assert!(!properties.defaults().check_method("DELETE"));
+        assert_eq!(
+            properties.defaults().check_headers(&["X-Token"]),
+            Some(vec!["X-Token"])
+        );
+    }
+}
@@ -1,7 +1,10 @@
+use super::cors_properties::CorsProperties;
+
 #[derive(Debug, serde::Deserialize, Clone)]
 pub struct HttpProperties {
     request: Option<RequestProperties>,
     response: Option<ResponseProperties>,
+    cors: Option<CorsProperties>,
 }
 
 impl HttpProperties {
@@ -9,6 +12,7 @@ impl HttpProperties {
         Self {
             request: None,
             response: None,
+            cors: None,
         }
     }
     pub fn request(&self) -> Option<&RequestProperties> {
@@ -18,6 +22,10 @@ impl HttpProperties {
     pub fn response(&self) -> Option<&ResponseProperties> {
         self.response.as_ref()
     }
+
+    pub fn cors(&self) -> Option<&CorsProperties> {
+        self.cors.as_ref()
+    }
 }
 
 #[derive(Debug, serde::Deserialize, Clone)]
@@ -65,7 +73,211 @@ impl RequestProperties {
     }
 }
 
+/// 响应配置属性
+///
+/// 对应 `next.server.http.response` 前缀。
+///
+/// # Response Properties
+///
+/// Bound to the `next.server.http.response` prefix, every feature is off until configured.
+///
+/// ```yaml
+/// next:
+///   server:
+///     http:
+///       response:
+///         compression:
+///           min_size: 1024
+///         security_headers: {}
+///         server_header: next-web
+///         etag: true
+///         response_time: true
+/// ```
 #[derive(Debug, serde::Deserialize, Clone)]
-pub struct ResponseProperties {}
+pub struct ResponseProperties {
+    compression: Option<CompressionProperties>,
+    security_headers: Option<SecurityHeadersProperties>,
+
+    /// `Server` 响应头
+    ///
+    /// Value of the `Server` header, replaces the one set by handlers.
+    server_header: Option<String>,
+
+    /// 是否生成 ETag 并处理 `If-None-Match`
+    ///
+    /// Whether an `ETag` is generated for `GET` and `HEAD` responses and `If-None-Match`
+    /// is answered with `304`, defaults to `false`.
+    etag: Option<bool>,
+
+    /// 是否添加响应耗时头
+    ///
+    /// Whether the handling time is added as `response_time_header`, defaults to `false`.
+    response_time: Option<bool>,
+    response_time_header: Option<String>,
+}
+
+impl ResponseProperties {
+    pub fn new() -> Self {
+        Self {
+            compression: None,
+            security_headers: None,
+            server_header: None,
+            etag: None,
+            response_time: None,
+            response_time_header: None,
+        }
+    }
+
+    /// The compression settings, `None` when disabled.
+    pub fn compression(&self) -> Option<&CompressionProperties> {
+        self.compression.as_ref().filter(|compression| compression.enabled())
+    }
+
+    /// The security headers settings, `None` when disabled.
+    pub fn security_headers(&self) -> Option<&SecurityHeadersProperties> {
+        self.security_headers.as_ref().filter(|headers| headers.enabled())
+    }
+
+    pub fn server_header(&self) -> Option<&str> {
+        self.server_header.as_deref()
+    }
+
+    pub fn etag(&self) -> bool {
+        self.etag.unwrap_or(false)
+    }
+
+    pub fn response_time(&self) -> bool {
+        self.response_time.unwrap_or(false)
+    }
+
+    pub fn response_time_header(&self) -> &str {
+        self.response_time_header.as_deref().unwrap_or("X-Response-Time")
+    }
+}
+
+/// 响应压缩配置
+///
+/// Response compression, enabled once the block is present.
+#[derive(Debug, serde::Deserialize, Clone)]
+pub struct CompressionProperties {
+    enabled: Option<bool>,
+
+    /// 可用算法，默认为 `gzip, br, zstd`
+    ///
+    /// Algorithms offered to clients, defaults to `gzip, br, zstd`.
+    algorithms: Option<Vec<CompressionAlgorithm>>,
+
+    /// 最小压缩大小 (字节)，默认为 `2048`
+    ///
+    /// Responses smaller than this are not compressed, in bytes, defaults to `2048`.
+    min_size: Option<u32>,
 
-impl ResponseProperties {}
+    /// 可压缩的 MIME 类型，支持 `text/*`
+    ///
+    /// Compressed content types, `text/*` matches every subtype.
+    mime_types: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CompressionAlgorithm {
+    Gzip,
+    Br,
+    Zstd,
+}
+
+impl CompressionProperties {
+    const DEFAULT_MIME_TYPES: &'static [&'static str] = &[
+        "text/html",
+        "text/plain",
+        "text/css",
+        "text/xml",
+        "text/javascript",
+        "application/javascript",
+        "application/json",
+        "application/xml",
+        "image/svg+xml",
+    ];
+
+    pub fn enabled(&self) -> bool {
+        self.enabled.unwrap_or(true)
+    }
+
+    pub fn algorithms(&self) -> Vec<CompressionAlgorithm> {
+        self.algorithms.clone().unwrap_or_else(|| {
+            vec![
+                CompressionAlgorithm::Gzip,
+                CompressionAlgorithm::Br,
+                CompressionAlgorithm::Zstd,
+            ]
+        })
+    }
+
+    pub fn min_size(&self) -> u32 {
+        self.min_size.unwrap_or(2048)
+    }
+
+    pub fn mime_types(&self) -> Vec<&str> {
+        match self.mime_types.as_ref() {
+            Some(mime_types) => mime_types.iter().map(String::as_str).collect(),
+            None => Self::DEFAULT_MIME_TYPES.to_vec(),
+        }
+    }
+}
+
+/// 默认安全响应头
+///
+/// Security headers added to every response that doesn't set them, enabled once the block is present.
+/// Set a header to an empty string to leave it out.
+#[derive(Debug, serde::Deserialize, Clone)]
+pub struct SecurityHeadersProperties {
+    enabled: Option<bool>,
+
+    /// Defaults to `nosniff`.
+    content_type_options: Option<String>,
+
+    /// Defaults to `DENY`.
+    frame_options: Option<String>,
+
+    /// Defaults to `0`, the auditor of old browsers causes more harm than good.
+    xss_protection: Option<String>,
+
+    /// Defaults to `no-referrer`.
+    referrer_policy: Option<String>,
+
+    /// `Strict-Transport-Security`, not set by default.
+    hsts: Option<String>,
+
+    /// `Content-Security-Policy`, not set by default.
+    content_security_policy: Option<String>,
+}
+
+impl SecurityHeadersProperties {
+    pub fn enabled(&self) -> bool {
+        self.enabled.unwrap_or(true)
+    }
+
+    /// The header names and values to add, empty values are left out.
+    pub fn headers(&self) -> Vec<(&'static str, &str)> {
+        [
+            (
+                "x-content-type-options",
+                self.content_type_options.as_deref().unwrap_or("nosniff"),
+            ),
+            ("x-frame-options", self.frame_options.as_deref().unwrap_or("DENY")),
+            ("x-xss-protection", self.xss_protection.as_deref().unwrap_or("0")),
+            (
+                "referrer-policy",
+                self.referrer_policy.as_deref().unwrap_or("no-referrer"),
+            ),
+            ("strict-transport-security", self.hsts.as_deref().unwrap_or_default()),
+            (
+                "content-security-policy",
+                self.content_security_policy.as_deref().unwrap_or_default(),
+            ),
+        ]
+        .into_iter()
+        .filter(|(_, value)| !value.is_empty())
+        .collect()
+    }
+}
@@ -0,0 +1,142 @@
+use serde_yaml::Value;
+
+/// 管理端点配置属性
+///
+/// 对应 `next.management` 前缀，用于控制 `/actuator/*` 管理端点。
+///
+/// # Management Properties
+///
+/// Bound to the `next.management` prefix, controls the `/actuator/*` management endpoints.
+///
+/// ```yaml
+/// next:
+///   management:
+///     base_path: /actuator
+///     port: 8081
+///     endpoints: [health, info, env]
+///     show_details: true
+///     health_timeout: 5000
+///     info:
+///       team: platform
+/// ```
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct ManagementProperties {
+    /// 是否启用管理端点
+    ///
+    /// Whether the management endpoints are enabled, defaults to `true`.
+    enabled: Option<bool>,
+
+    /// 管理端点的基础路径
+    ///
+    /// Base path of the management endpoints, defaults to `/actuator`.
+    base_path: Option<String>,
+
+    /// 管理端点的独立端口，不设置时与应用共用端口
+    ///
+    /// Separate port for the management endpoints, shares the server port when not set.
+    port: Option<u16>,
+
+    /// 独立端口绑定的地址
+    ///
+    /// Address the separate management port binds to, defaults to the server address.
+    addr: Option<String>,
+
+    /// 暴露的端点，`*` 表示全部
+    ///
+    /// Exposed endpoints, `*` exposes all of them. Defaults to `health` and `info`.
+    /// Available: health | info | env | beans | mappings | scheduledtasks
+    endpoints: Option<Vec<String>>,
+
+    /// 健康检查是否展示各组件详情
+    ///
+    /// Whether `/health` shows the details of every component, defaults to `false`.
+    show_details: Option<bool>,
+
+    /// 单个健康检查的超时时间（毫秒）
+    ///
+    /// Time in milliseconds a single health indicator may take before it is reported `DOWN`,
+    /// defaults to `5000`.
+    health_timeout: Option<u64>,
+
+    /// 是否暴露 `/livez` 与 `/readyz` 探针
+    ///
+    /// Whether the `/livez` and `/readyz` probes are exposed on the server port, defaults to `true`.
+    probes: Option<bool>,
+
+    /// 额外需要脱敏的属性关键字
+    ///
+    /// Additional key fragments whose values are masked by `/env`.
+    keys_to_sanitize: Option<Vec<String>>,
+
+    /// `/info` 端点返回的自定义信息
+    ///
+    /// Custom data returned by the `/info` endpoint.
+    info: Option<Value>,
+}
+
+impl ManagementProperties {
+    pub fn enabled(&self) -> bool {
+        self.enabled.unwrap_or(true)
+    }
+
+    pub fn base_path(&self) -> &str {
+        self.base_path.as_deref().unwrap_or("/actuator")
+    }
+
+    pub fn port(&self) -> Option<u16> {
+        self.port
+    }
+
+    pub fn addr(&self) -> Option<&str> {
+        self.addr.as_deref()
+    }
+
+    /// 判断端点是否暴露
+    ///
+    /// Whether the endpoint with the given id is exposed.
+    pub fn is_exposed(&self, endpoint: &str) -> bool {
+        match self.endpoints.as_ref() {
+            Some(endpoints) => endpoints
+                .iter()
+                .any(|item| item == "*" || item.eq_ignore_ascii_case(endpoint)),
+            None => matches!(endpoint, "health" | "info"),
+        }
+    }
+
+    pub fn show_details(&self) -> bool {
+        self.show_details.unwrap_or(false)
+    }
+
+    pub fn health_timeout(&self) -> std::time::Duration {
+        std::time::Duration::from_millis(self.health_timeout.unwrap_or(5000))
+    }
+
+    pub fn probes(&self) -> bool {
+        self.probes.unwrap_or(true)
+    }
+
+    pub fn keys_to_sanitize(&self) -> &[String] {
+        self.keys_to_sanitize.as_deref().unwrap_or_default()
+    }
+
+    pub fn info(&self) -> Option<&Value> {
+        self.info.as_ref()
+    }
+}
+
+impl Default for ManagementProperties {
+    fn default() -> Self {
+        Self {
+            enabled: Some(true),
+            base_path: None,
+            port: None,
+            addr: None,
+            endpoints: None,
+            show_details: None,
+            health_timeout: None,
+            probes: None,
+            keys_to_sanitize: None,
+            info: None,
+        }
+    }
+}
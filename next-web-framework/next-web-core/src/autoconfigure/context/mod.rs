@@ -1,7 +1,12 @@
 pub mod application_context_properties;
 pub mod application_properties;
+pub mod cors_properties;
 
 pub mod http_properties;
 pub mod logging_properties;
+pub mod management_properties;
 pub mod messages_properties;
+pub mod openapi_properties;
 pub mod server_properties;
+pub mod shutdown_properties;
+pub mod ssl_properties;
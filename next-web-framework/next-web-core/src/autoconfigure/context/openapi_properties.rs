@@ -0,0 +1,162 @@
+/// 接口文档配置属性
+///
+/// 对应 `next.openapi` 前缀，需要开启 `enable-openapi` 特性。
+///
+/// # OpenAPI Properties
+///
+/// Bound to the `next.openapi` prefix, controls the generated OpenAPI document and its UI
+/// pages, requires the `enable-openapi` feature.
+///
+/// The document is only served once `enabled` is set. Both pages are served by an UI
+/// embedded in the application, nothing is loaded from elsewhere. Setting the assets of
+/// Swagger UI or Redoc switches a page to them, such a page is only served when each of its
+/// cross origin assets carries an `integrity` hash, or when the application hosts them.
+///
+/// ```yaml
+/// next:
+///   openapi:
+///     enabled: true
+///     title: Order service
+///     version: 2.1.0
+///     api_docs_path: /v3/api-docs
+///     swagger_ui_path: /swagger-ui
+///     redoc_path: /redoc
+///     redoc_script:
+///       url: https://cdn.jsdelivr.net/npm/redoc@2.1.5/bundles/redoc.standalone.js
+///       integrity: sha384-...
+/// ```
+#[derive(Debug, Clone, Default, serde::Deserialize)]
+pub struct OpenApiProperties {
+    /// 是否启用接口文档
+    ///
+    /// Whether the document and the UI pages are served, defaults to `false`.
+    enabled: Option<bool>,
+
+    /// 文档标题，默认为应用名称
+    ///
+    /// Title of the document, defaults to the application name.
+    title: Option<String>,
+
+    /// 文档版本
+    ///
+    /// Version of the API, defaults to `1.0.0`.
+    version: Option<String>,
+
+    /// 文档描述
+    ///
+    /// Description of the API.
+    description: Option<String>,
+
+    /// 文档路径
+    ///
+    /// Path of the JSON document, defaults to `/v3/api-docs`.
+    api_docs_path: Option<String>,
+
+    /// Swagger UI 页面路径，为空时不提供
+    ///
+    /// Path of the Swagger UI page, defaults to `/swagger-ui`, an empty path disables it.
+    swagger_ui_path: Option<String>,
+
+    /// Redoc 页面路径，为空时不提供
+    ///
+    /// Path of the Redoc page, defaults to `/redoc`, an empty path disables it.
+    redoc_path: Option<String>,
+
+    /// Swagger UI 脚本，为空时使用内置页面
+    ///
+    /// The `swagger-ui-dist` bundle of the Swagger UI page, the embedded UI is served without
+    /// it. Set it together with `swagger_ui_stylesheet`.
+    swagger_ui_script: Option<OpenApiAsset>,
+
+    /// Swagger UI 样式
+    ///
+    /// The `swagger-ui-dist` stylesheet of the Swagger UI page.
+    swagger_ui_stylesheet: Option<OpenApiAsset>,
+
+    /// Redoc 脚本，为空时使用内置页面
+    ///
+    /// The `redoc` standalone bundle of the Redoc page, the embedded UI is served without it.
+    redoc_script: Option<OpenApiAsset>,
+}
+
+/// 接口文档页面资源
+///
+/// A script or stylesheet loaded by an UI page, with its Subresource Integrity hash.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
+pub struct OpenApiAsset {
+    /// 资源地址
+    ///
+    /// Absolute URL of the asset, or a path when the application hosts it.
+    url: String,
+
+    /// 资源完整性校验值
+    ///
+    /// Subresource Integrity hash of the asset, e.g. `sha384-...`.
+    integrity: Option<String>,
+}
+
+impl OpenApiAsset {
+    pub fn new(url: impl Into<String>, integrity: Option<String>) -> Self {
+        Self {
+            url: url.into(),
+            integrity,
+        }
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn integrity(&self) -> Option<&str> {
+        self.integrity.as_deref().filter(|integrity| !integrity.is_empty())
+    }
+
+    /// Whether the browser can trust the asset, it is either hosted by the application or
+    /// checked against its integrity hash.
+    pub fn is_verified(&self) -> bool {
+        self.integrity().is_some() || (self.url.starts_with('/') && !self.url.starts_with("//"))
+    }
+}
+
+impl OpenApiProperties {
+    pub fn enabled(&self) -> bool {
+        self.enabled.unwrap_or(false)
+    }
+
+    pub fn title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    pub fn version(&self) -> &str {
+        self.version.as_deref().unwrap_or("1.0.0")
+    }
+
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    pub fn api_docs_path(&self) -> &str {
+        self.api_docs_path.as_deref().unwrap_or("/v3/api-docs")
+    }
+
+    pub fn swagger_ui_path(&self) -> Option<&str> {
+        Some(self.swagger_ui_path.as_deref().unwrap_or("/swagger-ui"))
+            .filter(|path| !path.is_empty())
+    }
+
+    pub fn redoc_path(&self) -> Option<&str> {
+        Some(self.redoc_path.as_deref().unwrap_or("/redoc")).filter(|path| !path.is_empty())
+    }
+
+    pub fn swagger_ui_script(&self) -> Option<&OpenApiAsset> {
+        self.swagger_ui_script.as_ref()
+    }
+
+    pub fn swagger_ui_stylesheet(&self) -> Option<&OpenApiAsset> {
+        self.swagger_ui_stylesheet.as_ref()
+    }
+
+    pub fn redoc_script(&self) -> Option<&OpenApiAsset> {
+        self.redoc_script.as_ref()
+    }
+}
@@ -3,6 +3,8 @@ use serde::Deserialize;
 use crate::constants::application_constants::APPLICATION_DEFAULT_PORT;
 
 use super::http_properties::HttpProperties;
+use super::shutdown_properties::ShutdownProperties;
+use super::ssl_properties::SslProperties;
 
 /// Application server register
 #[derive(Debug, Default, Deserialize, Clone)]
@@ -13,6 +15,8 @@ pub struct ServerProperties {
     context_path: Option<String>,
     http: Option<HttpProperties>,
     local: Option<bool>,
+    shutdown: Option<ShutdownProperties>,
+    ssl: Option<SslProperties>,
 }
 
 impl ServerProperties {
@@ -28,6 +32,8 @@ impl ServerProperties {
             context_path,
             http,
             local,
+            shutdown: None,
+            ssl: None,
         }
     }
 
@@ -50,6 +56,14 @@ impl ServerProperties {
     pub fn local(&self) -> Option<bool> {
         self.local
     }
+
+    pub fn shutdown(&self) -> Option<&ShutdownProperties> {
+        self.shutdown.as_ref()
+    }
+
+    pub fn ssl(&self) -> Option<&SslProperties> {
+        self.ssl.as_ref()
+    }
 }
 
 fn default_port() -> Option<u16> {
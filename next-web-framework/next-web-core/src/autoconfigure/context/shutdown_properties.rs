@@ -0,0 +1,44 @@
+/// 优雅停机配置属性
+///
+/// 对应 `next.server.shutdown` 前缀。
+///
+/// # Shutdown Properties
+///
+/// Bound to the `next.server.shutdown` prefix, all durations are in seconds.
+///
+/// On a shutdown signal the readiness probe fails first, the server keeps serving for
+/// `drain_period` so load balancers can take the instance out of rotation, then the listener
+/// is closed and in-flight requests get up to `graceful_timeout` to complete.
+/// Finally the `Lifecycle` beans stop, every `ApplicationShutdown` hook runs and the
+/// `DisposableBean`s are destroyed, each one bounded by `timeout_per_hook`.
+#[derive(Debug, Clone, Default, serde::Deserialize)]
+pub struct ShutdownProperties {
+    /// 就绪探针失败后继续处理请求的时间
+    ///
+    /// Time the server keeps serving after the readiness probe starts failing, defaults to `0`.
+    drain_period: Option<u64>,
+
+    /// 等待处理中请求完成的最长时间
+    ///
+    /// Maximum time to wait for in-flight requests once the listener is closed, defaults to `30`.
+    graceful_timeout: Option<u64>,
+
+    /// 单个关闭钩子的最长执行时间
+    ///
+    /// Maximum time a single shutdown hook may take, defaults to `10`.
+    timeout_per_hook: Option<u64>,
+}
+
+impl ShutdownProperties {
+    pub fn drain_period(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.drain_period.unwrap_or(0))
+    }
+
+    pub fn graceful_timeout(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.graceful_timeout.unwrap_or(30))
+    }
+
+    pub fn timeout_per_hook(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.timeout_per_hook.unwrap_or(10))
+    }
+}
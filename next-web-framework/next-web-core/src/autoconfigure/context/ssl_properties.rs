@@ -0,0 +1,169 @@
+/// SSL 配置属性
+///
+/// 对应 `next.server.ssl` 前缀，需要启用 `tls-rustls` 特性。
+///
+/// # Ssl Properties
+///
+/// Bound to the `next.server.ssl` prefix, requires the `tls-rustls` feature.
+///
+/// The server identity is either a PEM `certificate` chain with its `certificate_private_key`,
+/// or a PKCS#12 `key_store`. Relative paths are resolved from the working directory.
+///
+/// ```yaml
+/// next:
+///   server:
+///     ssl:
+///       certificate: /etc/next/tls/cert.pem
+///       certificate_private_key: /etc/next/tls/key.pem
+///       protocol: TLSv1.3
+///       client_auth: need
+///       trust_certificate: /etc/next/tls/ca.pem
+/// ```
+#[derive(Debug, Clone, Default, serde::Deserialize)]
+pub struct SslProperties {
+    /// 是否启用，默认为 `true`
+    ///
+    /// Whether ssl is enabled, defaults to `true`.
+    enabled: Option<bool>,
+
+    /// PEM 证书链
+    ///
+    /// PEM certificate chain, the server certificate first.
+    certificate: Option<String>,
+
+    /// PEM 私钥 (PKCS#1, PKCS#8 或 SEC1)
+    ///
+    /// PEM private key of the certificate, PKCS#1, PKCS#8 and SEC1 are supported.
+    certificate_private_key: Option<String>,
+
+    /// PKCS#12 密钥库，与 `certificate` 二选一
+    ///
+    /// PKCS#12 key store holding the private key and its chain, used instead of `certificate`.
+    key_store: Option<String>,
+    key_store_password: Option<String>,
+
+    /// 最低协议版本，默认为 `TLSv1.2`
+    ///
+    /// Minimum protocol version, defaults to `TLSv1.2`.
+    protocol: Option<TlsVersion>,
+
+    /// ALPN 协议，默认为 `h2, http/1.1`
+    ///
+    /// ALPN protocols in order of preference, defaults to `h2, http/1.1`.
+    alpn: Option<Vec<String>>,
+
+    /// 客户端证书认证，默认为 `none`
+    ///
+    /// Client certificate authentication, defaults to `none`.
+    client_auth: Option<ClientAuth>,
+
+    /// 用于校验客户端证书的 PEM CA 证书
+    ///
+    /// PEM CA bundle the client certificates are verified with, required by `client_auth`.
+    trust_certificate: Option<String>,
+
+    /// 文件变化时是否重新加载证书，默认为 `true`
+    ///
+    /// Whether the certificates are reloaded when the files change, defaults to `true`.
+    reload: Option<bool>,
+}
+
+/// Minimum TLS protocol version.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Deserialize)]
+pub enum TlsVersion {
+    #[default]
+    #[serde(rename = "TLSv1.2")]
+    Tls12,
+    #[serde(rename = "TLSv1.3")]
+    Tls13,
+}
+
+/// Client certificate authentication mode.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ClientAuth {
+    /// No client certificate is requested.
+    #[default]
+    None,
+    /// A client certificate is requested, it is verified when one is provided.
+    Want,
+    /// A verified client certificate is required.
+    Need,
+}
+
+impl SslProperties {
+    pub fn enabled(&self) -> bool {
+        self.enabled.unwrap_or(true)
+    }
+
+    pub fn certificate(&self) -> Option<&str> {
+        self.certificate.as_deref()
+    }
+
+    pub fn certificate_private_key(&self) -> Option<&str> {
+        self.certificate_private_key.as_deref()
+    }
+
+    pub fn key_store(&self) -> Option<&str> {
+        self.key_store.as_deref()
+    }
+
+    pub fn key_store_password(&self) -> &str {
+        self.key_store_password.as_deref().unwrap_or_default()
+    }
+
+    pub fn protocol(&self) -> TlsVersion {
+        self.protocol.unwrap_or_default()
+    }
+
+    pub fn alpn(&self) -> Vec<&str> {
+        match self.alpn.as_ref() {
+            Some(alpn) => alpn.iter().map(String::as_str).collect(),
+            None => vec!["h2", "http/1.1"],
+        }
+    }
+
+    pub fn client_auth(&self) -> ClientAuth {
+        self.client_auth.unwrap_or_default()
+    }
+
+    pub fn trust_certificate(&self) -> Option<&str> {
+        self.trust_certificate.as_deref()
+    }
+
+    pub fn reload(&self) -> bool {
+        self.reload.unwrap_or(true)
+    }
+
+    /// Every file the server configuration is loaded from.
+    pub fn files(&self) -> Vec<&str> {
+        [
+            self.certificate(),
+            self.certificate_private_key(),
+            self.key_store(),
+            self.trust_certificate(),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+
+    /// Check the combination of the keys.
+    pub fn validate(&self) -> Result<(), String> {
+        match (self.certificate(), self.certificate_private_key(), self.key_store()) {
+            (Some(_), Some(_), None) | (None, None, Some(_)) => {}
+            (None, None, None) => {
+                return Err("either certificate and certificate_private_key or key_store must be set".to_string())
+            }
+            (_, _, Some(_)) => {
+                return Err("key_store cannot be combined with certificate and certificate_private_key".to_string())
+            }
+            _ => return Err("certificate and certificate_private_key must be set together".to_string()),
+        }
+
+        if self.client_auth() != ClientAuth::None && self.trust_certificate().is_none() {
+            return Err("trust_certificate must be set when client_auth is enabled".to_string());
+        }
+        Ok(())
+    }
+}
@@ -11,3 +11,11 @@ pub const RESOURCES: &'static str = "resources";
 pub const NEXT_SECURE_PROPERTIES: &'static str = "nsp";
 
 pub const NEXT_DECRYPT_PASSWORD: &'static str = "NEXT_DECRYPT_PASSWORD";
+
+pub const APPLICATION_PROFILE_CONFIG_PREFIX: &'static str = "/application-";
+
+pub const APPLICATION_CONFIG_SUFFIX: &'static str = ".yaml";
+
+pub const NEXT_PROFILES_ACTIVE: &'static str = "NEXT_PROFILES_ACTIVE";
+
+pub const NEXT_APPLICATION_JSON: &'static str = "NEXT_APPLICATION_JSON";
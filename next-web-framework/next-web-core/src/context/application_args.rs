@@ -1,5 +1,7 @@
 use clap::Parser;
 
+use crate::context::bean_graph::BeanGraphFormat;
+
 #[derive(Parser, Debug, Clone)]
 #[command(author, version, about)]
 pub struct ApplicationArgs {
@@ -8,10 +10,54 @@ pub struct ApplicationArgs {
 
     #[arg(long)]
     pub decrypt_password: Option<String>,
+
+    /// Comma separated list of active profiles, e.g. `--active-profiles dev,local`
+    #[arg(long, value_delimiter = ',')]
+    pub active_profiles: Vec<String>,
+
+    /// Print the providers, their dependencies and the unsatisfied ones once the context is
+    /// prepared, then exit before serving, e.g. `--print-bean-graph` (dot) or
+    /// `--print-bean-graph json`
+    #[arg(long, value_enum, num_args = 0..=1, default_missing_value = "dot")]
+    pub print_bean_graph: Option<BeanGraphFormat>,
+
+    /// Property overrides passed as `--next.server.port=8080`
+    #[arg(skip)]
+    pub property_overrides: Vec<(String, String)>,
+}
+
+impl ApplicationArgs {
+    /// Parse the given arguments.
+    ///
+    /// Every `--key=value` argument whose key contains a `.` is treated as a property override,
+    /// the remaining arguments are handed to clap.
+    pub fn parse_with_overrides<I, T>(args: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        let mut property_overrides = Vec::new();
+        let mut rest = Vec::new();
+
+        for arg in args.into_iter().map(Into::into) {
+            match arg
+                .strip_prefix("--")
+                .and_then(|s| s.split_once('='))
+                .filter(|(key, _)| key.contains('.'))
+            {
+                Some((key, value)) => property_overrides.push((key.to_string(), value.to_string())),
+                None => rest.push(arg),
+            }
+        }
+
+        let mut args = Self::parse_from(rest);
+        args.property_overrides = property_overrides;
+        args
+    }
 }
 
 impl Default for ApplicationArgs {
     fn default() -> Self {
-        Self::parse()
+        Self::parse_with_overrides(std::env::args())
     }
 }
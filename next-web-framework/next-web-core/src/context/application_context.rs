@@ -2,8 +2,11 @@ use std::any::{self, Any};
 use std::cmp::Ordering;
 use std::collections::hash_map::Keys;
 use std::{
-    any::TypeId, borrow::Cow, collections::HashMap, future::Future, hash::Hasher, pin::Pin,
+    any::TypeId, borrow::Cow, collections::BTreeSet, collections::HashMap, collections::HashSet, future::Future, hash::Hasher, pin::Pin,
+    panic::AssertUnwindSafe,
     sync::Arc,
+    task::{Context, Poll},
+    time::Duration,
 };
 
 use std::hash::Hash;
@@ -11,6 +14,12 @@ use std::hash::Hash;
 use tracing::trace;
 
 use crate::autoregister::auto_register::AutoRegisterModule;
+use crate::context::bean_graph::{BeanEdge, BeanGraph, BeanNode, DependencyError};
+use crate::error::BoxError;
+use crate::traits::lifecycle::disposable_bean::DisposableBean;
+use crate::traits::lifecycle::initializing_bean::InitializingBean;
+use crate::traits::lifecycle::lifecycle_bean::{Lifecycle, LifecycleBeans};
+use crate::context::properties::ApplicationProperties;
 use crate::util::singleton::SingletonUtil;
 
 /// A context is a container for all the providers and instances.
@@ -129,6 +138,13 @@ pub struct ApplicationContext {
     eager_create_functions: Vec<(Definition, EagerCreateFunction)>,
 
     dependency_chain: DependencyChain,
+
+    /// Keys of the instances given by [`ApplicationContextOptions`], they are not replaced by
+    /// providers with the same key loaded afterwards, e.g. mocks in tests.
+    preset_keys: HashSet<Key>,
+
+    /// The configuration the conditions of the providers are evaluated against.
+    properties: Option<ApplicationProperties>,
 }
 
 impl Default for ApplicationContext {
@@ -143,6 +159,8 @@ impl Default for ApplicationContext {
             conditional_providers: Default::default(),
             eager_create_functions: Default::default(),
             dependency_chain: Default::default(),
+            preset_keys: Default::default(),
+            properties: Default::default(),
         }
     }
 }
@@ -302,6 +320,17 @@ impl ApplicationContext {
         &self.conditional_providers
     }
 
+    /// Returns the configuration the conditions of the providers are evaluated against,
+    /// see [`ApplicationContextOptions::properties`].
+    pub fn properties(&self) -> Option<&ApplicationProperties> {
+        self.properties.as_ref()
+    }
+
+    /// Sets the configuration the conditions of the providers loaded afterwards are evaluated against.
+    pub fn set_properties(&mut self, properties: ApplicationProperties) {
+        self.properties = Some(properties);
+    }
+
     /// Returns a reference to the eager create functions.
     pub fn eager_create_functions(&self) -> &Vec<(Definition, EagerCreateFunction)> {
         &self.eager_create_functions
@@ -312,6 +341,99 @@ impl ApplicationContext {
         &self.dependency_chain.stack
     }
 
+    /// Returns the providers and the dependencies requested between them so far.
+    ///
+    /// A dependency is known once the constructor requesting it has run, so take the graph after
+    /// the eager instances are created and the application has started, or use
+    /// [`dry_run_async`](Self::dry_run_async).
+    pub fn bean_graph(&self) -> BeanGraph {
+        BeanGraph::new(
+            self.bean_nodes(),
+            self.bean_edges(),
+            self.dependency_chain.errors.clone(),
+        )
+    }
+
+    /// Runs the constructors of the providers not created yet in a snapshot of this context and
+    /// returns the complete graph, with every missing and circular dependency found on the way
+    /// instead of a panic on the first one.
+    ///
+    /// The instances are dropped with the snapshot, the constructors still run though, so keep
+    /// it to diagnostics such as `--print-bean-graph`.
+    pub async fn dry_run_async(&self) -> BeanGraph {
+        let mut providers = self
+            .provider_registry
+            .inner()
+            .values()
+            .filter(|provider| !self.single_registry.contains(provider.key()))
+            .map(|provider| (provider.key().clone(), provider.eager_create_function()))
+            .collect::<Vec<_>>();
+        providers.sort_by(|(left, _), (right, _)| left.cmp(right));
+
+        let mut snapshot = self.snapshot();
+        snapshot.dependency_chain.edges = self.dependency_chain.edges.clone();
+        snapshot.dependency_chain.collecting = true;
+
+        for (key, eager_create_function) in providers {
+            match eager_create_function {
+                EagerCreateFunction::Async(eager_create_function) => {
+                    snapshot
+                        .create_collecting_async(key.name, eager_create_function)
+                        .await
+                }
+                EagerCreateFunction::Sync(eager_create_function) => {
+                    snapshot.create_collecting(key.name, eager_create_function)
+                }
+                EagerCreateFunction::None => {}
+            }
+        }
+        snapshot.dependency_chain.collecting = false;
+
+        BeanGraph::new(
+            self.bean_nodes(),
+            snapshot.bean_edges(),
+            snapshot.dependency_chain.errors,
+        )
+    }
+
+    fn bean_nodes(&self) -> Vec<BeanNode> {
+        let mut nodes = self
+            .provider_registry
+            .inner()
+            .values()
+            .map(|provider| BeanNode {
+                key: provider.key().clone(),
+                scope: Some(provider.definition().scope),
+                created: self.single_registry.contains(provider.key()),
+            })
+            .collect::<Vec<_>>();
+
+        self.single_registry
+            .keys()
+            .filter(|key| !self.provider_registry.contains(key))
+            .for_each(|key| {
+                nodes.push(BeanNode {
+                    key: key.clone(),
+                    scope: None,
+                    created: true,
+                })
+            });
+
+        nodes
+    }
+
+    fn bean_edges(&self) -> Vec<BeanEdge> {
+        self.dependency_chain
+            .edges
+            .iter()
+            .map(|(from, to, absent)| BeanEdge {
+                from: from.clone(),
+                to: to.clone(),
+                absent: *absent,
+            })
+            .collect()
+    }
+
     /// Appends a standalone [`Singleton`](crate::Scope::Singleton) instance to the context with default name `""`.
     ///
     /// # Panics
@@ -370,6 +492,12 @@ impl ApplicationContext {
         let single = Single::new(instance, Some(Clone::clone)).into();
 
         let key = provider.key().clone();
+        if self.preset_keys.contains(&key) {
+            #[cfg(feature = "trace-log")]
+            trace!("Singleton registry insert skipped, preset key: {:?}\n", key);
+            return;
+        }
+
         self.provider_registry.insert(provider, self.allow_override);
         self.single_registry.insert(key.clone(), single);
 
@@ -389,6 +517,29 @@ impl ApplicationContext {
         self.insert_singleton_with_name(instance, name);
     }
 
+    /// Replaces a standalone [`Singleton`](crate::Scope::Singleton) instance with name.
+    ///
+    /// Unlike [`insert_singleton_with_name`](ApplicationContext::insert_singleton_with_name),
+    /// an existing instance with the same key is replaced regardless of [`allow_override`](ApplicationContext::allow_override).
+    ///
+    /// Instances that have already been resolved are not affected.
+    #[track_caller]
+    pub fn replace_singleton_with_name<T, N>(&mut self, instance: T, name: N)
+    where
+        T: 'static + Clone + Send + Sync,
+        N: Into<Cow<'static, str>>,
+    {
+        let name = name.into();
+        let provider: DynProvider =
+            Provider::<T>::never_construct(name.clone(), Scope::Singleton).into();
+        let single = Single::new(instance, Some(Clone::clone)).into();
+
+        let key = provider.key().clone();
+        self.provider_registry.remove(&key);
+        self.provider_registry.insert(provider, true);
+        self.single_registry.insert(key, single);
+    }
+
     /// Appends a standalone [`SingleOwner`](crate::Scope::SingleOwner) instance to the context with default name `""`.
     ///
     /// # Panics
@@ -452,6 +603,10 @@ impl ApplicationContext {
         let single = Single::new(instance, None).into();
 
         let key = provider.key().clone();
+        if self.preset_keys.contains(&key) {
+            return;
+        }
+
         self.provider_registry.insert(provider, self.allow_override);
         self.single_registry.insert(key.clone(), single);
 
@@ -747,7 +902,7 @@ impl ApplicationContext {
         let name = name.into();
         match self.inner_resolve(name, Behaviour::CreateThenReturnSingletonOrTransient) {
             Resolved::SingletonOrTransient(instance) => instance,
-            Resolved::NotFoundProvider(key) => no_provider_panic(key),
+            Resolved::NotFoundProvider(key) => self.no_provider_panic(key),
             Resolved::NotSingletonOrTransient(definition) => {
                 not_singleton_or_transient_panic(definition)
             }
@@ -830,7 +985,11 @@ impl ApplicationContext {
     ) -> Option<T> {
         match self.inner_resolve(name.into(), Behaviour::CreateThenReturnSingletonOrTransient) {
             Resolved::SingletonOrTransient(instance) => Some(instance),
-            Resolved::NotFoundProvider(_) | Resolved::NotSingletonOrTransient(_) => None,
+            Resolved::NotFoundProvider(key) => {
+                self.dependency_chain.record(&key, true);
+                None
+            }
+            Resolved::NotSingletonOrTransient(_) => None,
             Resolved::NotSingletonOrSingleOwner(_) | Resolved::NoReturn => unreachable!(),
         }
     }
@@ -880,7 +1039,7 @@ impl ApplicationContext {
     pub fn just_create<T: 'static + Send + Sync>(&mut self, name: Cow<'static, str>) {
         match self.inner_resolve::<T>(name, Behaviour::JustCreateAllScopeForEagerCreate) {
             Resolved::NoReturn => {}
-            Resolved::NotFoundProvider(key) => no_provider_panic(key),
+            Resolved::NotFoundProvider(key) => self.no_provider_panic(key),
             Resolved::SingletonOrTransient(_)
             | Resolved::NotSingletonOrTransient(_)
             | Resolved::NotSingletonOrSingleOwner(_) => {
@@ -951,7 +1110,7 @@ impl ApplicationContext {
     ) {
         match self.inner_resolve::<T>(name.into(), Behaviour::JustCreateSingletonOrSingleOwner) {
             Resolved::NoReturn => {}
-            Resolved::NotFoundProvider(key) => no_provider_panic(key),
+            Resolved::NotFoundProvider(key) => self.no_provider_panic(key),
             Resolved::NotSingletonOrSingleOwner(definition) => {
                 not_singleton_or_single_owner_panic(definition)
             }
@@ -1171,7 +1330,7 @@ impl ApplicationContext {
             .await
         {
             Resolved::SingletonOrTransient(instance) => instance,
-            Resolved::NotFoundProvider(key) => no_provider_panic(key),
+            Resolved::NotFoundProvider(key) => self.no_provider_panic(key),
             Resolved::NotSingletonOrTransient(definition) => {
                 not_singleton_or_transient_panic(definition)
             }
@@ -1244,7 +1403,11 @@ impl ApplicationContext {
             .await
         {
             Resolved::SingletonOrTransient(instance) => Some(instance),
-            Resolved::NotFoundProvider(_) | Resolved::NotSingletonOrTransient(_) => None,
+            Resolved::NotFoundProvider(key) => {
+                self.dependency_chain.record(&key, true);
+                None
+            }
+            Resolved::NotSingletonOrTransient(_) => None,
             Resolved::NotSingletonOrSingleOwner(_) | Resolved::NoReturn => unreachable!(),
         }
     }
@@ -1303,7 +1466,7 @@ impl ApplicationContext {
             .await
         {
             Resolved::NoReturn => {}
-            Resolved::NotFoundProvider(key) => no_provider_panic(key),
+            Resolved::NotFoundProvider(key) => self.no_provider_panic(key),
             Resolved::SingletonOrTransient(_)
             | Resolved::NotSingletonOrTransient(_)
             | Resolved::NotSingletonOrSingleOwner(_) => {
@@ -1375,7 +1538,7 @@ impl ApplicationContext {
             .await
         {
             Resolved::NoReturn => {}
-            Resolved::NotFoundProvider(key) => no_provider_panic(key),
+            Resolved::NotFoundProvider(key) => self.no_provider_panic(key),
             Resolved::NotSingletonOrSingleOwner(definition) => {
                 not_singleton_or_single_owner_panic(definition)
             }
@@ -1538,6 +1701,14 @@ impl ApplicationContext {
         self.contains_provider_with_name::<T>("")
     }
 
+    /// Returns true if the context contains a provider or an instance of the specified type,
+    /// whatever its name.
+    pub fn contains_type<T: 'static>(&self) -> bool {
+        let ty = Type::new::<T>();
+        self.provider_registry.inner().keys().any(|key| key.ty == ty)
+            || self.single_registry.keys().any(|key| key.ty == ty)
+    }
+
     /// Returns true if the context contains a provider for the specified type and name.
     ///
     /// # Example
@@ -1823,6 +1994,12 @@ impl ApplicationContext {
 impl ApplicationContext {
     #[track_caller]
     fn load_provider(&mut self, eager_create: bool, provider: DynProvider) {
+        if self.preset_keys.contains(provider.key()) {
+            #[cfg(feature = "trace-log")]
+            tracing::debug!("(=) keep preset instance: {:?}", provider.definition());
+            return;
+        }
+
         let definition = provider.definition();
         let need_eager_create = self.eager_create || eager_create || provider.eager_create();
 
@@ -1875,6 +2052,8 @@ impl ApplicationContext {
         });
     }
 
+    /// Every eager instance is attempted, the missing and circular dependencies are reported
+    /// together afterwards.
     #[track_caller]
     fn create_eager_instances(&mut self) {
         if self.eager_create_functions.is_empty() {
@@ -1882,10 +2061,13 @@ impl ApplicationContext {
         }
 
         self.eager_create_functions.reverse();
+        // Created while another eager instance is, the outer call reports
+        let nested = std::mem::replace(&mut self.dependency_chain.collecting, true);
 
         while let Some((definition, eager_create_function)) = self.eager_create_functions.pop() {
             match eager_create_function {
                 EagerCreateFunction::Async(_) => {
+                    self.dependency_chain.collecting = false;
                     panic!(
                         "unable to call an async eager create function in a sync context for: {:?}
 
@@ -1899,11 +2081,15 @@ please use instead:
                     )
                 }
                 EagerCreateFunction::Sync(eager_create_function) => {
-                    eager_create_function(self, definition.key.name)
+                    self.create_collecting(definition.key.name, eager_create_function)
                 }
                 EagerCreateFunction::None => unreachable!(),
             }
         }
+
+        if !nested {
+            self.dependency_chain.report();
+        }
     }
 
     async fn create_eager_instances_async(&mut self) {
@@ -1912,18 +2098,49 @@ please use instead:
         }
 
         self.eager_create_functions.reverse();
+        // Created while another eager instance is, the outer call reports
+        let nested = std::mem::replace(&mut self.dependency_chain.collecting, true);
 
         while let Some((definition, eager_create_function)) = self.eager_create_functions.pop() {
             match eager_create_function {
                 EagerCreateFunction::Async(eager_create_function) => {
-                    eager_create_function(self, definition.key.name).await
+                    self.create_collecting_async(definition.key.name, eager_create_function)
+                        .await
                 }
                 EagerCreateFunction::Sync(eager_create_function) => {
-                    eager_create_function(self, definition.key.name)
+                    self.create_collecting(definition.key.name, eager_create_function)
                 }
                 EagerCreateFunction::None => unreachable!(),
             }
         }
+
+        if !nested {
+            self.dependency_chain.report();
+        }
+    }
+
+    fn create_collecting(
+        &mut self,
+        name: Cow<'static, str>,
+        eager_create_function: fn(&mut ApplicationContext, Cow<'static, str>),
+    ) {
+        let depth = self.dependency_chain.stack.len();
+        if let Err(payload) = std::panic::catch_unwind(AssertUnwindSafe(|| {
+            eager_create_function(self, name)
+        })) {
+            self.dependency_chain.collected(depth, payload);
+        }
+    }
+
+    async fn create_collecting_async(
+        &mut self,
+        name: Cow<'static, str>,
+        eager_create_function: fn(&mut ApplicationContext, Cow<'static, str>) -> BoxFuture<'static, ()>,
+    ) {
+        let depth = self.dependency_chain.stack.len();
+        if let Err(payload) = CatchUnwind(eager_create_function(self, name)).await {
+            self.dependency_chain.collected(depth, payload);
+        }
     }
 
     #[track_caller]
@@ -1957,6 +2174,8 @@ please use instead:
             return Ok(Resolved::NotFoundProvider(key));
         };
 
+        self.dependency_chain.record(&key, false);
+
         let definition = provider.definition();
 
         if self.single_registry.contains(&key) {
@@ -2130,6 +2349,27 @@ please check all the references to the above type, there are 3 scenarios that wi
         instance
     }
 
+    #[track_caller]
+    fn no_provider_panic(&mut self, key: Key) -> ! {
+        let mut candidates = self
+            .provider_registry
+            .inner()
+            .keys()
+            .chain(self.single_registry.keys())
+            .filter(|k| k.ty == key.ty)
+            .map(|k| k.name.clone())
+            .collect::<Vec<_>>();
+        candidates.sort();
+        candidates.dedup();
+
+        let required_by = self.dependency_chain.stack.iter().rev().cloned().collect();
+        self.dependency_chain.fail(DependencyError::Missing {
+            key,
+            required_by,
+            candidates,
+        })
+    }
+
     fn names<T: 'static>(&self) -> Vec<Cow<'static, str>> {
         let type_id = TypeId::of::<T>();
 
@@ -2139,6 +2379,114 @@ please check all the references to the above type, there are 3 scenarios that wi
             .map(|key| key.name.clone())
             .collect()
     }
+
+    /// A context sharing the providers and the instances of this one, to create an instance
+    /// without holding this context. Hand it back with [`merge_created`](Self::merge_created).
+    ///
+    /// The registries are shared, not copied, until one of the contexts registers something.
+    pub(crate) fn snapshot(&self) -> ApplicationContext {
+        ApplicationContext {
+            allow_override: self.allow_override,
+            allow_only_single_eager_create: self.allow_only_single_eager_create,
+            eager_create: self.eager_create,
+            single_registry: SingleRegistry {
+                registry: self.single_registry.registry.clone(),
+                inserted: Some(Vec::new()),
+            },
+            provider_registry: self.provider_registry.clone(),
+            ..Default::default()
+        }
+    }
+
+    /// Whether the snapshot created instances or found dependencies this context doesn't know,
+    /// i.e. [`merge_created`](Self::merge_created) has something to keep.
+    pub(crate) fn has_created(&self, snapshot: &ApplicationContext) -> bool {
+        snapshot
+            .single_registry
+            .inserted
+            .iter()
+            .flatten()
+            .any(|key| !self.single_registry.contains(key))
+            || !snapshot
+                .dependency_chain
+                .edges
+                .is_subset(&self.dependency_chain.edges)
+    }
+
+    /// Keep the instances and the dependencies the snapshot created, an instance created here
+    /// meanwhile wins over the one of the snapshot.
+    pub(crate) fn merge_created(&mut self, snapshot: ApplicationContext) {
+        let ApplicationContext {
+            single_registry,
+            dependency_chain,
+            ..
+        } = snapshot;
+
+        for key in single_registry.inserted.into_iter().flatten() {
+            if self.single_registry.contains(&key) {
+                continue;
+            }
+            if let Some(single) = single_registry.registry.get(&key) {
+                self.single_registry.insert(key, single.clone());
+            }
+        }
+        self.dependency_chain.edges.extend(dependency_chain.edges);
+    }
+}
+
+/// Lifecycle callbacks of the beans
+impl ApplicationContext {
+    /// Call every [`InitializingBean`], stops at the first error.
+    pub async fn initialize_beans(&mut self) -> Result<(), BoxError> {
+        let beans = self.resolve_by_type::<Box<dyn InitializingBean>>();
+        for bean in beans {
+            bean.after_properties_set(self).await?;
+        }
+
+        Ok(())
+    }
+
+    /// The [`Lifecycle`] beans, sorted by phase.
+    pub fn lifecycle_beans(&mut self) -> LifecycleBeans {
+        LifecycleBeans::new(self.resolve_by_type::<Box<dyn Lifecycle>>())
+    }
+
+    /// Start the [`Lifecycle`] beans that start automatically and are not running, lowest phase
+    /// first, see [`LifecycleBeans::start`].
+    pub async fn start_lifecycle_beans(&mut self, timeout: Duration) -> Result<(), BoxError> {
+        self.lifecycle_beans().start(timeout).await
+    }
+
+    /// Stop the running [`Lifecycle`] beans, highest phase first, see [`LifecycleBeans::stop`].
+    pub async fn stop_lifecycle_beans(&mut self, timeout: Duration) {
+        self.lifecycle_beans().stop(timeout).await
+    }
+
+    /// Call every [`DisposableBean`], each one bounded by the timeout.
+    pub async fn destroy_beans(&mut self, timeout: Duration) {
+        let beans = self.resolve_by_type::<Box<dyn DisposableBean>>();
+
+        for mut bean in beans {
+            run_callback("disposable bean", timeout, async move { bean.destroy().await }).await;
+        }
+    }
+}
+
+/// Run the callback on its own task so a panic stays contained in it.
+pub(crate) async fn run_callback<F>(name: &str, timeout: Duration, callback: F)
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    let mut handle = tokio::spawn(callback);
+
+    match tokio::time::timeout(timeout, &mut handle).await {
+        Ok(Ok(())) => {}
+        Ok(Err(_)) => tracing::warn!("The {} panicked", name),
+        Err(_) => {
+            handle.abort();
+            tracing::warn!("The {} did not complete within {:?}", name, timeout);
+        }
+    }
 }
 
 /// =======================================================================================================
@@ -2168,10 +2516,6 @@ struct Holder<'a, T: Send + Sync> {
     definition: &'a Definition,
 }
 
-#[inline(always)]
-fn no_provider_panic(key: Key) -> ! {
-    panic!("no provider registered for: {:?}", key)
-}
 
 #[inline(always)]
 fn not_singleton_or_single_owner_panic(definition: Definition) -> ! {
@@ -2280,6 +2624,7 @@ pub struct ApplicationContextOptions {
     eager_create: bool,
     providers: Vec<DynProvider>,
     singles: Vec<DynSingle>,
+    properties: Option<ApplicationProperties>,
 }
 
 impl Default for ApplicationContextOptions {
@@ -2290,6 +2635,7 @@ impl Default for ApplicationContextOptions {
             eager_create: Default::default(),
             providers: Default::default(),
             singles: Default::default(),
+            properties: Default::default(),
         }
     }
 }
@@ -2379,6 +2725,10 @@ impl ApplicationContextOptions {
 
     /// Appends a standalone [`Singleton`](crate::Scope::Singleton) instance to the context with name.
     ///
+    /// Instances given here take precedence over the providers and instances with the same key
+    /// registered afterwards, e.g. by [`auto_register`](ApplicationContextOptions::auto_register),
+    /// which allows to replace them with mocks in tests.
+    ///
     /// # Example
     ///
     /// ```rust
@@ -2408,6 +2758,23 @@ impl ApplicationContextOptions {
         self
     }
 
+    /// Sets the configuration the conditions of the providers are evaluated against, e.g. the
+    /// `on_property` and `on_profile` conditions of `#[Singleton]`.
+    pub fn properties(mut self, properties: ApplicationProperties) -> Self {
+        self.properties = Some(properties);
+        self
+    }
+
+    /// Appends a standalone [`Singleton`](crate::Scope::Singleton) instance to the context with
+    /// the default name of its type, the name the `#[Singleton]` macro gives by default.
+    pub fn singleton_with_default_name<T>(self, instance: T) -> Self
+    where
+        T: 'static + Clone + Send + Sync,
+    {
+        let name = SingletonUtil::name::<T>();
+        self.singleton_with_name(instance, name)
+    }
+
     /// Appends a standalone [`SingleOwner`](crate::Scope::SingleOwner) instance to the context with default name `""`.
     ///
     /// # Example
@@ -2477,12 +2844,14 @@ impl ApplicationContextOptions {
             eager_create,
             providers,
             singles,
+            properties,
         } = self;
 
         let mut cx = ApplicationContext {
             allow_override,
             allow_only_single_eager_create,
             eager_create,
+            properties,
             ..Default::default()
         };
 
@@ -2493,7 +2862,8 @@ impl ApplicationContextOptions {
                 .for_each(|(provider, single)| {
                     let key = provider.key().clone();
                     cx.provider_registry.insert(provider, allow_override);
-                    cx.single_registry.insert(key, single);
+                    cx.single_registry.insert(key.clone(), single);
+                    cx.preset_keys.insert(key);
                 });
         }
 
@@ -2612,39 +2982,94 @@ impl ApplicationContextOptions {
 #[derive(Default, Clone)]
 struct DependencyChain {
     stack: Vec<Key>,
+    /// `(requester, dependency, absent)` recorded while resolving, see [`ApplicationContext::bean_graph`].
+    edges: BTreeSet<(Key, Key, bool)>,
+    /// Set while the failures are collected instead of panicking on the first one.
+    collecting: bool,
+    errors: Vec<DependencyError>,
 }
 
+/// The payload a collected [`DependencyError`] unwinds with, it skips the panic hook.
+struct CollectedDependencyError;
+
 impl DependencyChain {
+    #[track_caller]
     fn push(&mut self, key: Key) {
-        let already_contains = self.stack.contains(&key);
+        if let Some(position) = self.stack.iter().position(|k| k == &key) {
+            let mut cycle = self.stack[position..].to_vec();
+            cycle.push(key);
+            let required_by = self.stack[..position].iter().rev().cloned().collect();
+
+            self.fail(DependencyError::Circular { cycle, required_by });
+        }
+
         self.stack.push(key);
+    }
 
-        if already_contains {
-            let key = self.stack.last().unwrap();
+    fn pop(&mut self) {
+        self.stack.pop();
+    }
 
-            let mut buf = String::with_capacity(1024);
-            buf.push('[');
-            buf.push('\n');
+    /// Record that the provider being created requested `key`.
+    fn record(&mut self, key: &Key, absent: bool) {
+        if let Some(requester) = self.stack.last() {
+            self.edges.insert((requester.clone(), key.clone(), absent));
+        }
+    }
 
-            self.stack.iter().for_each(|k| {
-                if key == k {
-                    buf.push_str(" --> ")
-                } else {
-                    buf.push_str("  |  ")
-                }
+    #[track_caller]
+    fn fail(&mut self, error: DependencyError) -> ! {
+        if !self.collecting {
+            panic!("{}", error);
+        }
 
-                buf.push_str(&format!("{:?}", k));
-                buf.push('\n');
-            });
+        if !self.errors.iter().any(|other| other.same_as(&error)) {
+            self.errors.push(error);
+        }
+        std::panic::resume_unwind(Box::new(CollectedDependencyError))
+    }
 
-            buf.push(']');
+    /// Whether the unwinding `payload` is a collected failure, other panics are resumed.
+    fn collected(&mut self, depth: usize, payload: Box<dyn Any + Send>) {
+        if !payload.is::<CollectedDependencyError>() {
+            self.collecting = false;
+            std::panic::resume_unwind(payload);
+        }
+        self.stack.truncate(depth);
+    }
 
-            panic!("circular dependency detected: {}", buf);
+    /// Panic with every collected failure.
+    #[track_caller]
+    fn report(&mut self) {
+        self.collecting = false;
+        match self.errors.len() {
+            0 => {}
+            1 => panic!("{}", self.errors[0]),
+            len => {
+                let errors = self
+                    .errors
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>();
+                panic!("{} dependency errors:\n\n{}", len, errors.join("\n\n"))
+            }
         }
     }
+}
 
-    fn pop(&mut self) {
-        self.stack.pop();
+/// Polls the future in [`std::panic::catch_unwind`].
+struct CatchUnwind<F>(F);
+
+impl<F: Future + Unpin> Future for CatchUnwind<F> {
+    type Output = std::thread::Result<F::Output>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let future = &mut self.0;
+        match std::panic::catch_unwind(AssertUnwindSafe(|| Pin::new(future).poll(cx))) {
+            Ok(Poll::Ready(output)) => Poll::Ready(Ok(output)),
+            Ok(Poll::Pending) => Poll::Pending,
+            Err(payload) => Poll::Ready(Err(payload)),
+        }
     }
 }
 
@@ -2709,9 +3134,13 @@ pub trait FutureExt: Future {
     }
 }
 
+/// The map is shared with the snapshots of the context and copied on the first write while
+/// one is alive.
 #[derive(Default, Clone)]
 pub(crate) struct SingleRegistry {
-    registry: HashMap<Key, DynSingle>,
+    registry: Arc<HashMap<Key, DynSingle>>,
+    /// Keys inserted into the registry of a snapshot, see [`ApplicationContext::merge_created`].
+    inserted: Option<Vec<Key>>,
 }
 
 impl SingleRegistry {
@@ -2724,7 +3153,10 @@ impl SingleRegistry {
         // because when inserting a provider and a single with the same key into the context,
         // the provider must be inserted first, followed by the single,
         // and the checking of `allow_override` has already been done when the provider is inserted.
-        self.registry.insert(key, single);
+        if let Some(inserted) = self.inserted.as_mut() {
+            inserted.push(key.clone());
+        }
+        Arc::make_mut(&mut self.registry).insert(key, single);
     }
 
     pub(crate) fn get_owned<T: 'static>(&self, key: &Key) -> Option<T> {
@@ -2744,17 +3176,19 @@ impl SingleRegistry {
     }
 
     pub(crate) fn remove(&mut self, key: &Key) -> Option<DynSingle> {
-        self.registry.remove(key)
+        Arc::make_mut(&mut self.registry).remove(key)
     }
 
-    pub(crate) fn keys(&self) -> Keys<Key, DynSingle> {
+    pub(crate) fn keys(&self) -> Keys<'_, Key, DynSingle> {
         self.registry.keys()
     }
 }
 
+/// The map is shared with the snapshots of the context and copied on the first write while
+/// one is alive.
 #[derive(Default, Clone)]
 pub(crate) struct ProviderRegistry {
-    registry: HashMap<Key, DynProvider>,
+    registry: Arc<HashMap<Key, DynProvider>>,
 }
 
 impl ProviderRegistry {
@@ -2780,7 +3214,7 @@ impl ProviderRegistry {
             );
         }
 
-        self.registry.insert(key, provider);
+        Arc::make_mut(&mut self.registry).insert(key, provider);
     }
 
     pub(crate) fn get<T: 'static + Send + Sync>(&self, key: &Key) -> Option<&Provider<T>> {
@@ -2792,7 +3226,7 @@ impl ProviderRegistry {
     }
 
     pub(crate) fn remove(&mut self, key: &Key) -> Option<DynProvider> {
-        self.registry.remove(key)
+        Arc::make_mut(&mut self.registry).remove(key)
     }
 }
 
@@ -3731,7 +4165,7 @@ pub struct DynSingle {
 impl Clone for DynSingle {
     fn clone(&self) -> Self {
         Self {
-            origin: Arc::new(self.origin.clone()),
+            origin: Arc::clone(&self.origin),
         }
     }
 }
@@ -1,6 +1,11 @@
 use hashbrown::HashMap;
 use std::sync::Arc;
-use std::{borrow::Cow, fs, io, path::Path, time::SystemTime};
+use std::{
+    borrow::Cow,
+    fs, io,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
 use tracing::error;
 
 use sha2::Digest;
@@ -49,6 +54,17 @@ impl ResourceLoader for ApplicationResources {
     }
 }
 
+impl ApplicationResources {
+    /// The file on disk the resource was read from.
+    pub fn file_path(&self, path: impl AsRef<str>) -> Option<&Path> {
+        let path = path.as_ref().replace("\\", "/");
+        self.files
+            .inner
+            .get(path.as_str())
+            .map(|source| source.path.as_path())
+    }
+}
+
 impl Default for ApplicationResources {
     fn default() -> Self {
         let config = Arc::new(Config::default());
@@ -161,6 +177,7 @@ impl Files {
 
 pub struct ResourceFile {
     pub data: Cow<'static, [u8]>,
+    pub path: PathBuf,
     pub metadata: Metadata,
 }
 
@@ -210,6 +227,7 @@ fn read_file_from_fs(file_path: &Path, config: &Config) -> io::Result<ResourceFi
 
     Ok(ResourceFile {
         data,
+        path: file_path.to_path_buf(),
         metadata: Metadata {
             hash,
             last_modified: source_date_epoch.or(last_modified),
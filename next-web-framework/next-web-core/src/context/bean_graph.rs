@@ -0,0 +1,512 @@
+use std::{borrow::Cow, fmt::Write};
+
+use serde_json::{json, Value};
+
+use crate::context::application_context::{Key, Scope};
+
+/// 依赖图
+///
+/// The providers of an [`ApplicationContext`](crate::ApplicationContext) and the dependencies
+/// between them, taken with [`ApplicationContext::bean_graph`](crate::ApplicationContext::bean_graph).
+///
+/// The edges are recorded while the instances are created, a dependency shows up once the
+/// constructor requesting it has run. [`ApplicationContext::dry_run_async`](crate::ApplicationContext::dry_run_async)
+/// runs the remaining constructors to complete it.
+#[derive(Debug, Clone, Default)]
+pub struct BeanGraph {
+    nodes: Vec<BeanNode>,
+    edges: Vec<BeanEdge>,
+    errors: Vec<DependencyError>,
+}
+
+/// A provider, or an instance registered without one.
+#[derive(Debug, Clone)]
+pub struct BeanNode {
+    pub key: Key,
+    /// `None` for an instance registered without a provider.
+    pub scope: Option<Scope>,
+    /// Whether an instance is held by the context.
+    pub created: bool,
+}
+
+/// `from` requested `to` while it was created.
+#[derive(Debug, Clone)]
+pub struct BeanEdge {
+    pub from: Key,
+    pub to: Key,
+    /// No provider is registered for `to`, it was requested as an optional dependency.
+    pub absent: bool,
+}
+
+/// 依赖错误
+///
+/// A dependency that could not be resolved. The context panics with it, or collects them all
+/// while creating the eager instances and during a dry run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DependencyError {
+    /// No provider is registered for `key`.
+    Missing {
+        key: Key,
+        /// The providers being created, the direct requester first.
+        required_by: Vec<Key>,
+        /// Names the same type is registered under.
+        candidates: Vec<Cow<'static, str>>,
+    },
+    /// `cycle` requests itself, its first and last keys are the same.
+    Circular {
+        cycle: Vec<Key>,
+        /// The providers being created before the cycle, the direct requester first.
+        required_by: Vec<Key>,
+    },
+}
+
+impl DependencyError {
+    /// Whether both report the same problem, reached through different providers.
+    pub(crate) fn same_as(&self, other: &DependencyError) -> bool {
+        match (self, other) {
+            (
+                DependencyError::Missing {
+                    key, required_by, ..
+                },
+                DependencyError::Missing {
+                    key: other_key,
+                    required_by: other_required_by,
+                    ..
+                },
+            ) => key == other_key && required_by.first() == other_required_by.first(),
+            (
+                DependencyError::Circular { cycle, .. },
+                DependencyError::Circular {
+                    cycle: other_cycle, ..
+                },
+            ) => {
+                cycle.len() == other_cycle.len()
+                    && cycle.iter().all(|key| other_cycle.contains(key))
+            }
+            _ => false,
+        }
+    }
+}
+
+impl std::fmt::Display for DependencyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let required_by = match self {
+            DependencyError::Missing {
+                key,
+                required_by,
+                candidates,
+            } => {
+                write!(f, "no provider registered for: {:?}", key)?;
+                write_required_by(f, required_by)?;
+
+                if !candidates.is_empty() {
+                    write!(
+                        f,
+                        "\n\ndid you mean the same type registered under another name:"
+                    )?;
+                    for name in candidates {
+                        write!(f, "\n  - {:?}", name)?;
+                    }
+                }
+                return Ok(());
+            }
+            DependencyError::Circular { cycle, required_by } => {
+                writeln!(f, "circular dependency detected:")?;
+                if let Some((last, keys)) = cycle.split_last() {
+                    for key in keys {
+                        write!(f, "  {:?}\n  --> ", key)?;
+                    }
+                    write!(f, "{:?}", last)?;
+                }
+                required_by
+            }
+        };
+
+        write_required_by(f, required_by)
+    }
+}
+
+fn write_required_by(f: &mut std::fmt::Formatter<'_>, required_by: &[Key]) -> std::fmt::Result {
+    if !required_by.is_empty() {
+        write!(f, "\n\nrequired by:")?;
+        for key in required_by {
+            write!(f, "\n  <-- {:?}", key)?;
+        }
+    }
+    Ok(())
+}
+
+/// The format of `--print-bean-graph`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum BeanGraphFormat {
+    Dot,
+    Json,
+}
+
+impl BeanGraph {
+    pub(crate) fn new(
+        mut nodes: Vec<BeanNode>,
+        mut edges: Vec<BeanEdge>,
+        errors: Vec<DependencyError>,
+    ) -> Self {
+        nodes.sort_by_key(|node| node_id(&node.key));
+        edges.sort_by_key(|edge| (node_id(&edge.from), node_id(&edge.to)));
+        Self {
+            nodes,
+            edges,
+            errors,
+        }
+    }
+
+    pub fn nodes(&self) -> &[BeanNode] {
+        &self.nodes
+    }
+
+    pub fn edges(&self) -> &[BeanEdge] {
+        &self.edges
+    }
+
+    /// The missing and circular dependencies found by a dry run.
+    pub fn errors(&self) -> &[DependencyError] {
+        &self.errors
+    }
+
+    /// The keys requested by `key`.
+    pub fn dependencies_of<'a>(&'a self, key: &'a Key) -> impl Iterator<Item = &'a Key> + 'a {
+        self.edges
+            .iter()
+            .filter(move |edge| &edge.from == key)
+            .map(|edge| &edge.to)
+    }
+
+    /// The keys requesting `key`.
+    pub fn dependents_of<'a>(&'a self, key: &'a Key) -> impl Iterator<Item = &'a Key> + 'a {
+        self.edges
+            .iter()
+            .filter(move |edge| &edge.to == key)
+            .map(|edge| &edge.from)
+    }
+
+    /// The optional dependencies without a provider, with the providers requesting them.
+    pub fn absent_dependencies(&self) -> impl Iterator<Item = &BeanEdge> {
+        self.edges.iter().filter(|edge| edge.absent)
+    }
+
+    pub fn render(&self, format: BeanGraphFormat) -> String {
+        match format {
+            BeanGraphFormat::Dot => self.to_dot(),
+            BeanGraphFormat::Json => serde_json::to_string_pretty(&self.to_json()).unwrap(),
+        }
+    }
+
+    /// Graphviz source, e.g. `dot -Tsvg beans.dot -o beans.svg`.
+    pub fn to_dot(&self) -> String {
+        let mut buf = String::from("digraph beans {\n    rankdir=LR;\n    node [shape=box];\n");
+
+        for node in &self.nodes {
+            let scope = node
+                .scope
+                .map(|scope| format!("{:?}", scope))
+                .unwrap_or_else(|| "Instance".into());
+            let style = if node.created { "" } else { ", style=dashed" };
+            let _ = writeln!(
+                buf,
+                "    \"{}\" [label=\"{}\\n{}\\n({})\"{}];",
+                escape(&node_id(&node.key)),
+                escape(&node.key.name),
+                escape(node.key.ty.name),
+                scope,
+                style
+            );
+        }
+
+        for edge in self.edges.iter().filter(|edge| edge.absent) {
+            let _ = writeln!(
+                buf,
+                "    \"{}\" [label=\"{}\\n{}\\n(absent)\", color=red, style=dashed];",
+                escape(&node_id(&edge.to)),
+                escape(&edge.to.name),
+                escape(edge.to.ty.name),
+            );
+        }
+
+        for error in &self.errors {
+            if let DependencyError::Missing {
+                key, required_by, ..
+            } = error
+            {
+                let _ = writeln!(
+                    buf,
+                    "    \"{}\" [label=\"{}\\n{}\\n(missing)\", color=red];",
+                    escape(&node_id(key)),
+                    escape(&key.name),
+                    escape(key.ty.name),
+                );
+                if let Some(requester) = required_by.first() {
+                    let _ = writeln!(
+                        buf,
+                        "    \"{}\" -> \"{}\" [color=red];",
+                        escape(&node_id(requester)),
+                        escape(&node_id(key)),
+                    );
+                }
+            }
+        }
+
+        for edge in &self.edges {
+            let style = if edge.absent {
+                " [color=red, style=dashed]"
+            } else {
+                ""
+            };
+            let _ = writeln!(
+                buf,
+                "    \"{}\" -> \"{}\"{};",
+                escape(&node_id(&edge.from)),
+                escape(&node_id(&edge.to)),
+                style
+            );
+        }
+
+        buf.push_str("}\n");
+        buf
+    }
+
+    pub fn to_json(&self) -> Value {
+        let nodes = self
+            .nodes
+            .iter()
+            .map(|node| {
+                json!({
+                    "id": node_id(&node.key),
+                    "name": node.key.name,
+                    "type": node.key.ty.name,
+                    "scope": node.scope.map(|scope| format!("{:?}", scope)),
+                    "created": node.created,
+                })
+            })
+            .collect::<Vec<_>>();
+        let edges = self
+            .edges
+            .iter()
+            .map(|edge| {
+                json!({
+                    "from": node_id(&edge.from),
+                    "to": node_id(&edge.to),
+                    "absent": edge.absent,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let errors = self
+            .errors
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+
+        json!({ "nodes": nodes, "edges": edges, "errors": errors })
+    }
+}
+
+/// `name: type`, unique for a key.
+pub(crate) fn node_id(key: &Key) -> String {
+    format!("{}: {}", key.name, key.ty.name)
+}
+
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DependencyError;
+    use crate::context::application_context::{
+        singleton, ApplicationContext, DynProvider, Module, Provider, ResolveModule,
+    };
+
+    #[derive(Clone)]
+    struct Repository;
+
+    #[derive(Clone)]
+    struct Cache;
+
+    #[derive(Clone)]
+    #[allow(dead_code)]
+    struct Service(Repository, Option<Cache>);
+
+    struct TestModule;
+
+    impl Module for TestModule {
+        fn providers() -> Vec<DynProvider> {
+            vec![
+                Provider::from(singleton(|_| Repository).name("repository")).into(),
+                Provider::from(
+                    singleton(|cx| {
+                        Service(
+                            cx.resolve_with_name("repository"),
+                            cx.resolve_option_with_name("cache"),
+                        )
+                    })
+                    .name("service"),
+                )
+                .into(),
+            ]
+        }
+    }
+
+    #[test]
+    fn test_bean_graph() {
+        let mut ctx = ApplicationContext::create(vec![ResolveModule::new::<TestModule>()]);
+        ctx.resolve_with_name::<Service>("service");
+
+        let graph = ctx.bean_graph();
+        assert_eq!(graph.nodes().len(), 2);
+        assert!(graph.nodes().iter().all(|node| node.created));
+
+        let service = &graph.nodes()[1].key;
+        let dependencies = graph.dependencies_of(service).collect::<Vec<_>>();
+        assert_eq!(dependencies.len(), 2);
+        assert_eq!(graph.dependents_of(&graph.nodes()[0].key).count(), 1);
+
+        let absent = graph.absent_dependencies().collect::<Vec<_>>();
+        assert_eq!(absent.len(), 1);
+        assert_eq!(absent[0].to.name, "cache");
+
+        let dot = graph.to_dot();
+        assert!(dot.contains("-> \"cache: "));
+        assert!(dot.contains("color=red"));
+
+        let json = graph.to_json();
+        assert_eq!(json["nodes"].as_array().unwrap().len(), 2);
+        assert_eq!(json["edges"].as_array().unwrap().len(), 2);
+    }
+
+    #[derive(Clone)]
+    struct Left;
+
+    #[derive(Clone)]
+    struct Right;
+
+    struct CycleModule;
+
+    impl Module for CycleModule {
+        fn providers() -> Vec<DynProvider> {
+            vec![
+                Provider::from(
+                    singleton(|cx| {
+                        cx.resolve_with_name::<Right>("right");
+                        Left
+                    })
+                    .name("left"),
+                )
+                .into(),
+                Provider::from(
+                    singleton(|cx| {
+                        cx.resolve_with_name::<Left>("left");
+                        Right
+                    })
+                    .name("right"),
+                )
+                .into(),
+            ]
+        }
+    }
+
+    #[derive(Clone)]
+    struct Report;
+
+    #[derive(Clone)]
+    struct Mailer;
+
+    struct BrokenModule;
+
+    impl Module for BrokenModule {
+        fn providers() -> Vec<DynProvider> {
+            vec![
+                Provider::from(
+                    singleton(|cx| {
+                        cx.resolve_with_name::<Repository>("repo");
+                        Report
+                    })
+                    .name("report"),
+                )
+                .into(),
+                Provider::from(
+                    singleton(|cx| {
+                        cx.resolve_with_name::<Cache>("cache");
+                        Mailer
+                    })
+                    .name("mailer"),
+                )
+                .into(),
+            ]
+        }
+    }
+
+    #[test]
+    fn test_eager_creation_reports_every_error() {
+        let result = std::panic::catch_unwind(|| {
+            ApplicationContext::options().eager_create(true).create(vec![
+                ResolveModule::new::<BrokenModule>(),
+                ResolveModule::new::<CycleModule>(),
+            ])
+        });
+        let error = result.err().unwrap();
+        let message = error.downcast_ref::<String>().unwrap();
+
+        assert!(message.starts_with("3 dependency errors:"));
+        assert!(message.contains("name: \"repo\""));
+        assert!(message.contains("name: \"cache\""));
+        assert_eq!(message.matches("circular dependency detected:").count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_dry_run() {
+        let ctx = ApplicationContext::create(vec![
+            ResolveModule::new::<TestModule>(),
+            ResolveModule::new::<BrokenModule>(),
+            ResolveModule::new::<CycleModule>(),
+        ]);
+
+        let graph = ctx.dry_run_async().await;
+        assert!(graph.nodes().iter().all(|node| !node.created));
+        assert!(!ctx.contains_single_with_name::<Service>("service"));
+
+        let errors = graph.errors();
+        assert_eq!(errors.len(), 3);
+        assert!(errors.iter().any(|error| matches!(
+            error,
+            DependencyError::Missing { key, candidates, .. }
+                if key.name == "repo" && candidates == &["repository"]
+        )));
+        assert!(errors
+            .iter()
+            .any(|error| matches!(error, DependencyError::Circular { cycle, .. } if cycle.len() == 3)));
+
+        assert_eq!(graph.edges().len(), 4);
+        assert!(graph.to_dot().contains("(missing)"));
+        assert_eq!(graph.to_json()["errors"].as_array().unwrap().len(), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "did you mean the same type registered under another name:\n  - \"repository\"")]
+    fn test_missing_provider_suggestion() {
+        let mut ctx = ApplicationContext::create(vec![ResolveModule::new::<TestModule>()]);
+        ctx.resolve_with_name::<Repository>("repo");
+    }
+
+    #[test]
+    fn test_circular_dependency() {
+        let result = std::panic::catch_unwind(|| {
+            let mut ctx = ApplicationContext::create(vec![ResolveModule::new::<CycleModule>()]);
+            ctx.resolve_with_name::<Left>("left");
+        });
+        let error = result.unwrap_err();
+        let message = error.downcast_ref::<String>().unwrap();
+
+        assert!(message.starts_with("circular dependency detected:"));
+        assert_eq!(message.matches("--> ").count(), 2);
+        assert_eq!(message.matches("name: \"left\"").count(), 2);
+        assert_eq!(message.matches("name: \"right\"").count(), 1);
+    }
+}
@@ -0,0 +1,133 @@
+use serde_yaml::Value;
+
+use crate::context::application_context::ApplicationContext;
+use crate::context::properties::ApplicationProperties;
+
+/// 条件注册
+///
+/// The checks behind the `on_property`, `on_profile`, `on_bean` and `on_missing_bean`
+/// attributes of `#[Singleton]`, `#[SingleOwner]` and `#[Transient]`, they are evaluated once
+/// every unconditional provider is loaded, against the properties given by
+/// [`ApplicationContextOptions::properties`](crate::ApplicationContextOptions::properties).
+///
+/// ```ignore
+/// // Switched off with `next.data.redis.enabled: false`, replaced by a user `RedisService`
+/// #[Singleton(on_property = "next.data.redis.enabled!=false", on_missing_bean = RedisService)]
+/// #[derive(Clone)]
+/// pub struct RedisServiceAutoRegister(pub RedisClientProperties);
+/// ```
+pub struct Conditions;
+
+impl Conditions {
+    /// - `key` the key exists and its value is not `false`
+    /// - `key=value` the value of the key is `value`
+    /// - `key!=value` the key is missing or its value is not `value`
+    pub fn on_property(ctx: &ApplicationContext, expression: &str) -> bool {
+        Self::matches_property(ctx.properties(), expression)
+    }
+
+    /// The profiles are separated by `,`, one of them must be active, a profile starting with
+    /// `!` matches when it is not active, e.g. `dev,local` or `!prod`.
+    pub fn on_profile(ctx: &ApplicationContext, expression: &str) -> bool {
+        Self::matches_profile(ctx.properties(), expression)
+    }
+
+    /// A provider or an instance of `T` is registered, whatever its name.
+    pub fn on_bean<T: 'static>(ctx: &ApplicationContext) -> bool {
+        ctx.contains_type::<T>()
+    }
+
+    /// No provider or instance of `T` is registered, whatever its name.
+    pub fn on_missing_bean<T: 'static>(ctx: &ApplicationContext) -> bool {
+        !ctx.contains_type::<T>()
+    }
+
+    fn matches_property(properties: Option<&ApplicationProperties>, expression: &str) -> bool {
+        let value = |key: &str| {
+            properties
+                .and_then(|properties| properties.one_value::<Value>(key.trim()))
+                .filter(|value| !value.is_null())
+                .map(|value| match value {
+                    Value::String(s) => s,
+                    Value::Bool(b) => b.to_string(),
+                    Value::Number(n) => n.to_string(),
+                    // Mappings and sequences are only checked for their presence
+                    _ => String::new(),
+                })
+        };
+
+        if let Some((key, expected)) = expression.split_once("!=") {
+            return value(key).is_none_or(|value| value != expected.trim());
+        }
+
+        if let Some((key, expected)) = expression.split_once('=') {
+            return value(key).is_some_and(|value| value == expected.trim());
+        }
+
+        value(expression).is_some_and(|value| value != "false")
+    }
+
+    fn matches_profile(properties: Option<&ApplicationProperties>, expression: &str) -> bool {
+        let is_active = |profile: &str| {
+            properties.is_some_and(|properties| properties.is_profile_active(profile))
+        };
+
+        expression
+            .split(',')
+            .map(str::trim)
+            .filter(|profile| !profile.is_empty())
+            .any(|profile| match profile.strip_prefix('!') {
+                Some(profile) => !is_active(profile.trim()),
+                None => is_active(profile),
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::property_sources::{PropertySource, PropertySources};
+
+    fn properties() -> ApplicationProperties {
+        let mut sources = PropertySources::new();
+        sources.add_last(PropertySource::from_pairs(
+            "test",
+            [
+                ("next.data.redis.enabled", "false"),
+                ("next.data.redis.host", "localhost"),
+                ("next.mqtt.port", "1883"),
+            ],
+        )
+        .unwrap());
+        ApplicationProperties::from_sources(&sources, vec!["dev".into()]).unwrap()
+    }
+
+    #[test]
+    fn test_on_property() {
+        let properties = properties();
+        let matches = |expression| Conditions::matches_property(Some(&properties), expression);
+
+        assert!(matches("next.data.redis.host"));
+        assert!(!matches("next.data.redis.enabled"));
+        assert!(!matches("next.data.database.host"));
+
+        assert!(matches("next.mqtt.port=1883"));
+        assert!(!matches("next.mqtt.port=1884"));
+
+        assert!(!matches("next.data.redis.enabled!=false"));
+        assert!(matches("next.data.database.enabled!=false"));
+        assert!(Conditions::matches_property(None, "next.mqtt.enabled!=false"));
+    }
+
+    #[test]
+    fn test_on_profile() {
+        let properties = properties();
+        let matches = |expression| Conditions::matches_profile(Some(&properties), expression);
+
+        assert!(matches("dev"));
+        assert!(matches("prod, dev"));
+        assert!(!matches("prod"));
+        assert!(matches("!prod"));
+        assert!(!matches("!dev"));
+    }
+}
@@ -2,5 +2,10 @@ pub mod application_args;
 pub mod application_context;
 
 pub mod application_resources;
+pub mod bean_graph;
+pub mod conditions;
 pub mod next_properties;
 pub mod properties;
+pub mod properties_validator;
+pub mod property_sources;
+pub mod scope;
@@ -1,14 +1,19 @@
 use crate::autoconfigure::context::application_properties::AppliationProperties;
 use crate::autoconfigure::context::logging_properties::LoggingProperties;
+use crate::autoconfigure::context::management_properties::ManagementProperties;
 use crate::autoconfigure::context::messages_properties::MessagesProperties;
+use crate::autoconfigure::context::openapi_properties::OpenApiProperties;
 use crate::autoconfigure::context::server_properties::ServerProperties;
 
 #[derive(Debug, Clone, serde::Deserialize, Default)]
+#[serde(default)]
 pub struct NextProperties {
     server: ServerProperties,
     appliation: Option<AppliationProperties>,
     messages: Option<MessagesProperties>,
     logging: Option<LoggingProperties>,
+    management: Option<ManagementProperties>,
+    openapi: Option<OpenApiProperties>,
 }
 
 impl NextProperties {
@@ -26,4 +31,12 @@ impl NextProperties {
     pub fn messages(&self) -> Option<&MessagesProperties> {
         self.messages.as_ref()
     }
+
+    pub fn management(&self) -> Option<&ManagementProperties> {
+        self.management.as_ref()
+    }
+
+    pub fn openapi(&self) -> Option<&OpenApiProperties> {
+        self.openapi.as_ref()
+    }
 }
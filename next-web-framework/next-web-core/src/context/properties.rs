@@ -2,14 +2,25 @@ use dyn_clone::DynClone;
 
 use std::collections::HashMap;
 use std::fmt::Debug;
-use std::io::Read;
+use std::path::{Path, PathBuf};
 
-use crate::constants::application_constants::APPLICATION_CONFIG;
+use serde_yaml::Value;
+use tracing::warn;
+
+use crate::constants::application_constants::{
+    APPLICATION_CONFIG, APPLICATION_CONFIG_SUFFIX, APPLICATION_PROFILE_CONFIG_PREFIX,
+    NEXT_APPLICATION_JSON, NEXT_PROFILES_ACTIVE,
+};
 use crate::context::application_args::ApplicationArgs;
 use crate::context::application_resources::ResourceLoader;
+use crate::context::property_sources::{merge_value, parse_scalar, PropertySource, PropertySources};
 
 use super::application_resources::ApplicationResources;
 use super::next_properties::NextProperties;
+use crate::context::application_context::ApplicationContext;
+use crate::context::properties_validator::normalize_path;
+use crate::context::property_sources::insert_by_path;
+use crate::error::properties_validation_error::{PropertiesValidationError, PropertyViolation};
 use crate::AutoRegister;
 
 /// ApplicationProperties trait
@@ -18,23 +29,125 @@ use crate::AutoRegister;
 ///
 /// Please implement this trait in your application.
 ///
-pub trait Properties: DynClone + AutoRegister {}
+pub trait Properties: DynClone + AutoRegister {
+    /// The prefix the properties are bound from.
+    fn prefix(&self) -> &'static str {
+        ""
+    }
+
+    /// The keys under [`prefix`](Properties::prefix) the properties are bound from.
+    ///
+    /// Returns `None` if the keys are dynamic and cannot be checked.
+    fn property_keys(&self) -> Option<&'static [&'static str]> {
+        None
+    }
+
+    /// Validate the bound values, e.g. missing required keys and invalid types.
+    #[allow(unused_variables)]
+    fn validate(&self, properties: &ApplicationProperties) -> Vec<PropertyViolation> {
+        Vec::new()
+    }
+
+    /// Whether the properties are bound again when the configuration is refreshed.
+    fn refreshable(&self) -> bool {
+        false
+    }
+
+    /// Bind the properties again and replace the registered singleton.
+    #[allow(unused_variables)]
+    fn rebind(
+        &self,
+        ctx: &mut ApplicationContext,
+        properties: &ApplicationProperties,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        Ok(())
+    }
+}
 
 #[derive(Debug, Clone, serde::Deserialize)]
 pub struct ApplicationProperties {
     /// This Properties is Mapping data from the configuration file
+    #[serde(default)]
     next: NextProperties,
 
     /// Only for register that have not been deserialized
     #[serde(skip_deserializing)]
     mapping: Option<serde_yaml::Value>,
+
+    /// Profiles that were active when the configuration was loaded
+    #[serde(skip_deserializing)]
+    active_profiles: Vec<String>,
+
+    /// Configuration files the properties were loaded from
+    #[serde(skip_deserializing)]
+    config_files: Vec<PathBuf>,
 }
 
 impl ApplicationProperties {
+    /// Build the properties from the given sources only, without reading the configuration
+    /// files, the environment or the command line, e.g. an in-memory configuration in tests.
+    ///
+    /// Placeholders are replaced, see [`replace_placeholders`](ApplicationProperties::replace_placeholders).
+    /// Fails when the sources conflict or the `next` properties cannot be bound.
+    pub fn from_sources(
+        sources: &PropertySources,
+        active_profiles: Vec<String>,
+    ) -> Result<Self, PropertiesValidationError> {
+        let mut application_properties = ApplicationProperties::default();
+        application_properties.set_mapping(sources.merge()?);
+        application_properties.active_profiles = active_profiles;
+        application_properties.replace_placeholders()?;
+        Ok(application_properties)
+    }
+
+    /// Whether the dotted `path` is a key of the typed `next` properties, e.g.
+    /// `next.server.context_path`.
+    pub(crate) fn is_known_key(path: &str) -> bool {
+        let mut mapping = Value::Null;
+        if insert_by_path(&mut mapping, path, Value::Null).is_err() {
+            return false;
+        }
+
+        let mut ignored = false;
+        let mut callback = |_: serde_ignored::Path| ignored = true;
+        let deserializer = serde_ignored::Deserializer::new(mapping, &mut callback);
+        let error = serde_path_to_error::deserialize::<_, ApplicationProperties>(deserializer).err();
+        if ignored {
+            return false;
+        }
+
+        // The value of the key itself is invalid, but the key exists
+        match error {
+            Some(error) => {
+                let error_path = normalize_path(&error.path().to_string());
+                error_path == path
+                    || !path.starts_with(&format!("{}.", error_path))
+                    || error.inner().to_string().starts_with("missing field")
+            }
+            None => true,
+        }
+    }
+
     pub fn next(&self) -> &NextProperties {
         &self.next
     }
 
+    /// Get the active profiles, in the order they were applied.
+    pub fn active_profiles(&self) -> &[String] {
+        &self.active_profiles
+    }
+
+    /// Get the configuration files the properties were loaded from, including the
+    /// `--config-location` file.
+    pub fn config_files(&self) -> &[PathBuf] {
+        &self.config_files
+    }
+
+    /// Whether the given profile is active.
+    pub fn is_profile_active(&self, profile: &str) -> bool {
+        self.active_profiles.iter().any(|s| s == profile)
+    }
+
     /// Get a single value from the mapping
     ///
     /// # Example
@@ -141,22 +254,71 @@ impl ApplicationProperties {
     }
 
     /// Replace the placeholders in the properties.
-    pub fn replace_placeholders(&mut self) {
-        // Two situations
-        // ${author.name}  $${MY_ENV_VAR}
-
+    ///
+    /// Placeholders may appear anywhere in a string value and may carry a default after the first `:`
+    ///
+    /// `${author.name}` `${author.name:anonymous}` refer to another key of the mapping,
+    /// `${MY_ENV_VAR}` `${DB_HOST:localhost}` refer to an environment variable.
+    ///
+    /// Placeholders that cannot be resolved are kept and reported during validation.
+    pub fn replace_placeholders(&mut self) -> Result<(), PropertiesValidationError> {
         let temporary = self.mapping.clone();
-        self.mapping.as_mut().map(|mapping| {
-            let mapping = match mapping.as_mapping_mut() {
-                Some(mapping) => mapping,
-                None => return,
-            };
+        if let Some(mapping) = self.mapping.as_mut() {
+            helper(temporary.as_ref(), mapping);
+        }
 
-            mapping
-                .iter_mut()
-                .map(|val| val.1)
-                .for_each(|value| helper(temporary.as_ref(), value));
-        });
+        self.rebind()
+    }
+
+    /// Deserialize the typed `next` properties again from the current mapping.
+    ///
+    /// Fails with the key that cannot be bound, the `PropertiesValidator` reports every problem.
+    pub fn rebind(&mut self) -> Result<(), PropertiesValidationError> {
+        let Some(mapping) = self.mapping.as_ref() else {
+            return Ok(());
+        };
+
+        match serde_path_to_error::deserialize::<_, ApplicationProperties>(mapping.clone()) {
+            Ok(properties) => {
+                self.next = properties.next.clone();
+                Ok(())
+            }
+            Err(error) => Err(PropertyViolation::new(
+                normalize_path(&error.path().to_string()),
+                error.inner().to_string(),
+            )
+            .into()),
+        }
+    }
+
+    /// Whether the mapping contains the dotted key.
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.mapping
+            .as_ref()
+            .and_then(|mapping| lookup(mapping, key))
+            .is_some()
+    }
+
+    /// Get the direct child keys of the dotted prefix.
+    pub fn child_keys(&self, prefix: &str) -> Vec<String> {
+        let value = match self.mapping.as_ref() {
+            Some(mapping) if prefix.is_empty() => Some(mapping),
+            Some(mapping) => lookup(mapping, prefix),
+            None => None,
+        };
+
+        match value {
+            Some(Value::Mapping(mapping)) => mapping
+                .keys()
+                .filter_map(|key| match key {
+                    Value::String(s) => Some(s.clone()),
+                    Value::Number(n) => Some(n.to_string()),
+                    Value::Bool(b) => Some(b.to_string()),
+                    _ => None,
+                })
+                .collect(),
+            _ => Vec::new(),
+        }
     }
 
     pub fn set_mapping(&mut self, mapping: serde_yaml::Value) {
@@ -172,62 +334,116 @@ impl ApplicationProperties {
     }
 }
 
-fn helper(temporary: Option<&serde_yaml::Value>, value: &mut serde_yaml::Value) {
+fn helper(temporary: Option<&Value>, value: &mut Value) {
     match value {
-        serde_yaml::Value::String(s) => {
-            let s = s.trim();
-            if s.starts_with("${") && s.ends_with("}") {
-                let key = s[2..s.len() - 1].to_string();
-
-                if key
-                    .as_str()
-                    .chars()
-                    .filter(|&c| c != '_' && c.is_alphabetic())
-                    .all(|c| c.is_uppercase())
-                {
-                    // My suggestion is to panic directly
-                    let var = match std::env::var(& key) {
-                                Ok(var) => var,
-                                Err(_) => panic!("In the configuration file, the environment variable [{}] cannot be obtained. Please check the environment configuration.", key),
-                            };
-                    *value = serde_yaml::Value::String(var);
-                    return;
-                }
-                let mut temporary = match temporary {
-                    Some(temporary) => temporary,
-                    None => return,
-                };
-
-                let mut iter = key.split('.').peekable();
-                while let Some(parts) = iter.next() {
-                    match temporary.get(parts) {
-                        Some(val) => {
-                            temporary = val;
-                        }
-                        None => panic!("The key [{}] is not found in the configuration file", key),
-                    }
-
-                    if iter.peek().is_none() {
-                        // End of the key
-                        *value = temporary.clone();
-                    }
-                }
+        Value::String(s) => {
+            if let Some(resolved) = resolve_placeholders(temporary, s) {
+                *value = resolved;
             }
         }
-        serde_yaml::Value::Mapping(mapping) => {
+        Value::Mapping(mapping) => {
             mapping
                 .iter_mut()
                 .for_each(|(_, value)| helper(temporary, value));
         }
+        Value::Sequence(sequence) => {
+            sequence
+                .iter_mut()
+                .for_each(|value| helper(temporary, value));
+        }
         _ => return,
     };
 }
 
+fn resolve_placeholders(temporary: Option<&Value>, s: &str) -> Option<Value> {
+    if !s.contains("${") {
+        return None;
+    }
+
+    // The whole value is a single placeholder, keep the type of the resolved value
+    let trimmed = s.trim();
+    if trimmed.starts_with("${") && trimmed.find('}') == Some(trimmed.len() - 1) {
+        return resolve_placeholder(temporary, &trimmed[2..trimmed.len() - 1]);
+    }
+
+    let mut result = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(start) = rest.find("${") {
+        let end = match rest[start..].find('}') {
+            Some(end) => start + end,
+            None => break,
+        };
+
+        result.push_str(&rest[..start]);
+        match resolve_placeholder(temporary, &rest[start + 2..end]) {
+            Some(Value::String(s)) => result.push_str(&s),
+            Some(Value::Number(n)) => result.push_str(&n.to_string()),
+            Some(Value::Bool(b)) => result.push_str(&b.to_string()),
+            Some(Value::Null) => {}
+            Some(other) => result.push_str(serde_yaml::to_string(&other).unwrap_or_default().trim()),
+            // Keep it as is, it is reported by the `PropertiesValidator`
+            None => result.push_str(&rest[start..=end]),
+        }
+        rest = &rest[end + 1..];
+    }
+    result.push_str(rest);
+
+    Some(Value::String(result))
+}
+
+/// Returns `None` when neither the placeholder nor its default can be resolved.
+fn resolve_placeholder(temporary: Option<&Value>, expression: &str) -> Option<Value> {
+    let (key, default) = match expression.split_once(':') {
+        Some((key, default)) => (key.trim(), Some(default)),
+        None => (expression.trim(), None),
+    };
+
+    let is_env = key
+        .chars()
+        .filter(|&c| c != '_' && c.is_alphabetic())
+        .all(|c| c.is_uppercase());
+
+    let resolved = match is_env {
+        true => std::env::var(key).ok().map(|var| parse_scalar(&var)),
+        false => temporary.and_then(|temporary| lookup(temporary, key)).cloned(),
+    };
+
+    resolved.or_else(|| default.map(parse_scalar))
+}
+
+/// Returns the placeholders in `s` that could not be resolved.
+pub(crate) fn unresolved_placeholders(s: &str) -> Vec<&str> {
+    let mut placeholders = Vec::new();
+    let mut rest = s;
+    while let Some(start) = rest.find("${") {
+        match rest[start..].find('}') {
+            Some(end) => {
+                placeholders.push(&rest[start..=start + end]);
+                rest = &rest[start + end + 1..];
+            }
+            None => break,
+        }
+    }
+    placeholders
+}
+
+pub(crate) fn lookup<'a>(mut value: &'a Value, key: &str) -> Option<&'a Value> {
+    for part in key.split('.') {
+        value = match value {
+            Value::Sequence(sequence) => sequence.get(part.parse::<usize>().ok()?)?,
+            _ => value.get(part)?,
+        };
+    }
+    Some(value)
+}
+
 impl Default for ApplicationProperties {
     fn default() -> Self {
         Self {
             next: NextProperties::default(),
             mapping: None,
+            active_profiles: Vec::new(),
+            config_files: Vec::new(),
         }
     }
 }
@@ -235,54 +451,181 @@ impl Default for ApplicationProperties {
 fn into_application_properties(
     application_args: &ApplicationArgs,
     application_resources: &ApplicationResources,
-) -> ApplicationProperties {
-    use serde_yaml::Value;
+) -> Result<ApplicationProperties, PropertiesValidationError> {
+    let mut sources = PropertySources::new();
+    let mut config_files = Vec::new();
+
+    // 1. Base configuration file
+    if let Some(data) = application_resources.load(APPLICATION_CONFIG) {
+        config_files.extend(application_resources.file_path(APPLICATION_CONFIG).map(Path::to_path_buf));
+        sources.add_last(PropertySource::new(
+            APPLICATION_CONFIG,
+            parse_config(APPLICATION_CONFIG, data)?,
+        ));
+    }
+
+    // 2. External configuration file, it is read here so that it can activate profiles
+    let external = application_args
+        .config_location
+        .as_ref()
+        .filter(|path| !path.is_empty())
+        .map(|path| read_external_config(path).map(|value| PropertySource::new(path.as_str(), value)))
+        .transpose()?;
+
+    // 3. Profile specific configuration files
+    let active_profiles = resolve_active_profiles(application_args, &sources, external.as_ref())?;
+    for profile in active_profiles.iter() {
+        let name = format!(
+            "{}{}{}",
+            APPLICATION_PROFILE_CONFIG_PREFIX, profile, APPLICATION_CONFIG_SUFFIX
+        );
+        match application_resources.load(&name) {
+            Some(data) => {
+                config_files.extend(application_resources.file_path(&name).map(Path::to_path_buf));
+                sources.add_last(PropertySource::new(name.as_str(), parse_config(&name, data)?))
+            }
+            None => warn!("The configuration file for profile [{}] was not found: {}", profile, name),
+        }
+    }
 
-    let config_path: Option<String> = application_args.config_location.clone();
+    if let Some(external) = external {
+        config_files.push(PathBuf::from(external.name()));
+        sources.add_last(external);
+    }
 
-    let mut config = String::new();
+    // 4. Environment, `NEXT_APPLICATION_JSON` first then relaxed binding of single keys
+    if let Ok(json) = std::env::var(NEXT_APPLICATION_JSON) {
+        let value = serde_yaml::from_str::<Value>(&json).map_err(|error| {
+            PropertyViolation::new(
+                "",
+                format!(
+                    "The environment variable [{}] is not a valid json/yaml document: {}",
+                    NEXT_APPLICATION_JSON, error
+                ),
+            )
+        })?;
+        sources.add_last(PropertySource::new(NEXT_APPLICATION_JSON, value));
+    }
 
-    if config_path.as_ref().map(|s| !s.is_empty()).unwrap_or(false) {
-        let path = config_path.unwrap();
-        if let Ok(_) = std::fs::exists(&path) {
-            let mut file = std::fs::File::open(&path).unwrap();
-            let mut _buffer = String::new();
-            file.read_to_string(&mut _buffer).unwrap();
+    let environment = PropertySource::from_environment(
+        "environment",
+        &sources.merge()?,
+        std::env::vars(),
+        ApplicationProperties::is_known_key,
+    )?;
+    sources.add_last(environment);
+
+    // 5. Command line overrides
+    if !application_args.property_overrides.is_empty() {
+        sources.add_last(PropertySource::from_pairs(
+            "command-line",
+            application_args
+                .property_overrides
+                .iter()
+                .map(|(key, value)| (key.as_str(), value.as_str())),
+        )?);
+    }
 
-            if _buffer.is_empty() {
-                panic!(
-                    "The application configuration file is empty, file path: {}",
-                    &path
-                );
-            }
+    // into application properties, the remaining problems are reported by the `PropertiesValidator` before startup
+    let mut application_properties = ApplicationProperties::from_sources(&sources, active_profiles)?;
+    application_properties.config_files = config_files;
+    Ok(application_properties)
+}
 
-            config = _buffer;
-        } else {
-            panic!(
-                "Please check if the configuration file of the application exists: {:?}",
-                &path
-            );
-        }
-    } else {
-        if let Some(data) = application_resources.load(APPLICATION_CONFIG) {
-            config = String::from_utf8(data.to_vec()).unwrap();
-        }
+fn parse_config(name: &str, data: &[u8]) -> Result<Value, PropertyViolation> {
+    let config = std::str::from_utf8(data).map_err(|_| {
+        PropertyViolation::new(
+            "",
+            format!("The application configuration file is not valid utf-8, file: {}", name),
+        )
+    })?;
+
+    serde_yaml::from_str::<Value>(config).map_err(|error| {
+        PropertyViolation::new(
+            "",
+            format!(
+                "Failed to parse the application configuration file: {}, error: {}",
+                name, error
+            ),
+        )
+    })
+}
+
+fn read_external_config(path: &str) -> Result<Value, PropertyViolation> {
+    let buffer = std::fs::read_to_string(path).map_err(|error| {
+        PropertyViolation::new(
+            "",
+            format!(
+                "Please check if the configuration file of the application exists: {:?}, error: {}",
+                path, error
+            ),
+        )
+    })?;
+
+    if buffer.is_empty() {
+        return Err(PropertyViolation::new(
+            "",
+            format!("The application configuration file is empty, file path: {}", path),
+        ));
+    }
+
+    parse_config(path, buffer.as_bytes())
+}
+
+/// Profiles are taken from the first of:
+/// `--active-profiles`, `NEXT_PROFILES_ACTIVE`, `next.profiles.active` in the configuration files.
+fn resolve_active_profiles(
+    application_args: &ApplicationArgs,
+    sources: &PropertySources,
+    external: Option<&PropertySource>,
+) -> Result<Vec<String>, PropertiesValidationError> {
+    fn split(value: &str) -> Vec<String> {
+        value
+            .split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(ToString::to_string)
+            .collect()
     }
 
-    // mapping value
-    let mapping = serde_yaml::from_str::<Value>(&config).unwrap();
+    if !application_args.active_profiles.is_empty() {
+        return Ok(application_args
+            .active_profiles
+            .iter()
+            .flat_map(|s| split(s))
+            .collect());
+    }
+
+    if let Ok(profiles) = std::env::var(NEXT_PROFILES_ACTIVE) {
+        return Ok(split(&profiles));
+    }
 
-    // into application properties
-    let mut application_properties: ApplicationProperties =
-        serde_yaml::from_str(config.as_str()).unwrap_or_default();
-    application_properties.set_mapping(mapping);
+    let mut mapping = sources.merge()?;
+    if let Some(external) = external {
+        merge_value(&mut mapping, external.value().clone())?;
+    }
 
-    // return
-    return application_properties;
+    Ok(match mapping
+        .get("next")
+        .and_then(|next| next.get("profiles"))
+        .and_then(|profiles| profiles.get("active"))
+    {
+        Some(Value::String(profiles)) => split(profiles),
+        Some(Value::Sequence(profiles)) => profiles
+            .iter()
+            .filter_map(Value::as_str)
+            .flat_map(split)
+            .collect(),
+        _ => Vec::new(),
+    })
 }
 
-impl From<(&ApplicationArgs, &ApplicationResources)> for ApplicationProperties {
-    fn from((args, resources): (&ApplicationArgs, &ApplicationResources)) -> Self {
+impl TryFrom<(&ApplicationArgs, &ApplicationResources)> for ApplicationProperties {
+    type Error = PropertiesValidationError;
+
+    fn try_from(
+        (args, resources): (&ApplicationArgs, &ApplicationResources),
+    ) -> Result<Self, Self::Error> {
         into_application_properties(args, resources)
     }
 }
@@ -292,3 +635,36 @@ impl Drop for ApplicationProperties {
 }
 
 dyn_clone::clone_trait_object!(Properties);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_known_key() {
+        assert!(ApplicationProperties::is_known_key("next.server.context_path"));
+        assert!(ApplicationProperties::is_known_key("next.server.port"));
+        assert!(!ApplicationProperties::is_known_key("next.server.context.path"));
+        assert!(!ApplicationProperties::is_known_key("next.data.redis.host"));
+    }
+
+    #[test]
+    fn test_invalid_sources_are_reported() {
+        let mut sources = PropertySources::new();
+        sources.add_last(PropertySource::from_pairs("test", [("next.server.port", "abc")]).unwrap());
+
+        let error = ApplicationProperties::from_sources(&sources, Vec::new()).unwrap_err();
+        assert_eq!(error.violations[0].path, "next.server.port");
+    }
+
+    #[test]
+    fn test_invalid_config_files_are_reported() {
+        let path = std::env::temp_dir().join(format!("next-web-core-{}.yaml", std::process::id()));
+        std::fs::write(&path, "next: { server: [").unwrap();
+        let error = read_external_config(path.to_str().unwrap()).unwrap_err();
+        assert!(error.message.starts_with("Failed to parse the application configuration file"));
+
+        std::fs::remove_file(&path).unwrap();
+        assert!(read_external_config(path.to_str().unwrap()).is_err());
+    }
+}
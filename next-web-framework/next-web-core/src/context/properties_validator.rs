@@ -0,0 +1,307 @@
+use serde_yaml::Value;
+
+use crate::context::properties::{lookup, unresolved_placeholders, ApplicationProperties, Properties};
+use crate::error::properties_validation_error::{PropertiesValidationError, PropertyViolation};
+
+/// 配置校验器
+///
+/// Validates the application configuration and collects every problem into a single report:
+///
+/// - placeholders that cannot be resolved
+/// - invalid values of the built-in `next.*` sections, and the keys under `next` that neither
+///   a built-in section nor a `#[Properties(prefix = "next...")]` struct reads
+/// - missing, invalid and unknown keys of every `#[Properties(prefix = ...)]` struct
+/// - `validator` constraints of properties declared with `#[Properties(validate)]`
+pub struct PropertiesValidator;
+
+/// Keys under `next` read by the framework without a properties struct.
+const RESERVED_KEYS: [&str; 1] = ["next.profiles"];
+
+impl PropertiesValidator {
+    pub fn validate(
+        properties: &ApplicationProperties,
+        items: &[Box<dyn Properties>],
+    ) -> Result<(), PropertiesValidationError> {
+        let mut violations = Vec::new();
+
+        let prefixes = items
+            .iter()
+            .map(|item| item.prefix())
+            .filter(|prefix| !prefix.is_empty())
+            .collect::<Vec<_>>();
+
+        if let Some(mapping) = properties.mapping() {
+            Self::validate_placeholders(mapping, String::new(), &mut violations);
+            Self::validate_next(mapping, &prefixes, &mut violations);
+        }
+
+        for item in items.iter() {
+            violations.extend(item.validate(properties));
+
+            let prefix = item.prefix();
+            let keys = match item.property_keys() {
+                Some(keys) if !prefix.is_empty() => keys,
+                _ => continue,
+            };
+
+            for key in properties.child_keys(prefix) {
+                if keys.contains(&key.as_str()) {
+                    continue;
+                }
+
+                // The key belongs to another properties struct
+                let path = format!("{}.{}", prefix, key);
+                let nested = format!("{}.", path);
+                if prefixes
+                    .iter()
+                    .any(|prefix| *prefix == path || prefix.starts_with(&nested))
+                {
+                    continue;
+                }
+
+                violations.push(PropertyViolation::new(path, "unknown property"));
+            }
+        }
+
+        violations.sort_by(|a, b| a.path.cmp(&b.path));
+        violations.dedup();
+
+        match violations.is_empty() {
+            true => Ok(()),
+            false => Err(PropertiesValidationError { violations }),
+        }
+    }
+
+    fn validate_placeholders(value: &Value, path: String, violations: &mut Vec<PropertyViolation>) {
+        let join = |key: String| match path.is_empty() {
+            true => key,
+            false => format!("{}.{}", path, key),
+        };
+
+        match value {
+            Value::String(s) => {
+                for placeholder in unresolved_placeholders(s) {
+                    violations.push(PropertyViolation::new(
+                        path.clone(),
+                        format!(
+                            "could not resolve placeholder '{}', set the environment variable or key, or add a default like '${{KEY:default}}'",
+                            placeholder
+                        ),
+                    ));
+                }
+            }
+            Value::Mapping(mapping) => {
+                for (key, value) in mapping {
+                    if let Some(key) = key.as_str() {
+                        Self::validate_placeholders(value, join(key.to_string()), violations);
+                    }
+                }
+            }
+            Value::Sequence(sequence) => {
+                for (index, value) in sequence.iter().enumerate() {
+                    Self::validate_placeholders(value, join(index.to_string()), violations);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Every key under `next` is checked, e.g. `next.servr` and `next.server.prot`, except the
+    /// keys of the `prefixes` which are checked with their properties struct.
+    fn validate_next(mapping: &Value, prefixes: &[&str], violations: &mut Vec<PropertyViolation>) {
+        if mapping.get("next").is_none() {
+            return;
+        }
+
+        let mut ignored = Vec::new();
+        let mut callback =
+            |path: serde_ignored::Path| ignored.push(normalize_path(&path.to_string()));
+        let deserializer = serde_ignored::Deserializer::new(mapping.clone(), &mut callback);
+
+        match serde_path_to_error::deserialize::<_, ApplicationProperties>(deserializer) {
+            Ok(properties) => {
+                Self::validate_cors(&properties, violations);
+                Self::validate_ssl(&properties, violations);
+            }
+            Err(error) => violations.push(PropertyViolation::new(
+                normalize_path(&error.path().to_string()),
+                error.inner().to_string(),
+            )),
+        }
+
+        let prefixes = prefixes
+            .iter()
+            .chain(RESERVED_KEYS.iter())
+            .copied()
+            .collect::<Vec<_>>();
+        for path in ignored.into_iter().filter(|path| path.starts_with("next.")) {
+            Self::validate_owned(mapping, path, &prefixes, violations);
+        }
+    }
+
+    /// Report the key unless it is read under one of the `prefixes`, the keys of a parent of a
+    /// prefix are checked one by one, e.g. `next.data.mongo` next to `next.data.redis`.
+    fn validate_owned(
+        mapping: &Value,
+        path: String,
+        prefixes: &[&str],
+        violations: &mut Vec<PropertyViolation>,
+    ) {
+        if prefixes
+            .iter()
+            .any(|prefix| path == *prefix || path.starts_with(&format!("{}.", prefix)))
+        {
+            return;
+        }
+
+        let nested = format!("{}.", path);
+        let children = match lookup(mapping, &path) {
+            Some(Value::Mapping(children)) if prefixes.iter().any(|prefix| prefix.starts_with(&nested)) => {
+                children
+            }
+            _ => {
+                violations.push(PropertyViolation::new(path, "unknown property"));
+                return;
+            }
+        };
+
+        for key in children.keys().filter_map(Value::as_str) {
+            Self::validate_owned(mapping, format!("{}{}", nested, key), prefixes, violations);
+        }
+    }
+
+    fn validate_cors(properties: &ApplicationProperties, violations: &mut Vec<PropertyViolation>) {
+        let cors = match properties.next().server().http().and_then(|http| http.cors()) {
+            Some(cors) => cors,
+            None => return,
+        };
+
+        let prefix = "next.server.http.cors";
+        if let Err(message) = cors.defaults().validate() {
+            violations.push(PropertyViolation::new(prefix, message));
+        }
+        for (index, mapping) in cors.mappings().iter().enumerate() {
+            if let Err(message) = mapping.configuration().merge(cors.defaults()).validate() {
+                violations.push(PropertyViolation::new(
+                    format!("{}.mappings.{}", prefix, index),
+                    message,
+                ));
+            }
+        }
+    }
+    fn validate_ssl(properties: &ApplicationProperties, violations: &mut Vec<PropertyViolation>) {
+        if let Some(ssl) = properties.next().server().ssl().filter(|ssl| ssl.enabled()) {
+            if let Err(message) = ssl.validate() {
+                violations.push(PropertyViolation::new("next.server.ssl", message));
+            }
+        }
+    }
+}
+
+/// Remove the `?` segments that serde adds for `Option` values.
+pub(crate) fn normalize_path(path: &str) -> String {
+    path.split('.')
+        .filter(|segment| *segment != "?" && !segment.is_empty())
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::application_context::ApplicationContext;
+    use crate::AutoRegister;
+
+    #[derive(Clone)]
+    struct RedisProperties;
+
+    #[async_trait::async_trait]
+    impl AutoRegister for RedisProperties {
+        fn registered_name(&self) -> &'static str {
+            "redisProperties"
+        }
+
+        async fn register(
+            &self,
+            _ctx: &mut ApplicationContext,
+            _properties: &ApplicationProperties,
+        ) -> Result<(), Box<dyn std::error::Error>> {
+            Ok(())
+        }
+    }
+
+    impl Properties for RedisProperties {
+        fn prefix(&self) -> &'static str {
+            "next.data.redis"
+        }
+    }
+
+    fn paths(properties: &ApplicationProperties) -> Vec<String> {
+        let items: Vec<Box<dyn Properties>> = vec![Box::new(RedisProperties)];
+        PropertiesValidator::validate(properties, &items)
+            .err()
+            .map(|error| error.violations.into_iter().map(|v| v.path).collect())
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn test_validate_unknown_keys_and_placeholders() {
+        let mut properties = ApplicationProperties::default();
+        properties.set_mapping(
+            serde_yaml::from_str(
+                "next: { servr: { port: 80 }, server: { port: 80, prot: 80 }, logging: { levle: debug }, data: { redis: {}, mongo: {} }, profiles: { active: dev } }\nname: ${NEXT_WEB_UNDEFINED_VARIABLE}",
+            )
+            .unwrap(),
+        );
+
+        assert_eq!(
+            paths(&properties),
+            vec![
+                "name",
+                "next.data.mongo",
+                "next.logging.levle",
+                "next.server.prot",
+                "next.servr"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_invalid_value() {
+        let mut properties = ApplicationProperties::default();
+        properties.set_mapping(serde_yaml::from_str("next: { server: { port: abc } }").unwrap());
+
+        assert_eq!(paths(&properties), vec!["next.server.port"]);
+    }
+
+    #[test]
+    fn test_validate_cors_credentials_with_any_origin() {
+        let mut properties = ApplicationProperties::default();
+        properties.set_mapping(
+            serde_yaml::from_str(
+                "next: { server: { http: { cors: { allowed_origins: ['https://example.com'], allow_credentials: true, mappings: [{ path: /public/**, allowed_origins: ['*'] }] } } } }",
+            )
+            .unwrap(),
+        );
+
+        assert_eq!(paths(&properties), vec!["next.server.http.cors.mappings.0"]);
+    }
+
+    #[test]
+    fn test_validate_ssl() {
+        let mut properties = ApplicationProperties::default();
+        properties.set_mapping(
+            serde_yaml::from_str(
+                "next: { server: { ssl: { certificate: cert.pem, protocol: TLSv1.1 } } }",
+            )
+            .unwrap(),
+        );
+        assert_eq!(paths(&properties), vec!["next.server.ssl.protocol"]);
+
+        properties.set_mapping(
+            serde_yaml::from_str("next: { server: { ssl: { key_store: server.p12, client_auth: need } } }")
+                .unwrap(),
+        );
+        assert_eq!(paths(&properties), vec!["next.server.ssl"]);
+    }
+}
@@ -0,0 +1,537 @@
+use std::collections::{HashMap, HashSet};
+
+use serde_yaml::{Mapping, Value};
+use tracing::warn;
+
+use crate::constants::application_constants::{
+    NEXT_APPLICATION_JSON, NEXT_DECRYPT_PASSWORD, NEXT_PROFILES_ACTIVE,
+};
+use crate::error::properties_validation_error::{PropertiesValidationError, PropertyViolation};
+
+/// Environment variables with this prefix are bound to a known key even when the
+/// configuration files do not set it.
+const ENVIRONMENT_PREFIX: &str = "NEXT_";
+
+/// Names with more `_` separated words are not matched against the known keys, each `_`
+/// doubles the candidates.
+const MAX_ENVIRONMENT_WORDS: usize = 10;
+
+/// A single named layer of configuration data.
+#[derive(Debug, Clone)]
+pub struct PropertySource {
+    name: String,
+    value: Value,
+}
+
+impl PropertySource {
+    pub fn new(name: impl Into<String>, value: Value) -> Self {
+        Self {
+            name: name.into(),
+            value,
+        }
+    }
+
+    /// Build a source from flat `a.b.c = value` pairs.
+    ///
+    /// Values are parsed as yaml scalars, so `8080` becomes a number and `true` a bool.
+    /// Fails when a pair sets a value where another one sets a mapping, e.g. `next.server`
+    /// and `next.server.port`.
+    pub fn from_pairs<K, V>(
+        name: impl Into<String>,
+        pairs: impl IntoIterator<Item = (K, V)>,
+    ) -> Result<Self, PropertiesValidationError>
+    where
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        let name = name.into();
+        let mut value = Value::Mapping(Mapping::new());
+        let mut violations = Vec::new();
+        for (key, val) in pairs {
+            if let Err(violation) = insert_by_path(&mut value, key.as_ref(), parse_scalar(val.as_ref()))
+            {
+                violations.push(in_source(violation, &name));
+            }
+        }
+
+        match violations.is_empty() {
+            true => Ok(Self::new(name, value)),
+            false => Err(PropertiesValidationError { violations }),
+        }
+    }
+
+    /// Build a source from environment variables using relaxed binding.
+    ///
+    /// Every key of `current` can be overridden by its upper case form with `.`, `-` and
+    /// list indexes replaced by `_`, e.g. `NEXT_SERVER_PORT` overrides `next.server.port`
+    /// and `NEXT_SERVER_HOSTS_0` overrides the first item of `next.server.hosts`.
+    ///
+    /// A variable starting with `NEXT_` that matches no key of `current` is bound to the key
+    /// `is_known` accepts among the ways to read its `_` as `.` or `_`, so
+    /// `NEXT_SERVER_CONTEXT_PATH` becomes `next.server.context_path`. Variables matching no
+    /// known key, or more than one, are not bound and a warning is logged.
+    pub fn from_environment(
+        name: impl Into<String>,
+        current: &Value,
+        vars: impl IntoIterator<Item = (String, String)>,
+        is_known: impl Fn(&str) -> bool,
+    ) -> Result<Self, PropertiesValidationError> {
+        let vars: HashMap<String, String> = vars
+            .into_iter()
+            .map(|(key, value)| (key.to_uppercase(), value))
+            .collect();
+
+        let mut paths = Vec::new();
+        collect_paths(current, String::new(), &mut paths);
+
+        let mut used = HashSet::new();
+        let mut pairs = Vec::new();
+        for path in paths {
+            let candidates = [
+                path.replace(['.', '-'], "_").to_uppercase(),
+                path.replace('-', "").replace('.', "_").to_uppercase(),
+            ];
+            if let Some((var, value)) = candidates
+                .iter()
+                .find_map(|var| vars.get(var).map(|value| (var, value)))
+            {
+                used.insert(var.clone());
+                pairs.push((path, value.clone()));
+            }
+        }
+
+        let reserved = [NEXT_PROFILES_ACTIVE, NEXT_APPLICATION_JSON, NEXT_DECRYPT_PASSWORD];
+        for (var, value) in vars.iter() {
+            if !var.starts_with(ENVIRONMENT_PREFIX)
+                || used.contains(var)
+                || reserved.contains(&var.as_str())
+            {
+                continue;
+            }
+
+            let words = var[ENVIRONMENT_PREFIX.len()..]
+                .split('_')
+                .filter(|s| !s.is_empty())
+                .map(str::to_lowercase)
+                .collect::<Vec<_>>();
+            if words.is_empty() || words.len() > MAX_ENVIRONMENT_WORDS {
+                warn!("The environment variable [{}] is not bound, no known property matches it", var);
+                continue;
+            }
+
+            let mut paths = (0..1usize << (words.len() - 1))
+                .map(|separators| {
+                    let mut path = String::from("next.");
+                    for (index, word) in words.iter().enumerate() {
+                        if index > 0 {
+                            path.push(if separators & (1 << (index - 1)) != 0 { '_' } else { '.' });
+                        }
+                        path.push_str(word);
+                    }
+                    path
+                })
+                .filter(|path| is_known(path))
+                .collect::<Vec<_>>();
+
+            match paths.len() {
+                1 => pairs.push((paths.remove(0), value.clone())),
+                0 => warn!(
+                    "The environment variable [{}] is not bound, no known property matches it",
+                    var
+                ),
+                _ => warn!(
+                    "The environment variable [{}] is not bound, it matches the properties {:?}",
+                    var, paths
+                ),
+            }
+        }
+
+        Self::from_pairs(name, pairs)
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn value(&self) -> &Value {
+        &self.value
+    }
+}
+
+/// Ordered collection of [`PropertySource`]s.
+///
+/// Sources added later take precedence over sources added earlier,
+/// the resulting mapping is produced by [`PropertySources::merge`].
+///
+/// The application uses the following chain (lowest to highest):
+///
+/// `defaults` < `application.yaml` < `application-{profile}.yaml` < `--config-location` < environment < command line
+#[derive(Debug, Clone, Default)]
+pub struct PropertySources {
+    sources: Vec<PropertySource>,
+}
+
+impl PropertySources {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a source with the highest precedence so far.
+    pub fn add_last(&mut self, source: PropertySource) {
+        self.sources.push(source);
+    }
+
+    /// Add a source with the lowest precedence so far.
+    pub fn add_first(&mut self, source: PropertySource) {
+        self.sources.insert(0, source);
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.sources.iter().any(|source| source.name() == name)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &PropertySource> {
+        self.sources.iter()
+    }
+
+    /// Deep merge all sources into a single mapping.
+    ///
+    /// Fails when a source sets a value where a lower one sets a mapping.
+    pub fn merge(&self) -> Result<Value, PropertiesValidationError> {
+        let mut value = Value::Mapping(Mapping::new());
+        let mut violations = Vec::new();
+        for source in self.sources.iter() {
+            if let Err(error) = merge_value(&mut value, source.value().clone()) {
+                violations.extend(
+                    error
+                        .violations
+                        .into_iter()
+                        .map(|violation| in_source(violation, source.name())),
+                );
+            }
+        }
+
+        match violations.is_empty() {
+            true => Ok(value),
+            false => Err(PropertiesValidationError { violations }),
+        }
+    }
+}
+
+/// Parse a raw string as a yaml scalar, falling back to a plain string.
+pub fn parse_scalar(value: &str) -> Value {
+    match serde_yaml::from_str::<Value>(value) {
+        Ok(Value::Mapping(_)) | Ok(Value::Sequence(_)) | Ok(Value::Tagged(_)) | Err(_) => {
+            Value::String(value.to_string())
+        }
+        Ok(Value::Null) if !value.trim().is_empty() && value.trim() != "null" => {
+            Value::String(value.to_string())
+        }
+        Ok(value) => value,
+    }
+}
+
+/// Merge `overlay` into `base`.
+///
+/// Mappings are merged recursively, any other value (including sequences) is replaced.
+/// A mapping whose keys are all list indexes is merged into a sequence item by item.
+/// A `null` overlay does not erase an existing value, and a mapping is never replaced by a
+/// single value, every such key is reported.
+pub fn merge_value(base: &mut Value, overlay: Value) -> Result<(), PropertiesValidationError> {
+    let mut violations = Vec::new();
+    merge_at(base, overlay, String::new(), &mut violations);
+
+    match violations.is_empty() {
+        true => Ok(()),
+        false => Err(PropertiesValidationError { violations }),
+    }
+}
+
+fn merge_at(base: &mut Value, overlay: Value, path: String, violations: &mut Vec<PropertyViolation>) {
+    let join = |key: &Value| {
+        let key = match key {
+            Value::String(s) => s.clone(),
+            Value::Number(n) => n.to_string(),
+            Value::Bool(b) => b.to_string(),
+            _ => String::new(),
+        };
+        match path.is_empty() {
+            true => key,
+            false => format!("{}.{}", path, key),
+        }
+    };
+
+    match (base, overlay) {
+        (Value::Mapping(base), Value::Mapping(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(&key) {
+                    Some(existing) => merge_at(existing, value, join(&key), violations),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (Value::Sequence(base), Value::Mapping(overlay))
+            if overlay.keys().all(|key| as_index(key).is_some()) =>
+        {
+            for (key, value) in overlay {
+                let index = as_index(&key).unwrap_or_default();
+                match base.get_mut(index) {
+                    Some(existing) => merge_at(existing, value, join(&key), violations),
+                    None => base.push(value),
+                }
+            }
+        }
+        (_, Value::Null) => {}
+        (Value::Mapping(_), _) => violations.push(PropertyViolation::new(path, MAPPING_REPLACED)),
+        (base, overlay) => *base = overlay,
+    }
+}
+
+const MAPPING_REPLACED: &str = "a single value can not replace the nested properties of this key";
+
+/// Name the source the violation was found in.
+fn in_source(violation: PropertyViolation, source: &str) -> PropertyViolation {
+    PropertyViolation::new(
+        violation.path,
+        format!("{} (property source [{}])", violation.message, source),
+    )
+}
+
+fn as_index(key: &Value) -> Option<usize> {
+    match key {
+        Value::Number(n) => n.as_u64().map(|n| n as usize),
+        Value::String(s) => s.parse::<usize>().ok(),
+        _ => None,
+    }
+}
+
+/// Collect the dotted paths of all scalar leaves, list items use their index as segment.
+fn collect_paths(value: &Value, prefix: String, paths: &mut Vec<String>) {
+    let join = |key: String| match prefix.is_empty() {
+        true => key,
+        false => format!("{}.{}", prefix, key),
+    };
+
+    match value {
+        Value::Mapping(mapping) => {
+            for (key, value) in mapping {
+                let key = match key {
+                    Value::String(s) => s.clone(),
+                    Value::Number(n) => n.to_string(),
+                    Value::Bool(b) => b.to_string(),
+                    _ => continue,
+                };
+                collect_paths(value, join(key), paths);
+            }
+        }
+        Value::Sequence(sequence) => {
+            for (index, value) in sequence.iter().enumerate() {
+                collect_paths(value, join(index.to_string()), paths);
+            }
+        }
+        _ => {
+            if !prefix.is_empty() {
+                paths.push(prefix);
+            }
+        }
+    }
+}
+
+/// Compare two mappings and return the dotted keys whose values were added, removed or changed.
+pub fn changed_keys(old: &Value, new: &Value) -> Vec<String> {
+    fn leaves(value: &Value) -> HashMap<String, &Value> {
+        let mut paths = Vec::new();
+        collect_paths(value, String::new(), &mut paths);
+        paths
+            .into_iter()
+            .filter_map(|path| {
+                let leaf = path.split('.').try_fold(value, |value, key| match value {
+                    Value::Sequence(sequence) => sequence.get(key.parse::<usize>().ok()?),
+                    _ => value.get(key),
+                })?;
+                Some((path, leaf))
+            })
+            .collect()
+    }
+
+    let old = leaves(old);
+    let new = leaves(new);
+
+    let mut keys = old
+        .iter()
+        .filter(|(key, value)| new.get(*key) != Some(*value))
+        .map(|(key, _)| key.clone())
+        .chain(new.keys().filter(|key| !old.contains_key(*key)).cloned())
+        .collect::<Vec<_>>();
+    keys.sort();
+    keys
+}
+
+/// Insert `value` at the dotted `path`, creating intermediate mappings when needed.
+///
+/// Fails when the path goes through a single value, or when a single value would replace the
+/// mapping at `path`.
+pub fn insert_by_path(root: &mut Value, path: &str, value: Value) -> Result<(), PropertyViolation> {
+    let mut current = root;
+    let mut iter = path.split('.').filter(|s| !s.is_empty()).peekable();
+    let mut visited = Vec::new();
+
+    while let Some(key) = iter.next() {
+        if current.is_null() {
+            *current = Value::Mapping(Mapping::new());
+        }
+
+        let Some(mapping) = current.as_mapping_mut() else {
+            return Err(PropertyViolation::new(
+                visited.join("."),
+                format!("[{}] sets nested properties below a single value", path),
+            ));
+        };
+        visited.push(key);
+        let key = Value::String(key.to_string());
+
+        if iter.peek().is_none() {
+            if mapping.get(&key).is_some_and(Value::is_mapping) && !value.is_mapping() {
+                return Err(PropertyViolation::new(path, MAPPING_REPLACED));
+            }
+            mapping.insert(key, value);
+            return Ok(());
+        }
+
+        current = mapping
+            .entry(key)
+            .or_insert_with(|| Value::Mapping(Mapping::new()));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_precedence() {
+        let mut sources = PropertySources::new();
+        sources.add_last(PropertySource::new(
+            "base",
+            serde_yaml::from_str("next: { server: { port: 8080, local: true } }").unwrap(),
+        ));
+        sources.add_last(PropertySource::new(
+            "profile",
+            serde_yaml::from_str("next: { server: { port: 9090 } }").unwrap(),
+        ));
+        sources.add_last(PropertySource::from_pairs(
+            "command-line",
+            [("next.server.local", "false")],
+        )
+        .unwrap());
+
+        let value = sources.merge().unwrap();
+        let server = &value["next"]["server"];
+        assert_eq!(server["port"].as_u64(), Some(9090));
+        assert_eq!(server["local"].as_bool(), Some(false));
+    }
+
+    #[test]
+    fn test_relaxed_environment_binding() {
+        let current: Value = serde_yaml::from_str(
+            "next: { server: { port: 8080, context_path: /api, hosts: [a, b] } }",
+        )
+        .unwrap();
+
+        let source = PropertySource::from_environment(
+            "environment",
+            &current,
+            [
+                ("NEXT_SERVER_PORT".to_string(), "9090".to_string()),
+                ("NEXT_SERVER_CONTEXT_PATH".to_string(), "/v2".to_string()),
+                ("NEXT_SERVER_HOSTS_1".to_string(), "c".to_string()),
+                ("NEXT_DATA_REDIS_HOST".to_string(), "localhost".to_string()),
+                ("NEXT_PROFILES_ACTIVE".to_string(), "dev".to_string()),
+                ("PATH".to_string(), "/usr/bin".to_string()),
+            ],
+            |path| path == "next.data.redis.host",
+        )
+        .unwrap();
+
+        let mut value = current.clone();
+        merge_value(&mut value, source.value().clone()).unwrap();
+
+        assert_eq!(value["next"]["server"]["port"].as_u64(), Some(9090));
+        assert_eq!(value["next"]["server"]["context_path"].as_str(), Some("/v2"));
+        assert_eq!(value["next"]["server"]["hosts"][0].as_str(), Some("a"));
+        assert_eq!(value["next"]["server"]["hosts"][1].as_str(), Some("c"));
+        assert_eq!(value["next"]["data"]["redis"]["host"].as_str(), Some("localhost"));
+        assert!(value["next"].get("profiles").is_none());
+        assert!(value.get("path").is_none());
+    }
+
+    #[test]
+    fn test_changed_keys() {
+        let old: Value =
+            serde_yaml::from_str("next: { server: { port: 8080, hosts: [a] }, logging: { level: info } }").unwrap();
+        let new: Value =
+            serde_yaml::from_str("next: { server: { port: 8080, hosts: [a, b] }, logging: { level: debug } }").unwrap();
+
+        assert_eq!(
+            changed_keys(&old, &new),
+            vec!["next.logging.level".to_string(), "next.server.hosts.1".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_environment_binds_known_keys_only() {
+        let current: Value = serde_yaml::from_str("next: { server: { port: 8080 } }").unwrap();
+        let is_known = |path: &str| ["next.server", "next.server.context_path"].contains(&path);
+
+        let source = PropertySource::from_environment(
+            "environment",
+            &current,
+            [
+                ("NEXT_SERVER_CONTEXT_PATH".to_string(), "/api".to_string()),
+                ("NEXT_SERVER_CONTEXT".to_string(), "unknown".to_string()),
+            ],
+            is_known,
+        )
+        .unwrap();
+        assert_eq!(
+            source.value(),
+            &serde_yaml::from_str::<Value>("next: { server: { context_path: /api } }").unwrap()
+        );
+
+        let source = PropertySource::from_environment(
+            "environment",
+            &current,
+            [("NEXT_SERVER".to_string(), "x".to_string())],
+            is_known,
+        )
+        .unwrap();
+        let mut value = current.clone();
+        let error = merge_value(&mut value, source.value().clone()).unwrap_err();
+        assert_eq!(error.violations[0].path, "next.server");
+        assert_eq!(value["next"]["server"]["port"].as_u64(), Some(8080));
+    }
+
+    #[test]
+    fn test_insert_by_path() {
+        let mut value = Value::Null;
+        insert_by_path(&mut value, "next.data.redis.host", Value::String("localhost".into())).unwrap();
+        assert_eq!(value["next"]["data"]["redis"]["host"].as_str(), Some("localhost"));
+
+        assert!(insert_by_path(&mut value, "next.data", Value::String("x".into())).is_err());
+        assert!(insert_by_path(&mut value, "next.data.redis.host.port", Value::Null).is_err());
+        assert_eq!(value["next"]["data"]["redis"]["host"].as_str(), Some("localhost"));
+
+        let mut sources = PropertySources::new();
+        sources.add_last(PropertySource::new(
+            "base",
+            serde_yaml::from_str("next: { server: { port: 8080 } }").unwrap(),
+        ));
+        sources.add_last(PropertySource::from_pairs("command-line", [("next.server", "x")]).unwrap());
+        let error = sources.merge().unwrap_err();
+        assert!(error.violations[0].message.contains("command-line"));
+    }
+}
@@ -0,0 +1,503 @@
+use std::any::Any;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use axum::http::header::COOKIE;
+use axum::http::request::Parts;
+use tokio::sync::{OnceCell, RwLock};
+
+use crate::context::application_context::Key;
+use crate::util::singleton::SingletonUtil;
+use crate::ApplicationContext;
+
+/// 自定义作用域
+///
+/// A scope hands out one instance of a bean per scope id, e.g. per request, per session or per
+/// tenant, instead of one per application.
+///
+/// The beans of a scope are declared as [`Transient`](crate::Scope::Transient) providers, the
+/// scope creates the instance the first time it is asked for within a scope id and keeps it
+/// until the scope id is evicted.
+///
+/// Register the scope as a singleton and take the beans in the handlers with `FindScoped<T, S>`.
+///
+/// # Example
+///
+/// A tenant scope keyed by a header:
+///
+/// ```rust,ignore
+/// #[Singleton(name = "tenantScope")]
+/// #[derive(Clone, Default)]
+/// pub struct TenantScope(ScopeCache);
+///
+/// #[async_trait]
+/// impl Scope for TenantScope {
+///     fn name(&self) -> &str {
+///         "tenant"
+///     }
+///
+///     async fn beans(&self, parts: &mut Parts) -> Option<ScopedBeans> {
+///         let tenant_id = parts.headers.get("x-tenant-id")?.to_str().ok()?;
+///         Some(self.0.beans(tenant_id))
+///     }
+/// }
+///
+/// // Drop the instances of the tenant
+/// tenant_scope.0.evict("acme");
+/// ```
+#[async_trait]
+pub trait Scope: Send + Sync {
+    fn name(&self) -> &str;
+
+    /// The beans of the scope the request is in, `None` if the request is outside of the scope,
+    /// e.g. a request without a valid session for the session scope.
+    async fn beans(&self, parts: &mut Parts) -> Option<ScopedBeans>;
+}
+
+type ScopedInstance = Arc<OnceCell<Box<dyn Any + Send + Sync>>>;
+
+/// The instances of one scope id.
+///
+/// Cloning shares the instances, they are dropped with the last clone.
+#[derive(Clone, Default)]
+pub struct ScopedBeans {
+    instances: Arc<Mutex<HashMap<Key, ScopedInstance>>>,
+}
+
+impl ScopedBeans {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return the instance of `T` of this scope id, created with the provider of `T` the first
+    /// time it is asked for.
+    ///
+    /// The provider is looked up under the default name of `T` first, then under `""`.
+    /// Returns `None` if there is no provider.
+    ///
+    /// The instance is created on a snapshot of the context, neither the context nor the other
+    /// beans of the scope id are locked meanwhile. Concurrent requests of the same scope id wait
+    /// for it and share it.
+    pub async fn get_or_create<T>(&self, ctx: &Arc<RwLock<ApplicationContext>>) -> Option<T>
+    where
+        T: 'static + Clone + Send + Sync,
+    {
+        let name = SingletonUtil::name::<T>();
+
+        for name in [Cow::Owned(name), Cow::Borrowed("")] {
+            if !ctx
+                .read()
+                .await
+                .contains_provider_with_name::<T>(name.clone())
+            {
+                continue;
+            }
+
+            let cell = self
+                .instances
+                .lock()
+                .unwrap()
+                .entry(Key::new::<T>(name.clone()))
+                .or_default()
+                .clone();
+            let instance = cell
+                .get_or_try_init(|| async {
+                    let mut snapshot = ctx.read().await.snapshot();
+                    let created = snapshot.resolve_option_with_name_async::<T>(name).await;
+                    // Keeps the singletons the constructor created on the way, the context is
+                    // only locked for writing when there are some
+                    if ctx.read().await.has_created(&snapshot) {
+                        ctx.write().await.merge_created(snapshot);
+                    }
+                    created
+                        .map(|instance| Box::new(instance) as Box<dyn Any + Send + Sync>)
+                        .ok_or(())
+                })
+                .await;
+            if let Ok(instance) = instance {
+                return instance.downcast_ref::<T>().cloned();
+            }
+        }
+
+        None
+    }
+
+    /// The number of instances created in this scope id.
+    pub async fn len(&self) -> usize {
+        self.instances
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|instance| instance.initialized())
+            .count()
+    }
+
+    pub async fn is_empty(&self) -> bool {
+        self.len().await == 0
+    }
+}
+
+/// The [`ScopedBeans`] of every live scope id, the building block of scopes keyed by an id.
+///
+/// Unbounded by default, the scope ids are kept until they are evicted. A bounded cache also
+/// drops the scope ids not used for `time_to_idle` and the least recently used ones beyond
+/// `maximum_size`.
+///
+/// Cloning shares the cache.
+#[derive(Clone, Default)]
+pub struct ScopeCache {
+    scopes: Arc<Mutex<HashMap<String, (ScopedBeans, Instant)>>>,
+    time_to_idle: Option<Duration>,
+    maximum_size: Option<usize>,
+}
+
+impl ScopeCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn bounded(time_to_idle: Duration, maximum_size: usize) -> Self {
+        Self {
+            time_to_idle: Some(time_to_idle),
+            maximum_size: Some(maximum_size.max(1)),
+            ..Default::default()
+        }
+    }
+
+    /// The beans of the scope id, created empty if the scope id is new.
+    pub fn beans(&self, scope_id: impl AsRef<str>) -> ScopedBeans {
+        let now = Instant::now();
+        let mut scopes = self.scopes.lock().unwrap();
+
+        if let Some((beans, last_access)) = scopes.get_mut(scope_id.as_ref()) {
+            if self
+                .time_to_idle
+                .is_none_or(|time_to_idle| now.duration_since(*last_access) < time_to_idle)
+            {
+                *last_access = now;
+                return beans.clone();
+            }
+        }
+
+        // Purge on insertion only, the lookups stay cheap
+        if let Some(time_to_idle) = self.time_to_idle {
+            scopes.retain(|_, (_, last_access)| now.duration_since(*last_access) < time_to_idle);
+        }
+        if let Some(maximum_size) = self.maximum_size {
+            while scopes.len() >= maximum_size {
+                let Some(oldest) = scopes
+                    .iter()
+                    .min_by_key(|(_, (_, last_access))| *last_access)
+                    .map(|(scope_id, _)| scope_id.clone())
+                else {
+                    break;
+                };
+                scopes.remove(&oldest);
+            }
+        }
+
+        let beans = ScopedBeans::new();
+        scopes.insert(scope_id.as_ref().to_string(), (beans.clone(), now));
+        beans
+    }
+
+    /// Drop the beans of the scope id, requests still using them keep them until they complete.
+    ///
+    /// Returns `false` if the scope id had no beans.
+    pub fn evict(&self, scope_id: impl AsRef<str>) -> bool {
+        self.scopes
+            .lock()
+            .unwrap()
+            .remove(scope_id.as_ref())
+            .is_some()
+    }
+
+    pub fn contains(&self, scope_id: impl AsRef<str>) -> bool {
+        self.scopes.lock().unwrap().contains_key(scope_id.as_ref())
+    }
+
+    pub fn scope_ids(&self) -> Vec<String> {
+        self.scopes.lock().unwrap().keys().cloned().collect()
+    }
+}
+
+/// 请求作用域
+///
+/// One instance per HTTP request, kept in the extensions of the request and dropped with it.
+#[derive(Clone, Copy, Default)]
+pub struct RequestScope;
+
+#[async_trait]
+impl Scope for RequestScope {
+    fn name(&self) -> &str {
+        "request"
+    }
+
+    async fn beans(&self, parts: &mut Parts) -> Option<ScopedBeans> {
+        Some(
+            parts
+                .extensions
+                .get_or_insert_default::<ScopedBeans>()
+                .clone(),
+        )
+    }
+}
+
+/// 会话校验
+///
+/// Tells whether a session id sent by a client belongs to a live session, the security crate
+/// implements it for its session manager.
+#[async_trait]
+pub trait SessionIdValidator: Send + Sync {
+    async fn is_valid(&self, session_id: &str) -> bool;
+}
+
+/// 会话作用域
+///
+/// One instance per session, keyed by the session id cookie, `JSESSIONID` by default as the
+/// security session manager.
+///
+/// With a [`SessionIdValidator`] only the sessions it accepts get beans, otherwise any cookie
+/// value does. The sessions not used for 30 minutes are dropped and at most 10 000 are kept,
+/// see [`with_cache`](Self::with_cache). Evict the session when it stops or expires, the
+/// security crate does it when the scope is added to the listeners of its session manager.
+///
+/// ```rust,ignore
+/// ctx.insert_singleton_with_default_name(
+///     SessionScope::default().with_validator(Arc::new(session_manager)),
+/// );
+/// ```
+#[derive(Clone)]
+pub struct SessionScope {
+    cookie_name: String,
+    cache: ScopeCache,
+    validator: Option<Arc<dyn SessionIdValidator>>,
+}
+
+impl SessionScope {
+    pub const DEFAULT_COOKIE_NAME: &'static str = "JSESSIONID";
+    pub const DEFAULT_TIME_TO_IDLE: Duration = Duration::from_secs(30 * 60);
+    pub const DEFAULT_MAXIMUM_SESSIONS: usize = 10_000;
+
+    pub fn new(cookie_name: impl Into<String>) -> Self {
+        Self {
+            cookie_name: cookie_name.into(),
+            cache: ScopeCache::bounded(Self::DEFAULT_TIME_TO_IDLE, Self::DEFAULT_MAXIMUM_SESSIONS),
+            validator: None,
+        }
+    }
+
+    pub fn with_validator(mut self, validator: Arc<dyn SessionIdValidator>) -> Self {
+        self.validator = Some(validator);
+        self
+    }
+
+    /// Replace the cache of the sessions, e.g. with a time to idle matching the session
+    /// timeout.
+    pub fn with_cache(mut self, cache: ScopeCache) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    pub fn cookie_name(&self) -> &str {
+        &self.cookie_name
+    }
+
+    pub fn cache(&self) -> &ScopeCache {
+        &self.cache
+    }
+
+    /// Drop the instances of the session.
+    pub fn evict(&self, session_id: impl AsRef<str>) -> bool {
+        self.cache.evict(session_id)
+    }
+
+    fn session_id<'a>(&self, parts: &'a Parts) -> Option<&'a str> {
+        parts
+            .headers
+            .get_all(COOKIE)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(';'))
+            .filter_map(|pair| pair.trim().split_once('='))
+            .find(|(name, _)| *name == self.cookie_name)
+            .map(|(_, value)| value)
+            .filter(|value| !value.is_empty())
+    }
+}
+
+impl Default for SessionScope {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_COOKIE_NAME)
+    }
+}
+
+#[async_trait]
+impl Scope for SessionScope {
+    fn name(&self) -> &str {
+        "session"
+    }
+
+    async fn beans(&self, parts: &mut Parts) -> Option<ScopedBeans> {
+        let session_id = self.session_id(parts)?;
+        if let Some(validator) = self.validator.as_ref() {
+            if !validator.is_valid(session_id).await {
+                self.cache.evict(session_id);
+                return None;
+            }
+        }
+        Some(self.cache.beans(session_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use axum::http::Request;
+
+    use crate::context::application_context::{
+        singleton, transient, DynProvider, Module, Provider, ResolveModule,
+    };
+
+    use super::*;
+
+    static CREATED: AtomicUsize = AtomicUsize::new(0);
+
+    #[derive(Clone)]
+    struct Counter(usize);
+
+    struct TestModule;
+
+    impl Module for TestModule {
+        fn providers() -> Vec<DynProvider> {
+            vec![Provider::from(
+                transient(|_| Counter(CREATED.fetch_add(1, Ordering::SeqCst))).name("counter"),
+            )
+            .into()]
+        }
+    }
+
+    fn parts(cookie: Option<&str>) -> Parts {
+        let mut request = Request::builder();
+        if let Some(cookie) = cookie {
+            request = request.header(COOKIE, cookie);
+        }
+        request.body(()).unwrap().into_parts().0
+    }
+
+    #[tokio::test]
+    async fn test_scopes() {
+        let ctx = Arc::new(RwLock::new(ApplicationContext::create(vec![
+            ResolveModule::new::<TestModule>(),
+        ])));
+
+        // Request scope: shared within the request
+        let mut request = parts(None);
+        let first = RequestScope.beans(&mut request).await.unwrap();
+        let a = first.get_or_create::<Counter>(&ctx).await.unwrap().0;
+        let second = RequestScope.beans(&mut request).await.unwrap();
+        assert_eq!(second.get_or_create::<Counter>(&ctx).await.unwrap().0, a);
+        let other = RequestScope.beans(&mut parts(None)).await.unwrap();
+        assert_ne!(other.get_or_create::<Counter>(&ctx).await.unwrap().0, a);
+
+        // Session scope: keyed by the cookie, dropped when evicted
+        let scope = SessionScope::default();
+        assert!(scope.beans(&mut parts(None)).await.is_none());
+
+        let mut request = parts(Some("theme=dark; JSESSIONID=s1"));
+        let b = scope.beans(&mut request).await.unwrap();
+        let b = b.get_or_create::<Counter>(&ctx).await.unwrap().0;
+        let mut request = parts(Some("JSESSIONID=s1"));
+        let again = scope.beans(&mut request).await.unwrap();
+        assert_eq!(again.get_or_create::<Counter>(&ctx).await.unwrap().0, b);
+
+        assert!(scope.evict("s1"));
+        assert!(!scope.cache().contains("s1"));
+        let fresh = scope.beans(&mut request).await.unwrap();
+        assert!(fresh.is_empty().await);
+        assert_ne!(fresh.get_or_create::<Counter>(&ctx).await.unwrap().0, b);
+    }
+
+    #[derive(Clone)]
+    struct Repository;
+
+    #[derive(Clone)]
+    #[allow(dead_code)]
+    struct Cart(Repository);
+
+    struct CartModule;
+
+    impl Module for CartModule {
+        fn providers() -> Vec<DynProvider> {
+            vec![
+                Provider::from(singleton(|_| Repository).name("repository")).into(),
+                Provider::from(
+                    transient(|cx| Cart(cx.resolve_with_name("repository"))).name("cart"),
+                )
+                .into(),
+            ]
+        }
+    }
+
+    #[tokio::test]
+    async fn test_created_on_snapshot() {
+        let ctx = Arc::new(RwLock::new(ApplicationContext::create(vec![
+            ResolveModule::new::<CartModule>(),
+        ])));
+
+        let beans = ScopedBeans::new();
+        assert!(beans.get_or_create::<Cart>(&ctx).await.is_some());
+        assert_eq!(beans.len().await, 1);
+        // The singleton created for the cart is kept by the context
+        assert!(ctx
+            .read()
+            .await
+            .contains_single_with_name::<Repository>("repository"));
+
+        // Another cart only reuses what the context has, there is nothing to merge
+        let mut snapshot = ctx.read().await.snapshot();
+        assert!(snapshot.resolve_option_with_name_async::<Cart>("cart").await.is_some());
+        assert!(!ctx.read().await.has_created(&snapshot));
+    }
+
+    struct KnownSessions(&'static [&'static str]);
+
+    #[async_trait]
+    impl SessionIdValidator for KnownSessions {
+        async fn is_valid(&self, session_id: &str) -> bool {
+            self.0.contains(&session_id)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_session_validator() {
+        let scope = SessionScope::default().with_validator(Arc::new(KnownSessions(&["s1"])));
+
+        assert!(scope.beans(&mut parts(Some("JSESSIONID=s1"))).await.is_some());
+        assert!(scope.beans(&mut parts(Some("JSESSIONID=forged"))).await.is_none());
+        assert_eq!(scope.cache().scope_ids(), vec!["s1"]);
+    }
+
+    #[test]
+    fn test_bounded_cache() {
+        let cache = ScopeCache::bounded(Duration::from_secs(60), 2);
+        cache.beans("a");
+        cache.beans("b");
+        cache.beans("a");
+        // `b` is the least recently used
+        cache.beans("c");
+
+        let mut scope_ids = cache.scope_ids();
+        scope_ids.sort();
+        assert_eq!(scope_ids, vec!["a", "c"]);
+
+        let cache = ScopeCache::bounded(Duration::ZERO, 10);
+        cache.beans("a");
+        cache.beans("b");
+        assert_eq!(cache.scope_ids(), vec!["b"]);
+    }
+}
@@ -1,4 +1,5 @@
 pub mod invalid_parameter_error;
 pub mod illegal_state_error;
 pub mod idempotency_error;
+pub mod properties_validation_error;
 pub type BoxError = Box<dyn std::error::Error + Send + Sync>;
@@ -0,0 +1,71 @@
+use std::fmt::Display;
+
+/// A single configuration problem and the yaml path it was found at.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PropertyViolation {
+    pub path: String,
+    pub message: String,
+}
+
+impl PropertyViolation {
+    pub fn new(path: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl Display for PropertyViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.path.is_empty() {
+            true => write!(f, "{}", self.message),
+            false => write!(f, "{}: {}", self.path, self.message),
+        }
+    }
+}
+
+/// 配置校验错误
+///
+/// Every problem found while validating the application configuration.
+#[derive(Debug, Clone, Default)]
+pub struct PropertiesValidationError {
+    pub violations: Vec<PropertyViolation>,
+}
+
+impl PropertiesValidationError {
+    pub fn is_empty(&self) -> bool {
+        self.violations.is_empty()
+    }
+}
+
+impl Display for PropertiesValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f)?;
+        writeln!(f, "***************************")?;
+        writeln!(f, "APPLICATION FAILED TO START")?;
+        writeln!(f, "***************************")?;
+        writeln!(f)?;
+        writeln!(
+            f,
+            "The application configuration is invalid, {} problem(s) found:",
+            self.violations.len()
+        )?;
+        writeln!(f)?;
+        for violation in self.violations.iter() {
+            writeln!(f, "  - {}", violation)?;
+        }
+        writeln!(f)?;
+        write!(f, "Please correct the configuration and restart the application.")
+    }
+}
+
+impl std::error::Error for PropertiesValidationError {}
+
+impl From<PropertyViolation> for PropertiesValidationError {
+    fn from(violation: PropertyViolation) -> Self {
+        Self {
+            violations: vec![violation],
+        }
+    }
+}
@@ -0,0 +1 @@
+pub mod outbox_message;
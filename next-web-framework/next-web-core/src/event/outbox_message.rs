@@ -0,0 +1,63 @@
+use serde::{Deserialize, Serialize};
+
+/// 发件箱消息
+///
+/// Outbox message
+///
+/// An event stored by a transactional outbox, relayed after the commit to the destination
+/// `<scheme>:<target>`, e.g. `local:`, `rabbitmq:orders/order.created` or `mqtt:orders/created`.
+/// The payload is JSON.
+///
+/// Messages are delivered at least once, consumers should deduplicate them by `id`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OutboxMessage {
+    pub id: String,
+    pub destination: String,
+    pub event_type: String,
+    pub payload: String,
+    /// Epoch milliseconds
+    pub created_at: i64,
+    /// Failed relay attempts so far
+    pub attempts: i32,
+}
+
+impl OutboxMessage {
+    /// 获取目标协议，如 `rabbitmq`
+    ///
+    /// Get the scheme of the destination, e.g. `rabbitmq`
+    pub fn scheme(&self) -> &str {
+        self.destination
+            .split_once(':')
+            .map(|(scheme, _)| scheme)
+            .unwrap_or(&self.destination)
+    }
+
+    /// 获取目标地址，如 `orders/order.created`
+    ///
+    /// Get the target of the destination, e.g. `orders/order.created`
+    pub fn target(&self) -> &str {
+        self.destination
+            .split_once(':')
+            .map(|(_, target)| target)
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_destination() {
+        let message = OutboxMessage {
+            id: String::from("1"),
+            destination: String::from("rabbitmq:orders/order.created"),
+            event_type: String::from("OrderCreated"),
+            payload: String::from("{}"),
+            created_at: 0,
+            attempts: 0,
+        };
+        assert_eq!(message.scheme(), "rabbitmq");
+        assert_eq!(message.target(), "orders/order.created");
+    }
+}
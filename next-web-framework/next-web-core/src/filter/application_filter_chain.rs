@@ -1,17 +1,38 @@
+use std::sync::Arc;
+
 use async_trait::async_trait;
+use axum::middleware::Next;
 
 use crate::{
     error::BoxError,
+    filter::filter_registration::FilterRegistration,
     traits::{
         filter::http_filter_chain::HttpFilterChain,
         http::{http_request::HttpRequest, http_response::HttpResponse},
     },
 };
 
+/// 应用过滤器链
+///
+/// Runs the filters that match the request path one after the other, after the last one the
+/// request is passed to the handler and its response replaces `response`.
+///
+/// A chain without `next` only runs the filters, e.g. when it is the original chain of a proxy.
 #[derive(Clone, Default)]
 pub struct ApplicationFilterChain {
+    filters: Arc<Vec<FilterRegistration>>,
     pos: usize,
-    n: usize,
+    next: Option<Next>,
+}
+
+impl ApplicationFilterChain {
+    pub fn new(filters: Arc<Vec<FilterRegistration>>, next: Next) -> Self {
+        Self {
+            filters,
+            pos: 0,
+            next: Some(next),
+        }
+    }
 }
 
 #[async_trait]
@@ -21,9 +42,29 @@ impl HttpFilterChain for ApplicationFilterChain {
         request: &mut dyn HttpRequest,
         response: &mut dyn HttpResponse,
     ) -> Result<(), BoxError> {
-         // Call the next filter if there is one
-        if self.pos < self.n {
-            // self.
+        // Call the next filter if there is one
+        if let Some(offset) = self.filters[self.pos..]
+            .iter()
+            .position(|registration| registration.matches(request.path()))
+        {
+            let pos = self.pos + offset;
+            let chain = Self {
+                filters: self.filters.clone(),
+                pos: pos + 1,
+                next: self.next.clone(),
+            };
+            return self.filters[pos]
+                .filter()
+                .do_filter(request, response, &chain)
+                .await;
+        }
+
+        // End of the chain, call the handler
+        if let Some(next) = self.next.clone() {
+            let req = request
+                .take()
+                .ok_or("The request of the filter chain was already consumed")?;
+            response.replace(next.run(req).await);
         }
 
         Ok(())
@@ -0,0 +1,117 @@
+use std::sync::Arc;
+
+use crate::traits::filter::http_filter::HttpFilter;
+use crate::util::path_matcher::PathMatcher;
+
+/// 过滤器注册信息
+///
+/// A filter together with the path patterns it applies to, resolved once when the chain is built.
+#[derive(Clone)]
+pub struct FilterRegistration {
+    filter: Arc<dyn HttpFilter>,
+    url_patterns: Vec<String>,
+    exclude_url_patterns: Vec<String>,
+}
+
+impl FilterRegistration {
+    pub fn new(filter: Arc<dyn HttpFilter>) -> Self {
+        Self {
+            url_patterns: filter.url_patterns(),
+            exclude_url_patterns: filter.exclude_url_patterns(),
+            filter,
+        }
+    }
+
+    /// Create the registrations sorted by [`Ordered`](crate::traits::ordered::Ordered), lowest first.
+    pub fn sorted(filters: Vec<Arc<dyn HttpFilter>>) -> Vec<Self> {
+        let mut registrations = filters.into_iter().map(Self::new).collect::<Vec<_>>();
+        registrations.sort_by_key(|registration| registration.filter.order());
+        registrations
+    }
+
+    pub fn filter(&self) -> &dyn HttpFilter {
+        self.filter.as_ref()
+    }
+
+    /// Whether the filter applies to the path.
+    pub fn matches(&self, path: &str) -> bool {
+        if self
+            .exclude_url_patterns
+            .iter()
+            .any(|pattern| PathMatcher::matches(pattern, path))
+        {
+            return false;
+        }
+
+        self.url_patterns.is_empty()
+            || self
+                .url_patterns
+                .iter()
+                .any(|pattern| PathMatcher::matches(pattern, path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+
+    use super::*;
+    use crate::error::BoxError;
+    use crate::traits::filter::http_filter_chain::HttpFilterChain;
+    use crate::traits::http::{http_request::HttpRequest, http_response::HttpResponse};
+    use crate::traits::named::Named;
+    use crate::traits::ordered::Ordered;
+
+    #[derive(Clone)]
+    struct TestFilter(&'static str, i32);
+
+    impl Named for TestFilter {
+        fn name(&self) -> &str {
+            self.0
+        }
+    }
+
+    impl Ordered for TestFilter {
+        fn order(&self) -> i32 {
+            self.1
+        }
+    }
+
+    #[async_trait]
+    impl HttpFilter for TestFilter {
+        async fn do_filter(
+            &self,
+            request: &mut dyn HttpRequest,
+            response: &mut dyn HttpResponse,
+            filter_chain: &dyn HttpFilterChain,
+        ) -> Result<(), BoxError> {
+            filter_chain.do_filter(request, response).await
+        }
+
+        fn url_patterns(&self) -> Vec<String> {
+            vec!["/api/**".into()]
+        }
+
+        fn exclude_url_patterns(&self) -> Vec<String> {
+            vec!["/api/public/**".into()]
+        }
+    }
+
+    #[test]
+    fn test_sorted_and_matches() {
+        let registrations = FilterRegistration::sorted(vec![
+            Arc::new(TestFilter("tenant", 10)),
+            Arc::new(TestFilter("auth", -10)),
+        ]);
+
+        let names = registrations
+            .iter()
+            .map(|registration| registration.filter().name())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["auth", "tenant"]);
+
+        assert!(registrations[0].matches("/api/users"));
+        assert!(!registrations[0].matches("/api/public/login"));
+        assert!(!registrations[0].matches("/health"));
+    }
+}
@@ -1 +1,2 @@
 pub mod application_filter_chain;
+pub mod filter_registration;
@@ -0,0 +1,121 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+use serde_json::Value;
+
+/// 健康状态
+///
+/// Health status of a component.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum HealthStatus {
+    Up,
+    Down,
+    OutOfService,
+    Unknown,
+}
+
+impl HealthStatus {
+    /// Severity used for aggregation, the highest one wins.
+    fn severity(&self) -> u8 {
+        match self {
+            HealthStatus::Down => 3,
+            HealthStatus::OutOfService => 2,
+            HealthStatus::Up => 1,
+            HealthStatus::Unknown => 0,
+        }
+    }
+
+    /// 聚合多个状态，返回最严重的状态
+    ///
+    /// Aggregate several statuses into one, `DOWN` > `OUT_OF_SERVICE` > `UP` > `UNKNOWN`.
+    /// An empty iterator aggregates to `UP`.
+    pub fn aggregate(statuses: impl IntoIterator<Item = HealthStatus>) -> HealthStatus {
+        statuses
+            .into_iter()
+            .max_by_key(|status| status.severity())
+            .unwrap_or(HealthStatus::Up)
+    }
+
+    pub fn is_up(&self) -> bool {
+        matches!(self, HealthStatus::Up)
+    }
+}
+
+/// 组件健康信息
+///
+/// Health of a single component, a status plus optional details.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Health {
+    status: HealthStatus,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    details: BTreeMap<String, Value>,
+}
+
+impl Health {
+    pub fn new(status: HealthStatus) -> Self {
+        Self {
+            status,
+            details: BTreeMap::new(),
+        }
+    }
+
+    pub fn up() -> Self {
+        Self::new(HealthStatus::Up)
+    }
+
+    pub fn down() -> Self {
+        Self::new(HealthStatus::Down)
+    }
+
+    pub fn unknown() -> Self {
+        Self::new(HealthStatus::Unknown)
+    }
+
+    /// Build a `DOWN` health carrying the error message as `error` detail.
+    pub fn from_error(error: impl std::fmt::Display) -> Self {
+        Self::down().with_detail("error", error.to_string())
+    }
+
+    pub fn with_detail(mut self, key: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.details.insert(key.into(), value.into());
+        self
+    }
+
+    pub fn status(&self) -> HealthStatus {
+        self.status
+    }
+
+    pub fn details(&self) -> &BTreeMap<String, Value> {
+        &self.details
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aggregate() {
+        assert_eq!(HealthStatus::aggregate([]), HealthStatus::Up);
+        assert_eq!(
+            HealthStatus::aggregate([HealthStatus::Up, HealthStatus::Unknown]),
+            HealthStatus::Up
+        );
+        assert_eq!(
+            HealthStatus::aggregate([HealthStatus::Up, HealthStatus::Down, HealthStatus::OutOfService]),
+            HealthStatus::Down
+        );
+    }
+
+    #[test]
+    fn test_serialize() {
+        let health = Health::from_error("connection refused");
+        let value = serde_json::to_value(&health).unwrap();
+        assert_eq!(value["status"], "DOWN");
+        assert_eq!(value["details"]["error"], "connection refused");
+
+        let value = serde_json::to_value(Health::new(HealthStatus::OutOfService)).unwrap();
+        assert_eq!(value, serde_json::json!({ "status": "OUT_OF_SERVICE" }));
+    }
+}
@@ -0,0 +1 @@
+pub mod health_status;
@@ -0,0 +1,62 @@
+use crate::autoconfigure::context::cors_properties::{CorsConfiguration, CorsProperties};
+use crate::util::path_matcher::PathMatcher;
+
+/// 跨域规则注册表
+///
+/// # Cors Registry
+///
+/// Holds the path patterns and their [`CorsConfiguration`], a request uses the
+/// configuration of the first matching pattern.
+///
+/// The `next.server.http.cors` mappings are registered first, then the ones of every
+/// [`CorsConfigurer`](crate::traits::http::cors_configurer::CorsConfigurer),
+/// the defaults of the properties are registered last for `/**`.
+#[derive(Debug, Clone, Default)]
+pub struct CorsRegistry {
+    mappings: Vec<(String, CorsConfiguration)>,
+}
+
+impl CorsRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create the registry with the mappings of the properties, the defaults are not added.
+    pub fn from_properties(properties: &CorsProperties) -> Self {
+        let mut registry = Self::new();
+        for mapping in properties.mappings() {
+            registry.add_mapping(
+                mapping.path(),
+                mapping.configuration().merge(properties.defaults()),
+            );
+        }
+        registry
+    }
+
+    /// Register the configuration of the path pattern, e.g. `/api/**`.
+    pub fn add_mapping(&mut self, path: impl ToString, configuration: CorsConfiguration) -> &mut Self {
+        self.mappings.push((path.to_string(), configuration));
+        self
+    }
+
+    /// Remove the mappings whose configuration is rejected by `f`.
+    pub fn retain(&mut self, f: impl FnMut(&(String, CorsConfiguration)) -> bool) {
+        self.mappings.retain(f);
+    }
+
+    /// Get the configuration of the first pattern matching the path.
+    pub fn get(&self, path: &str) -> Option<&CorsConfiguration> {
+        self.mappings
+            .iter()
+            .find(|(pattern, _)| PathMatcher::matches(pattern, path))
+            .map(|(_, configuration)| configuration)
+    }
+
+    pub fn mappings(&self) -> &[(String, CorsConfiguration)] {
+        &self.mappings
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.mappings.is_empty()
+    }
+}
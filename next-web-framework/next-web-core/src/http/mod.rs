@@ -1,2 +1,3 @@
 pub mod auth_type;
 pub mod cookie;
+pub mod cors_registry;
@@ -12,14 +12,19 @@ pub mod constants;
 pub mod context;
 pub mod convert;
 pub mod error;
+pub mod event;
+pub mod health;
+pub mod openapi;
 pub mod traits;
 pub mod state;
+pub mod transaction;
 pub mod util;
 
 pub use self::autoregister::auto_register::*;
 pub use self::context::application_context::*;
 pub use async_trait::async_trait;
 pub use dyn_clone::{clone_trait_object, DynClone, clone_box};
+pub use validator;
 
 #[cfg(feature = "http-request")]
 pub extern crate headers;
\ No newline at end of file
@@ -0,0 +1,131 @@
+use super::api_schema::SchemaFn;
+
+/// Where a parameter is taken from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParameterIn {
+    Path,
+    Query,
+    Header,
+}
+
+impl ParameterIn {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Path => "path",
+            Self::Query => "query",
+            Self::Header => "header",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ApiParameter {
+    pub name: &'static str,
+    pub location: ParameterIn,
+    pub required: bool,
+    pub schema: Option<SchemaFn>,
+}
+
+/// A request or response body.
+#[derive(Debug, Clone)]
+pub struct ApiContent {
+    pub content_type: &'static str,
+    pub schema: Option<SchemaFn>,
+}
+
+/// 接口描述
+///
+/// The OpenAPI metadata of a handler, generated by the routing macros from the doc comments
+/// and the signature of the handler.
+///
+/// A `None` schema is a type without [`ApiSchema`](super::ApiSchema), it is documented as any
+/// value.
+#[derive(Debug, Clone, Default)]
+pub struct ApiOperation {
+    pub summary: Option<&'static str>,
+    pub description: Option<&'static str>,
+    pub deprecated: bool,
+    pub parameters: Vec<ApiParameter>,
+    /// The fields of these objects are documented as query parameters, e.g. `Query<Page>`.
+    pub query_objects: Vec<SchemaFn>,
+    pub request_body: Option<ApiContent>,
+    pub response: Option<ApiContent>,
+}
+
+impl ApiOperation {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn summary(mut self, summary: &'static str) -> Self {
+        self.summary = Some(summary);
+        self
+    }
+
+    pub fn description(mut self, description: &'static str) -> Self {
+        self.description = Some(description);
+        self
+    }
+
+    pub fn deprecated(mut self, deprecated: bool) -> Self {
+        self.deprecated = deprecated;
+        self
+    }
+
+    pub fn path_param(mut self, name: &'static str, schema: Option<SchemaFn>) -> Self {
+        self.parameters.push(ApiParameter {
+            name,
+            location: ParameterIn::Path,
+            required: true,
+            schema,
+        });
+        self
+    }
+
+    pub fn query_param(
+        mut self,
+        name: &'static str,
+        required: bool,
+        schema: Option<SchemaFn>,
+    ) -> Self {
+        self.parameters.push(ApiParameter {
+            name,
+            location: ParameterIn::Query,
+            required,
+            schema,
+        });
+        self
+    }
+
+    pub fn header(mut self, name: &'static str, required: bool) -> Self {
+        self.parameters.push(ApiParameter {
+            name,
+            location: ParameterIn::Header,
+            required,
+            schema: None,
+        });
+        self
+    }
+
+    /// Document the fields of the object as query parameters, skipped without a schema.
+    pub fn query(mut self, schema: Option<SchemaFn>) -> Self {
+        self.query_objects.extend(schema);
+        self
+    }
+
+    pub fn request_body(mut self, content_type: &'static str, schema: Option<SchemaFn>) -> Self {
+        self.request_body = Some(ApiContent {
+            content_type,
+            schema,
+        });
+        self
+    }
+
+    pub fn response(mut self, content_type: &'static str, schema: Option<SchemaFn>) -> Self {
+        self.response = Some(ApiContent {
+            content_type,
+            schema,
+        });
+        self
+    }
+}
@@ -0,0 +1,248 @@
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::marker::PhantomData;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use serde_json::{json, Value};
+
+/// Builds the schema of a type, registering the named schemas it uses.
+pub type SchemaFn = fn(&mut SchemaRegistry) -> Value;
+
+/// 接口模型
+///
+/// A type that can describe itself as a JSON schema in the OpenAPI document.
+///
+/// Derive it with `#[derive(ApiSchema)]`, the derive honours the `serde` attributes
+/// `rename`, `rename_all`, `skip` and `default`, and takes the doc comments of the fields as
+/// descriptions.
+/// Types deriving `schemars::JsonSchema` can use it instead, see `schemars_api_schema!` behind
+/// the `schemars` feature.
+pub trait ApiSchema {
+    /// The name under `#/components/schemas`, `None` inlines the schema where it is used.
+    fn schema_name() -> Option<Cow<'static, str>> {
+        None
+    }
+
+    fn schema(registry: &mut SchemaRegistry) -> Value;
+}
+
+/// The named schemas of a document, `#/components/schemas`.
+#[derive(Debug, Clone, Default)]
+pub struct SchemaRegistry {
+    schemas: BTreeMap<String, Value>,
+}
+
+impl SchemaRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The schema to use for `T`: a `$ref` for a named schema, registered the first time,
+    /// the schema itself otherwise.
+    pub fn reference<T: ApiSchema + ?Sized>(&mut self) -> Value {
+        match T::schema_name() {
+            Some(name) => {
+                if !self.schemas.contains_key(name.as_ref()) {
+                    // Placeholder first so recursive types stop at the `$ref`
+                    self.schemas.insert(name.to_string(), Value::Null);
+                    let schema = T::schema(self);
+                    self.schemas.insert(name.to_string(), schema);
+                }
+                Self::ref_of(&name)
+            }
+            None => T::schema(self),
+        }
+    }
+
+    /// Register a named schema, keeps the existing one.
+    pub fn define(&mut self, name: impl Into<String>, schema: Value) {
+        self.schemas.entry(name.into()).or_insert(schema);
+    }
+
+    /// Follow a `$ref` to a registered schema.
+    pub fn resolve<'a>(&'a self, schema: &'a Value) -> &'a Value {
+        schema
+            .get("$ref")
+            .and_then(Value::as_str)
+            .and_then(|path| path.strip_prefix("#/components/schemas/"))
+            .and_then(|name| self.schemas.get(name))
+            .unwrap_or(schema)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.schemas.get(name)
+    }
+
+    pub fn schemas(&self) -> &BTreeMap<String, Value> {
+        &self.schemas
+    }
+
+    pub fn into_schemas(self) -> BTreeMap<String, Value> {
+        self.schemas
+    }
+
+    pub fn ref_of(name: &str) -> Value {
+        json!({ "$ref": format!("#/components/schemas/{}", name) })
+    }
+}
+
+/// The schema of `ApiResponse<T>`, the response envelope of the web crate.
+pub fn api_response_schema(registry: &mut SchemaRegistry, data: Option<SchemaFn>) -> Value {
+    let data = data.map(|data| data(registry)).unwrap_or_else(|| json!({}));
+    json!({
+        "type": "object",
+        "properties": {
+            "status": { "type": "integer", "format": "uint16" },
+            "message": { "type": "string" },
+            "data": { "anyOf": [data, { "type": "null" }] },
+        },
+        "required": ["status", "message"],
+    })
+}
+
+/// Looks up the [`SchemaFn`] of a type whether or not it implements [`ApiSchema`], used by the
+/// routing macros so that handlers taking types without a schema still compile.
+///
+/// ```rust,ignore
+/// use next_web_core::openapi::{SchemaProbe, ViaApiSchema, ViaUnknown};
+///
+/// let schema = (&&SchemaProbe::<User>::new()).schema_fn();
+/// ```
+pub struct SchemaProbe<T: ?Sized>(PhantomData<T>);
+
+impl<T: ?Sized> SchemaProbe<T> {
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T: ?Sized> Default for SchemaProbe<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub trait ViaApiSchema {
+    fn schema_fn(&self) -> Option<SchemaFn>;
+}
+
+impl<T: ApiSchema + ?Sized> ViaApiSchema for &SchemaProbe<T> {
+    fn schema_fn(&self) -> Option<SchemaFn> {
+        Some(SchemaRegistry::reference::<T>)
+    }
+}
+
+pub trait ViaUnknown {
+    fn schema_fn(&self) -> Option<SchemaFn>;
+}
+
+impl<T: ?Sized> ViaUnknown for SchemaProbe<T> {
+    fn schema_fn(&self) -> Option<SchemaFn> {
+        None
+    }
+}
+
+macro_rules! impl_primitive {
+    ($($ty:ty => $schema:tt),+ $(,)?) => {
+        $(
+            impl ApiSchema for $ty {
+                fn schema(_: &mut SchemaRegistry) -> Value {
+                    json!($schema)
+                }
+            }
+        )+
+    };
+}
+
+impl_primitive! {
+    bool => { "type": "boolean" },
+    i8 => { "type": "integer", "format": "int8" },
+    i16 => { "type": "integer", "format": "int16" },
+    i32 => { "type": "integer", "format": "int32" },
+    i64 => { "type": "integer", "format": "int64" },
+    isize => { "type": "integer", "format": "int64" },
+    u8 => { "type": "integer", "format": "uint8", "minimum": 0 },
+    u16 => { "type": "integer", "format": "uint16", "minimum": 0 },
+    u32 => { "type": "integer", "format": "uint32", "minimum": 0 },
+    u64 => { "type": "integer", "format": "uint64", "minimum": 0 },
+    usize => { "type": "integer", "format": "uint64", "minimum": 0 },
+    f32 => { "type": "number", "format": "float" },
+    f64 => { "type": "number", "format": "double" },
+    char => { "type": "string", "minLength": 1, "maxLength": 1 },
+    str => { "type": "string" },
+    String => { "type": "string" },
+    () => { "type": "null" },
+    Value => {},
+}
+
+macro_rules! impl_wrapper {
+    ($($ty:ident),+) => {
+        $(
+            impl<T: ApiSchema + ?Sized> ApiSchema for $ty<T> {
+                fn schema_name() -> Option<Cow<'static, str>> {
+                    T::schema_name()
+                }
+
+                fn schema(registry: &mut SchemaRegistry) -> Value {
+                    T::schema(registry)
+                }
+            }
+        )+
+    };
+}
+
+impl_wrapper!(Box, Arc, Rc);
+
+impl<T: ApiSchema + ?Sized> ApiSchema for &T {
+    fn schema_name() -> Option<Cow<'static, str>> {
+        T::schema_name()
+    }
+
+    fn schema(registry: &mut SchemaRegistry) -> Value {
+        T::schema(registry)
+    }
+}
+
+impl<T: ApiSchema> ApiSchema for Option<T> {
+    fn schema(registry: &mut SchemaRegistry) -> Value {
+        json!({ "anyOf": [registry.reference::<T>(), { "type": "null" }] })
+    }
+}
+
+macro_rules! impl_array {
+    ($($ty:ident $(, $unique:literal)?);+) => {
+        $(
+            impl<T: ApiSchema> ApiSchema for $ty<T> {
+                fn schema(registry: &mut SchemaRegistry) -> Value {
+                    #[allow(unused_mut)]
+                    let mut schema = json!({ "type": "array", "items": registry.reference::<T>() });
+                    $(schema["uniqueItems"] = Value::Bool($unique);)?
+                    schema
+                }
+            }
+        )+
+    };
+}
+
+impl_array!(Vec; HashSet, true; BTreeSet, true);
+
+impl<T: ApiSchema> ApiSchema for [T] {
+    fn schema(registry: &mut SchemaRegistry) -> Value {
+        json!({ "type": "array", "items": registry.reference::<T>() })
+    }
+}
+
+macro_rules! impl_map {
+    ($($ty:ident),+) => {
+        $(
+            impl<K, V: ApiSchema> ApiSchema for $ty<K, V> {
+                fn schema(registry: &mut SchemaRegistry) -> Value {
+                    json!({ "type": "object", "additionalProperties": registry.reference::<V>() })
+                }
+            }
+        )+
+    };
+}
+
+impl_map!(HashMap, BTreeMap);
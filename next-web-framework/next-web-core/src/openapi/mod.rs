@@ -0,0 +1,15 @@
+pub mod api_operation;
+pub mod api_schema;
+pub mod open_api_builder;
+
+#[cfg(feature = "schemars")]
+pub mod schemars_schema;
+
+pub use api_operation::{ApiContent, ApiOperation, ApiParameter, ParameterIn};
+pub use api_schema::{
+    api_response_schema, ApiSchema, SchemaFn, SchemaProbe, SchemaRegistry, ViaApiSchema, ViaUnknown,
+};
+pub use open_api_builder::OpenApiBuilder;
+
+#[doc(hidden)]
+pub use serde_json;
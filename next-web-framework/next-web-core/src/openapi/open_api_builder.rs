@@ -0,0 +1,279 @@
+use std::collections::BTreeMap;
+
+use serde_json::{json, Map, Value};
+
+use super::api_operation::{ApiContent, ApiOperation};
+use super::api_schema::{SchemaFn, SchemaRegistry};
+
+/// 接口文档构建器
+///
+/// Builds an OpenAPI 3.1 document from the [`ApiOperation`]s of the handlers.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let mut builder = OpenApiBuilder::new("Order service", "1.0.0");
+/// builder.add_operation("GET", "/orders/{id}", "get_order", &operation);
+///
+/// let document = builder.build();
+/// ```
+#[derive(Debug, Clone)]
+pub struct OpenApiBuilder {
+    title: String,
+    version: String,
+    description: Option<String>,
+    servers: Vec<String>,
+    paths: BTreeMap<String, Map<String, Value>>,
+    registry: SchemaRegistry,
+}
+
+impl OpenApiBuilder {
+    pub const OPENAPI_VERSION: &'static str = "3.1.0";
+
+    const METHODS: &'static [&'static str] = &[
+        "get", "put", "post", "delete", "options", "head", "patch", "trace",
+    ];
+
+    pub fn new(title: impl Into<String>, version: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            version: version.into(),
+            description: None,
+            servers: Vec::new(),
+            paths: BTreeMap::new(),
+            registry: SchemaRegistry::new(),
+        }
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    pub fn server(mut self, url: impl Into<String>) -> Self {
+        self.servers.push(url.into());
+        self
+    }
+
+    pub fn registry(&mut self) -> &mut SchemaRegistry {
+        &mut self.registry
+    }
+
+    /// Add the operation of a handler.
+    ///
+    /// `method` is an HTTP method in any case, `ANY` and unknown methods are skipped.
+    /// Returns `false` if the operation was skipped.
+    pub fn add_operation(
+        &mut self,
+        method: &str,
+        path: &str,
+        operation_id: &str,
+        operation: &ApiOperation,
+    ) -> bool {
+        let method = method.to_ascii_lowercase();
+        if !Self::METHODS.contains(&method.as_str()) {
+            return false;
+        }
+
+        let value = self.operation(operation_id, operation);
+        self.paths
+            .entry(Self::openapi_path(path))
+            .or_default()
+            .insert(method, value);
+        true
+    }
+
+    pub fn build(self) -> Value {
+        let mut info = json!({ "title": self.title, "version": self.version });
+        if let Some(description) = self.description {
+            info["description"] = Value::String(description);
+        }
+
+        let mut document = json!({
+            "openapi": Self::OPENAPI_VERSION,
+            "info": info,
+            "paths": self.paths,
+            "components": { "schemas": self.registry.into_schemas() },
+        });
+        if !self.servers.is_empty() {
+            document["servers"] = self
+                .servers
+                .into_iter()
+                .map(|url| json!({ "url": url }))
+                .collect();
+        }
+        document
+    }
+
+    fn operation(&mut self, operation_id: &str, operation: &ApiOperation) -> Value {
+        let mut value = json!({ "operationId": operation_id });
+        if let Some(summary) = operation.summary {
+            value["summary"] = summary.into();
+        }
+        if let Some(description) = operation.description {
+            value["description"] = description.into();
+        }
+        if operation.deprecated {
+            value["deprecated"] = true.into();
+        }
+
+        let mut parameters = operation
+            .parameters
+            .iter()
+            .map(|parameter| {
+                json!({
+                    "name": parameter.name,
+                    "in": parameter.location.as_str(),
+                    "required": parameter.required,
+                    "schema": self.schema(parameter.schema.as_ref()),
+                })
+            })
+            .collect::<Vec<_>>();
+        for query in &operation.query_objects {
+            let schema = query(&mut self.registry);
+            let schema = self.registry.resolve(&schema);
+            let required = schema.get("required").and_then(Value::as_array);
+            if let Some(properties) = schema.get("properties").and_then(Value::as_object) {
+                parameters.extend(properties.iter().map(|(name, property)| {
+                    json!({
+                        "name": name,
+                        "in": "query",
+                        "required": required.is_some_and(|required| required.contains(&Value::String(name.clone()))),
+                        "schema": property,
+                    })
+                }));
+            }
+        }
+        if !parameters.is_empty() {
+            value["parameters"] = Value::Array(parameters);
+        }
+
+        if let Some(body) = &operation.request_body {
+            value["requestBody"] = json!({ "required": true, "content": self.content(body) });
+        }
+
+        let mut response = json!({ "description": "OK" });
+        if let Some(body) = &operation.response {
+            response["content"] = self.content(body);
+        }
+        value["responses"] = json!({ "200": response });
+
+        value
+    }
+
+    fn content(&mut self, content: &ApiContent) -> Value {
+        json!({ content.content_type: { "schema": self.schema(content.schema.as_ref()) } })
+    }
+
+    fn schema(&mut self, schema: Option<&SchemaFn>) -> Value {
+        schema
+            .map(|schema| schema(&mut self.registry))
+            .unwrap_or_else(|| json!({}))
+    }
+
+    /// `/files/{*path}` to `/files/{path}`.
+    fn openapi_path(path: &str) -> String {
+        path.replace("{*", "{")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use super::*;
+    use crate::openapi::{ApiSchema, SchemaProbe, ViaApiSchema, ViaUnknown};
+
+    struct User;
+
+    impl ApiSchema for User {
+        fn schema_name() -> Option<Cow<'static, str>> {
+            Some("User".into())
+        }
+
+        fn schema(registry: &mut SchemaRegistry) -> Value {
+            json!({
+                "type": "object",
+                "properties": {
+                    "name": registry.reference::<String>(),
+                    "friends": registry.reference::<Vec<User>>(),
+                },
+                "required": ["name"],
+            })
+        }
+    }
+
+    struct Page;
+
+    impl ApiSchema for Page {
+        fn schema(registry: &mut SchemaRegistry) -> Value {
+            json!({
+                "type": "object",
+                "properties": {
+                    "page": registry.reference::<u32>(),
+                    "size": registry.reference::<Option<u32>>(),
+                },
+                "required": ["page"],
+            })
+        }
+    }
+
+    struct Undocumented;
+
+    #[test]
+    fn test_build_document() {
+        let operation = ApiOperation::new()
+            .summary("Find the friends of a user")
+            .path_param("id", (&&SchemaProbe::<u64>::new()).schema_fn())
+            .query((&&SchemaProbe::<Page>::new()).schema_fn())
+            .request_body(
+                "application/json",
+                (&&SchemaProbe::<Undocumented>::new()).schema_fn(),
+            )
+            .response(
+                "application/json",
+                (&&SchemaProbe::<Vec<User>>::new()).schema_fn(),
+            );
+
+        let mut builder = OpenApiBuilder::new("Test", "1.0.0").server("/api");
+        assert!(builder.add_operation("GET", "/users/{id}/friends", "friends", &operation));
+        assert!(!builder.add_operation("ANY", "/users", "any", &operation));
+        let document = builder.build();
+
+        assert_eq!(document["openapi"], "3.1.0");
+        assert_eq!(document["servers"][0]["url"], "/api");
+
+        let get = &document["paths"]["/users/{id}/friends"]["get"];
+        assert_eq!(get["operationId"], "friends");
+        assert_eq!(get["summary"], "Find the friends of a user");
+
+        let parameters = get["parameters"].as_array().unwrap();
+        assert_eq!(parameters.len(), 3);
+        assert_eq!(parameters[0]["in"], "path");
+        assert_eq!(parameters[0]["schema"]["format"], "uint64");
+        assert_eq!(parameters[1]["name"], "page");
+        assert_eq!(parameters[1]["required"], true);
+        assert_eq!(parameters[2]["name"], "size");
+        assert_eq!(parameters[2]["required"], false);
+
+        assert_eq!(
+            get["requestBody"]["content"]["application/json"]["schema"],
+            json!({})
+        );
+        assert_eq!(
+            get["responses"]["200"]["content"]["application/json"]["schema"]["items"]["$ref"],
+            "#/components/schemas/User"
+        );
+
+        // Recursive schemas are registered once
+        let user = &document["components"]["schemas"]["User"];
+        assert_eq!(
+            user["properties"]["friends"]["items"]["$ref"],
+            "#/components/schemas/User"
+        );
+        assert_eq!(
+            document["components"]["schemas"].as_object().unwrap().len(),
+            1
+        );
+    }
+}
@@ -0,0 +1,43 @@
+use schemars::generate::SchemaSettings;
+use schemars::JsonSchema;
+use serde_json::Value;
+
+use super::api_schema::SchemaRegistry;
+
+/// The schema of a `schemars::JsonSchema` type, its definitions are moved to the registry.
+pub fn schemars_schema<T: JsonSchema + ?Sized>(registry: &mut SchemaRegistry) -> Value {
+    let mut settings = SchemaSettings::draft2020_12();
+    settings.definitions_path = "/components/schemas".into();
+    settings.meta_schema = None;
+
+    let mut generator = settings.into_generator();
+    let schema = generator.subschema_for::<T>().to_value();
+    for (name, definition) in generator.take_definitions(true) {
+        registry.define(name, definition);
+    }
+    schema
+}
+
+/// Implement [`ApiSchema`](super::ApiSchema) for types deriving `schemars::JsonSchema`.
+///
+/// ```rust,ignore
+/// #[derive(Deserialize, JsonSchema)]
+/// struct CreateOrder {
+///     product_id: u64,
+///     quantity: u32,
+/// }
+///
+/// next_web_core::schemars_api_schema!(CreateOrder);
+/// ```
+#[macro_export]
+macro_rules! schemars_api_schema {
+    ($($ty:ty),+ $(,)?) => {
+        $(
+            impl $crate::openapi::ApiSchema for $ty {
+                fn schema(registry: &mut $crate::openapi::SchemaRegistry) -> $crate::openapi::serde_json::Value {
+                    $crate::openapi::schemars_schema::schemars_schema::<$ty>(registry)
+                }
+            }
+        )+
+    };
+}
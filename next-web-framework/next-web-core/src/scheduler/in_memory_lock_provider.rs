@@ -0,0 +1,109 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+use async_trait::async_trait;
+
+use crate::{
+    error::BoxError, scheduler::lock_configuration::LockConfiguration,
+    traits::schedule::scheduler_lock_provider::SchedulerLockProvider,
+};
+
+/// 内存锁提供者
+///
+/// Keeps the locks in memory, jobs are only locked within the process.
+/// Used when no other provider is registered, and in tests.
+#[derive(Clone, Default)]
+pub struct InMemoryLockProvider {
+    /// Lock name to the time it expires at
+    locks: Arc<Mutex<HashMap<String, SystemTime>>>,
+}
+
+impl InMemoryLockProvider {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether the lock is currently held.
+    pub fn is_locked(&self, name: &str) -> bool {
+        self.locks
+            .lock()
+            .unwrap()
+            .get(name)
+            .is_some_and(|lock_until| *lock_until > SystemTime::now())
+    }
+}
+
+#[async_trait]
+impl SchedulerLockProvider for InMemoryLockProvider {
+    async fn lock(&self, configuration: &LockConfiguration) -> Result<bool, BoxError> {
+        let mut locks = self.locks.lock().unwrap();
+        let now = SystemTime::now();
+        match locks.get(configuration.name()) {
+            Some(lock_until) if *lock_until > now => Ok(false),
+            _ => {
+                locks.insert(
+                    configuration.name().to_string(),
+                    configuration.lock_at_most_until(),
+                );
+                Ok(true)
+            }
+        }
+    }
+
+    async fn unlock(&self, configuration: &LockConfiguration) -> Result<(), BoxError> {
+        let mut locks = self.locks.lock().unwrap();
+        let unlock_time = configuration.unlock_time();
+        if unlock_time > SystemTime::now() {
+            locks.insert(configuration.name().to_string(), unlock_time);
+        } else {
+            locks.remove(configuration.name());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_lock_and_unlock() {
+        let provider = InMemoryLockProvider::new();
+        let configuration = LockConfiguration::new("job", Duration::from_secs(60), Duration::ZERO);
+
+        assert!(provider.lock(&configuration).await.unwrap());
+        assert!(!provider.lock(&configuration).await.unwrap());
+        assert!(provider.is_locked("job"));
+
+        provider.unlock(&configuration).await.unwrap();
+        assert!(!provider.is_locked("job"));
+        assert!(provider.lock(&configuration).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_lock_at_least_for() {
+        let provider = InMemoryLockProvider::new();
+        let configuration =
+            LockConfiguration::new("job", Duration::from_secs(60), Duration::from_secs(30));
+
+        assert!(provider.lock(&configuration).await.unwrap());
+        provider.unlock(&configuration).await.unwrap();
+
+        // Kept until lock_at_least_for elapsed
+        assert!(provider.is_locked("job"));
+        assert!(!provider.lock(&configuration).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_expired_lock() {
+        let provider = InMemoryLockProvider::new();
+        let configuration = LockConfiguration::new("job", Duration::ZERO, Duration::ZERO);
+
+        assert!(provider.lock(&configuration).await.unwrap());
+        // Held at most for zero, the next execution gets it
+        assert!(provider.lock(&configuration).await.unwrap());
+    }
+}
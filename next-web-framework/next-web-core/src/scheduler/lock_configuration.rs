@@ -0,0 +1,110 @@
+use std::sync::OnceLock;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// 定时任务锁配置
+///
+/// Lock of a single execution of a scheduled job.
+///
+/// The lock is held until `lock_at_most_for` elapsed when the instance dies while running
+/// the job, and at least `lock_at_least_for` so that replicas with a slightly different
+/// clock don't run the job again right after it finished.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LockConfiguration {
+    name: String,
+    lock_at_most_for: Duration,
+    lock_at_least_for: Duration,
+    created_at: SystemTime,
+}
+
+impl LockConfiguration {
+    pub fn new(
+        name: impl Into<String>,
+        lock_at_most_for: Duration,
+        lock_at_least_for: Duration,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            lock_at_most_for,
+            // Can't be held longer than the maximum
+            lock_at_least_for: lock_at_least_for.min(lock_at_most_for),
+            created_at: SystemTime::now(),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn lock_at_most_for(&self) -> Duration {
+        self.lock_at_most_for
+    }
+
+    pub fn lock_at_least_for(&self) -> Duration {
+        self.lock_at_least_for
+    }
+
+    pub fn created_at(&self) -> SystemTime {
+        self.created_at
+    }
+
+    /// Time the lock expires if it is never released.
+    pub fn lock_at_most_until(&self) -> SystemTime {
+        self.created_at + self.lock_at_most_for
+    }
+
+    /// Time the lock can be released at, never before `lock_at_least_for` elapsed.
+    pub fn unlock_time(&self) -> SystemTime {
+        SystemTime::now().max(self.created_at + self.lock_at_least_for)
+    }
+
+    /// How long the lock still has to be held once the job finished, zero when it can be
+    /// released right away.
+    pub fn remaining_lock_at_least_for(&self) -> Duration {
+        self.unlock_time()
+            .duration_since(SystemTime::now())
+            .unwrap_or_default()
+    }
+}
+
+/// 当前实例的标识，记录在锁中
+///
+/// Identifier of this instance, stored as owner of the locks it holds.
+pub fn instance_id() -> &'static str {
+    static INSTANCE_ID: OnceLock<String> = OnceLock::new();
+    INSTANCE_ID.get_or_init(|| {
+        let hostname = std::env::var("HOSTNAME")
+            .or_else(|_| std::env::var("COMPUTERNAME"))
+            .unwrap_or_else(|_| String::from("unknown"));
+        let started_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos();
+        format!("{}:{}:{:x}", hostname, std::process::id(), started_at)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lock_times() {
+        let configuration =
+            LockConfiguration::new("job", Duration::from_secs(60), Duration::from_secs(10));
+        assert_eq!(
+            configuration.lock_at_most_until(),
+            configuration.created_at() + Duration::from_secs(60)
+        );
+        assert_eq!(
+            configuration.unlock_time(),
+            configuration.created_at() + Duration::from_secs(10)
+        );
+        assert!(configuration.remaining_lock_at_least_for() > Duration::from_secs(9));
+
+        let configuration = LockConfiguration::new("job", Duration::from_secs(1), Duration::from_secs(10));
+        assert_eq!(configuration.lock_at_least_for(), Duration::from_secs(1));
+
+        let configuration = LockConfiguration::new("job", Duration::from_secs(60), Duration::ZERO);
+        assert_eq!(configuration.remaining_lock_at_least_for(), Duration::ZERO);
+    }
+}
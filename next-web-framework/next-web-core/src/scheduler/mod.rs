@@ -1,2 +1,4 @@
 pub mod context;
+pub mod in_memory_lock_provider;
+pub mod lock_configuration;
 pub mod schedule_type;
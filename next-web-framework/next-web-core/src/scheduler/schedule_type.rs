@@ -10,6 +10,42 @@ pub enum ScheduleType {
     OneShot(WithArgs),
 }
 
+impl ScheduleType {
+    pub fn args(&self) -> &WithArgs {
+        match self {
+            ScheduleType::Cron(args) | ScheduleType::FixedRate(args) | ScheduleType::OneShot(args) => args,
+        }
+    }
+}
+
+/// 上一次执行还未结束时的处理方式
+///
+/// What to do when the job is due while the previous execution is still running.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum OverlapPolicy {
+    /// Run the executions concurrently
+    #[default]
+    Concurrent,
+    /// Skip the execution
+    Skip,
+    /// Run the execution once the previous one finished, at most one execution is queued
+    Queue,
+}
+
+/// 错过执行时间时的处理方式，例如时钟跳变或进程停顿之后
+///
+/// What to do when the job runs later than `misfire_threshold` after its fire time,
+/// e.g. after a clock jump or a long stall of the process.
+/// The missed fire times are never replayed one by one.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum MisfirePolicy {
+    /// Run the job once right away
+    #[default]
+    FireOnce,
+    /// Skip the execution and wait for the next fire time
+    Skip,
+}
+
 #[derive(Debug, Default, Clone, PartialEq, Eq)]
 pub struct WithArgs {
     pub cron: Option<&'static str>,
@@ -18,4 +54,16 @@ pub struct WithArgs {
     pub timezone: Option<&'static str>,
 
     pub time_unit: Option<&'static str>,
-}
\ No newline at end of file
+
+    /// Name of the scheduler lock, the job is only locked when `lock_at_most_for` is set
+    pub lock_name: Option<&'static str>,
+    /// milliseconds
+    pub lock_at_most_for: Option<u64>,
+    /// milliseconds
+    pub lock_at_least_for: Option<u64>,
+
+    pub overlap: OverlapPolicy,
+    pub misfire: MisfirePolicy,
+    /// milliseconds
+    pub misfire_threshold: Option<u64>,
+}
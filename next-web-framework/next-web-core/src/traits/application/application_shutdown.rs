@@ -143,4 +143,15 @@ where
     ///
     /// Log errors but avoid panicking to ensure other shutdown s run.
     async fn shutdown(&mut self);
-}
\ No newline at end of file
+
+    /// 关闭钩子的超时时间
+    ///
+    /// 返回 `None` 时使用 `next.server.shutdown.timeout_per_hook`。
+    ///
+    /// Timeout of this hook, `None` falls back to `next.server.shutdown.timeout_per_hook`.
+    fn timeout(&self) -> Option<std::time::Duration> {
+        None
+    }
+}
+
+dyn_clone::clone_trait_object!(ApplicationShutdown);
@@ -31,4 +31,15 @@ where
     fn event_id(&self) -> TypeId {
         TypeId::of::<Self>()
     }
+
+    /// 获取事件的父类型ID，监听父类型的监听器也会收到该事件
+    ///
+    /// Get the type IDs of the markers this event belongs to, listeners of a marker receive
+    /// the event too, e.g. `vec![TypeId::of::<SecurityEvent>()]` with `struct SecurityEvent;`.
+    ///
+    /// Every event belongs to `dyn ApplicationEvent`, a listener of
+    /// `TypeId::of::<dyn ApplicationEvent>()` receives all events.
+    fn super_event_ids(&self) -> Vec<TypeId> {
+        Vec::new()
+    }
 }
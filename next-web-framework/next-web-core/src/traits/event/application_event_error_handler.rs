@@ -0,0 +1,26 @@
+use dyn_clone::DynClone;
+
+use crate::traits::ordered::Ordered;
+
+use super::application_event::ApplicationEvent;
+
+/// 应用事件错误处理器
+///
+/// Called when a listener panics while handling an event, the listener keeps receiving the
+/// next events.
+///
+/// Register it as `Box<dyn ApplicationEventErrorHandler>` to be picked up, the error is
+/// logged otherwise. When several are registered the one with the lowest [`Ordered`] order
+/// is used, the application fails to start if two share that order.
+pub trait ApplicationEventErrorHandler
+where
+    Self: Send + Sync,
+    Self: DynClone + Ordered,
+{
+    /// 处理监听器错误
+    ///
+    /// Handle the error of the listener `listener`
+    fn handle_error(&self, listener: &str, event: &dyn ApplicationEvent, error: &str);
+}
+
+dyn_clone::clone_trait_object!(ApplicationEventErrorHandler);
@@ -3,18 +3,62 @@ use async_trait::async_trait;
 use dyn_clone::DynClone;
 use std::any::TypeId;
 
+/// 监听器执行方式
+///
+/// How the multicaster invokes a listener
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ListenerMode {
+    /// 按顺序依次执行，执行完成后才分发下一个事件
+    ///
+    /// Invoked by the multicaster in [`order`](ApplicationListener::order) order, the next event is dispatched once
+    /// the synchronous listeners are done with the current one.
+    #[default]
+    Sync,
+    /// 在独立任务中执行，不阻塞其他监听器
+    ///
+    /// Invoked on its own task, with its own bounded queue of events, without holding up
+    /// the other listeners.
+    Async,
+}
+
+/// 事务阶段
+///
+/// Phase of the transaction a transactional event listener is invoked in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TransactionPhase {
+    /// 提交前，监听器 panic 时回滚事务
+    ///
+    /// Before the commit, the transaction is rolled back when a listener panics
+    BeforeCommit,
+    /// 提交后
+    ///
+    /// After the commit
+    AfterCommit,
+    /// 回滚后
+    ///
+    /// After the rollback
+    AfterRollback,
+}
+
 /// 应用事件监听器
 ///
+/// 监听器按 `order` 从小到大执行，接收 `event_id` 类型及以其为父类型的事件。
+///
 /// Application event listener
+///
+/// Listeners run sorted by [`order`](Self::order), lowest first. A listener receives the events of its
+/// `event_id` and the events that name it in their
+/// [`super_event_ids`](ApplicationEvent::super_event_ids).
 #[async_trait]
 pub trait ApplicationListener
 where
     Self: Send + Sync,
     Self: DynClone,
 {
-    /// 获取事件ID
+    /// 获取事件ID，为空时接收所有ID的事件
     ///
-    /// Get event ID
+    /// Get event ID, events published with another non-empty ID are not received.
+    /// An empty ID receives the events of every ID.
     fn id(&self) -> &'static str;
 
     /// 获取事件类型
@@ -22,6 +66,37 @@ where
     /// Get event typeid
     fn event_id(&self) -> TypeId;
 
+    /// 是否处理该事件
+    ///
+    /// Whether to handle the event, e.g. only the changes of some keys
+    #[allow(unused_variables)]
+    fn supports_event(&self, event: &dyn ApplicationEvent) -> bool {
+        true
+    }
+
+    /// 获取执行顺序，值越小越先执行
+    ///
+    /// Get the order the listener is invoked in, lower values run first
+    fn order(&self) -> i32 {
+        0
+    }
+
+    /// 获取执行方式
+    ///
+    /// Get how the listener is invoked
+    fn mode(&self) -> ListenerMode {
+        ListenerMode::Sync
+    }
+
+    /// 获取事务阶段，为 `None` 时在事件发布后立即执行
+    ///
+    /// Get the transaction phase the listener is invoked in, `None` invokes it once the event
+    /// is published. A transactional event listener only receives the events published
+    /// inside a transaction, e.g. `TransactionalExecutor::execute_in_transaction`.
+    fn transaction_phase(&self) -> Option<TransactionPhase> {
+        None
+    }
+
     /// 处理应用事件
     ///
     /// Handle application event
@@ -1,4 +1,7 @@
 pub mod application_event;
+pub mod application_event_error_handler;
 pub mod application_event_multicaster;
 pub mod application_event_publisher;
 pub mod application_listener;
+pub mod outbox_publisher;
+pub mod outbox_store;
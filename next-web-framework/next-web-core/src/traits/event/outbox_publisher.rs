@@ -0,0 +1,30 @@
+use async_trait::async_trait;
+use dyn_clone::DynClone;
+
+use crate::error::BoxError;
+use crate::event::outbox_message::OutboxMessage;
+
+/// 发件箱消息发布者
+///
+/// Outbox publisher
+///
+/// Publishes the outbox messages of a destination scheme, e.g. `rabbitmq`, to their broker.
+/// Register it as `Box<dyn OutboxPublisher>` to be picked up by the relay.
+#[async_trait]
+pub trait OutboxPublisher
+where
+    Self: Send + Sync,
+    Self: DynClone,
+{
+    /// 支持的目标协议
+    ///
+    /// Scheme of the destinations it publishes to
+    fn scheme(&self) -> &'static str;
+
+    /// 发布消息，返回成功后消息不会再次发布
+    ///
+    /// Publish the message, once it returns `Ok` the message is not relayed again
+    async fn publish(&self, message: &OutboxMessage) -> Result<(), BoxError>;
+}
+
+dyn_clone::clone_trait_object!(OutboxPublisher);
@@ -0,0 +1,51 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use dyn_clone::DynClone;
+use tokio::sync::Notify;
+
+use crate::error::BoxError;
+use crate::event::outbox_message::OutboxMessage;
+
+/// 发件箱存储
+///
+/// Outbox store
+///
+/// Gives the committed messages of a transactional outbox to the relay, which marks them
+/// once they are published. Register it as `Box<dyn OutboxStore>` to be relayed.
+#[async_trait]
+pub trait OutboxStore
+where
+    Self: Send + Sync,
+    Self: DynClone,
+{
+    /// 获取到期的未发布消息，按创建时间排序
+    ///
+    /// Get up to `limit` messages not published nor dead yet whose next attempt is due, the
+    /// oldest first
+    async fn pending(&self, limit: usize) -> Result<Vec<OutboxMessage>, BoxError>;
+
+    /// 标记消息已发布
+    ///
+    /// Mark the message as published, it is not relayed again
+    async fn mark_published(&self, id: &str) -> Result<(), BoxError>;
+
+    /// 记录发布失败，消息会在 `retry_at` 之后再次发布
+    ///
+    /// Record a failed attempt, the message is relayed again once `retry_at`, in epoch
+    /// milliseconds, has passed
+    async fn mark_failed(&self, id: &str, error: &str, retry_at: i64) -> Result<(), BoxError>;
+
+    /// 标记消息为死信，不再发布
+    ///
+    /// Record the last failed attempt of a message that ran out of attempts, it is kept for
+    /// inspection and is not relayed again
+    async fn mark_dead(&self, id: &str, error: &str) -> Result<(), BoxError>;
+
+    /// 有消息提交时通知
+    ///
+    /// Notified when new messages are committed, the relay polls otherwise
+    fn notifier(&self) -> Arc<Notify>;
+}
+
+dyn_clone::clone_trait_object!(OutboxStore);
@@ -9,15 +9,31 @@ use crate::{
         filter::http_filter_chain::HttpFilterChain,
         http::{http_request::HttpRequest, http_response::HttpResponse},
         named::Named,
+        ordered::Ordered,
     },
 };
 
+/// HTTP 过滤器
+///
+/// 过滤器按 [`Ordered`] 从小到大组成过滤器链，调用 `filter_chain.do_filter` 继续执行，
+/// 不调用则直接返回 `response` 中写入的响应。
+///
+/// Http filter
+///
+/// Filters form a chain sorted by [`Ordered`], lowest first. A filter continues the chain
+/// with `filter_chain.do_filter(request, response)`, once it returns `response` holds the
+/// response of the handler and can be modified. A filter that does not continue the chain
+/// short-circuits it, the `response` it wrote is sent back, e.g. a `401`.
+///
+/// Returning an error also stops the chain, the client receives a `500`.
+///
+/// Register it as `Arc<dyn HttpFilter>` to be picked up.
 #[async_trait]
 pub trait HttpFilter
 where
     Self: Send + Sync,
     Self: Any + DynClone,
-    Self: Named
+    Self: Named + Ordered,
 {
     async fn do_filter(
         &self,
@@ -26,6 +42,20 @@ where
         filter_chain: &dyn HttpFilterChain,
     ) -> Result<(), BoxError>;
 
+    /// 过滤器生效的路径，为空时对所有路径生效
+    ///
+    /// Path patterns the filter applies to, e.g. `/api/**`, every path when empty.
+    fn url_patterns(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// 过滤器不生效的路径
+    ///
+    /// Path patterns the filter is skipped for, they take precedence over [`url_patterns`](HttpFilter::url_patterns).
+    fn exclude_url_patterns(&self) -> Vec<String> {
+        Vec::new()
+    }
+
     #[allow(unused_variables)]
     fn supports(&self, name: &str) -> bool {
         false
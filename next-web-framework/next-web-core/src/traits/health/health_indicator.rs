@@ -0,0 +1,32 @@
+use async_trait::async_trait;
+use dyn_clone::DynClone;
+
+use crate::health::health_status::Health;
+
+/// 健康指示器
+///
+/// 由各个服务（数据库、Redis、MQ 等）提供，`/actuator/health` 会聚合所有指示器的结果。
+///
+/// Health indicator
+///
+/// Contributed by services (database, redis, mq ...), `/actuator/health` aggregates
+/// the result of every registered indicator.
+/// Register it as `Box<dyn HealthIndicator>` to be picked up.
+#[async_trait]
+pub trait HealthIndicator
+where
+    Self: Send + Sync,
+    Self: DynClone,
+{
+    /// 组件名称
+    ///
+    /// Name of the component, used as key in the health report.
+    fn name(&self) -> &str;
+
+    /// 检查组件健康状态
+    ///
+    /// Check the health of the component.
+    async fn health(&self) -> Health;
+}
+
+dyn_clone::clone_trait_object!(HealthIndicator);
@@ -0,0 +1,37 @@
+use dyn_clone::DynClone;
+
+use crate::http::cors_registry::CorsRegistry;
+
+/// 跨域配置器
+///
+/// 以编程方式注册跨域规则，作为单例提供即可生效。
+///
+/// Cors configurer
+///
+/// Registers cors mappings in code, in addition to the `next.server.http.cors` properties.
+/// Register it as `Box<dyn CorsConfigurer>` to be picked up.
+///
+/// ```ignore
+/// impl CorsConfigurer for ApiCorsConfigurer {
+///     fn configure(&self, registry: &mut CorsRegistry) {
+///         registry.add_mapping(
+///             "/api/**",
+///             CorsConfiguration::new()
+///                 .with_allowed_origins(["https://*.example.com"])
+///                 .with_allowed_methods(["GET", "POST", "DELETE"]),
+///         );
+///     }
+/// }
+/// ```
+pub trait CorsConfigurer
+where
+    Self: Send + Sync,
+    Self: DynClone,
+{
+    /// 注册跨域规则
+    ///
+    /// Register the mappings.
+    fn configure(&self, registry: &mut CorsRegistry);
+}
+
+dyn_clone::clone_trait_object!(CorsConfigurer);
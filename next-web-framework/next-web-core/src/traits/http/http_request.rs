@@ -1,4 +1,5 @@
 use axum::{
+    body::Body,
     extract::Request,
     http::{uri::Scheme, Uri, Version},
 };
@@ -62,6 +63,11 @@ where
     fn clean_up(&mut self);
 
     fn is_secure(&self) -> bool;
+
+    /// Take the request out, e.g. to pass it on to the handler at the end of the filter chain.
+    ///
+    /// A copy without body is left in place, so the request can still be read afterwards.
+    fn take(&mut self) -> Option<Request>;
 }
 
 pub type OneMap = HashMap<String, AnyValue>;
@@ -177,4 +183,10 @@ impl HttpRequest for Request {
     fn is_secure(&self) -> bool {
         self.uri().scheme() == Some(&Scheme::HTTPS)
     }
+
+    fn take(&mut self) -> Option<Request> {
+        let (parts, body) = std::mem::take(self).into_parts();
+        *self = Request::from_parts(parts.clone(), Body::empty());
+        Some(Request::from_parts(parts, body))
+    }
 }
@@ -27,6 +27,9 @@ where
 
     fn set_redirect(&mut self, url: &str);
 
+    /// Replace the whole response, e.g. with the response of the handler.
+    fn replace(&mut self, response: Response);
+
     // fn set_cookie(&mut self, cookie: Cookie);
 }
 
@@ -92,4 +95,8 @@ impl HttpResponse for Response {
             self.headers_mut().insert(header::LOCATION, url);
         }
     }
+
+    fn replace(&mut self, response: Response) {
+        *self = response;
+    }
 }
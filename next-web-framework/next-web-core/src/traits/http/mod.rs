@@ -1,3 +1,4 @@
+pub mod cors_configurer;
 pub mod http_response;
 pub mod request_dispatcher;
 #[cfg(feature = "http-request")]
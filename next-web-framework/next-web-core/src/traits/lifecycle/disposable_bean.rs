@@ -0,0 +1,20 @@
+use async_trait::async_trait;
+use dyn_clone::DynClone;
+
+/// 销毁回调
+///
+/// Called last when the application shuts down, after the [`Lifecycle`](super::lifecycle::Lifecycle)
+/// beans are stopped and the [`ApplicationShutdown`](crate::traits::application::application_shutdown::ApplicationShutdown)
+/// hooks have run, so connection pools and clients are closed once nothing uses them anymore,
+/// see [`ApplicationContext::destroy_beans`](crate::ApplicationContext::destroy_beans).
+///
+/// Register the implementation as a `Box<dyn DisposableBean>` singleton.
+#[async_trait]
+pub trait DisposableBean
+where
+    Self: DynClone + Send + Sync,
+{
+    async fn destroy(&mut self);
+}
+
+dyn_clone::clone_trait_object!(DisposableBean);
@@ -0,0 +1,39 @@
+use async_trait::async_trait;
+use dyn_clone::DynClone;
+
+use crate::error::BoxError;
+use crate::ApplicationContext;
+
+/// 初始化回调
+///
+/// Called once every singleton is registered and the infrastructure is initialized, before the
+/// router is built, see [`ApplicationContext::initialize_beans`].
+///
+/// Register the implementation as a `Box<dyn InitializingBean>` singleton. An error aborts the
+/// startup.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// #[Singleton(binds = [Self::into_initializing_bean])]
+/// #[derive(Clone)]
+/// struct CacheWarmer;
+///
+/// #[async_trait]
+/// impl InitializingBean for CacheWarmer {
+///     async fn after_properties_set(&self, ctx: &mut ApplicationContext) -> Result<(), BoxError> {
+///         let repository = ctx.resolve::<ProductRepository>();
+///         // fill the cache
+///         Ok(())
+///     }
+/// }
+/// ```
+#[async_trait]
+pub trait InitializingBean
+where
+    Self: DynClone + Send + Sync,
+{
+    async fn after_properties_set(&self, ctx: &mut ApplicationContext) -> Result<(), BoxError>;
+}
+
+dyn_clone::clone_trait_object!(InitializingBean);
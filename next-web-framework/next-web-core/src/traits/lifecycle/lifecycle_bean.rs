@@ -0,0 +1,243 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use dyn_clone::DynClone;
+
+use crate::context::application_context::run_callback;
+use crate::error::BoxError;
+
+/// 生命周期组件
+///
+/// A component started before the HTTP server is marked ready and stopped before the server
+/// shuts down its dependencies, e.g. a message consumer, see
+/// [`ApplicationContext::start_lifecycle_beans`] and
+/// [`ApplicationContext::stop_lifecycle_beans`](crate::ApplicationContext::stop_lifecycle_beans).
+///
+/// The components start in ascending [`phase`](Lifecycle::phase) and stop in descending phase:
+/// a consumer with a higher phase than the producer it forwards to starts after it and stops
+/// before it.
+///
+/// Register the implementation as a `Box<dyn Lifecycle>` singleton. The context calls a clone of
+/// the singleton, keep the running state shared between the clones, e.g. in an `Arc`.
+///
+/// [`ApplicationContext::start_lifecycle_beans`]: crate::ApplicationContext::start_lifecycle_beans
+#[async_trait]
+pub trait Lifecycle
+where
+    Self: DynClone + Send + Sync,
+{
+    async fn start(&mut self) -> Result<(), BoxError>;
+
+    async fn stop(&mut self);
+
+    fn is_running(&self) -> bool;
+
+    /// Lower phases start first and stop last.
+    fn phase(&self) -> i32 {
+        0
+    }
+
+    /// Whether the context starts the component, `false` leaves it to the application.
+    fn is_auto_startup(&self) -> bool {
+        true
+    }
+}
+
+dyn_clone::clone_trait_object!(Lifecycle);
+
+/// 生命周期组件集合
+///
+/// The [`Lifecycle`] beans of a context, taken with
+/// [`ApplicationContext::lifecycle_beans`](crate::ApplicationContext::lifecycle_beans) so they
+/// start and stop without holding the context.
+#[derive(Clone, Default)]
+pub struct LifecycleBeans(Vec<Box<dyn Lifecycle>>);
+
+impl LifecycleBeans {
+    pub fn new(mut beans: Vec<Box<dyn Lifecycle>>) -> Self {
+        beans.sort_by_key(|bean| bean.phase());
+        Self(beans)
+    }
+
+    /// Start the beans that start automatically and are not running, lowest phase first.
+    ///
+    /// Stops at the first bean failing to start, the beans started before it are stopped
+    /// again, each one bounded by the timeout, and the error is returned.
+    pub async fn start(&self, timeout: Duration) -> Result<(), BoxError> {
+        let mut started = Vec::new();
+        for bean in self
+            .0
+            .iter()
+            .filter(|bean| bean.is_auto_startup() && !bean.is_running())
+        {
+            let mut bean = bean.clone();
+            if let Err(error) = bean.start().await {
+                Self(started).stop(timeout).await;
+                return Err(format!(
+                    "failed to start the lifecycle bean with phase {}: {}",
+                    bean.phase(),
+                    error
+                )
+                .into());
+            }
+            started.push(bean);
+        }
+
+        Ok(())
+    }
+
+    /// Stop the running beans, highest phase first. Each one is bounded by the timeout, a bean
+    /// that hangs or panics does not prevent the following ones from stopping.
+    pub async fn stop(&self, timeout: Duration) {
+        for bean in self.0.iter().rev().filter(|bean| bean.is_running()) {
+            let mut bean = bean.clone();
+            let name = format!("lifecycle bean with phase {}", bean.phase());
+            run_callback(&name, timeout, async move { bean.stop().await }).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    use crate::context::application_context::{
+        singleton, ApplicationContext, DynProvider, Module, Provider, ResolveModule,
+    };
+
+    use super::*;
+
+    static EVENTS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+    #[derive(Clone)]
+    struct Component {
+        name: &'static str,
+        phase: i32,
+        running: Arc<AtomicBool>,
+    }
+
+    #[async_trait]
+    impl Lifecycle for Component {
+        async fn start(&mut self) -> Result<(), BoxError> {
+            self.running.store(true, Ordering::SeqCst);
+            EVENTS.lock().unwrap().push(format!("start {}", self.name));
+            Ok(())
+        }
+
+        async fn stop(&mut self) {
+            self.running.store(false, Ordering::SeqCst);
+            EVENTS.lock().unwrap().push(format!("stop {}", self.name));
+            if self.name == "consumer" {
+                panic!("a failing stop does not prevent the others");
+            }
+        }
+
+        fn is_running(&self) -> bool {
+            self.running.load(Ordering::SeqCst)
+        }
+
+        fn phase(&self) -> i32 {
+            self.phase
+        }
+    }
+
+    fn component(name: &'static str, phase: i32) -> DynProvider {
+        let running = Arc::new(AtomicBool::new(false));
+        Provider::from(
+            singleton(move |_| {
+                Box::new(Component {
+                    name,
+                    phase,
+                    running: running.clone(),
+                }) as Box<dyn Lifecycle>
+            })
+            .name(name),
+        )
+        .into()
+    }
+
+    struct TestModule;
+
+    impl Module for TestModule {
+        fn providers() -> Vec<DynProvider> {
+            vec![component("consumer", 100), component("pool", -100), component("cache", 0)]
+        }
+    }
+
+    #[tokio::test]
+    async fn test_lifecycle_phases() {
+        let mut ctx = ApplicationContext::create(vec![ResolveModule::new::<TestModule>()]);
+
+        ctx.start_lifecycle_beans(Duration::from_secs(1)).await.unwrap();
+        ctx.start_lifecycle_beans(Duration::from_secs(1)).await.unwrap();
+        ctx.stop_lifecycle_beans(Duration::from_secs(1)).await;
+
+        assert_eq!(
+            *EVENTS.lock().unwrap(),
+            [
+                "start pool",
+                "start cache",
+                "start consumer",
+                "stop consumer",
+                "stop cache",
+                "stop pool"
+            ]
+        );
+    }
+
+    #[derive(Clone)]
+    struct Step {
+        phase: i32,
+        fails: bool,
+        running: Arc<AtomicBool>,
+        events: Arc<Mutex<Vec<String>>>,
+    }
+
+    #[async_trait]
+    impl Lifecycle for Step {
+        async fn start(&mut self) -> Result<(), BoxError> {
+            if self.fails {
+                return Err("broker unavailable".into());
+            }
+            self.running.store(true, Ordering::SeqCst);
+            self.events.lock().unwrap().push(format!("start {}", self.phase));
+            Ok(())
+        }
+
+        async fn stop(&mut self) {
+            self.running.store(false, Ordering::SeqCst);
+            self.events.lock().unwrap().push(format!("stop {}", self.phase));
+        }
+
+        fn is_running(&self) -> bool {
+            self.running.load(Ordering::SeqCst)
+        }
+
+        fn phase(&self) -> i32 {
+            self.phase
+        }
+    }
+
+    #[tokio::test]
+    async fn test_failed_start() {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let step = |phase, fails| -> Box<dyn Lifecycle> {
+            Box::new(Step {
+                phase,
+                fails,
+                running: Default::default(),
+                events: events.clone(),
+            })
+        };
+        let beans = LifecycleBeans::new(vec![step(2, false), step(1, false), step(3, true), step(4, false)]);
+
+        let error = beans.start(Duration::from_secs(1)).await.unwrap_err();
+        assert!(error.to_string().contains("phase 3"));
+        assert_eq!(
+            *events.lock().unwrap(),
+            ["start 1", "start 2", "stop 2", "stop 1"]
+        );
+    }
+}
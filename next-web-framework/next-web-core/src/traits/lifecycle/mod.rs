@@ -0,0 +1,3 @@
+pub mod disposable_bean;
+pub mod initializing_bean;
+pub mod lifecycle_bean;
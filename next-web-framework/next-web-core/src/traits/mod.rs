@@ -10,7 +10,9 @@ pub mod error_solver;
 pub mod event;
 pub mod from_context;
 pub mod group;
+pub mod health;
 pub mod http;
+pub mod lifecycle;
 pub mod locale_resolver;
 pub mod ordered;
 pub mod properties_post_processor;
@@ -22,4 +24,5 @@ pub mod status;
 pub mod store;
 pub mod stream;
 pub mod to_error_response;
+pub mod transaction;
 pub mod use_router;
@@ -1 +1,2 @@
-pub mod scheduled_task;
\ No newline at end of file
+pub mod scheduled_task;
+pub mod scheduler_lock_provider;
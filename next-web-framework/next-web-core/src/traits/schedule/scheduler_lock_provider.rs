@@ -0,0 +1,35 @@
+use async_trait::async_trait;
+use dyn_clone::DynClone;
+
+use crate::{error::BoxError, scheduler::lock_configuration::LockConfiguration};
+
+/// 定时任务锁提供者
+///
+/// 多实例部署时，只有获得锁的实例会执行带锁的 `#[Scheduled]` 任务，其余实例跳过本次执行。
+///
+/// Scheduler lock provider
+///
+/// With several replicas only the instance that acquires the lock runs a locked
+/// `#[Scheduled]` job, the others skip the execution.
+/// Register it as `Box<dyn SchedulerLockProvider>` to be picked up, without one an
+/// in-memory provider is used which only locks within the process.
+#[async_trait]
+pub trait SchedulerLockProvider
+where
+    Self: Send + Sync,
+    Self: DynClone,
+{
+    /// 尝试获取锁，锁被其他实例持有时返回 `false`
+    ///
+    /// Try to acquire the lock until [`LockConfiguration::lock_at_most_until`],
+    /// `false` when it is held by another instance.
+    async fn lock(&self, configuration: &LockConfiguration) -> Result<bool, BoxError>;
+
+    /// 释放锁，锁至少保留到 [`LockConfiguration::unlock_time`]
+    ///
+    /// Release the lock, it is kept until [`LockConfiguration::unlock_time`] when the job
+    /// finished before `lock_at_least_for`.
+    async fn unlock(&self, configuration: &LockConfiguration) -> Result<(), BoxError>;
+}
+
+dyn_clone::clone_trait_object!(SchedulerLockProvider);
@@ -0,0 +1,32 @@
+use async_trait::async_trait;
+
+use crate::error::BoxError;
+
+/// 事务同步回调
+///
+/// Transaction synchronization
+///
+/// Callbacks around the completion of the current transaction, registered with
+/// [`TransactionSynchronizationManager::register`](crate::transaction::transaction_synchronization_manager::TransactionSynchronizationManager::register).
+#[async_trait]
+pub trait TransactionSynchronization
+where
+    Self: Send + Sync,
+{
+    /// 提交前调用，返回错误时回滚事务
+    ///
+    /// Called before the commit, the transaction is rolled back when it returns an error
+    async fn before_commit(&self) -> Result<(), BoxError> {
+        Ok(())
+    }
+
+    /// 提交后调用
+    ///
+    /// Called after the commit
+    async fn after_commit(&self) {}
+
+    /// 回滚后调用
+    ///
+    /// Called after the rollback
+    async fn after_rollback(&self) {}
+}
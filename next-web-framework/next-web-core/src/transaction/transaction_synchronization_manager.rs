@@ -0,0 +1,123 @@
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+
+use crate::error::BoxError;
+use crate::traits::transaction::transaction_synchronization::TransactionSynchronization;
+
+tokio::task_local! {
+    static SYNCHRONIZATIONS: Synchronizations;
+}
+
+/// 事务同步管理器
+///
+/// Transaction synchronization manager
+///
+/// Keeps the synchronizations registered while a transaction is running. The transaction
+/// runs its block with [`scope`](Self::scope) and calls the returned [`Synchronizations`]
+/// once it commits or rolls back.
+pub struct TransactionSynchronizationManager;
+
+impl TransactionSynchronizationManager {
+    /// 在事务范围内执行
+    ///
+    /// Run `block` as the body of a transaction, the synchronizations registered by it are
+    /// returned with its output. A nested scope has its own synchronizations.
+    pub async fn scope<F: Future>(block: F) -> (F::Output, Synchronizations) {
+        let synchronizations = Synchronizations::default();
+        let output = SYNCHRONIZATIONS.scope(synchronizations.clone(), block).await;
+        (output, synchronizations)
+    }
+
+    /// 当前是否处于事务中
+    ///
+    /// Whether the current task runs inside a transaction
+    pub fn is_active() -> bool {
+        SYNCHRONIZATIONS.try_with(|_| ()).is_ok()
+    }
+
+    /// 注册事务同步回调，不在事务中时返回 `false`
+    ///
+    /// Register a synchronization to the current transaction, returns `false` when there is
+    /// no transaction
+    pub fn register(synchronization: Arc<dyn TransactionSynchronization>) -> bool {
+        SYNCHRONIZATIONS
+            .try_with(|synchronizations| synchronizations.0.lock().unwrap().push(synchronization))
+            .is_ok()
+    }
+}
+
+/// 事务同步回调集合
+///
+/// Synchronizations registered during a transaction, called in registration order
+#[derive(Clone, Default)]
+pub struct Synchronizations(Arc<Mutex<Vec<Arc<dyn TransactionSynchronization>>>>);
+
+impl Synchronizations {
+    fn all(&self) -> Vec<Arc<dyn TransactionSynchronization>> {
+        self.0.lock().unwrap().clone()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.lock().unwrap().is_empty()
+    }
+
+    /// Stops at the first error, the transaction must then be rolled back.
+    pub async fn before_commit(&self) -> Result<(), BoxError> {
+        for synchronization in self.all() {
+            synchronization.before_commit().await?;
+        }
+        Ok(())
+    }
+
+    pub async fn after_commit(&self) {
+        for synchronization in self.all() {
+            synchronization.after_commit().await;
+        }
+    }
+
+    pub async fn after_rollback(&self) {
+        for synchronization in self.all() {
+            synchronization.after_rollback().await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+
+    use super::*;
+
+    struct Recorder(Arc<Mutex<Vec<&'static str>>>);
+
+    #[async_trait]
+    impl TransactionSynchronization for Recorder {
+        async fn before_commit(&self) -> Result<(), BoxError> {
+            self.0.lock().unwrap().push("before_commit");
+            Ok(())
+        }
+
+        async fn after_commit(&self) {
+            self.0.lock().unwrap().push("after_commit");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_scope() {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        assert!(!TransactionSynchronizationManager::is_active());
+        assert!(!TransactionSynchronizationManager::register(Arc::new(Recorder(calls.clone()))));
+
+        let (active, synchronizations) = TransactionSynchronizationManager::scope(async {
+            TransactionSynchronizationManager::register(Arc::new(Recorder(calls.clone())))
+        })
+        .await;
+        assert!(active);
+        assert!(calls.lock().unwrap().is_empty());
+
+        synchronizations.before_commit().await.unwrap();
+        synchronizations.after_commit().await;
+        synchronizations.after_rollback().await;
+        assert_eq!(*calls.lock().unwrap(), vec!["before_commit", "after_commit"]);
+    }
+}
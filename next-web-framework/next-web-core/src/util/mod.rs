@@ -2,3 +2,4 @@ pub mod http_method;
 pub mod time;
 pub mod singleton;
 pub mod locale;
+pub mod path_matcher;
@@ -0,0 +1,90 @@
+/// 路径匹配工具
+///
+/// Ant style path matcher:
+///
+/// - `?` matches one character inside a segment
+/// - `*` matches zero or more characters inside a segment
+/// - `**` matches zero or more segments, e.g. `/api/**`
+pub struct PathMatcher;
+
+impl PathMatcher {
+    /// Whether the path matches the pattern.
+    pub fn matches(pattern: &str, path: &str) -> bool {
+        let pattern = pattern.split('/').filter(|s| !s.is_empty()).collect::<Vec<_>>();
+        let path = path.split('/').filter(|s| !s.is_empty()).collect::<Vec<_>>();
+
+        Self::match_segments(&pattern, &path)
+    }
+
+    /// Whether the text matches the pattern, `*` and `?` may appear anywhere in the pattern.
+    pub fn wildcard_match(pattern: &str, text: &str) -> bool {
+        let pattern = pattern.as_bytes();
+        let text = text.as_bytes();
+
+        let (mut p, mut t) = (0, 0);
+        // Position of the last `*` and the text position it was tried with
+        let mut backtrack: Option<(usize, usize)> = None;
+
+        while t < text.len() {
+            if p < pattern.len() && (pattern[p] == b'?' || pattern[p] == text[t]) {
+                p += 1;
+                t += 1;
+            } else if p < pattern.len() && pattern[p] == b'*' {
+                backtrack = Some((p, t));
+                p += 1;
+            } else if let Some((star, matched)) = backtrack {
+                p = star + 1;
+                t = matched + 1;
+                backtrack = Some((star, matched + 1));
+            } else {
+                return false;
+            }
+        }
+
+        pattern[p..].iter().all(|c| *c == b'*')
+    }
+
+    fn match_segments(pattern: &[&str], path: &[&str]) -> bool {
+        match pattern.split_first() {
+            None => path.is_empty(),
+            Some((&"**", rest)) => {
+                (0..=path.len()).any(|skip| Self::match_segments(rest, &path[skip..]))
+            }
+            Some((segment, rest)) => match path.split_first() {
+                Some((first, path)) => {
+                    Self::wildcard_match(segment, first) && Self::match_segments(rest, path)
+                }
+                None => false,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches() {
+        assert!(PathMatcher::matches("/**", "/"));
+        assert!(PathMatcher::matches("/**", "/api/users/1"));
+        assert!(PathMatcher::matches("/api/**", "/api"));
+        assert!(PathMatcher::matches("/api/**/detail", "/api/users/1/detail"));
+        assert!(PathMatcher::matches("/api/*/1", "/api/users/1"));
+        assert!(PathMatcher::matches("/api/user?", "/api/users"));
+        assert!(PathMatcher::matches("/api/*.json", "/api/users.json"));
+
+        assert!(!PathMatcher::matches("/api/*", "/api/users/1"));
+        assert!(!PathMatcher::matches("/api/**", "/apis/users"));
+        assert!(!PathMatcher::matches("/api/user?", "/api/user"));
+    }
+
+    #[test]
+    fn test_wildcard_match() {
+        assert!(PathMatcher::wildcard_match("https://*.example.com", "https://a.example.com"));
+        assert!(PathMatcher::wildcard_match("*", ""));
+        assert!(PathMatcher::wildcard_match("http://localhost:*", "http://localhost:8080"));
+        assert!(!PathMatcher::wildcard_match("https://*.example.com", "https://example.com"));
+        assert!(!PathMatcher::wildcard_match("https://*.example.com", "https://a.example.com.evil.io"));
+    }
+}
@@ -2,7 +2,7 @@ use std::sync::Arc;
 
 use next_web_core::{
     ApplicationContext, AutoRegister, async_trait, context::properties::ApplicationProperties,
-    traits::singleton::Singleton,
+    traits::{health::health_indicator::HealthIndicator, singleton::Singleton},
 };
 use rudi_dev::Singleton;
 
@@ -13,7 +13,13 @@ use crate::{
 };
 
 /// Register the `DatabaseService` as a singleton with the `DatabaseServiceAutoRegister` type.
-#[Singleton(binds = [Self::into_auto_register])]
+///
+/// Switched off with `next.data.database.enabled: false`, a user provided `DatabaseService` replaces it.
+#[Singleton(
+    on_property = "next.data.database.enabled!=false",
+    on_missing_bean = DatabaseService,
+    binds = [Self::into_auto_register]
+)]
 #[derive(Clone)]
 pub struct DatabaseServiceAutoRegister(pub DatabaseClientProperties);
 
@@ -58,6 +64,31 @@ impl AutoRegister for DatabaseServiceAutoRegister {
 
         // Insert the  service into the context and name it with the singleton name
         let singleton_name = database_service.singleton_name();
+        // Lock scheduled jobs across instances
+        #[cfg(feature = "scheduler-lock")]
+        {
+            use next_web_core::traits::schedule::scheduler_lock_provider::SchedulerLockProvider;
+
+            let lock_provider: Box<dyn SchedulerLockProvider> = Box::new(
+                crate::scheduler_lock::database_scheduler_lock_provider::DatabaseSchedulerLockProvider::new(
+                    database_service.clone(),
+                ),
+            );
+            ctx.insert_singleton_with_name(lock_provider, format!("{}SchedulerLockProvider", singleton_name));
+        }
+        // Relay the events stored with the transactions
+        #[cfg(feature = "outbox")]
+        {
+            use next_web_core::traits::event::outbox_store::OutboxStore;
+
+            let outbox = crate::outbox::transactional_outbox::TransactionalOutbox::new(database_service.clone());
+            let outbox_store: Box<dyn OutboxStore> = Box::new(outbox.clone());
+            ctx.insert_singleton_with_name(outbox_store, format!("{}OutboxStore", singleton_name));
+            ctx.insert_singleton_with_default_name(outbox);
+        }
+
+        let health_indicator: Box<dyn HealthIndicator> = Box::new(database_service.clone());
+        ctx.insert_singleton_with_name(health_indicator, format!("{}HealthIndicator", singleton_name));
         ctx.insert_singleton_with_name(database_service, singleton_name);
 
         Ok(())
@@ -4,4 +4,8 @@ pub mod wrapper;
 pub mod auto_register;
 pub mod properties;
 pub mod service;
+#[cfg(feature = "scheduler-lock")]
+pub mod scheduler_lock;
+#[cfg(feature = "outbox")]
+pub mod outbox;
 mod interceptor;
\ No newline at end of file
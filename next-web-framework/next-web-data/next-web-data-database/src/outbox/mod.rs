@@ -0,0 +1 @@
+pub mod transactional_outbox;
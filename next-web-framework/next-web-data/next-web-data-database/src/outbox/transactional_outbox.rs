@@ -0,0 +1,202 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use next_web_core::async_trait;
+use next_web_core::error::BoxError;
+use next_web_core::event::outbox_message::OutboxMessage;
+use next_web_core::scheduler::lock_configuration::instance_id;
+use next_web_core::traits::event::outbox_store::OutboxStore;
+use next_web_core::traits::transaction::transaction_synchronization::TransactionSynchronization;
+use next_web_core::transaction::transaction_synchronization_manager::TransactionSynchronizationManager;
+use rbatis::executor::Executor;
+use rbs::Value;
+use serde::Serialize;
+use tokio::sync::Notify;
+
+use crate::service::database_service::DatabaseService;
+
+static SEQUENCE: AtomicU64 = AtomicU64::new(0);
+
+/// 事务发件箱
+///
+/// Transactional outbox
+///
+/// Stores events with the statements of a transaction, they are relayed to their
+/// destination once it commits and are never sent when it rolls back. A message that keeps
+/// failing is retried with a backoff until it runs out of attempts, then `dead_at` is set
+/// and it stays in the table for inspection:
+///
+/// ```sql
+/// CREATE TABLE event_outbox (
+///     id              VARCHAR(255) NOT NULL PRIMARY KEY,
+///     destination     VARCHAR(255) NOT NULL,
+///     event_type      VARCHAR(255) NOT NULL,
+///     payload         TEXT         NOT NULL,
+///     created_at      BIGINT       NOT NULL,
+///     published_at    BIGINT       NULL,
+///     attempts        INT          NOT NULL DEFAULT 0,
+///     next_attempt_at BIGINT       NOT NULL DEFAULT 0,
+///     dead_at         BIGINT       NULL,
+///     last_error      TEXT         NULL
+/// );
+/// ```
+///
+/// ```ignore
+/// rbs.execute_in_transaction(|tx| async move {
+///     tx.exec("INSERT INTO orders (id) VALUES (?)", vec![id.clone().into()]).await?;
+///     outbox.save(&tx, "rabbitmq:orders/order.created", "OrderCreated", &order).await?;
+///     Ok(())
+/// })
+/// .await?;
+/// ```
+#[derive(Clone)]
+pub struct TransactionalOutbox {
+    service: DatabaseService,
+    table_name: String,
+    notify: Arc<Notify>,
+}
+
+impl TransactionalOutbox {
+    pub fn new(service: DatabaseService) -> Self {
+        Self {
+            service,
+            table_name: String::from("event_outbox"),
+            notify: Arc::new(Notify::new()),
+        }
+    }
+
+    /// Name of the outbox table, `event_outbox` by default.
+    pub fn with_table_name(mut self, table_name: impl Into<String>) -> Self {
+        self.table_name = table_name.into();
+        self
+    }
+
+    /// 保存事件，事务提交后发布
+    ///
+    /// Store the event with `executor`, the transaction it belongs to, and relay it to
+    /// `destination` once the transaction commits. Returns the id of the message.
+    pub async fn save<T: Serialize>(
+        &self,
+        executor: &dyn Executor,
+        destination: &str,
+        event_type: &str,
+        payload: &T,
+    ) -> Result<String, BoxError> {
+        let id = format!(
+            "{}:{}",
+            instance_id(),
+            SEQUENCE.fetch_add(1, Ordering::Relaxed)
+        );
+        let insert = format!(
+            "INSERT INTO {} (id, destination, event_type, payload, created_at, attempts) VALUES (?, ?, ?, ?, ?, 0)",
+            self.table_name
+        );
+        executor
+            .exec(
+                &insert,
+                vec![
+                    Value::String(id.clone()),
+                    Value::String(destination.to_string()),
+                    Value::String(event_type.to_string()),
+                    Value::String(serde_json::to_string(payload)?),
+                    Value::I64(now_millis()),
+                ],
+            )
+            .await?;
+
+        let notify = OutboxCommitNotify(self.notify.clone());
+        if !TransactionSynchronizationManager::register(Arc::new(notify)) {
+            // Not in a transaction, the message is already committed
+            self.notify.notify_one();
+        }
+
+        Ok(id)
+    }
+}
+
+fn now_millis() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64
+}
+
+/// Wakes up the relay once the transaction of the message commits.
+struct OutboxCommitNotify(Arc<Notify>);
+
+#[async_trait]
+impl TransactionSynchronization for OutboxCommitNotify {
+    async fn after_commit(&self) {
+        self.0.notify_one();
+    }
+}
+
+#[async_trait]
+impl OutboxStore for TransactionalOutbox {
+    async fn pending(&self, limit: usize) -> Result<Vec<OutboxMessage>, BoxError> {
+        let select = format!(
+            "SELECT id, destination, event_type, payload, created_at, attempts FROM {} WHERE published_at IS NULL AND dead_at IS NULL AND next_attempt_at <= ? ORDER BY created_at, id LIMIT ?",
+            self.table_name
+        );
+        let messages = self
+            .service
+            .query_decode(
+                &select,
+                vec![Value::I64(now_millis()), Value::U64(limit as u64)],
+            )
+            .await?;
+        Ok(messages)
+    }
+
+    async fn mark_published(&self, id: &str) -> Result<(), BoxError> {
+        let update = format!("UPDATE {} SET published_at = ? WHERE id = ?", self.table_name);
+        self.service
+            .exec(
+                &update,
+                vec![Value::I64(now_millis()), Value::String(id.to_string())],
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn mark_failed(&self, id: &str, error: &str, retry_at: i64) -> Result<(), BoxError> {
+        let update = format!(
+            "UPDATE {} SET attempts = attempts + 1, next_attempt_at = ?, last_error = ? WHERE id = ?",
+            self.table_name
+        );
+        self.service
+            .exec(
+                &update,
+                vec![
+                    Value::I64(retry_at),
+                    Value::String(error.to_string()),
+                    Value::String(id.to_string()),
+                ],
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn mark_dead(&self, id: &str, error: &str) -> Result<(), BoxError> {
+        let update = format!(
+            "UPDATE {} SET attempts = attempts + 1, dead_at = ?, last_error = ? WHERE id = ?",
+            self.table_name
+        );
+        self.service
+            .exec(
+                &update,
+                vec![
+                    Value::I64(now_millis()),
+                    Value::String(error.to_string()),
+                    Value::String(id.to_string()),
+                ],
+            )
+            .await?;
+        Ok(())
+    }
+
+    fn notifier(&self) -> Arc<Notify> {
+        self.notify.clone()
+    }
+}
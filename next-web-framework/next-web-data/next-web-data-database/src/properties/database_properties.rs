@@ -2,7 +2,7 @@ use next_web_macros::Properties;
 use rudi_dev::Singleton;
 
 /// Properties for Database client.
-#[Singleton(default, binds=[Self::into_properties])]
+#[Singleton(default, on_property = "next.data.database.enabled!=false", binds=[Self::into_properties])]
 #[Properties(prefix = "next.data.database")]
 #[derive(Debug, Clone, Default, serde::Deserialize)]
 pub struct DatabaseClientProperties {
@@ -22,6 +22,8 @@ pub struct DatabaseClientProperties {
     database: String,
     /// Database URL extra parameters.
     url_extra: Option<String>,
+    /// Whether the database client is registered, `false` switches it off.
+    enabled: Option<bool>,
 }
 
 impl DatabaseClientProperties {
@@ -57,4 +59,8 @@ impl DatabaseClientProperties {
     pub fn url_extra(&self) -> Option<&str> {
         self.url_extra.as_ref().map(|s| s.as_str())
     }
+
+    pub fn enabled(&self) -> bool {
+        self.enabled.unwrap_or(true)
+    }
 }
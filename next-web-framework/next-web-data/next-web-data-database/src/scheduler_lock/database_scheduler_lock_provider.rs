@@ -0,0 +1,112 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use next_web_core::async_trait;
+use next_web_core::error::BoxError;
+use next_web_core::scheduler::lock_configuration::{LockConfiguration, instance_id};
+use next_web_core::traits::schedule::scheduler_lock_provider::SchedulerLockProvider;
+use rbs::Value;
+
+use crate::service::database_service::DatabaseService;
+
+/// 数据库定时任务锁提供者
+///
+/// Database scheduler lock provider
+///
+/// Keeps one row per lock, the times are epoch milliseconds of the application clock:
+///
+/// ```sql
+/// CREATE TABLE scheduler_lock (
+///     name        VARCHAR(64)  NOT NULL PRIMARY KEY,
+///     lock_until  BIGINT       NOT NULL,
+///     locked_at   BIGINT       NOT NULL,
+///     locked_by   VARCHAR(255) NOT NULL
+/// );
+/// ```
+#[derive(Clone)]
+pub struct DatabaseSchedulerLockProvider {
+    service: DatabaseService,
+    table_name: String,
+}
+
+impl DatabaseSchedulerLockProvider {
+    pub fn new(service: DatabaseService) -> Self {
+        Self {
+            service,
+            table_name: String::from("scheduler_lock"),
+        }
+    }
+
+    /// Name of the lock table, `scheduler_lock` by default.
+    pub fn with_table_name(mut self, table_name: impl Into<String>) -> Self {
+        self.table_name = table_name.into();
+        self
+    }
+}
+
+fn millis(time: SystemTime) -> Value {
+    Value::I64(
+        time.duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as i64,
+    )
+}
+
+#[async_trait]
+impl SchedulerLockProvider for DatabaseSchedulerLockProvider {
+    async fn lock(&self, configuration: &LockConfiguration) -> Result<bool, BoxError> {
+        let name = Value::String(configuration.name().to_string());
+        let lock_until = millis(configuration.lock_at_most_until());
+        let locked_at = millis(configuration.created_at());
+        let locked_by = Value::String(instance_id().to_string());
+
+        // First execution of the job, fails when the row already exists
+        let insert = format!(
+            "INSERT INTO {} (name, lock_until, locked_at, locked_by) VALUES (?, ?, ?, ?)",
+            self.table_name
+        );
+        let inserted = self
+            .service
+            .exec(
+                &insert,
+                vec![name.clone(), lock_until.clone(), locked_at.clone(), locked_by.clone()],
+            )
+            .await;
+        if inserted.is_ok_and(|result| result.rows_affected > 0) {
+            return Ok(true);
+        }
+
+        // Take over the lock once it expired
+        let update = format!(
+            "UPDATE {} SET lock_until = ?, locked_at = ?, locked_by = ? WHERE name = ? AND lock_until <= ?",
+            self.table_name
+        );
+        let result = self
+            .service
+            .exec(
+                &update,
+                vec![lock_until, locked_at.clone(), locked_by, name, locked_at],
+            )
+            .await?;
+
+        Ok(result.rows_affected > 0)
+    }
+
+    async fn unlock(&self, configuration: &LockConfiguration) -> Result<(), BoxError> {
+        let update = format!(
+            "UPDATE {} SET lock_until = ? WHERE name = ? AND locked_by = ?",
+            self.table_name
+        );
+        self.service
+            .exec(
+                &update,
+                vec![
+                    millis(configuration.unlock_time()),
+                    Value::String(configuration.name().to_string()),
+                    Value::String(instance_id().to_string()),
+                ],
+            )
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1 @@
+pub mod database_scheduler_lock_provider;
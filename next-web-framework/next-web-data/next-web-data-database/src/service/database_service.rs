@@ -1,5 +1,8 @@
 use std::ops::Deref;
 
+use next_web_core::async_trait;
+use next_web_core::health::health_status::Health;
+use next_web_core::traits::health::health_indicator::HealthIndicator;
 use next_web_core::traits::{service::Service, singleton::Singleton};
 use rbatis::RBatis;
 
@@ -92,6 +95,20 @@ impl DatabaseService {
 }
 
 
+#[async_trait]
+impl HealthIndicator for DatabaseService {
+    fn name(&self) -> &str {
+        "db"
+    }
+
+    async fn health(&self) -> Health {
+        match self.rbs.exec("SELECT 1", vec![]).await {
+            Ok(_) => Health::up().with_detail("database", self.properties.driver()),
+            Err(error) => Health::from_error(error).with_detail("database", self.properties.driver()),
+        }
+    }
+}
+
 impl Deref for DatabaseService {
     
     type Target = RBatis;
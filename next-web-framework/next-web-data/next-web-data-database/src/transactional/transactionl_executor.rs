@@ -1,7 +1,17 @@
+use next_web_core::transaction::transaction_synchronization_manager::{
+    Synchronizations, TransactionSynchronizationManager,
+};
 use rbatis::async_trait;
+use rbatis::executor::RBatisTxExecutor;
 use std::{error::Error, future::Future};
 use tracing::error;
 
+/// 事务执行器
+///
+/// Transactional executor
+///
+/// The synchronizations registered while the block runs, e.g. by the transactional event
+/// listeners, are called before the commit and after the commit or the rollback.
 #[async_trait]
 pub trait TransactionalExecutor {
     async fn execute_transaction<F, Fut>(
@@ -12,6 +22,21 @@ pub trait TransactionalExecutor {
         F: Send,
         F: FnOnce(&rbatis::RBatis) -> Fut,
         Fut: Future<Output = Result<(), Box<dyn Error + Send + Sync>>> + Send;
+
+    /// 在事务中执行，`block` 中的语句通过传入的事务执行
+    ///
+    /// Run `block` in a transaction, its statements must be executed with the given
+    /// transaction. It is committed when `block` returns `Ok`, rolled back otherwise and
+    /// the error of `block` is returned.
+    async fn execute_in_transaction<F, Fut, T>(
+        &self,
+        block: F,
+    ) -> Result<T, Box<dyn Error + Send + Sync>>
+    where
+        F: Send,
+        F: FnOnce(RBatisTxExecutor) -> Fut,
+        Fut: Future<Output = Result<T, Box<dyn Error + Send + Sync>>> + Send,
+        T: Send;
 }
 
 #[async_trait]
@@ -26,32 +51,85 @@ impl TransactionalExecutor for rbatis::RBatis {
         Fut: Future<Output = Result<(), Box<dyn Error + Send + Sync>>> + Send,
     {
         match self.acquire_begin().await {
-            Ok(tx) => match block(self).await {
-                Ok(_) => tx.commit().await.map_err(|err| {
-                    error!("Transactional commit error: {:?}", err);
-                    err.into()
-                }),
-                Err(msg) => {
-                    error!("Transactional block error: {}", msg.to_string());
-
-                    tx.rollback().await.map_err(|err| {
-                        error!("Transactional rollback error: {:?}", err);
-                        err.into()
-                    })
+            Ok(tx) => {
+                let (result, synchronizations) =
+                    TransactionSynchronizationManager::scope(block(self)).await;
+                match result {
+                    Ok(_) => commit(&tx, &synchronizations).await,
+                    Err(msg) => {
+                        error!("Transactional block error: {}", msg.to_string());
+
+                        let result = tx.rollback().await.map_err(|err| {
+                            error!("Transactional rollback error: {:?}", err);
+                            err.into()
+                        });
+                        synchronizations.after_rollback().await;
+                        result
+                    }
                 }
-            },
+            }
             Err(err) => {
                 error!("Transactional begin error: {}", err.to_string());
                 return Err(Box::new(err));
             }
         }
     }
+
+    async fn execute_in_transaction<F, Fut, T>(
+        &self,
+        block: F,
+    ) -> Result<T, Box<dyn Error + Send + Sync>>
+    where
+        F: Send,
+        F: FnOnce(RBatisTxExecutor) -> Fut,
+        Fut: Future<Output = Result<T, Box<dyn Error + Send + Sync>>> + Send,
+        T: Send,
+    {
+        let tx = self.acquire_begin().await.map_err(|err| {
+            error!("Transactional begin error: {}", err.to_string());
+            err
+        })?;
+
+        let (result, synchronizations) =
+            TransactionSynchronizationManager::scope(block(tx.clone())).await;
+        match result {
+            Ok(value) => commit(&tx, &synchronizations).await.map(|_| value),
+            Err(msg) => {
+                error!("Transactional block error: {}", msg.to_string());
+
+                if let Err(err) = tx.rollback().await {
+                    error!("Transactional rollback error: {:?}", err);
+                }
+                synchronizations.after_rollback().await;
+                Err(msg)
+            }
+        }
+    }
 }
 
-async fn main() {
-    let rbs = rbatis::RBatis::new();
+/// Commit unless a synchronization fails before, then call the synchronizations of the outcome.
+async fn commit(
+    tx: &RBatisTxExecutor,
+    synchronizations: &Synchronizations,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let result = match synchronizations.before_commit().await {
+        Ok(()) => tx.commit().await.map_err(|err| {
+            error!("Transactional commit error: {:?}", err);
+            err.into()
+        }),
+        Err(msg) => {
+            error!("Transactional before commit error: {}", msg.to_string());
 
-    rbs.execute_transaction(|rb| async { Ok(()) })
-        .await
-        .unwrap();
+            if let Err(err) = tx.rollback().await {
+                error!("Transactional rollback error: {:?}", err);
+            }
+            Err(msg)
+        }
+    };
+
+    match result {
+        Ok(()) => synchronizations.after_commit().await,
+        Err(_) => synchronizations.after_rollback().await,
+    }
+    result
 }
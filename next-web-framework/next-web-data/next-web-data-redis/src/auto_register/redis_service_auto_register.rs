@@ -3,7 +3,7 @@ use std::{sync::Arc, time::Duration};
 use next_web_core::{
     ApplicationContext, AutoRegister, async_trait,
     context::properties::ApplicationProperties,
-    traits::singleton::Singleton,
+    traits::{health::health_indicator::HealthIndicator, singleton::Singleton},
 };
 use redis::{Cmd, ConnectionLike};
 use rudi_dev::Singleton;
@@ -16,7 +16,13 @@ use crate::{
 use crate::core::event::expired_keys_event::RedisExpiredKeysEvent;
 
 /// Register the `DatabaseService` as a singleton with the `DatabaseServiceAutoRegister` type.
-#[Singleton(binds = [Self::into_auto_register])]
+///
+/// Switched off with `next.data.redis.enabled: false`, a user provided `RedisService` replaces it.
+#[Singleton(
+    on_property = "next.data.redis.enabled!=false",
+    on_missing_bean = RedisService,
+    binds = [Self::into_auto_register]
+)]
 #[derive(Clone)]
 pub struct RedisServiceAutoRegister(pub RedisClientProperties);
 
@@ -69,6 +75,21 @@ impl AutoRegister for RedisServiceAutoRegister {
             redis_service.connections.push(connect);
         }
 
+        // Lock scheduled jobs across instances
+        #[cfg(feature = "scheduler-lock")]
+        {
+            use next_web_core::traits::schedule::scheduler_lock_provider::SchedulerLockProvider;
+
+            let lock_provider: Box<dyn SchedulerLockProvider> = Box::new(
+                crate::core::scheduler_lock::redis_scheduler_lock_provider::RedisSchedulerLockProvider::new(
+                    redis_service.clone(),
+                ),
+            );
+            ctx.insert_singleton_with_name(lock_provider, format!("{}SchedulerLockProvider", singleton_name));
+        }
+
+        let health_indicator: Box<dyn HealthIndicator> = Box::new(redis_service.clone());
+        ctx.insert_singleton_with_name(health_indicator, format!("{}HealthIndicator", singleton_name));
         ctx.insert_singleton_with_name(redis_service, singleton_name.to_owned());
 
         // Listen for expired keys
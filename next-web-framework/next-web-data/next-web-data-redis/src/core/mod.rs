@@ -1,2 +1,4 @@
 pub mod event;
-pub mod redis_lock;
\ No newline at end of file
+pub mod redis_lock;
+#[cfg(feature = "scheduler-lock")]
+pub mod scheduler_lock;
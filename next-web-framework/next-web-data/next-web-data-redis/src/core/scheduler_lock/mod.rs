@@ -0,0 +1 @@
+pub mod redis_scheduler_lock_provider;
@@ -0,0 +1,89 @@
+use next_web_core::async_trait;
+use next_web_core::error::BoxError;
+use next_web_core::scheduler::lock_configuration::{LockConfiguration, instance_id};
+use next_web_core::traits::schedule::scheduler_lock_provider::SchedulerLockProvider;
+use redis::Script;
+
+use crate::service::redis_service::RedisService;
+
+/// Release the lock if this instance holds it, keep it for `ARGV[2]` milliseconds when
+/// `lock_at_least_for` didn't elapse yet.
+const UNLOCK_SCRIPT: &str = r#"
+if redis.call("GET", KEYS[1]) == ARGV[1] then
+  if tonumber(ARGV[2]) > 0 then
+    return redis.call("PEXPIRE", KEYS[1], ARGV[2])
+  end
+  return redis.call("DEL", KEYS[1])
+end
+return 0
+"#;
+
+/// Redis 定时任务锁提供者
+///
+/// Redis scheduler lock provider
+///
+/// The lock is a key set with `NX` that expires after `lock_at_most_for`, its value is
+/// the id of the instance holding it.
+#[derive(Clone)]
+pub struct RedisSchedulerLockProvider {
+    service: RedisService,
+    key_prefix: String,
+}
+
+impl RedisSchedulerLockProvider {
+    pub fn new(service: RedisService) -> Self {
+        Self {
+            service,
+            key_prefix: String::from("job-lock:"),
+        }
+    }
+
+    /// Prefix of the lock keys, `job-lock:` by default.
+    pub fn with_key_prefix(mut self, key_prefix: impl Into<String>) -> Self {
+        self.key_prefix = key_prefix.into();
+        self
+    }
+
+    fn key(&self, configuration: &LockConfiguration) -> String {
+        format!("{}{}", self.key_prefix, configuration.name())
+    }
+}
+
+#[async_trait]
+impl SchedulerLockProvider for RedisSchedulerLockProvider {
+    async fn lock(&self, configuration: &LockConfiguration) -> Result<bool, BoxError> {
+        let mut connection = self
+            .service
+            .get_connection()
+            .ok_or("No redis connection available")?;
+
+        let millis = configuration.lock_at_most_for().as_millis().max(1) as u64;
+        let result: Option<String> = redis::cmd("SET")
+            .arg(self.key(configuration))
+            .arg(instance_id())
+            .arg("NX")
+            .arg("PX")
+            .arg(millis)
+            .query_async(&mut connection)
+            .await?;
+
+        Ok(result.is_some())
+    }
+
+    async fn unlock(&self, configuration: &LockConfiguration) -> Result<(), BoxError> {
+        let mut connection = self
+            .service
+            .get_connection()
+            .ok_or("No redis connection available")?;
+
+        let millis = configuration.remaining_lock_at_least_for().as_millis() as u64;
+        Script::new(UNLOCK_SCRIPT)
+            .key(self.key(configuration))
+            .arg(instance_id())
+            .arg(millis)
+            .invoke_async::<i64>(&mut connection)
+            .await?;
+
+        Ok(())
+    }
+}
@@ -2,7 +2,7 @@ use next_web_macros::Properties;
 use rudi_dev::Singleton;
 
 /// Properties for Redis client.
-#[Singleton(default, binds=[Self::into_properties])]
+#[Singleton(default, on_property = "next.data.redis.enabled!=false", binds=[Self::into_properties])]
 #[Properties(prefix = "next.data.redis")]
 #[derive(Debug, Clone, serde::Deserialize)]
 pub struct RedisClientProperties {
@@ -12,9 +12,15 @@ pub struct RedisClientProperties {
     password: Option<String>,
     database: Option<u64>,
     connect_timeout: Option<u64>,
+    enabled: Option<bool>,
 }
 
 impl RedisClientProperties {
+    /// Whether the redis client is registered, `next.data.redis.enabled: false` switches it off.
+    pub fn enabled(&self) -> bool {
+        self.enabled.unwrap_or(true)
+    }
+
     pub fn username(&self) -> Option<&str> {
         self.username.as_deref()
     }
@@ -73,6 +79,7 @@ impl Default for RedisClientProperties {
             password: None,
             database: Some(0),
             connect_timeout: Some(5000),
+            enabled: None,
         }
     }
 }
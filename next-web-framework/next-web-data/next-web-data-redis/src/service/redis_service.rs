@@ -6,6 +6,9 @@ use std::{
     },
 };
 
+use next_web_core::async_trait;
+use next_web_core::health::health_status::Health;
+use next_web_core::traits::health::health_indicator::HealthIndicator;
 use next_web_core::traits::{service::Service, singleton::Singleton};
 use redis::{Client, aio::MultiplexedConnection};
 
@@ -135,6 +138,38 @@ impl RedisService {
     }
 }
 
+#[async_trait]
+impl HealthIndicator for RedisService {
+    fn name(&self) -> &str {
+        "redis"
+    }
+
+    async fn health(&self) -> Health {
+        let mut connection = match self.get_connection() {
+            Some(connection) => connection,
+            None => return Health::down().with_detail("error", "no connection available"),
+        };
+
+        match redis::cmd("INFO")
+            .arg("server")
+            .query_async::<String>(&mut connection)
+            .await
+        {
+            Ok(info) => {
+                let health = Health::up();
+                match info
+                    .lines()
+                    .find_map(|line| line.strip_prefix("redis_version:"))
+                {
+                    Some(version) => health.with_detail("version", version.trim()),
+                    None => health,
+                }
+            }
+            Err(error) => Health::from_error(error),
+        }
+    }
+}
+
 impl Deref for RedisService {
     type Target = Client;
 
@@ -0,0 +1,165 @@
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use axum::routing::{get, post};
+use axum::{Extension, Router};
+use next_web_core::autoconfigure::context::management_properties::ManagementProperties;
+use next_web_core::state::application_state::ApplicationState;
+use next_web_core::traits::health::health_indicator::HealthIndicator;
+use next_web_core::ApplicationContext;
+use tracing::{error, info, warn};
+
+use super::endpoints::{self, ActuatorState, RequestMapping};
+use super::sanitizer::Sanitizer;
+use crate::autoregister::handler_autoregister::HttpHandlerAutoRegister;
+
+/// 管理端点路由
+///
+/// Builds the `/actuator/*` router from the `next.management` properties.
+///
+/// The router expects an [`ApplicationState`] extension, when it is served on the
+/// application port the state is added together with the application routes,
+/// otherwise [`ActuatorRouter::serve`] adds it.
+pub struct ActuatorRouter;
+
+impl ActuatorRouter {
+    /// Available endpoints, in the order they are listed by `/mappings`.
+    pub const ENDPOINTS: &'static [&'static str] =
+        &["health", "info", "env", "beans", "mappings", "scheduledtasks"];
+
+    /// Build the router of the exposed endpoints, `None` when nothing is exposed.
+    pub fn router(
+        properties: &ManagementProperties,
+        context_path: &str,
+        ctx: &mut ApplicationContext,
+    ) -> Option<Router> {
+        if !properties.enabled() {
+            return None;
+        }
+
+        let exposed = Self::ENDPOINTS
+            .iter()
+            .filter(|endpoint| properties.is_exposed(endpoint))
+            .collect::<Vec<_>>();
+        if exposed.is_empty() {
+            return None;
+        }
+
+        let base_path = properties.base_path().trim_end_matches('/');
+
+        let mut mappings = inventory::iter::<&dyn HttpHandlerAutoRegister>
+            .into_iter()
+            .filter(|handler| !handler.path().is_empty())
+            .map(|handler| RequestMapping {
+                handler: handler.name().to_string(),
+                method: handler.method().to_string(),
+                path: format!("{}{}", context_path, handler.path()),
+            })
+            .collect::<Vec<_>>();
+        mappings.extend(exposed.iter().map(|endpoint| RequestMapping {
+            handler: format!("actuator::{}", endpoint),
+            method: "GET".to_string(),
+            path: format!("{}{}/{}", context_path, base_path, endpoint),
+        }));
+        if exposed.contains(&&"scheduledtasks") {
+            let path = format!("{}{}/scheduledtasks", context_path, base_path);
+            mappings.push(RequestMapping {
+                handler: "actuator::scheduledtasks".to_string(),
+                method: "GET".to_string(),
+                path: format!("{}/{{name}}", path),
+            });
+            mappings.push(RequestMapping {
+                handler: "actuator::scheduledtasks".to_string(),
+                method: "POST".to_string(),
+                path: format!("{}/{{name}}/{{action}}", path),
+            });
+        }
+        mappings.sort_by(|a, b| a.path.cmp(&b.path).then_with(|| a.method.cmp(&b.method)));
+
+        let state = ActuatorState {
+            indicators: Self::named_indicators(ctx.resolve_by_type::<Box<dyn HealthIndicator>>()),
+            health_timeout: properties.health_timeout(),
+            show_details: properties.show_details(),
+            sanitizer: Sanitizer::new(properties.keys_to_sanitize()),
+            mappings,
+        };
+
+        let router = exposed
+            .into_iter()
+            .fold(Router::new(), |router, endpoint| {
+                let path = format!("{}/{}", base_path, endpoint);
+                match *endpoint {
+                    "health" => router.route(&path, get(endpoints::health)),
+                    "info" => router.route(&path, get(endpoints::info)),
+                    "env" => router.route(&path, get(endpoints::env)),
+                    "beans" => router.route(&path, get(endpoints::beans)),
+                    "mappings" => router.route(&path, get(endpoints::mappings)),
+                    "scheduledtasks" => router
+                        .route(&path, get(endpoints::scheduled_tasks))
+                        .route(&format!("{}/{{name}}", path), get(endpoints::scheduled_task))
+                        .route(
+                            &format!("{}/{{name}}/{{action}}", path),
+                            post(endpoints::scheduled_task_action),
+                        ),
+                    _ => router,
+                }
+            })
+            .layer(Extension(Arc::new(state)));
+
+        Some(router)
+    }
+
+    /// Names the indicators uniquely, a repeated name gets a `-2`, `-3`... suffix so
+    /// components don't overwrite each other in `/health`.
+    fn named_indicators(
+        indicators: Vec<Box<dyn HealthIndicator>>,
+    ) -> Vec<(String, Box<dyn HealthIndicator>)> {
+        let mut seen = HashMap::<String, usize>::new();
+        indicators
+            .into_iter()
+            .map(|indicator| {
+                let name = indicator.name().to_string();
+                let count = seen.entry(name.clone()).or_default();
+                *count += 1;
+                if *count == 1 {
+                    return (name, indicator);
+                }
+
+                let unique = format!("{}-{}", name, count);
+                warn!(
+                    "Health indicator name `{}` is used more than once, reporting it as `{}`",
+                    name, unique
+                );
+                (unique, indicator)
+            })
+            .collect()
+    }
+
+    /// Serve the management endpoints on their own port.
+    pub fn serve(router: Router, addr: &str, port: u16, state: ApplicationState) {
+        let socket_addr: SocketAddr = match format!("{}:{}", addr, port).parse() {
+            Ok(socket_addr) => socket_addr,
+            Err(error) => {
+                error!("Invalid management address {}:{}: {}", addr, port, error);
+                return;
+            }
+        };
+
+        let router = router.layer(Extension(state));
+        tokio::spawn(async move {
+            let listener = match tokio::net::TcpListener::bind(&socket_addr).await {
+                Ok(listener) => listener,
+                Err(error) => {
+                    error!("Failed to bind management port {}: {}", socket_addr, error);
+                    return;
+                }
+            };
+
+            info!("Management endpoints listening on: {}", socket_addr);
+            if let Err(error) = axum::serve(listener, router).await {
+                error!("Management server stopped: {}", error);
+            }
+        });
+    }
+}
@@ -0,0 +1,344 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+#[cfg(feature = "enable-scheduling")]
+use axum::extract::Path;
+use axum::{Extension, Json};
+use next_web_core::context::properties::ApplicationProperties;
+use next_web_core::health::health_status::{Health, HealthStatus};
+use next_web_core::state::application_state::ApplicationState;
+use next_web_core::traits::health::health_indicator::HealthIndicator;
+use next_web_core::util::singleton::SingletonUtil;
+use serde_json::{json, Map, Value};
+
+use super::sanitizer::Sanitizer;
+
+/// A route registered by the application, exposed by `/mappings`.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct RequestMapping {
+    pub handler: String,
+    pub method: String,
+    pub path: String,
+}
+
+/// Shared data of the actuator endpoints, collected once when the router is built.
+#[derive(Clone)]
+pub struct ActuatorState {
+    /// Indicators keyed by a unique component name.
+    pub(crate) indicators: Vec<(String, Box<dyn HealthIndicator>)>,
+    pub(crate) health_timeout: Duration,
+    pub(crate) show_details: bool,
+    pub(crate) sanitizer: Sanitizer,
+    pub(crate) mappings: Vec<RequestMapping>,
+}
+
+/// `GET /health`
+///
+/// Aggregates every [`HealthIndicator`], responds `503` when the aggregated status is not `UP`.
+/// An indicator that doesn't answer within the health timeout is reported `DOWN`.
+pub async fn health(Extension(actuator): Extension<Arc<ActuatorState>>) -> impl IntoResponse {
+    let timeout = actuator.health_timeout;
+    let healths = futures::future::join_all(actuator.indicators.iter().map(
+        |(name, indicator)| async move {
+            let health = match tokio::time::timeout(timeout, indicator.health()).await {
+                Ok(health) => health,
+                Err(_) => Health::down().with_detail(
+                    "error",
+                    format!("health check timed out after {}ms", timeout.as_millis()),
+                ),
+            };
+            (name.clone(), health)
+        },
+    ))
+    .await;
+
+    let status = HealthStatus::aggregate(healths.iter().map(|(_, health)| health.status()));
+    let code = match status {
+        HealthStatus::Down | HealthStatus::OutOfService => StatusCode::SERVICE_UNAVAILABLE,
+        _ => StatusCode::OK,
+    };
+
+    let mut body = json!({ "status": status });
+    if actuator.show_details && !healths.is_empty() {
+        body["components"] = json!(healths.into_iter().collect::<BTreeMap<_, _>>());
+    }
+
+    (code, Json(body))
+}
+
+/// `GET /info`
+pub async fn info(Extension(state): Extension<ApplicationState>) -> impl IntoResponse {
+    let properties = application_properties(&state).await;
+
+    let mut body = Map::new();
+    if let Some(application) = properties.next().appliation() {
+        body.insert("app".into(), json!({ "name": application.name() }));
+    }
+    body.insert("profiles".into(), json!(properties.active_profiles()));
+
+    let info = properties
+        .next()
+        .management()
+        .and_then(|management| management.info())
+        .and_then(|info| serde_json::to_value(info).ok());
+    if let Some(Value::Object(info)) = info {
+        body.extend(info);
+    }
+
+    Json(Value::Object(body))
+}
+
+/// `GET /env`
+///
+/// Lists every property as a flat dotted key, sensitive values are masked.
+pub async fn env(
+    Extension(state): Extension<ApplicationState>,
+    Extension(actuator): Extension<Arc<ActuatorState>>,
+) -> impl IntoResponse {
+    let properties = application_properties(&state).await;
+
+    let mut flat = BTreeMap::new();
+    if let Some(mapping) = properties.mapping() {
+        flatten(mapping, String::new(), &mut flat);
+    }
+
+    let properties_value = flat
+        .into_iter()
+        .map(|(key, value)| {
+            let value = match value {
+                Value::String(raw) => Value::String(actuator.sanitizer.sanitize(&key, &raw)),
+                other => {
+                    let raw = other.to_string();
+                    let masked = actuator.sanitizer.sanitize(&key, &raw);
+                    if masked == raw { other } else { Value::String(masked) }
+                }
+            };
+            (key, value)
+        })
+        .collect::<Map<_, _>>();
+
+    Json(json!({
+        "activeProfiles": properties.active_profiles(),
+        "properties": properties_value,
+    }))
+}
+
+/// `GET /beans`
+///
+/// Lists every provider of the [`ApplicationContext`](next_web_core::ApplicationContext).
+pub async fn beans(Extension(state): Extension<ApplicationState>) -> impl IntoResponse {
+    let ctx = state.context().read().await;
+
+    let mut beans = ctx
+        .provider_registry()
+        .iter()
+        .map(|(key, provider)| {
+            let definition = provider.definition();
+            json!({
+                "name": key.name,
+                "type": key.ty.name,
+                "scope": format!("{:?}", definition.scope),
+                "eagerCreate": provider.eager_create(),
+                "instantiated": ctx.single_registry().contains_key(key),
+                "bindings": provider
+                    .binding_definitions()
+                    .map(|bindings| bindings.iter().map(|item| item.key.ty.name).collect::<Vec<_>>())
+                    .unwrap_or_default(),
+            })
+        })
+        .collect::<Vec<_>>();
+    beans.sort_by(|a, b| a["name"].as_str().cmp(&b["name"].as_str()));
+
+    Json(json!({ "beans": beans }))
+}
+
+/// `GET /mappings`
+pub async fn mappings(Extension(actuator): Extension<Arc<ActuatorState>>) -> impl IntoResponse {
+    Json(json!({ "mappings": actuator.mappings }))
+}
+
+/// `GET /scheduledtasks`
+#[cfg(feature = "enable-scheduling")]
+pub async fn scheduled_tasks(Extension(state): Extension<ApplicationState>) -> impl IntoResponse {
+    let mut tasks = Vec::new();
+    if let Some(manager) = scheduling::manager(&state).await {
+        tasks = manager.jobs().await.iter().map(scheduling::job_json).collect();
+    }
+    tasks.sort_by(|a, b| a["name"].as_str().cmp(&b["name"].as_str()));
+
+    Json(json!({ "tasks": tasks }))
+}
+
+/// `GET /scheduledtasks/{name}`, the job with its last executions.
+#[cfg(feature = "enable-scheduling")]
+pub async fn scheduled_task(
+    Extension(state): Extension<ApplicationState>,
+    Path(name): Path<String>,
+) -> axum::response::Response {
+    let Some(manager) = scheduling::manager(&state).await else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+    let (Some(job), Ok(history)) = (manager.job(&name).await, manager.history(&name).await) else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+
+    let mut task = scheduling::job_json(&job);
+    task["history"] = history.iter().map(scheduling::execution_json).collect();
+    Json(task).into_response()
+}
+
+/// `POST /scheduledtasks/{name}/{action}`, `action` is `pause`, `resume` or `trigger`.
+#[cfg(feature = "enable-scheduling")]
+pub async fn scheduled_task_action(
+    Extension(state): Extension<ApplicationState>,
+    Path((name, action)): Path<(String, String)>,
+) -> axum::response::Response {
+    use crate::manager::scheduled_job::SchedulerError;
+
+    let Some(manager) = scheduling::manager(&state).await else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+    let result = match action.as_str() {
+        "pause" => manager.pause(&name).await,
+        "resume" => manager.resume(&name).await,
+        "trigger" => manager.trigger(&name).await,
+        _ => return StatusCode::NOT_FOUND.into_response(),
+    };
+
+    match result {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(SchedulerError::JobNotFound(_)) => StatusCode::NOT_FOUND.into_response(),
+        Err(error) => (StatusCode::BAD_REQUEST, Json(json!({ "error": error.to_string() }))).into_response(),
+    }
+}
+
+#[cfg(feature = "enable-scheduling")]
+mod scheduling {
+    use next_web_core::scheduler::schedule_type::{MisfirePolicy, OverlapPolicy, ScheduleType};
+    use next_web_core::state::application_state::ApplicationState;
+    use next_web_core::util::singleton::SingletonUtil;
+    use serde_json::{json, Value};
+
+    use crate::manager::job_scheduler_manager::{JobSchedulerManager, ScheduledJobInfo};
+    use crate::manager::scheduled_job::{JobExecution, JobOutcome};
+
+    pub(super) async fn manager(state: &ApplicationState) -> Option<JobSchedulerManager> {
+        state
+            .context()
+            .read()
+            .await
+            .get_single_option_with_name::<JobSchedulerManager>(SingletonUtil::name::<JobSchedulerManager>())
+            .cloned()
+    }
+
+    pub(super) fn job_json(job: &ScheduledJobInfo) -> Value {
+        let (kind, args) = match &job.schedule {
+            ScheduleType::Cron(args) => ("cron", args),
+            ScheduleType::FixedRate(args) => ("fixedRate", args),
+            ScheduleType::OneShot(args) => ("oneShot", args),
+        };
+        json!({
+            "id": job.id.to_string(),
+            "name": job.name,
+            "type": kind,
+            "cron": args.cron,
+            "fixedRate": args.fixed_rate,
+            "initialDelay": args.initial_delay,
+            "timeUnit": args.time_unit,
+            "timezone": args.timezone,
+            "overlap": match args.overlap {
+                OverlapPolicy::Concurrent => "concurrent",
+                OverlapPolicy::Skip => "skip",
+                OverlapPolicy::Queue => "queue",
+            },
+            "misfire": match args.misfire {
+                MisfirePolicy::FireOnce => "fire_once",
+                MisfirePolicy::Skip => "skip",
+            },
+            "status": job.status.as_str(),
+            "running": job.running,
+            "nextExecution": job.next_fire_time.map(|time| time.to_rfc3339()),
+            "lastExecution": job.last_execution.as_ref().map(execution_json),
+        })
+    }
+
+    pub(super) fn execution_json(execution: &JobExecution) -> Value {
+        let (outcome, message) = match &execution.outcome {
+            JobOutcome::Success => ("success", None),
+            JobOutcome::Failed(message) => ("failed", Some(message)),
+            JobOutcome::Skipped(message) => ("skipped", Some(message)),
+        };
+        json!({
+            "trigger": execution.trigger.as_str(),
+            "fireTime": execution.fire_time.map(|time| time.to_rfc3339()),
+            "startedAt": execution.started_at.to_rfc3339(),
+            "durationMs": execution.duration.as_secs_f64() * 1000.0,
+            "outcome": outcome,
+            "message": message,
+        })
+    }
+}
+
+/// `GET /scheduledtasks`, scheduling is disabled so there is nothing to list.
+#[cfg(not(feature = "enable-scheduling"))]
+pub async fn scheduled_tasks() -> impl IntoResponse {
+    Json(json!({ "tasks": [] }))
+}
+
+/// `GET /scheduledtasks/{name}`, scheduling is disabled.
+#[cfg(not(feature = "enable-scheduling"))]
+pub async fn scheduled_task() -> impl IntoResponse {
+    StatusCode::NOT_FOUND
+}
+
+/// `POST /scheduledtasks/{name}/{action}`, scheduling is disabled.
+#[cfg(not(feature = "enable-scheduling"))]
+pub async fn scheduled_task_action() -> impl IntoResponse {
+    StatusCode::NOT_FOUND
+}
+
+async fn application_properties(state: &ApplicationState) -> ApplicationProperties {
+    state
+        .context()
+        .read()
+        .await
+        .get_single_option_with_name::<ApplicationProperties>(SingletonUtil::name::<ApplicationProperties>())
+        .cloned()
+        .unwrap_or_default()
+}
+
+/// Flatten a yaml mapping into dotted keys, list items use their index as segment.
+fn flatten(value: &serde_yaml::Value, prefix: String, out: &mut BTreeMap<String, Value>) {
+    let join = |key: String| match prefix.is_empty() {
+        true => key,
+        false => format!("{}.{}", prefix, key),
+    };
+
+    match value {
+        serde_yaml::Value::Mapping(mapping) => {
+            for (key, value) in mapping {
+                let key = match key {
+                    serde_yaml::Value::String(s) => s.clone(),
+                    serde_yaml::Value::Number(n) => n.to_string(),
+                    serde_yaml::Value::Bool(b) => b.to_string(),
+                    _ => continue,
+                };
+                flatten(value, join(key), out);
+            }
+        }
+        serde_yaml::Value::Sequence(sequence) => {
+            for (index, value) in sequence.iter().enumerate() {
+                flatten(value, join(index.to_string()), out);
+            }
+        }
+        serde_yaml::Value::Tagged(tagged) => flatten(&tagged.value, prefix, out),
+        other => {
+            if !prefix.is_empty() {
+                out.insert(prefix, serde_json::to_value(other).unwrap_or(Value::Null));
+            }
+        }
+    }
+}
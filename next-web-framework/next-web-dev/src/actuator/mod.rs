@@ -0,0 +1,3 @@
+pub mod actuator_router;
+pub mod endpoints;
+pub mod sanitizer;
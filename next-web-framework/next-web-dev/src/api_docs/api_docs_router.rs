@@ -0,0 +1,283 @@
+use std::sync::Arc;
+
+use axum::response::Html;
+use axum::routing::get;
+use axum::{Json, Router};
+use next_web_core::autoconfigure::context::openapi_properties::{OpenApiAsset, OpenApiProperties};
+use next_web_core::openapi::OpenApiBuilder;
+use serde_json::Value;
+use tracing::warn;
+
+use crate::autoregister::handler_autoregister::HttpHandlerAutoRegister;
+
+/// 接口文档路由
+///
+/// Serves the OpenAPI document of the routing macro handlers, and the Swagger UI and Redoc
+/// pages browsing it, from the `next.openapi` properties.
+///
+/// Without configured assets both pages are served by the embedded UI, the Swagger UI page
+/// can send requests while the Redoc page only reads. Configured assets are loaded with
+/// Subresource Integrity, a page whose cross origin assets have no integrity hash is not
+/// served. The document itself is built once at startup.
+pub struct ApiDocsRouter;
+
+impl ApiDocsRouter {
+    /// Build the router of the document and its pages, `None` when disabled.
+    pub fn router(
+        properties: &OpenApiProperties,
+        application_name: &str,
+        context_path: &str,
+    ) -> Option<Router> {
+        if !properties.enabled() {
+            return None;
+        }
+
+        let document = Arc::new(Self::document(properties, application_name, context_path));
+        let api_docs_url = format!("{}{}", context_path, properties.api_docs_path());
+
+        let mut router = Router::new().route(
+            properties.api_docs_path(),
+            get(move || {
+                let document = document.clone();
+                async move { Json(document.as_ref().clone()) }
+            }),
+        );
+        let title = Self::title(properties, application_name);
+        if let Some(path) = properties.swagger_ui_path() {
+            let page = match (
+                properties.swagger_ui_script(),
+                properties.swagger_ui_stylesheet(),
+            ) {
+                (None, None) => Some(Self::embedded(title, &api_docs_url, true)),
+                (Some(script), Some(stylesheet)) => Self::verified(path, &[script, stylesheet])
+                    .then(|| {
+                        SWAGGER_UI
+                            .replace("{stylesheet}", &Self::attributes("href", stylesheet))
+                            .replace("{script}", &Self::attributes("src", script))
+                            .replace("{api_docs_url}", &api_docs_url)
+                    }),
+                _ => {
+                    warn!(
+                        "The api docs page {} is not served, set both its script and its stylesheet",
+                        path
+                    );
+                    None
+                }
+            };
+            if let Some(page) = page.map(Html) {
+                router = router.route(path, get(move || async move { page.clone() }));
+            }
+        }
+        if let Some(path) = properties.redoc_path() {
+            let page = match properties.redoc_script() {
+                None => Some(Self::embedded(title, &api_docs_url, false)),
+                Some(script) => Self::verified(path, &[script]).then(|| {
+                    REDOC
+                        .replace("{script}", &Self::attributes("src", script))
+                        .replace("{api_docs_url}", &api_docs_url)
+                }),
+            };
+            if let Some(page) = page.map(Html) {
+                router = router.route(path, get(move || async move { page.clone() }));
+            }
+        }
+
+        Some(router)
+    }
+
+    /// The embedded UI, it only loads the document.
+    fn embedded(title: &str, api_docs_url: &str, try_it: bool) -> String {
+        // `</` would end the script element the url is written into
+        let api_docs_url = serde_json::to_string(api_docs_url)
+            .unwrap_or_default()
+            .replace("</", "<\\/");
+        API_DOCS_UI
+            .replace("{title}", &escape(title))
+            .replace("{try_it}", if try_it { "true" } else { "false" })
+            .replace("{api_docs_url}", &api_docs_url)
+    }
+
+    fn title<'a>(properties: &'a OpenApiProperties, application_name: &'a str) -> &'a str {
+        match properties.title() {
+            Some(title) => title,
+            None if !application_name.is_empty() => application_name,
+            None => "API",
+        }
+    }
+
+    fn verified(path: &str, assets: &[&OpenApiAsset]) -> bool {
+        match assets.iter().find(|asset| !asset.is_verified()) {
+            Some(asset) => {
+                warn!(
+                    "The api docs page {} is not served, its asset {} has no integrity hash",
+                    path,
+                    asset.url()
+                );
+                false
+            }
+            None => true,
+        }
+    }
+
+    fn attributes(name: &str, asset: &OpenApiAsset) -> String {
+        let mut attributes = format!("{}=\"{}\"", name, escape(asset.url()));
+        if let Some(integrity) = asset.integrity() {
+            attributes.push_str(&format!(
+                " integrity=\"{}\" crossorigin=\"anonymous\"",
+                escape(integrity)
+            ));
+        }
+        attributes
+    }
+
+    /// The OpenAPI document of every handler registered by the routing macros.
+    pub fn document(
+        properties: &OpenApiProperties,
+        application_name: &str,
+        context_path: &str,
+    ) -> Value {
+        let mut builder =
+            OpenApiBuilder::new(Self::title(properties, application_name), properties.version());
+        if let Some(description) = properties.description() {
+            builder = builder.description(description);
+        }
+        if !context_path.is_empty() {
+            builder = builder.server(context_path);
+        }
+
+        let mut handlers = inventory::iter::<&dyn HttpHandlerAutoRegister>
+            .into_iter()
+            .filter(|handler| !handler.path().is_empty())
+            .filter_map(|handler| handler.operation().map(|operation| (handler, operation)))
+            .collect::<Vec<_>>();
+        handlers.sort_by(|(a, _), (b, _)| {
+            a.path()
+                .cmp(b.path())
+                .then_with(|| a.method().cmp(b.method()))
+        });
+
+        for (handler, operation) in handlers {
+            builder.add_operation(handler.method(), handler.path(), handler.name(), &operation);
+        }
+
+        builder.build()
+    }
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+const API_DOCS_UI: &str = include_str!("api_docs.html");
+
+const SWAGGER_UI: &str = r##"<!DOCTYPE html>
+<html lang="en">
+<head>
+  <meta charset="utf-8" />
+  <meta name="viewport" content="width=device-width, initial-scale=1" />
+  <title>Swagger UI</title>
+  <link rel="stylesheet" {stylesheet} />
+</head>
+<body>
+  <div id="swagger-ui"></div>
+  <script {script}></script>
+  <script>
+    window.onload = () => {
+      window.ui = SwaggerUIBundle({ url: "{api_docs_url}", dom_id: "#swagger-ui" });
+    };
+  </script>
+</body>
+</html>
+"##;
+
+const REDOC: &str = r##"<!DOCTYPE html>
+<html lang="en">
+<head>
+  <meta charset="utf-8" />
+  <meta name="viewport" content="width=device-width, initial-scale=1" />
+  <title>Redoc</title>
+</head>
+<body>
+  <redoc spec-url="{api_docs_url}"></redoc>
+  <script {script}></script>
+</body>
+</html>
+"##;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use http_body_util::BodyExt;
+    use tower::ServiceExt;
+
+    async fn get_page(router: &Router, path: &str) -> (StatusCode, String) {
+        let request = Request::get(path).body(Body::empty()).unwrap();
+        let response = router.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        (status, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_default_pages_are_embedded() {
+        let properties: OpenApiProperties =
+            serde_json::from_value(serde_json::json!({ "enabled": true })).unwrap();
+        let router = ApiDocsRouter::router(&properties, "orders</script>", "/api").unwrap();
+
+        let (status, page) = get_page(&router, "/swagger-ui").await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(page.contains("<title>orders&lt;/script&gt;</title>"));
+        assert!(page.contains(r#"const API_DOCS_URL = "/api/v3/api-docs";"#));
+        assert!(page.contains("const TRY_IT = true;"));
+        assert!(!page.contains("https://"));
+
+        let (status, page) = get_page(&router, "/redoc").await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(page.contains("const TRY_IT = false;"));
+
+        let (status, _) = get_page(&router, "/v3/api-docs").await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_unverified_assets_are_not_served() {
+        let properties: OpenApiProperties = serde_json::from_value(serde_json::json!({
+            "enabled": true,
+            "swagger_ui_script": { "url": "/static/swagger-ui-bundle.js" },
+            "swagger_ui_stylesheet": { "url": "/static/swagger-ui.css" },
+            "redoc_script": { "url": "https://cdn.example.com/redoc.js" },
+        }))
+        .unwrap();
+        let router = ApiDocsRouter::router(&properties, "", "").unwrap();
+
+        let (status, page) = get_page(&router, "/swagger-ui").await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(page.contains(r#"src="/static/swagger-ui-bundle.js""#));
+
+        let (status, _) = get_page(&router, "/redoc").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[test]
+    fn test_asset_attributes() {
+        let pinned = OpenApiAsset::new("https://cdn.example.com/a.js", Some("sha384-abc".into()));
+        assert!(pinned.is_verified());
+        assert_eq!(
+            ApiDocsRouter::attributes("src", &pinned),
+            r#"src="https://cdn.example.com/a.js" integrity="sha384-abc" crossorigin="anonymous""#
+        );
+
+        let hosted = OpenApiAsset::new("/static/a.js", None);
+        assert!(hosted.is_verified());
+        assert_eq!(ApiDocsRouter::attributes("src", &hosted), r#"src="/static/a.js""#);
+
+        assert!(!OpenApiAsset::new("https://cdn.example.com/a.js", None).is_verified());
+        assert!(!OpenApiAsset::new("//cdn.example.com/a.js", None).is_verified());
+    }
+}
@@ -0,0 +1 @@
+pub mod api_docs_router;
@@ -14,42 +14,73 @@ use next_web_core::context::application_args::ApplicationArgs;
 use next_web_core::context::application_context::ApplicationContext;
 use next_web_core::context::application_resources::{ApplicationResources, ResourceLoader};
 use next_web_core::context::properties::{ApplicationProperties, Properties};
+use next_web_core::context::properties_validator::PropertiesValidator;
+use next_web_core::error::properties_validation_error::PropertiesValidationError;
+use next_web_core::context::scope::{RequestScope, SessionScope};
 use next_web_core::filter::application_filter_chain::ApplicationFilterChain;
+use next_web_core::filter::filter_registration::FilterRegistration;
 use next_web_core::state::application_state::ApplicationState;
 use next_web_core::traits::application::application_ready_event::ApplicationReadyEvent;
 use next_web_core::traits::apply_router::ApplyRouter;
 use next_web_core::traits::error_solver::ErrorSolver;
 use next_web_core::traits::filter::http_filter::HttpFilter;
+use next_web_core::traits::filter::http_filter_chain::HttpFilterChain;
+use next_web_core::traits::http::cors_configurer::CorsConfigurer;
 use next_web_core::traits::properties_post_processor::PropertiesPostProcessor;
 use next_web_core::traits::use_router::UseRouter;
+use next_web_core::util::singleton::SingletonUtil;
 use next_web_core::AutoRegister;
 use std::net::SocketAddr;
 use std::sync::Arc;
 use tower_http::catch_panic::CatchPanicLayer;
-use tower_http::cors::CorsLayer;
 use tower_http::limit::RequestBodyLimitLayer;
 use tower_http::timeout::TimeoutLayer;
 use tower_http::trace::TraceLayer;
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
 #[allow(unused_imports)]
-use tracing::{error, info};
+use tracing::{error, info, warn};
 
+use crate::actuator::actuator_router::ActuatorRouter;
+use crate::application::graceful_shutdown::GracefulShutdown;
 use crate::application::next_application::NextApplication;
+use crate::application::prepared_application::PreparedApplication;
+use crate::availability::application_availability::ApplicationAvailability;
+use crate::availability::availability_state::AvailabilityState;
+use crate::availability::probes::Probes;
 
 use crate::application::permitted_groups::PERMITTED_GROUPS;
 use crate::autoregister::default_autoregister::DefaultAutoRegister;
 use crate::autoregister::handler_autoregister::HttpHandlerAutoRegister;
 
+use crate::middleware::cors::cors_middleware::CorsMiddleware;
+use crate::middleware::response::response_middleware::ResponseMiddleware;
 use crate::banner::top_banner::{TopBanner, DEFAULT_TOP_BANNER};
 use crate::configurer::http_method_handler_configurer::{RouteState, RouterContext};
-use crate::event::default_application_event_multicaster::DefaultApplicationEventMulticaster;
+use crate::event::default_application_event_multicaster::{
+    select_error_handler, DefaultApplicationEventMulticaster, DEFAULT_EVENT_CAPACITY,
+};
 use crate::event::default_application_event_publisher::DefaultApplicationEventPublisher;
+use crate::event::outbox_event::LocalOutboxPublisher;
+use crate::event::outbox_relay::OutboxRelay;
+use crate::refresh::context_refresher::ContextRefresher;
 use crate::util::local_date_time::LocalDateTime;
+use crate::util::logging_level::LoggingLevel;
 
 use next_web_core::traits::application::application_shutdown::ApplicationShutdown;
 
+use next_web_core::traits::event::application_event_error_handler::ApplicationEventErrorHandler;
 use next_web_core::traits::event::application_event_multicaster::ApplicationEventMulticaster;
 use next_web_core::traits::event::application_listener::ApplicationListener;
+use next_web_core::traits::event::outbox_publisher::OutboxPublisher;
+use next_web_core::traits::event::outbox_store::OutboxStore;
+
+#[cfg(feature = "enable-refresh")]
+use crate::refresh::config_file_watcher::ConfigFileWatcher;
 
+#[cfg(feature = "enable-openapi")]
+use crate::api_docs::api_docs_router::ApiDocsRouter;
 #[cfg(feature = "enable-scheduling")]
 use crate::autoregister::scheduler_autoregister::SchedulerAutoRegister;
 #[cfg(feature = "enable-scheduling")]
@@ -57,6 +88,8 @@ use crate::manager::job_scheduler_manager::JobSchedulerManager;
 #[cfg(feature = "enable-scheduling")]
 #[allow(unused_imports)]
 use next_web_core::traits::schedule::scheduled_task::ScheduledTask;
+#[cfg(feature = "enable-scheduling")]
+use next_web_core::traits::schedule::scheduler_lock_provider::SchedulerLockProvider;
 
 #[async_trait]
 pub trait Application
@@ -189,24 +222,40 @@ where
 
         // tracing::subscriber::set_global_default(subscriber).expect("setting default subscriber failed");
 
-        let logger = tracing_subscriber::fmt()
-            // test
-            .with_max_level(
+        let (level_filter, level_handle) = tracing_subscriber::reload::Layer::new(
+            LevelFilter::from_level(
                 logging
                     .map(|log| log.level())
                     .unwrap_or(tracing::Level::INFO),
-            )
+            ),
+        );
+        LoggingLevel::install(level_handle);
+
+        let logger = tracing_subscriber::fmt::layer()
             .with_ansi(false)
             .event_format(config);
 
+        let registry = tracing_subscriber::registry().with(level_filter);
         if let Some(file_appender) = file_appender {
             let (non_blocking, _worker) = tracing_appender::non_blocking(file_appender);
-            logger.with_writer(non_blocking).with_test_writer().init();
+            registry
+                .with(logger.with_writer(non_blocking).with_test_writer())
+                .init();
         } else {
-            logger.init();
+            registry.with(logger).init();
         }
     }
 
+    /// Validate the configuration before it is bound, the report lists every problem.
+    fn validate_properties(
+        &self,
+        ctx: &mut ApplicationContext,
+        application_properties: &ApplicationProperties,
+    ) -> Result<(), PropertiesValidationError> {
+        let properties = ctx.resolve_by_type::<Box<dyn Properties>>();
+        PropertiesValidator::validate(application_properties, &properties)
+    }
+
     /// Autowire properties
     async fn autowire_properties(
         &self,
@@ -233,6 +282,15 @@ where
         ctx.insert_singleton_with_default_name(application_args.to_owned());
         ctx.insert_singleton_with_default_name(application_resources.to_owned());
 
+        // [scopes] a `SessionScope` registered by the application, e.g. with its cookie name, is kept
+        ctx.insert_singleton_with_default_name(RequestScope);
+        let session_scope = SingletonUtil::name::<SessionScope>();
+        if !ctx.contains_provider_with_name::<SessionScope>(session_scope.clone())
+            && !ctx.contains_single_with_name::<SessionScope>(session_scope)
+        {
+            ctx.insert_singleton_with_default_name(SessionScope::default());
+        }
+
         // If a declarative macro is used for submission, it should not be found in the Application Context
         for default_auto_register in inventory::iter::<&dyn DefaultAutoRegister>.into_iter() {
             default_auto_register
@@ -258,13 +316,35 @@ where
         _application_properties: &ApplicationProperties,
     ) {
         // Register application event
-        let (tx, rx) = flume::unbounded();
+        let (tx, rx) = flume::bounded(DEFAULT_EVENT_CAPACITY);
         let mut default_event_publisher = DefaultApplicationEventPublisher::new();
         let mut multicaster = DefaultApplicationEventMulticaster::new();
 
         default_event_publisher.set_channel(Some(tx));
         multicaster.set_event_channel(rx);
 
+        let error_handlers = ctx
+            .get_providers_by_type::<Box<dyn ApplicationEventErrorHandler>>()
+            .into_iter()
+            .map(|provider| provider.definition().key.name.clone())
+            .collect::<Vec<_>>()
+            .into_iter()
+            .map(|name| {
+                let handler = ctx.resolve_with_name::<Box<dyn ApplicationEventErrorHandler>>(name.clone());
+                (name, handler)
+            })
+            .collect();
+        match select_error_handler(error_handlers) {
+            Ok(Some(error_handler)) => multicaster.set_error_handler(error_handler),
+            Ok(None) => {}
+            Err(error) => {
+                error!("{}", error);
+                eprintln!("{}", error);
+                std::process::exit(1);
+            }
+        }
+        default_event_publisher.set_multicaster(multicaster.clone());
+
         let listeners = ctx.resolve_by_type::<Box<dyn ApplicationListener>>();
         for listener in listeners.into_iter() {
             multicaster.add_application_listener(listener).await;
@@ -272,13 +352,36 @@ where
 
         multicaster.run();
 
+        // Relay the transactional outbox messages
+        let outbox_stores = ctx.resolve_by_type::<Box<dyn OutboxStore>>();
+        for store in outbox_stores.into_iter() {
+            let mut publishers = ctx.resolve_by_type::<Box<dyn OutboxPublisher>>();
+            publishers.push(Box::new(LocalOutboxPublisher::new(default_event_publisher.clone())));
+            OutboxRelay::new(store, publishers).start();
+        }
+
         // Register jobs
         #[cfg(feature = "enable-scheduling")]
         {
-            let mut manager = JobSchedulerManager::with_channel_size(240).await;
+            let mut manager = JobSchedulerManager::new();
+
+            let mut lock_providers = ctx.resolve_by_type::<Box<dyn SchedulerLockProvider>>();
+            if lock_providers.len() > 1 {
+                warn!(
+                    "{} scheduler lock providers are registered, only the first one is used",
+                    lock_providers.len()
+                );
+            }
+            if !lock_providers.is_empty() {
+                manager.set_lock_provider(lock_providers.swap_remove(0));
+            }
+
+            // A job that can not be added, e.g. two jobs with the same name, is a bug of the application
             for scheduler in inventory::iter::<&dyn SchedulerAutoRegister>.into_iter() {
-                if let Err(error) = manager.add(scheduler.register(ctx)).await {
+                if let Err(error) = manager.add_with_name(scheduler.name(), scheduler.register(ctx)).await {
                     error!("JobSchedulerManager Failed to add job: {}", error);
+                    eprintln!("Failed to add the scheduled job: {}", error);
+                    std::process::exit(1);
                 }
             }
 
@@ -293,6 +396,8 @@ where
         }
 
         let rest_client = RestClient::new();
+        ctx.insert_singleton_with_default_name(ApplicationAvailability::new());
+        ctx.insert_singleton_with_default_name(ContextRefresher::new());
         ctx.insert_singleton_with_default_name(default_event_publisher);
         ctx.insert_singleton_with_default_name(multicaster);
         ctx.insert_singleton_with_default_name(rest_client);
@@ -324,28 +429,20 @@ where
         router
     }
 
-    /// Bind tcp server.
-    async fn bind_tcp_server(
+    /// Build the router of the application from the prepared context: routes, management
+    /// endpoints, middleware and filters, then trigger the [`ApplicationReadyEvent`].
+    ///
+    /// No server is bound, the router can be served or called directly, e.g. in tests.
+    async fn prepare_application(
         &self,
         mut ctx: ApplicationContext,
         application_properties: &ApplicationProperties,
-        time: std::time::Instant,
-    ) {
+    ) -> PreparedApplication {
         // 1. Read server configuration
         let config = application_properties.next().server();
         let context_path = config.context_path().unwrap_or("");
         let server_port = config.port().unwrap_or(APPLICATION_DEFAULT_PORT);
 
-        let server_addr = if let Some(addr) = config.addr() {
-            addr
-        } else {
-            if config.local().unwrap_or(true) {
-                "127.0.0.1"
-            } else {
-                "0.0.0.0"
-            }
-        };
-
         let req_timeout = config.http().map(|http| {
             http.request()
                 .map(|req| req.timeout().unwrap_or(5))
@@ -382,9 +479,57 @@ where
                 .fold(axum::Router::new(), |acc, r| acc.merge(r)),
         );
 
+        // Management endpoints share the server port unless a different one is configured
+        let management = application_properties
+            .next()
+            .management()
+            .cloned()
+            .unwrap_or_default();
+        let management_port = management.port().filter(|port| *port != server_port);
+        let management_router = ActuatorRouter::router(
+            &management,
+            match management_port {
+                Some(_) => "",
+                None => context_path,
+            },
+            &mut ctx,
+        );
+        let management_router = match management_port {
+            Some(_) => management_router,
+            None => {
+                if let Some(router) = management_router {
+                    app = app.merge(router);
+                }
+                None
+            }
+        };
+
+        // OpenAPI document and its UI pages
+        #[cfg(feature = "enable-openapi")]
+        {
+            let openapi = application_properties
+                .next()
+                .openapi()
+                .cloned()
+                .unwrap_or_default();
+            let application_name = application_properties
+                .next()
+                .appliation()
+                .map(|app| app.name())
+                .unwrap_or_default();
+            if let Some(router) = ApiDocsRouter::router(&openapi, application_name, context_path) {
+                app = app.merge(router);
+            }
+        }
+
         // 4. Obtain necessary instances
-        #[cfg(not(feature = "tls-rustls"))]
         let shutdowns = ctx.resolve_by_type::<Box<dyn ApplicationShutdown>>();
+        let availability = ctx
+            .get_single_option_with_name::<ApplicationAvailability>(
+                SingletonUtil::name::<ApplicationAvailability>(),
+            )
+            .cloned()
+            .unwrap_or_default();
 
         // 5. Add global middleware layer
         {
@@ -411,23 +556,22 @@ where
                     }
 
                     // Response
-                    // TODO: response middleware
-                    #[allow(unused_variables)]
-                    if let Some(resp) = http.response() {}
+                    if let Some(resp) = http.response() {
+                        app = ResponseMiddleware::layer(app, resp);
+                    }
                 }
                 None => {}
             };
 
+            // Cors, no layer at all unless it is configured
+            let cors = config.http().and_then(|http| http.cors());
+            let cors_configurers = ctx.resolve_by_type::<Box<dyn CorsConfigurer>>();
+            if cors.is_some() || !cors_configurers.is_empty() {
+                app = CorsMiddleware::layer(app, CorsMiddleware::registry(cors, &cors_configurers));
+            }
+
             // Layer
             app = app
-                // Cors
-                .layer(
-                    CorsLayer::new()
-                        .allow_origin(tower_http::cors::Any)
-                        .allow_methods(tower_http::cors::Any)
-                        .allow_headers(tower_http::cors::Any)
-                        .max_age(std::time::Duration::from_secs(60) * 10),
-                )
                 // Handler request  max timeout
                 .layer(TimeoutLayer::new(std::time::Duration::from_secs(
                     req_timeout.unwrap_or(5),
@@ -436,12 +580,17 @@ where
                 .layer(CatchPanicLayer::custom(Self::catch_panic));
 
             // Filter
-            let filters = ctx.resolve_by_type::<Arc<dyn HttpFilter>>();
+            let filters = FilterRegistration::sorted(ctx.resolve_by_type::<Arc<dyn HttpFilter>>());
             if !filters.is_empty() {
                 app = app.route_layer(from_fn_with_state(Arc::new(filters), http_filter_layer));
             }
         }
 
+        // Probes are added after the middleware so filters and timeouts don't apply to them
+        if management.probes() {
+            app = app.merge(Probes::router(availability.clone()));
+        }
+
         // 6. Trigger the ApplicationReadyEvent
         for ready_event in ctx.resolve_by_type::<Box<dyn ApplicationReadyEvent>>() {
             ready_event.ready(&mut ctx).await;
@@ -451,7 +600,8 @@ where
         self.on_ready(&mut ctx).await;
 
         // 8. Add State to [Context]
-        app = app.route_layer(axum::Extension(ApplicationState::from_context(ctx)));
+        let state = ApplicationState::from_context(ctx);
+        app = app.route_layer(axum::Extension(state.clone()));
 
         // 9. Nest context path
         let app = match context_path.is_empty() {
@@ -466,6 +616,63 @@ where
             }
         };
 
+        PreparedApplication {
+            router: app,
+            state,
+            availability,
+            shutdowns,
+            management: management_router.zip(management_port),
+        }
+    }
+
+    /// Bind tcp server.
+    async fn bind_tcp_server(
+        &self,
+        ctx: ApplicationContext,
+        application_properties: &ApplicationProperties,
+        time: std::time::Instant,
+    ) {
+        let config = application_properties.next().server();
+        let server_port = config.port().unwrap_or(APPLICATION_DEFAULT_PORT);
+
+        let server_addr = if let Some(addr) = config.addr() {
+            addr
+        } else {
+            if config.local().unwrap_or(true) {
+                "127.0.0.1"
+            } else {
+                "0.0.0.0"
+            }
+        };
+
+        let PreparedApplication {
+            router: app,
+            state,
+            availability,
+            shutdowns,
+            management: management_router,
+        } = self.prepare_application(ctx, application_properties).await;
+
+        // Optional dependencies nobody provides are usually a missing feature or a typo in a name
+        for edge in state.context().read().await.bean_graph().absent_dependencies() {
+            warn!(
+                "Bean `{}` ({}) requested the optional dependency `{}` ({}), no provider is registered for it",
+                edge.from.name, edge.from.ty.name, edge.to.name, edge.to.ty.name
+            );
+        }
+
+        #[cfg(feature = "enable-refresh")]
+        ConfigFileWatcher::spawn(state.clone()).await;
+
+        if let Some((router, port)) = management_router {
+            let addr = application_properties
+                .next()
+                .management()
+                .and_then(|management| management.addr())
+                .unwrap_or(server_addr);
+            ActuatorRouter::serve(router, addr, port, state.clone());
+        }
+
         #[rustfmt::skip]
         println!("\nApplication Listening on:  {}", format!("{}:{}", server_addr, server_port));
         println!("Application Started   at:  {}", LocalDateTime::now());
@@ -474,67 +681,91 @@ where
 
         // 10. Start server
         let socket_addr: SocketAddr = format!("{}:{}", server_addr, server_port).parse().unwrap();
-        // Turn off signal monitoring
-        #[cfg(not(feature = "tls-rustls"))]
-        let shutdown_signal = async move {
-            let ctrl_c = async {
-                tokio::signal::ctrl_c()
-                    .await
-                    .expect("failed to install Ctrl+C handler");
-            };
-
-            #[cfg(unix)]
-            let terminate = async {
-                tokio::signal::unix::SignalKind::terminate()
-                    .then(|signal| signal.recv())
-                    .await;
-            };
-
-            #[cfg(not(unix))]
-            let terminate = std::future::pending::<()>();
+        let shutdown_properties = config.shutdown().cloned().unwrap_or_default();
+        let graceful_shutdown = Arc::new(GracefulShutdown::new(
+            availability.clone(),
+            shutdown_properties.clone(),
+        ));
 
-            tokio::select! {
-                _ = ctrl_c => info!("Received Ctrl+C. Shutting down..."),
-                _ = terminate => info!("Received terminate signal. Shutting down..."),
+        // Configure certificate and private key used by https
+        #[cfg(feature = "tls-rustls")]
+        let tls_config = match crate::tls::server_tls_config::ServerTlsConfig::resolve(config.ssl()).await {
+            Ok(tls_config) => tls_config,
+            Err(e) => {
+                error!("Failed to load the ssl configuration: {}", e);
+                eprintln!("Failed to load the ssl configuration: {}", e);
+                std::process::exit(1);
             }
-
-            // Execute all shutdown hooks
-            for mut service in shutdowns {
-                service.shutdown().await;
+        };
+        #[cfg(not(feature = "tls-rustls"))]
+        let tls_config = {
+            if config.ssl().is_some_and(|ssl| ssl.enabled()) {
+                warn!("`next.server.ssl` is ignored, enable the `tls-rustls` feature to serve https");
             }
+            None::<()>
         };
 
-        // Configure certificate and private key used by https
-        #[cfg(feature = "tls-rustls")]
+        // Start the lifecycle beans before the application is ready, without holding the context
+        let lifecycle_beans = state.context().write().await.lifecycle_beans();
+        if let Err(error) = lifecycle_beans
+            .start(shutdown_properties.timeout_per_hook())
+            .await
         {
-            use axum_server::tls_rustls::RustlsConfig;
+            error!("Failed to start the application: {}", error);
+            eprintln!("Failed to start the application: {}", error);
+            std::process::exit(1);
+        }
 
-            let certs_dir = std::path::PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap())
-                .join("self_signed_certs");
-            let tls_config =
-                RustlsConfig::from_pem_file(certs_dir.join("cert.pem"), certs_dir.join("key.pem"))
+        #[allow(unused_variables)]
+        if let Some(tls_config) = tls_config {
+            #[cfg(feature = "tls-rustls")]
+            {
+                let handle = axum_server::Handle::new();
+                let shutdown_handle = handle.clone();
+                let signal = graceful_shutdown.clone();
+                tokio::spawn(async move {
+                    signal.signal().await;
+                    // In-flight requests that exceed the graceful timeout are dropped
+                    shutdown_handle.graceful_shutdown(Some(signal.graceful_timeout()));
+                });
+
+                let mut server = axum_server::bind_rustls(socket_addr, tls_config).handle(handle);
+                // IMPORTANT: This is required to advertise our support for HTTP/2 websockets to the client.
+                // If you use axum::serve, it is enabled by default.
+                server.http_builder().http2().enable_connect_protocol();
+
+                availability.set_state(AvailabilityState::Ready);
+                server
+                    .serve(app.into_make_service_with_connect_info::<SocketAddr>())
                     .await
                     .unwrap();
-
-            let mut server = axum_server::bind_rustls(socket_addr, tls_config);
-            // IMPORTANT: This is required to advertise our support for HTTP/2 websockets to the client.
-            // If you use axum::serve, it is enabled by default.
-            server.http_builder().http2().enable_connect_protocol();
-            server.serve(app.into_make_service()).await.unwrap();
-        }
-
-        #[cfg(not(feature = "tls-rustls"))]
-        {
+            }
+        } else {
             let listener = tokio::net::TcpListener::bind(&socket_addr).await.unwrap();
 
-            axum::serve(
+            let signal = graceful_shutdown.clone();
+            let server = axum::serve(
                 listener,
                 app.into_make_service_with_connect_info::<SocketAddr>(),
             )
-            .with_graceful_shutdown(shutdown_signal)
-            .await
-            .unwrap();
+            .with_graceful_shutdown(async move { signal.signal().await });
+
+            availability.set_state(AvailabilityState::Ready);
+
+            // In-flight requests that exceed the graceful timeout are dropped
+            tokio::select! {
+                result = std::future::IntoFuture::into_future(server) => result.unwrap(),
+                _ = graceful_shutdown.deadline() => warn!(
+                    "In-flight requests did not complete within {:?}, closing anyway",
+                    graceful_shutdown.graceful_timeout()
+                ),
+            }
         }
+
+        // Stop the lifecycle beans, execute all shutdown hooks, then destroy the beans
+        graceful_shutdown.stop_lifecycle_beans(&state).await;
+        graceful_shutdown.run_hooks(shutdowns).await;
+        graceful_shutdown.destroy_beans(&state).await;
     }
 
     /// Run the application.
@@ -545,13 +776,16 @@ where
         // Record application start time
         let start_time = std::time::Instant::now();
 
-        // Get a base application instance
-        let mut next_application: NextApplication<Self> = NextApplication::new();
+        // The configuration problems are reported here and stop the application
+        let exit = |report: PropertiesValidationError| -> ! {
+            error!("{}", report);
+            eprintln!("{}", report);
+            std::process::exit(1);
+        };
 
-        // Perform a series of processing on application properties before executing the next step
-        next_application
-            .application_properties
-            .replace_placeholders();
+        // Get a base application instance
+        let mut next_application: NextApplication<Self> =
+            NextApplication::new().unwrap_or_else(|report| exit(report));
 
         // Banner show
         Self::banner_show(next_application.application_resources());
@@ -562,18 +796,17 @@ where
             .appliation()
             .map(|s| s.context().allow_override())
             .unwrap_or(false);
+        // The conditions of `#[Singleton(on_property = ..)]` are evaluated against the properties
         let mut ctx = ApplicationContext::options()
             .allow_override(allow_override)
+            .properties(next_application.application_properties().clone())
             .auto_register();
 
         info!("Init Application context success");
 
-        let mut post_processors = ctx.resolve_by_type::<Box<dyn PropertiesPostProcessor>>();
-        post_processors.sort_by_key(|item| item.order());
-
-        post_processors.into_iter().for_each(|mut item| {
-            item.post_process_properties(next_application.application_properties.mapping_mut())
-        });
+        next_application
+            .application
+            .post_process_properties(&mut ctx, &mut next_application.application_properties);
 
         let properties = next_application.application_properties();
         let args = next_application.application_args();
@@ -584,60 +817,109 @@ where
         application.init_logging(properties);
         info!("Logging initialized");
 
+        // Validate and autowire properties
+        if let Err(report) = application.validate_properties(&mut ctx, properties) {
+            exit(report);
+        }
+        application
+            .prepare_context(&mut ctx, properties, args, resources)
+            .await;
+
+        // `--print-bean-graph`: dump the providers instead of serving. The remaining ones are
+        // dry run in a snapshot, so every missing and circular dependency is listed
+        if let Some(format) = args.print_bean_graph {
+            let graph = ctx.dry_run_async().await;
+            println!("{}", graph.render(format));
+            for error in graph.errors() {
+                eprintln!("{}\n", error);
+            }
+            std::process::exit(if graph.errors().is_empty() { 0 } else { 1 });
+        }
+
+        info!("Starting Async Runtime: [Tokio/1.44.1]");
+        info!("Starting HTTP  Server:  [Axum/0.8.4]");
+
+        application
+            .bind_tcp_server(ctx, properties, start_time)
+            .await;
+    }
+
+    /// Apply the [`PropertiesPostProcessor`]s to the mapping of the properties, lowest order first.
+    fn post_process_properties(
+        &self,
+        ctx: &mut ApplicationContext,
+        application_properties: &mut ApplicationProperties,
+    ) {
+        let mut post_processors = ctx.resolve_by_type::<Box<dyn PropertiesPostProcessor>>();
+        post_processors.sort_by_key(|item| item.order());
+
+        post_processors.into_iter().for_each(|mut item| {
+            item.post_process_properties(application_properties.mapping_mut())
+        });
+
+        ctx.set_properties(application_properties.clone());
+    }
+
+    /// Prepare the context once the properties are validated: autowire the properties, register
+    /// the singletons, initialize the infrastructure and the middleware, then call the
+    /// `InitializingBean`s.
+    async fn prepare_context(
+        &self,
+        ctx: &mut ApplicationContext,
+        properties: &ApplicationProperties,
+        args: &ApplicationArgs,
+        resources: &ApplicationResources,
+    ) {
         // Autowire properties
-        application.autowire_properties(&mut ctx, properties).await;
+        self.autowire_properties(ctx, properties).await;
         info!("Configuration properties loaded");
 
         // Register singleton
-        application
-            .register_singleton(&mut ctx, properties, args, resources)
+        self.register_singleton(ctx, properties, args, resources)
             .await;
         info!("Singleton services registered");
 
         // Init infrastructure
-        application.init_infrastructure(&mut ctx, properties).await;
+        self.init_infrastructure(ctx, properties).await;
         info!("Infrastructure initialized",);
 
         // Init middleware
-        application.init_middleware(properties).await;
+        self.init_middleware(properties).await;
         info!("Middleware initialized");
 
         #[cfg(feature = "enable-grpc")]
         {
-            application
-                .register_rpc_server(&mut ctx, properties, args, resources)
+            self.register_rpc_server(ctx, properties, args, resources)
                 .await;
             info!("gRPC server started");
 
-            application
-                .connect_rpc_client(&mut ctx, properties, args, resources)
+            self.connect_rpc_client(ctx, properties, args, resources)
                 .await;
             info!("gRPC client connected",);
         }
 
-        info!("Starting Async Runtime: [Tokio/1.44.1]");
-        info!("Starting HTTP  Server:  [Axum/0.8.4]");
-
-        application
-            .bind_tcp_server(ctx, properties, start_time)
-            .await;
+        // Call the `InitializingBean`s
+        if let Err(error) = ctx.initialize_beans().await {
+            error!("Failed to initialize the beans: {}", error);
+            eprintln!("Failed to initialize the beans: {}", error);
+            std::process::exit(1);
+        }
     }
 }
 
-type FilterResult = Result<Response, Response>;
+/// Run the request through the [`HttpFilter`] chain, the handler is called at its end.
 async fn http_filter_layer(
-    State(filters): State<Arc<Vec<Arc<dyn HttpFilter>>>>,
+    State(filters): State<Arc<Vec<FilterRegistration>>>,
     mut req: Request,
     next: Next,
-) -> FilterResult {
-    let filter_chain = ApplicationFilterChain::default();
+) -> Response {
+    let filter_chain = ApplicationFilterChain::new(filters, next);
     let mut resp = Response::new(Body::empty());
 
-    for filter in filters.iter() {
-        if let Err(err) = filter.do_filter(&mut req, &mut resp, &filter_chain).await {
-            error!("The {} filter encountered an error: {}", filter.name(), err)
-        }
+    if let Err(err) = filter_chain.do_filter(&mut req, &mut resp).await {
+        error!("The http filter chain encountered an error: {}", err);
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
     }
 
-    Ok(next.run(req).await)
+    resp
 }
@@ -0,0 +1,126 @@
+use std::panic::AssertUnwindSafe;
+use std::time::Duration;
+
+use futures::FutureExt;
+use next_web_core::autoconfigure::context::shutdown_properties::ShutdownProperties;
+use next_web_core::state::application_state::ApplicationState;
+use next_web_core::traits::application::application_shutdown::ApplicationShutdown;
+use tracing::{info, warn};
+
+use crate::availability::application_availability::ApplicationAvailability;
+use crate::availability::availability_state::AvailabilityState;
+
+/// 优雅停机
+///
+/// Coordinates the shutdown of the application:
+///
+/// 1. wait for `Ctrl+C` or `SIGTERM`
+/// 2. flip readiness to `REFUSING_TRAFFIC` and keep serving for the drain period
+/// 3. flip to `DRAINING`, the server closes its listener and completes in-flight requests
+/// 4. stop the [`Lifecycle`](next_web_core::traits::lifecycle::lifecycle_bean::Lifecycle) beans, highest phase first
+/// 5. run the [`ApplicationShutdown`] hooks, highest order first
+/// 6. destroy the [`DisposableBean`](next_web_core::traits::lifecycle::disposable_bean::DisposableBean)s
+///
+/// Each callback of the last three steps is bounded by a timeout.
+pub(crate) struct GracefulShutdown {
+    availability: ApplicationAvailability,
+    properties: ShutdownProperties,
+}
+
+impl GracefulShutdown {
+    pub(crate) fn new(availability: ApplicationAvailability, properties: ShutdownProperties) -> Self {
+        Self {
+            availability,
+            properties,
+        }
+    }
+
+    /// Resolves once the server should stop accepting connections.
+    pub(crate) async fn signal(&self) {
+        Self::wait_for_signal().await;
+
+        self.availability.set_state(AvailabilityState::RefusingTraffic);
+        let drain_period = self.properties.drain_period();
+        if !drain_period.is_zero() {
+            info!("Draining traffic for {:?} before closing the listener", drain_period);
+            tokio::time::sleep(drain_period).await;
+        }
+
+        self.availability.set_state(AvailabilityState::Draining);
+    }
+
+    /// Resolves once in-flight requests exceeded the graceful timeout.
+    pub(crate) async fn deadline(&self) {
+        self.availability.wait_for(AvailabilityState::Draining).await;
+        tokio::time::sleep(self.properties.graceful_timeout()).await;
+    }
+
+    pub(crate) fn graceful_timeout(&self) -> Duration {
+        self.properties.graceful_timeout()
+    }
+
+    /// Run the shutdown hooks in descending order, a hook that hangs or panics does not
+    /// prevent the remaining hooks from running.
+    pub(crate) async fn run_hooks(&self, mut hooks: Vec<Box<dyn ApplicationShutdown>>) {
+        hooks.sort_by_key(|hook| std::cmp::Reverse(hook.order()));
+
+        for mut hook in hooks {
+            let timeout = hook
+                .timeout()
+                .unwrap_or_else(|| self.properties.timeout_per_hook());
+            let order = hook.order();
+
+            match tokio::time::timeout(timeout, AssertUnwindSafe(hook.shutdown()).catch_unwind())
+                .await
+            {
+                Ok(Ok(())) => {}
+                Ok(Err(_)) => warn!("Shutdown hook with order {} panicked", order),
+                Err(_) => warn!(
+                    "Shutdown hook with order {} did not complete within {:?}",
+                    order, timeout
+                ),
+            }
+        }
+    }
+
+    /// Stop the lifecycle beans before the hooks run.
+    pub(crate) async fn stop_lifecycle_beans(&self, state: &ApplicationState) {
+        // Stopped without holding the context, the in-flight requests may still need it
+        let beans = state.context().write().await.lifecycle_beans();
+        beans.stop(self.properties.timeout_per_hook()).await;
+    }
+
+    /// Destroy the beans once the hooks ran.
+    pub(crate) async fn destroy_beans(&self, state: &ApplicationState) {
+        state
+            .context()
+            .write()
+            .await
+            .destroy_beans(self.properties.timeout_per_hook())
+            .await;
+    }
+
+    async fn wait_for_signal() {
+        let ctrl_c = async {
+            tokio::signal::ctrl_c()
+                .await
+                .expect("failed to install Ctrl+C handler");
+        };
+
+        #[cfg(unix)]
+        let terminate = async {
+            tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+                .expect("failed to install signal handler")
+                .recv()
+                .await;
+        };
+
+        #[cfg(not(unix))]
+        let terminate = std::future::pending::<()>();
+
+        tokio::select! {
+            _ = ctrl_c => info!("Received Ctrl+C. Shutting down..."),
+            _ = terminate => info!("Received terminate signal. Shutting down..."),
+        }
+    }
+}
@@ -1,4 +1,7 @@
 pub mod permitted_groups;
 pub mod next_application;
 mod application;
-pub use application::Application;
\ No newline at end of file
+mod graceful_shutdown;
+mod prepared_application;
+pub use application::Application;
+pub use prepared_application::PreparedApplication;
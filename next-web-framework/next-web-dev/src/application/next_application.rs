@@ -4,6 +4,7 @@ use next_web_core::context::{
     application_args::ApplicationArgs, application_resources::ApplicationResources,
     properties::ApplicationProperties,
 };
+use next_web_core::error::properties_validation_error::PropertiesValidationError;
 
 use super::application::Application;
 use next_web_core::autoconfigure::context::server_properties::ServerProperties;
@@ -17,18 +18,19 @@ pub struct NextApplication<A: Application> {
 }
 
 impl<A: Application + Default> NextApplication<A> {
-    pub fn new() -> Self {
+    /// Load the configuration of the application, fails when it can not be read or bound.
+    pub fn new() -> Result<Self, PropertiesValidationError> {
         let application_args = ApplicationArgs::default();
         let application_resources = ApplicationResources::default();
         let application_properties =
-            ApplicationProperties::from((&application_args, &application_resources));
+            ApplicationProperties::try_from((&application_args, &application_resources))?;
 
-        Self {
+        Ok(Self {
             application_properties,
             application_args,
             application_resources,
             application: A::default(),
-        }
+        })
     }
 
     /// Get the application register.
@@ -0,0 +1,42 @@
+use axum::Router;
+use next_web_core::state::application_state::ApplicationState;
+use next_web_core::traits::application::application_shutdown::ApplicationShutdown;
+
+use crate::availability::application_availability::ApplicationAvailability;
+
+/// 已就绪的应用
+///
+/// The application once its context is ready and its router is built, before a server is bound,
+/// see [`Application::prepare_application`](super::Application::prepare_application).
+pub struct PreparedApplication {
+    pub(crate) router: Router,
+    pub(crate) state: ApplicationState,
+    pub(crate) availability: ApplicationAvailability,
+    pub(crate) shutdowns: Vec<Box<dyn ApplicationShutdown>>,
+    /// The management endpoints when they are served on their own port.
+    pub(crate) management: Option<(Router, u16)>,
+}
+
+impl PreparedApplication {
+    /// The router with every route, middleware and filter, nested under the context path.
+    pub fn router(&self) -> &Router {
+        &self.router
+    }
+
+    pub fn state(&self) -> &ApplicationState {
+        &self.state
+    }
+
+    pub fn availability(&self) -> &ApplicationAvailability {
+        &self.availability
+    }
+
+    /// The management endpoints router, `None` unless they are served on their own port.
+    pub fn management_router(&self) -> Option<&Router> {
+        self.management.as_ref().map(|(router, _)| router)
+    }
+
+    pub fn into_router(self) -> Router {
+        self.router
+    }
+}
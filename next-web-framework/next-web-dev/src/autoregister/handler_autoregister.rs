@@ -1,4 +1,5 @@
 use axum::Router;
+use next_web_core::openapi::ApiOperation;
 
 use crate::configurer::http_method_handler_configurer::RouterContext;
 
@@ -6,6 +7,26 @@ pub trait HttpHandlerAutoRegister
 where Self: Send + Sync +'static
 {
     fn register<'a>(&self, __router: Router, __context: &'a mut RouterContext) -> Router;
+
+    /// Name of the handler function.
+    fn name(&self) -> &'static str {
+        ""
+    }
+
+    /// Uppercase HTTP method the handler is bound to, e.g. `GET`.
+    fn method(&self) -> &'static str {
+        ""
+    }
+
+    /// Route path the handler is bound to, without the context path.
+    fn path(&self) -> &'static str {
+        ""
+    }
+
+    /// OpenAPI metadata of the handler, generated by the routing macros.
+    fn operation(&self) -> Option<ApiOperation> {
+        None
+    }
 }
 
 inventory::collect!(&'static dyn HttpHandlerAutoRegister);
@@ -7,6 +7,11 @@ pub trait SchedulerAutoRegister
 where Self: Send + Sync +'static
 {
     fn register(&self, __ctx: &mut ApplicationContext) -> AnJob;
+
+    /// Name of the job, the module path and the name of the scheduled function unless set.
+    fn name(&self) -> &'static str {
+        ""
+    }
 }
 
 inventory::collect!(&'static dyn SchedulerAutoRegister);
@@ -21,7 +26,7 @@ macro_rules! submit_scheduler {
 }
 
 
-type JobFuture =    Box<dyn Fn() -> std::pin::Pin<Box<dyn Future<Output = ()> + Send + Sync>> + Send + Sync + 'static>;
+type JobFuture =    Box<dyn Fn() -> std::pin::Pin<Box<dyn Future<Output = ()> + Send>> + Send + Sync + 'static>;
 type JobFunction =  Box<dyn Fn() + Send + Sync + 'static>;
 pub enum AnJob {
     Async((ScheduleType,    JobFuture)),
@@ -0,0 +1,60 @@
+use std::sync::Arc;
+
+use next_web_core::traits::singleton::Singleton;
+use tokio::sync::watch;
+use tracing::info;
+
+use super::availability_state::AvailabilityState;
+
+/// 应用可用性
+///
+/// Holds the current [`AvailabilityState`] of the application.
+///
+/// Registered as a singleton, components can resolve it to read the state,
+/// wait for a state or flip the application to `REFUSING_TRAFFIC` themselves.
+#[derive(Clone)]
+pub struct ApplicationAvailability {
+    sender: Arc<watch::Sender<AvailabilityState>>,
+}
+
+impl Singleton for ApplicationAvailability {}
+
+impl ApplicationAvailability {
+    pub fn new() -> Self {
+        let (sender, _) = watch::channel(AvailabilityState::Starting);
+        Self {
+            sender: Arc::new(sender),
+        }
+    }
+
+    /// The current state.
+    pub fn state(&self) -> AvailabilityState {
+        *self.sender.borrow()
+    }
+
+    /// Change the state, returns the previous one.
+    pub fn set_state(&self, state: AvailabilityState) -> AvailabilityState {
+        let previous = self.sender.send_replace(state);
+        if previous != state {
+            info!("Application availability changed: {} -> {}", previous, state);
+        }
+        previous
+    }
+
+    /// Subscribe to state changes.
+    pub fn subscribe(&self) -> watch::Receiver<AvailabilityState> {
+        self.sender.subscribe()
+    }
+
+    /// Wait until the application reaches `state`.
+    pub async fn wait_for(&self, state: AvailabilityState) {
+        let mut receiver = self.subscribe();
+        let _ = receiver.wait_for(|current| *current == state).await;
+    }
+}
+
+impl Default for ApplicationAvailability {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,42 @@
+use std::fmt::Display;
+
+/// 应用可用状态
+///
+/// Availability state of the application, drives the `/readyz` probe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum AvailabilityState {
+    /// The application is starting and does not accept traffic yet.
+    Starting,
+    /// The application is ready to accept traffic.
+    Ready,
+    /// The application is running but asks not to receive traffic,
+    /// e.g. during the drain period of a shutdown.
+    RefusingTraffic,
+    /// The listener is closed and in-flight requests are completing.
+    Draining,
+}
+
+impl AvailabilityState {
+    /// Whether the application is alive, it stays alive until the process exits.
+    pub fn is_live(&self) -> bool {
+        true
+    }
+
+    /// Whether the application accepts traffic.
+    pub fn is_ready(&self) -> bool {
+        matches!(self, AvailabilityState::Ready)
+    }
+}
+
+impl Display for AvailabilityState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            AvailabilityState::Starting => "STARTING",
+            AvailabilityState::Ready => "READY",
+            AvailabilityState::RefusingTraffic => "REFUSING_TRAFFIC",
+            AvailabilityState::Draining => "DRAINING",
+        };
+        f.write_str(name)
+    }
+}
@@ -0,0 +1,3 @@
+pub mod application_availability;
+pub mod availability_state;
+pub mod probes;
@@ -0,0 +1,42 @@
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::{Json, Router};
+use serde_json::json;
+
+use super::application_availability::ApplicationAvailability;
+
+/// Kubernetes style probes.
+///
+/// - `/livez` responds `200` as long as the server answers.
+/// - `/readyz` responds `200` only in the `READY` state, `503` otherwise.
+pub struct Probes;
+
+impl Probes {
+    pub fn router(availability: ApplicationAvailability) -> Router {
+        let readiness = availability.clone();
+        Router::new()
+            .route(
+                "/livez",
+                get(move || {
+                    let state = availability.state();
+                    async move { Self::respond(state.is_live(), state) }
+                }),
+            )
+            .route(
+                "/readyz",
+                get(move || {
+                    let state = readiness.state();
+                    async move { Self::respond(state.is_ready(), state) }
+                }),
+            )
+    }
+
+    fn respond(ok: bool, state: impl serde::Serialize) -> impl IntoResponse {
+        let code = match ok {
+            true => StatusCode::OK,
+            false => StatusCode::SERVICE_UNAVAILABLE,
+        };
+        (code, Json(json!({ "status": state })))
+    }
+}
@@ -1,38 +1,120 @@
 use flume::{Receiver, Sender};
-use tokio::sync::Mutex;
-use std::collections::HashMap;
+use futures::FutureExt;
 use next_web_core::{
-    async_trait, common::key::Key, traits::event::{
+    async_trait,
+    common::key::Key,
+    error::BoxError,
+    traits::event::{
         application_event::ApplicationEvent,
+        application_event_error_handler::ApplicationEventErrorHandler,
         application_event_multicaster::ApplicationEventMulticaster,
-        application_listener::ApplicationListener,
-    }
+        application_listener::{ApplicationListener, ListenerMode, TransactionPhase},
+    },
 };
+use std::any::{Any, TypeId};
+use std::borrow::Cow;
+use std::panic::AssertUnwindSafe;
 use std::sync::Arc;
+use tokio::sync::{Mutex, RwLock};
+use tracing::error;
 #[cfg(feature = "trace-log")]
 use tracing::debug;
 
+/// 事件通道默认容量
+///
+/// Default capacity of the event channels, once it is reached publishing waits or fails,
+/// see [`DefaultApplicationEventPublisher`](super::default_application_event_publisher::DefaultApplicationEventPublisher)
+pub const DEFAULT_EVENT_CAPACITY: usize = 1024;
+
+pub(crate) type SharedEvent = Arc<Box<dyn ApplicationEvent>>;
+
+/// Picks the error handler with the lowest order among the named handlers.
+///
+/// Fails with the names of the handlers when several share the lowest order.
+pub(crate) fn select_error_handler(
+    mut handlers: Vec<(Cow<'static, str>, Box<dyn ApplicationEventErrorHandler>)>,
+) -> Result<Option<Box<dyn ApplicationEventErrorHandler>>, String> {
+    handlers.sort_by_key(|(_, handler)| handler.order());
+    let Some(lowest) = handlers.first().map(|(_, handler)| handler.order()) else {
+        return Ok(None);
+    };
+
+    let conflicts = handlers
+        .iter()
+        .filter(|(_, handler)| handler.order() == lowest)
+        .map(|(name, _)| format!("`{}`", name))
+        .collect::<Vec<_>>();
+    if conflicts.len() > 1 {
+        return Err(format!(
+            "{} application event error handlers share the order {}: {}, give them distinct orders",
+            conflicts.len(),
+            lowest,
+            conflicts.join(", ")
+        ));
+    }
+
+    Ok(handlers.into_iter().next().map(|(_, handler)| handler))
+}
+
+#[derive(Clone)]
+enum ListenerTarget {
+    Sync(Arc<Mutex<Box<dyn ApplicationListener>>>),
+    Async(Sender<SharedEvent>),
+}
+
+#[derive(Clone)]
+struct ListenerEntry {
+    key: Key,
+    order: i32,
+    phase: Option<TransactionPhase>,
+    target: ListenerTarget,
+}
+
+impl ListenerEntry {
+    fn matches(&self, id: &str, event_ids: &[TypeId], phase: Option<TransactionPhase>) -> bool {
+        self.phase == phase
+            && (self.key.id().is_empty() || id.is_empty() || self.key.id() == id)
+            && event_ids.contains(&self.key.tid())
+    }
+}
+
 /// 默认的事件多播器实现
 ///
 /// Default implementation of event multicaster
+///
+/// Every listener of the event type, or of one of its
+/// [`super_event_ids`](ApplicationEvent::super_event_ids), receives the event in
+/// [`order`](ApplicationListener::order) order. A panicking listener is
+/// reported to the error handler and keeps receiving the next events.
 #[derive(Clone)]
 pub struct DefaultApplicationEventMulticaster {
-    // 使用 TypeId 存储不同类型事件的监听器
-    // Use TypeId to store listeners for different event types
-    listeners: Arc<Mutex<HashMap<Key, Sender<Box<dyn ApplicationEvent>>>>>,
+    // 按顺序存储的监听器
+    // Listeners sorted by order
+    listeners: Arc<RwLock<Vec<ListenerEntry>>>,
 
     // 事件通道
     // Event channel
-    event_channel: Option<Receiver<(String, Box<dyn ApplicationEvent>)>>,
+    event_channel: Option<Receiver<(String, SharedEvent)>>,
+
+    error_handler: Option<Arc<dyn ApplicationEventErrorHandler>>,
+    capacity: usize,
 }
 
 impl DefaultApplicationEventMulticaster {
     /// 创建新的事件多播器实例
     /// Create a new event multicaster instance
     pub fn new() -> Self {
+        Self::with_capacity(DEFAULT_EVENT_CAPACITY)
+    }
+
+    /// 创建指定异步监听器队列容量的事件多播器
+    /// Create an event multicaster whose asynchronous listeners queue up to `capacity` events
+    pub fn with_capacity(capacity: usize) -> Self {
         DefaultApplicationEventMulticaster {
-            listeners: Arc::new(Mutex::new(HashMap::new())),
+            listeners: Arc::new(RwLock::new(Vec::new())),
             event_channel: None,
+            error_handler: None,
+            capacity: capacity.max(1),
         }
     }
 
@@ -40,64 +122,419 @@ impl DefaultApplicationEventMulticaster {
     /// Set event channel
     pub(crate) fn set_event_channel(
         &mut self,
-        channel: Receiver<(String, Box<dyn ApplicationEvent>)>,
+        channel: Receiver<(String, SharedEvent)>,
     ) {
         self.event_channel = Some(channel);
     }
 
+    /// 设置错误处理器
+    /// Set the handler of the listener panics, they are logged otherwise.
+    /// Must be set before the asynchronous listeners are added.
+    pub fn set_error_handler(&mut self, handler: Box<dyn ApplicationEventErrorHandler>) {
+        self.error_handler = Some(Arc::from(handler));
+    }
+
     /// 运行事件多播器
     /// Run event multicaster
     pub fn run(&self) {
         let channel = self.event_channel.clone().unwrap();
-        let listeners = self.listeners.clone();
+        let multicaster = self.clone();
         tokio::spawn(async move {
-            while let Ok(event) = channel.recv() {
-                if let Some(listeners) =
-                    listeners.lock().await.get(&Key::new(event.0, event.1.event_id()))
-                {
-                    let _ = listeners.send(event.1);
-                }
+            while let Ok((id, event)) = channel.recv_async().await {
+                let _ = multicaster.dispatch(&id, event, None).await;
             }
         });
     }
+
+    /// 分发事件给匹配的监听器
+    /// Dispatch the event to the matching listeners, returns once the synchronous ones are done.
+    /// Transactional event listeners are not invoked.
+    pub async fn multicast_event(&self, id: &str, event: Box<dyn ApplicationEvent>) {
+        let _ = self.dispatch(id, Arc::new(event), None).await;
+    }
+
+    /// 分发事务事件给指定阶段的监听器
+    /// Dispatch the event to the transactional event listeners of `phase`, fails when one of
+    /// the synchronous listeners panicked
+    pub(crate) async fn multicast_transactional_event(
+        &self,
+        id: &str,
+        event: SharedEvent,
+        phase: TransactionPhase,
+    ) -> Result<(), BoxError> {
+        self.dispatch(id, event, Some(phase)).await
+    }
+
+    async fn dispatch(
+        &self,
+        id: &str,
+        event: SharedEvent,
+        phase: Option<TransactionPhase>,
+    ) -> Result<(), BoxError> {
+        let mut event_ids = event.super_event_ids();
+        event_ids.push(event.event_id());
+        event_ids.push(TypeId::of::<dyn ApplicationEvent>());
+
+        let listeners = self
+            .listeners
+            .read()
+            .await
+            .iter()
+            .filter(|entry| entry.matches(id, &event_ids, phase))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        #[cfg(feature = "trace-log")]
+        debug!("Multicast event: {}, listeners: {}", event.source(), listeners.len());
+
+        let mut result = Ok(());
+        for entry in listeners {
+            match entry.target {
+                ListenerTarget::Sync(listener) => {
+                    let mut listener = listener.lock().await;
+                    if let Err(error) =
+                        invoke(listener.as_mut(), &event, self.error_handler.as_deref()).await
+                    {
+                        result = result.and(Err(error));
+                    }
+                }
+                ListenerTarget::Async(sender) => {
+                    // Waits while the queue of the listener is full
+                    let _ = sender.send_async(event.clone()).await;
+                }
+            }
+        }
+        result
+    }
+}
+
+impl Default for DefaultApplicationEventMulticaster {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 #[async_trait]
 impl ApplicationEventMulticaster for DefaultApplicationEventMulticaster {
     async fn add_application_listener(&mut self, mut listener: Box<dyn ApplicationListener>) {
-        let tid = listener.event_id();
-
-        let (sender, receiver) = flume::unbounded();
         let key = Key::new(listener.id(), listener.event_id());
-        let mut listeners = self.listeners.lock().await;
-        if listeners.contains_key(&key) {
-            panic!(
-                "Listener already exists for event type: {:?}, key: {}",
-                tid, key
-            );
-        }
-        if let None = listeners.insert(key, sender) {
-            tokio::spawn(async move {
-                while let Ok(event) = receiver.recv() {
-                    listener.on_application_event(&event).await;
-                    #[cfg(feature = "trace-log")]
-                    debug!("Received event: {:?}", event.source());
-                }
-            });
-            #[cfg(feature = "trace-log")]
-            {
-                let id = listener.id();
-                debug!("Added listener for event type: {:?}, id: {}", tid, id);
+        let order = listener.order();
+        let phase = listener.transaction_phase();
+
+        let target = match listener.mode() {
+            ListenerMode::Sync => ListenerTarget::Sync(Arc::new(Mutex::new(listener))),
+            ListenerMode::Async => {
+                let (sender, receiver) = flume::bounded::<SharedEvent>(self.capacity);
+                let error_handler = self.error_handler.clone();
+                tokio::spawn(async move {
+                    while let Ok(event) = receiver.recv_async().await {
+                        let _ = invoke(listener.as_mut(), &event, error_handler.as_deref()).await;
+                    }
+                });
+                ListenerTarget::Async(sender)
             }
-        }
+        };
+
+        #[cfg(feature = "trace-log")]
+        debug!("Added listener for event type: {}", key);
+
+        let mut listeners = self.listeners.write().await;
+        // Listeners of the same order keep their registration order
+        let index = listeners.partition_point(|entry| entry.order <= order);
+        listeners.insert(
+            index,
+            ListenerEntry {
+                key,
+                order,
+                phase,
+                target,
+            },
+        );
     }
 
     async fn remove_application_listener(&mut self, key: &Key) {
-        // 使用监听器的唯一ID进行匹配删除
-        // Use listener's unique ID to match and remove
-        if let Some(_) = self.listeners.lock().await.remove(key) {
-            #[cfg(feature = "trace-log")]
-            debug!("Removed listener for event type: {}", key);
+        // 使用监听器的ID和事件类型进行匹配删除，异步监听器的任务随通道关闭而结束
+        // Use listener's ID and event type to match and remove, the task of an asynchronous
+        // listener ends with its channel
+        self.listeners.write().await.retain(|entry| &entry.key != key);
+
+        #[cfg(feature = "trace-log")]
+        debug!("Removed listener for event type: {}", key);
+    }
+}
+
+async fn invoke(
+    listener: &mut dyn ApplicationListener,
+    event: &Box<dyn ApplicationEvent>,
+    error_handler: Option<&dyn ApplicationEventErrorHandler>,
+) -> Result<(), BoxError> {
+    if !listener.supports_event(event.as_ref()) {
+        return Ok(());
+    }
+
+    let id = listener.id();
+    if let Err(panic) = AssertUnwindSafe(listener.on_application_event(event))
+        .catch_unwind()
+        .await
+    {
+        let message = panic_message(panic);
+        match error_handler {
+            Some(handler) => handler.handle_error(id, event.as_ref(), &message),
+            None => error!(
+                "Application listener {:?} failed to handle event {}: {}",
+                id,
+                event.source(),
+                message
+            ),
+        }
+        return Err(format!("Application listener {:?} failed: {}", id, message).into());
+    }
+    Ok(())
+}
+
+fn panic_message(panic: Box<dyn Any + Send>) -> String {
+    match panic.downcast::<String>() {
+        Ok(message) => *message,
+        Err(panic) => match panic.downcast::<&'static str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => String::from("panicked"),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex as StdMutex;
+    use std::time::Duration;
+
+    use next_web_core::traits::ordered::Ordered;
+
+    use super::*;
+
+    struct SecurityEvent;
+
+    #[derive(Clone)]
+    struct LoginEvent(&'static str);
+
+    impl ApplicationEvent for LoginEvent {
+        fn super_event_ids(&self) -> Vec<TypeId> {
+            vec![TypeId::of::<SecurityEvent>()]
+        }
+    }
+
+    #[derive(Clone)]
+    struct Recorder {
+        name: &'static str,
+        order: i32,
+        event_id: TypeId,
+        mode: ListenerMode,
+        phase: Option<TransactionPhase>,
+        calls: Arc<StdMutex<Vec<String>>>,
+    }
+
+    impl Recorder {
+        fn new(name: &'static str, order: i32, calls: &Arc<StdMutex<Vec<String>>>) -> Self {
+            Self {
+                name,
+                order,
+                event_id: TypeId::of::<LoginEvent>(),
+                mode: ListenerMode::Sync,
+                phase: None,
+                calls: calls.clone(),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl ApplicationListener for Recorder {
+        fn order(&self) -> i32 {
+            self.order
+        }
+
+        fn id(&self) -> &'static str {
+            ""
+        }
+
+        fn event_id(&self) -> TypeId {
+            self.event_id
+        }
+
+        fn supports_event(&self, event: &dyn ApplicationEvent) -> bool {
+            let event = (event as &dyn Any).downcast_ref::<LoginEvent>().unwrap();
+            event.0 != "ignored"
+        }
+
+        fn mode(&self) -> ListenerMode {
+            self.mode
+        }
+
+        fn transaction_phase(&self) -> Option<TransactionPhase> {
+            self.phase
+        }
+
+        async fn on_application_event(&mut self, event: &Box<dyn ApplicationEvent>) {
+            let event = (event.as_ref() as &dyn Any).downcast_ref::<LoginEvent>().unwrap();
+            if event.0 == "panic" && self.name == "first" {
+                panic!("listener failed");
+            }
+            self.calls
+                .lock()
+                .unwrap()
+                .push(format!("{}:{}", self.name, event.0));
+        }
+    }
+
+    #[derive(Clone)]
+    struct CollectingErrorHandler(Arc<StdMutex<Vec<String>>>);
+
+    impl Ordered for CollectingErrorHandler {
+        fn order(&self) -> i32 {
+            0
+        }
+    }
+
+    impl ApplicationEventErrorHandler for CollectingErrorHandler {
+        fn handle_error(&self, _listener: &str, _event: &dyn ApplicationEvent, error: &str) {
+            self.0.lock().unwrap().push(error.to_string());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_ordered_fan_out() {
+        let calls = Arc::new(StdMutex::new(Vec::new()));
+        let mut multicaster = DefaultApplicationEventMulticaster::new();
+
+        let mut marker = Recorder::new("marker", 0, &calls);
+        marker.event_id = TypeId::of::<SecurityEvent>();
+        multicaster.add_application_listener(Box::new(Recorder::new("second", 10, &calls))).await;
+        multicaster.add_application_listener(Box::new(marker)).await;
+        multicaster.add_application_listener(Box::new(Recorder::new("first", -10, &calls))).await;
+
+        multicaster.multicast_event("", Box::new(LoginEvent("alice"))).await;
+        multicaster.multicast_event("", Box::new(LoginEvent("ignored"))).await;
+
+        assert_eq!(
+            *calls.lock().unwrap(),
+            vec!["first:alice", "marker:alice", "second:alice"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_panicking_listener() {
+        let calls = Arc::new(StdMutex::new(Vec::new()));
+        let errors = Arc::new(StdMutex::new(Vec::new()));
+        let mut multicaster = DefaultApplicationEventMulticaster::new();
+        multicaster.set_error_handler(Box::new(CollectingErrorHandler(errors.clone())));
+
+        multicaster.add_application_listener(Box::new(Recorder::new("first", 0, &calls))).await;
+        multicaster.add_application_listener(Box::new(Recorder::new("second", 1, &calls))).await;
+
+        multicaster.multicast_event("", Box::new(LoginEvent("panic"))).await;
+        multicaster.multicast_event("", Box::new(LoginEvent("bob"))).await;
+
+        assert_eq!(*errors.lock().unwrap(), vec!["listener failed"]);
+        assert_eq!(
+            *calls.lock().unwrap(),
+            vec!["second:panic", "first:bob", "second:bob"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_transactional_listener() {
+        use next_web_core::traits::event::application_event_publisher::ApplicationEventPublisher;
+        use next_web_core::transaction::transaction_synchronization_manager::TransactionSynchronizationManager;
+
+        use crate::event::default_application_event_publisher::DefaultApplicationEventPublisher;
+
+        let calls = Arc::new(StdMutex::new(Vec::new()));
+        let mut multicaster = DefaultApplicationEventMulticaster::new();
+        for (name, phase) in [
+            ("before_commit", TransactionPhase::BeforeCommit),
+            ("after_commit", TransactionPhase::AfterCommit),
+            ("after_rollback", TransactionPhase::AfterRollback),
+        ] {
+            let mut listener = Recorder::new(name, 0, &calls);
+            listener.phase = Some(phase);
+            multicaster.add_application_listener(Box::new(listener)).await;
+        }
+
+        let mut publisher = DefaultApplicationEventPublisher::new();
+        publisher.set_multicaster(multicaster.clone());
+
+        // Not received outside of a transaction
+        publisher.publish_event("", LoginEvent("alice")).unwrap();
+        let (_, synchronizations) = TransactionSynchronizationManager::scope(async {
+            publisher.publish_event("", LoginEvent("bob")).unwrap();
+        })
+        .await;
+        assert!(calls.lock().unwrap().is_empty());
+
+        synchronizations.before_commit().await.unwrap();
+        synchronizations.after_commit().await;
+        assert_eq!(
+            *calls.lock().unwrap(),
+            vec!["before_commit:bob", "after_commit:bob"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_async_listener() {
+        let calls = Arc::new(StdMutex::new(Vec::new()));
+        let mut multicaster = DefaultApplicationEventMulticaster::with_capacity(1);
+
+        let mut listener = Recorder::new("async", 0, &calls);
+        listener.mode = ListenerMode::Async;
+        multicaster.add_application_listener(Box::new(listener)).await;
+
+        for name in ["a", "b", "c"] {
+            multicaster.multicast_event("", Box::new(LoginEvent(name))).await;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        assert_eq!(*calls.lock().unwrap(), vec!["async:a", "async:b", "async:c"]);
+
+        multicaster
+            .remove_application_listener(&Key::new("", TypeId::of::<LoginEvent>()))
+            .await;
+        assert!(multicaster.listeners.read().await.is_empty());
+    }
+
+    #[derive(Clone)]
+    struct OrderedErrorHandler(i32);
+
+    impl Ordered for OrderedErrorHandler {
+        fn order(&self) -> i32 {
+            self.0
+        }
+    }
+
+    impl ApplicationEventErrorHandler for OrderedErrorHandler {
+        fn handle_error(&self, _listener: &str, _event: &dyn ApplicationEvent, _error: &str) {}
+    }
+
+    #[test]
+    fn test_select_error_handler() {
+        let handler = |order| -> Box<dyn ApplicationEventErrorHandler> {
+            Box::new(OrderedErrorHandler(order))
         };
+
+        assert!(select_error_handler(Vec::new()).unwrap().is_none());
+
+        let selected = select_error_handler(vec![
+            ("late".into(), handler(2)),
+            ("early".into(), handler(1)),
+        ])
+        .unwrap()
+        .unwrap();
+        assert_eq!(selected.order(), 1);
+
+        let error = select_error_handler(vec![
+            ("a".into(), handler(1)),
+            ("b".into(), handler(1)),
+            ("c".into(), handler(2)),
+        ])
+        .err()
+        .unwrap();
+        assert!(error.contains("`a`") && error.contains("`b`") && !error.contains("`c`"));
     }
 }
@@ -1,41 +1,131 @@
-use flume::Sender;
+use std::sync::Arc;
+
+use flume::{Sender, TrySendError};
 use next_web_core::{
+    async_trait,
     error::BoxError,
-    traits::event::{
-        application_event::ApplicationEvent, application_event_publisher::ApplicationEventPublisher,
+    traits::{
+        event::{
+            application_event::ApplicationEvent,
+            application_event_publisher::ApplicationEventPublisher,
+            application_listener::TransactionPhase,
+        },
+        transaction::transaction_synchronization::TransactionSynchronization,
     },
+    transaction::transaction_synchronization_manager::TransactionSynchronizationManager,
+};
+
+use super::default_application_event_multicaster::{
+    DefaultApplicationEventMulticaster, SharedEvent,
 };
 
+/// 默认的事件发布者实现
+///
+/// Default implementation of event publisher
+///
+/// An event published inside a transaction is also given to the transactional event
+/// listeners, in the phase they listen to, once the transaction completes.
 #[derive(Clone)]
 pub struct DefaultApplicationEventPublisher {
-    channel: Option<Sender<(String, Box<dyn ApplicationEvent>)>>,
+    channel: Option<Sender<(String, SharedEvent)>>,
+    multicaster: Option<DefaultApplicationEventMulticaster>,
 }
 
 impl DefaultApplicationEventPublisher {
     pub fn new() -> Self {
-        Self { channel: None }
+        Self {
+            channel: None,
+            multicaster: None,
+        }
     }
 
-    pub(crate) fn set_channel(
-        &mut self,
-        channel: Option<Sender<(String, Box<dyn ApplicationEvent>)>>,
-    ) {
+    pub(crate) fn set_channel(&mut self, channel: Option<Sender<(String, SharedEvent)>>) {
         self.channel = channel;
     }
+
+    pub(crate) fn set_multicaster(&mut self, multicaster: DefaultApplicationEventMulticaster) {
+        self.multicaster = Some(multicaster);
+    }
+
+    /// 发布事件，事件通道已满时等待
+    /// Publish event, waits while the event channel is full
+    pub async fn publish_event_async(
+        &self,
+        id: impl ToString,
+        event: impl ApplicationEvent,
+    ) -> Result<(), BoxError> {
+        let id = id.to_string();
+        let event: SharedEvent = Arc::new(Box::new(event));
+        self.register_transactional_event(&id, &event);
+
+        match self.channel.as_ref() {
+            Some(channel) => channel
+                .send_async((id, event))
+                .await
+                .map_err(|_| "The application event channel is closed".into()),
+            None => Ok(()),
+        }
+    }
+
+    fn register_transactional_event(&self, id: &str, event: &SharedEvent) {
+        if let Some(multicaster) = self.multicaster.as_ref() {
+            TransactionSynchronizationManager::register(Arc::new(TransactionalEventSynchronization {
+                id: id.to_string(),
+                event: event.clone(),
+                multicaster: multicaster.clone(),
+            }));
+        }
+    }
 }
 
 impl ApplicationEventPublisher for DefaultApplicationEventPublisher {
-    /// 发布事件
-    /// Publish event
+    /// 发布事件，事件通道已满时返回错误
+    /// Publish event, fails when the event channel is full, see [`publish_event_async`](Self::publish_event_async)
     fn publish_event(
         &self,
         id: impl ToString,
         event: impl ApplicationEvent,
     ) -> Result<(), BoxError> {
-        let event = Box::new(event);
+        let id = id.to_string();
+        let event: SharedEvent = Arc::new(Box::new(event));
+        self.register_transactional_event(&id, &event);
+
         match self.channel.as_ref() {
-            Some(channel) => channel.send((id.to_string(), event)).map_err(Into::into),
+            Some(channel) => channel.try_send((id, event)).map_err(|error| match error {
+                TrySendError::Full(_) => "The application event channel is full".into(),
+                TrySendError::Disconnected(_) => "The application event channel is closed".into(),
+            }),
             None => Ok(()),
         }
     }
 }
+
+/// Gives an event published inside a transaction to the transactional event listeners.
+struct TransactionalEventSynchronization {
+    id: String,
+    event: SharedEvent,
+    multicaster: DefaultApplicationEventMulticaster,
+}
+
+#[async_trait]
+impl TransactionSynchronization for TransactionalEventSynchronization {
+    async fn before_commit(&self) -> Result<(), BoxError> {
+        self.multicaster
+            .multicast_transactional_event(&self.id, self.event.clone(), TransactionPhase::BeforeCommit)
+            .await
+    }
+
+    async fn after_commit(&self) {
+        let _ = self
+            .multicaster
+            .multicast_transactional_event(&self.id, self.event.clone(), TransactionPhase::AfterCommit)
+            .await;
+    }
+
+    async fn after_rollback(&self) {
+        let _ = self
+            .multicaster
+            .multicast_transactional_event(&self.id, self.event.clone(), TransactionPhase::AfterRollback)
+            .await;
+    }
+}
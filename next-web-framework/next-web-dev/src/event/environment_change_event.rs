@@ -0,0 +1,34 @@
+use next_web_core::traits::event::application_event::ApplicationEvent;
+
+/// 配置变更事件
+///
+/// Published after the application properties have been refreshed,
+/// carries the dotted keys whose values were added, removed or changed.
+#[derive(Debug, Clone)]
+pub struct EnvironmentChangeEvent {
+    keys: Vec<String>,
+}
+
+impl EnvironmentChangeEvent {
+    pub fn new(keys: Vec<String>) -> Self {
+        Self { keys }
+    }
+
+    /// 获取变更的配置键
+    ///
+    /// Get the changed keys
+    pub fn keys(&self) -> &[String] {
+        &self.keys
+    }
+
+    /// 判断是否有以指定前缀开头的配置键发生变更
+    ///
+    /// Whether any key under the given prefix has changed
+    pub fn contains_prefix(&self, prefix: &str) -> bool {
+        self.keys
+            .iter()
+            .any(|key| key == prefix || key.starts_with(&format!("{}.", prefix)))
+    }
+}
+
+impl ApplicationEvent for EnvironmentChangeEvent {}
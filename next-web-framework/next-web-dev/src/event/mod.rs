@@ -1,3 +1,5 @@
 pub mod default_application_event_publisher;
 pub mod default_application_event_multicaster;
-
+pub mod environment_change_event;
+pub mod outbox_event;
+pub mod outbox_relay;
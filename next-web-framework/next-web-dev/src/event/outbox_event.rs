@@ -0,0 +1,68 @@
+use next_web_core::{
+    async_trait,
+    error::BoxError,
+    event::outbox_message::OutboxMessage,
+    traits::event::{application_event::ApplicationEvent, outbox_publisher::OutboxPublisher},
+};
+use serde::de::DeserializeOwned;
+
+use super::default_application_event_publisher::DefaultApplicationEventPublisher;
+
+/// 发件箱事件
+///
+/// Published to the in-process listeners for the outbox messages of the `local:<target>`
+/// destinations, with `<target>` as event ID.
+#[derive(Debug, Clone)]
+pub struct OutboxEvent {
+    message: OutboxMessage,
+}
+
+impl OutboxEvent {
+    pub fn new(message: OutboxMessage) -> Self {
+        Self { message }
+    }
+
+    pub fn message(&self) -> &OutboxMessage {
+        &self.message
+    }
+
+    pub fn event_type(&self) -> &str {
+        &self.message.event_type
+    }
+
+    /// 反序列化消息内容
+    ///
+    /// Deserialize the JSON payload of the message
+    pub fn payload<T: DeserializeOwned>(&self) -> Result<T, serde_json::Error> {
+        serde_json::from_str(&self.message.payload)
+    }
+}
+
+impl ApplicationEvent for OutboxEvent {}
+
+/// 本地发件箱消息发布者
+///
+/// Publishes the outbox messages of the `local` destinations as [`OutboxEvent`]s
+#[derive(Clone)]
+pub struct LocalOutboxPublisher {
+    publisher: DefaultApplicationEventPublisher,
+}
+
+impl LocalOutboxPublisher {
+    pub fn new(publisher: DefaultApplicationEventPublisher) -> Self {
+        Self { publisher }
+    }
+}
+
+#[async_trait]
+impl OutboxPublisher for LocalOutboxPublisher {
+    fn scheme(&self) -> &'static str {
+        "local"
+    }
+
+    async fn publish(&self, message: &OutboxMessage) -> Result<(), BoxError> {
+        self.publisher
+            .publish_event_async(message.target(), OutboxEvent::new(message.clone()))
+            .await
+    }
+}
@@ -0,0 +1,361 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use next_web_core::{
+    error::BoxError,
+    traits::event::{outbox_publisher::OutboxPublisher, outbox_store::OutboxStore},
+};
+use tracing::{error, warn};
+
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(5);
+const DEFAULT_BATCH_SIZE: usize = 100;
+const DEFAULT_MAX_ATTEMPTS: i32 = 10;
+const DEFAULT_INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const DEFAULT_MAX_BACKOFF: Duration = Duration::from_secs(600);
+
+/// 一批消息的中继结果
+///
+/// Outcome of relaying a batch of messages
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RelayBatch {
+    /// Messages read from the store
+    pub read: usize,
+    /// Messages published, rescheduled or moved to dead letter, they left the pending messages
+    pub settled: usize,
+}
+
+/// 发件箱中继
+///
+/// Outbox relay
+///
+/// Publishes the committed messages of an outbox store with the publisher of their
+/// destination scheme. A message is marked as published once its publisher succeeded, a
+/// failed one is retried with an exponential backoff and marked as dead once it failed
+/// `max_attempts` times. A message can be delivered more than once but is never lost.
+#[derive(Clone)]
+pub struct OutboxRelay {
+    store: Arc<dyn OutboxStore>,
+    publishers: Arc<HashMap<&'static str, Box<dyn OutboxPublisher>>>,
+    poll_interval: Duration,
+    batch_size: usize,
+    max_attempts: i32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+}
+
+impl OutboxRelay {
+    pub fn new(store: Box<dyn OutboxStore>, publishers: Vec<Box<dyn OutboxPublisher>>) -> Self {
+        let mut schemes = HashMap::new();
+        for publisher in publishers {
+            if schemes.contains_key(publisher.scheme()) {
+                warn!(
+                    "Outbox publisher of scheme {:?} is registered more than once, only the first one is used",
+                    publisher.scheme()
+                );
+                continue;
+            }
+            schemes.insert(publisher.scheme(), publisher);
+        }
+
+        Self {
+            store: Arc::from(store),
+            publishers: Arc::new(schemes),
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            batch_size: DEFAULT_BATCH_SIZE,
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            initial_backoff: DEFAULT_INITIAL_BACKOFF,
+            max_backoff: DEFAULT_MAX_BACKOFF,
+        }
+    }
+
+    /// Interval between two polls of the store when no commit is notified, 5 seconds by default.
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Messages read from the store at once, 100 by default.
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Attempts before a message is marked as dead, 10 by default.
+    pub fn with_max_attempts(mut self, max_attempts: i32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Delay before the first retry, doubled after every failed attempt up to `max_backoff`,
+    /// 1 second and 10 minutes by default.
+    pub fn with_backoff(mut self, initial_backoff: Duration, max_backoff: Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+        self.max_backoff = max_backoff.max(initial_backoff);
+        self
+    }
+
+    /// 启动中继
+    ///
+    /// Relay the messages in the background until the application stops
+    pub fn start(self) {
+        tokio::spawn(async move {
+            let notifier = self.store.notifier();
+            loop {
+                let notified = notifier.notified();
+                tokio::pin!(notified);
+                // Do not miss a commit notified while relaying
+                notified.as_mut().enable();
+
+                match self.relay_pending().await {
+                    // More messages may be waiting, go on as long as the batch made progress
+                    Ok(batch) if batch.read == self.batch_size && batch.settled > 0 => continue,
+                    Ok(_) => {}
+                    Err(error) => error!("Outbox relay failed to read pending messages: {}", error),
+                }
+
+                tokio::select! {
+                    _ = notified => {}
+                    _ = tokio::time::sleep(self.poll_interval) => {}
+                }
+            }
+        });
+    }
+
+    /// 发布一批未发布的消息
+    ///
+    /// Publish a batch of pending messages
+    pub async fn relay_pending(&self) -> Result<RelayBatch, BoxError> {
+        let messages = self.store.pending(self.batch_size).await?;
+        let mut batch = RelayBatch {
+            read: messages.len(),
+            settled: 0,
+        };
+        for message in messages.iter() {
+            let result = match self.publishers.get(message.scheme()) {
+                Some(publisher) => publisher.publish(message).await,
+                None => Err(format!("No outbox publisher for scheme {:?}", message.scheme()).into()),
+            };
+
+            let attempt = message.attempts.saturating_add(1);
+            let marked = match result {
+                Ok(()) => self.store.mark_published(&message.id).await,
+                Err(error) if attempt >= self.max_attempts => {
+                    error!(
+                        "Outbox message {} to {} failed {} times, moving it to dead letter: {}",
+                        message.id, message.destination, attempt, error
+                    );
+                    self.store.mark_dead(&message.id, &error.to_string()).await
+                }
+                Err(error) => {
+                    let backoff = self.backoff(attempt);
+                    warn!(
+                        "Outbox message {} to {} failed, attempt {}, retrying in {:?}: {}",
+                        message.id, message.destination, attempt, backoff, error
+                    );
+                    let retry_at = now_millis().saturating_add(backoff.as_millis() as i64);
+                    self.store
+                        .mark_failed(&message.id, &error.to_string(), retry_at)
+                        .await
+                }
+            };
+            match marked {
+                Ok(()) => batch.settled += 1,
+                Err(error) => error!("Outbox relay failed to mark message {}: {}", message.id, error),
+            }
+        }
+
+        Ok(batch)
+    }
+
+    /// Delay before the next attempt once `attempt` attempts failed.
+    fn backoff(&self, attempt: i32) -> Duration {
+        let exponent = attempt.saturating_sub(1).clamp(0, 31) as u32;
+        self.initial_backoff
+            .saturating_mul(2u32.saturating_pow(exponent))
+            .min(self.max_backoff)
+    }
+}
+
+fn now_millis() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use next_web_core::async_trait;
+    use next_web_core::event::outbox_message::OutboxMessage;
+    use tokio::sync::Notify;
+
+    use super::*;
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    enum Status {
+        Pending { retry_at: i64 },
+        Published,
+        Dead,
+    }
+
+    #[derive(Clone, Default)]
+    struct MemoryStore {
+        messages: Arc<Mutex<Vec<(OutboxMessage, Status)>>>,
+        notify: Arc<Notify>,
+    }
+
+    impl MemoryStore {
+        fn push(&self, id: &str, destination: &str) {
+            let message = OutboxMessage {
+                id: id.to_string(),
+                destination: destination.to_string(),
+                event_type: String::from("OrderCreated"),
+                payload: String::from("{}"),
+                created_at: 0,
+                attempts: 0,
+            };
+            self.messages
+                .lock()
+                .unwrap()
+                .push((message, Status::Pending { retry_at: 0 }));
+        }
+
+        fn with_status(&self, expected: fn(&Status) -> bool) -> Vec<String> {
+            let messages = self.messages.lock().unwrap();
+            messages
+                .iter()
+                .filter(|(_, status)| expected(status))
+                .map(|(message, _)| message.id.clone())
+                .collect()
+        }
+
+        /// Makes the messages waiting for a retry due now
+        fn expire_backoff(&self) {
+            for (_, status) in self.messages.lock().unwrap().iter_mut() {
+                if let Status::Pending { retry_at } = status {
+                    *retry_at = 0;
+                }
+            }
+        }
+
+        fn update(&self, id: &str, status: Status) {
+            let mut messages = self.messages.lock().unwrap();
+            for (message, current) in messages.iter_mut() {
+                if message.id == id {
+                    if status != Status::Published {
+                        message.attempts += 1;
+                    }
+                    *current = status;
+                }
+            }
+        }
+    }
+
+    #[async_trait]
+    impl OutboxStore for MemoryStore {
+        async fn pending(&self, limit: usize) -> Result<Vec<OutboxMessage>, BoxError> {
+            let now = now_millis();
+            let messages = self.messages.lock().unwrap();
+            Ok(messages
+                .iter()
+                .filter(|(_, status)| matches!(status, Status::Pending { retry_at } if *retry_at <= now))
+                .map(|(message, _)| message.clone())
+                .take(limit)
+                .collect())
+        }
+
+        async fn mark_published(&self, id: &str) -> Result<(), BoxError> {
+            self.update(id, Status::Published);
+            Ok(())
+        }
+
+        async fn mark_failed(&self, id: &str, _error: &str, retry_at: i64) -> Result<(), BoxError> {
+            self.update(id, Status::Pending { retry_at });
+            Ok(())
+        }
+
+        async fn mark_dead(&self, id: &str, _error: &str) -> Result<(), BoxError> {
+            self.update(id, Status::Dead);
+            Ok(())
+        }
+
+        fn notifier(&self) -> Arc<Notify> {
+            self.notify.clone()
+        }
+    }
+
+    #[derive(Clone)]
+    struct FlakyPublisher(Arc<Mutex<usize>>);
+
+    #[async_trait]
+    impl OutboxPublisher for FlakyPublisher {
+        fn scheme(&self) -> &'static str {
+            "mqtt"
+        }
+
+        async fn publish(&self, _message: &OutboxMessage) -> Result<(), BoxError> {
+            let mut calls = self.0.lock().unwrap();
+            *calls += 1;
+            match *calls {
+                1 => Err("broker unavailable".into()),
+                _ => Ok(()),
+            }
+        }
+    }
+
+    fn published(status: &Status) -> bool {
+        *status == Status::Published
+    }
+
+    fn dead(status: &Status) -> bool {
+        *status == Status::Dead
+    }
+
+    #[tokio::test]
+    async fn test_relay_pending() {
+        let store = MemoryStore::default();
+        store.push("1", "mqtt:orders/created");
+        store.push("2", "kafka:orders");
+
+        let relay = OutboxRelay::new(
+            Box::new(store.clone()),
+            vec![Box::new(FlakyPublisher(Arc::new(Mutex::new(0))))],
+        )
+        .with_max_attempts(3);
+
+        // The broker is down and there is no publisher for kafka
+        let batch = relay.relay_pending().await.unwrap();
+        assert_eq!(batch, RelayBatch { read: 2, settled: 2 });
+        assert!(store.with_status(published).is_empty());
+
+        // Both wait for their backoff
+        assert_eq!(relay.relay_pending().await.unwrap().read, 0);
+
+        // Delivered on the next attempt
+        store.expire_backoff();
+        assert_eq!(relay.relay_pending().await.unwrap().read, 2);
+        assert_eq!(store.with_status(published), vec!["1"]);
+
+        // Out of attempts
+        store.expire_backoff();
+        assert_eq!(store.pending(10).await.unwrap()[0].attempts, 2);
+        relay.relay_pending().await.unwrap();
+        assert_eq!(store.with_status(dead), vec!["2"]);
+        assert!(store.pending(10).await.unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_backoff() {
+        let relay = OutboxRelay::new(Box::new(MemoryStore::default()), Vec::new())
+            .with_backoff(Duration::from_secs(1), Duration::from_secs(5));
+
+        assert_eq!(relay.backoff(1), Duration::from_secs(1));
+        assert_eq!(relay.backoff(2), Duration::from_secs(2));
+        assert_eq!(relay.backoff(3), Duration::from_secs(4));
+        assert_eq!(relay.backoff(4), Duration::from_secs(5));
+        assert_eq!(relay.backoff(i32::MAX), Duration::from_secs(5));
+    }
+}
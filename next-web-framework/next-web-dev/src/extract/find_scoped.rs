@@ -0,0 +1,79 @@
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+
+use axum::{
+    extract::FromRequestParts,
+    http::{request::Parts, StatusCode},
+};
+use next_web_core::{
+    context::scope::{RequestScope, Scope},
+    state::application_state::ApplicationState,
+    util::singleton::SingletonUtil,
+};
+
+/// Take the instance of `T` of the scope `S` the request is in, [`RequestScope`] by default.
+///
+/// `T` is declared with `#[Transient]`, `S` is a singleton registered under its default name,
+/// e.g. `FindScoped<ShoppingCart, SessionScope>`. Rejects with `400` if the request is outside
+/// of the scope, e.g. without a valid session.
+pub struct FindScoped<T, S = RequestScope>(pub T, PhantomData<fn() -> S>);
+
+impl<T, S> FindScoped<T, S> {
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: Clone, S> Clone for FindScoped<T, S> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone(), PhantomData)
+    }
+}
+
+impl<T, S> Deref for FindScoped<T, S> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T, S> DerefMut for FindScoped<T, S> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<St, T, S> FromRequestParts<St> for FindScoped<T, S>
+where
+    St: Send + Sync,
+    T: Send + Sync + Clone + 'static,
+    S: Scope + Clone + 'static,
+{
+    type Rejection = (StatusCode, &'static str);
+
+    async fn from_request_parts(req: &mut Parts, _state: &St) -> Result<Self, Self::Rejection> {
+        let Some(state) = req.extensions.get::<ApplicationState>().cloned() else {
+            return Err((StatusCode::INTERNAL_SERVER_ERROR, "Internal Server Error"));
+        };
+
+        let scope = state
+            .context()
+            .read()
+            .await
+            .get_single_option_with_name::<S>(SingletonUtil::name::<S>())
+            .cloned();
+        let Some(scope) = scope else {
+            return Err((StatusCode::INTERNAL_SERVER_ERROR, "Internal Server Error"));
+        };
+
+        let Some(beans) = scope.beans(req).await else {
+            return Err((StatusCode::BAD_REQUEST, "Bad Request"));
+        };
+
+        match beans.get_or_create::<T>(state.context()).await {
+            Some(instance) => Ok(Self(instance, PhantomData)),
+            None => Err((StatusCode::INTERNAL_SERVER_ERROR, "Internal Server Error")),
+        }
+    }
+}
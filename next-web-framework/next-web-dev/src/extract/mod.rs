@@ -1,4 +1,5 @@
 pub mod data;
+pub mod find_scoped;
 pub mod find_singleton;
 pub(crate) mod required_header;
 pub mod typed_header;
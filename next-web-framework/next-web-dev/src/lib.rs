@@ -1,6 +1,8 @@
+pub mod actuator;
 pub mod application;
 pub mod autoconfigure;
 pub mod autoregister;
+pub mod availability;
 pub mod banner;
 pub mod common;
 pub mod configurer;
@@ -13,6 +15,7 @@ pub mod interceptor;
 pub mod macros;
 pub mod manager;
 pub mod middleware;
+pub mod refresh;
 pub mod service;
 
 pub mod stream;
@@ -36,6 +39,7 @@ pub use next_web_macros::{
     Properties
 };
 
+pub use next_web_macros::ApiSchema;
 pub use next_web_macros::Desensitized;
 pub use next_web_macros::{Builder, FieldName, GetSet, RequiredArgsConstructor};
 
@@ -46,7 +50,7 @@ pub mod state_machine;
 pub use next_web_macros::Scheduled;
 
 #[cfg(feature = "enable-web-security")]
-pub use next_web_macros::PreAuthorize;
+pub use next_web_macros::{PostAuthorize, PreAuthorize, Secured};
 #[cfg(feature = "enable-web-security")]
 pub use next_web_security as security;
 
@@ -58,6 +62,12 @@ pub use next_web_retry as retry;
 #[cfg(feature = "enable-i18n")]
 pub mod i18n;
 
+#[cfg(feature = "enable-openapi")]
+pub mod api_docs;
+
+#[cfg(feature = "tls-rustls")]
+pub mod tls;
+
 #[cfg(target_os = "windows")]
 #[global_allocator]
 static GLOBAL: mimalloc::MiMalloc = mimalloc::MiMalloc;
@@ -1,225 +1,215 @@
-use std::{collections::HashSet, sync::Arc};
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
 
 use next_web_core::{
-    scheduler::{context::JobExecutionContext, schedule_type::ScheduleType},
-    traits::singleton::Singleton,
-    util::time::TimeUnit,
+    scheduler::{
+        context::JobExecutionContext, in_memory_lock_provider::InMemoryLockProvider,
+        schedule_type::ScheduleType,
+    },
+    traits::{schedule::scheduler_lock_provider::SchedulerLockProvider, singleton::Singleton},
 };
 use tokio::sync::RwLock;
-use tokio_cron_scheduler::{Job, JobScheduler, JobSchedulerError};
 use tracing::warn;
+use uuid::Uuid;
 
+use super::scheduled_job::{JobExecution, ScheduledJob, SchedulerError};
 use crate::autoregister::scheduler_autoregister::AnJob;
 
+pub use super::scheduled_job::ScheduledJobInfo;
+
+/// 定时任务管理器
+///
+/// Runs the jobs and manages them by their name: pause, resume, trigger, reschedule and
+/// the history of their executions.
 #[derive(Clone)]
 pub struct JobSchedulerManager {
-    ids: Arc<RwLock<HashSet<u128>>>,
-    scheduler: JobScheduler,
+    jobs: Arc<RwLock<HashMap<String, Arc<ScheduledJob>>>>,
     context: JobExecutionContext,
+    lock_provider: Arc<dyn SchedulerLockProvider>,
+    started: Arc<AtomicBool>,
 }
 
 impl Singleton for JobSchedulerManager {}
 
 impl JobSchedulerManager {
-    pub async fn with_channel_size(size: usize) -> Self {
+    pub fn new() -> Self {
         Self {
-            ids: Arc::new(RwLock::new(HashSet::new())),
+            jobs: Arc::new(RwLock::new(HashMap::new())),
             context: JobExecutionContext::default(),
-            scheduler: JobScheduler::new_with_channel_size(size).await.unwrap(),
+            lock_provider: Arc::new(InMemoryLockProvider::new()),
+            started: Arc::new(AtomicBool::new(false)),
         }
     }
 
-    pub async fn add(&self, job: AnJob) -> Result<(), JobSchedulerError> {
-        let job = Self::pack(job)?;
-        let uid = self.scheduler.add(job).await?;
-        self.ids.write().await.insert(uid.as_u128());
+    #[deprecated(note = "jobs are no longer dispatched through a channel, use `JobSchedulerManager::new`")]
+    pub async fn with_channel_size(_size: usize) -> Self {
+        Self::new()
+    }
+
+    /// Set the provider of the locks of jobs with `lock_at_most_for`, must be set before
+    /// the jobs are added.
+    pub fn set_lock_provider(&mut self, provider: Box<dyn SchedulerLockProvider>) {
+        self.lock_provider = Arc::from(provider);
+    }
 
-        Ok(())
+    pub fn context(&self) -> &JobExecutionContext {
+        &self.context
     }
 
-    // pub async fn add_job(&self, job: Arc<dyn ScheduledTask>) {
-    //     let jjb = Self::generate_job(job, self.context.clone());
-    //     if jjb.is_err() {
-    //         warn!(
-    //             "JobSchedulerManager failed to add job, error: {}",
-    //             jjb.err().unwrap()
-    //         );
-    //         return;
-    //     }
-    // }
+    /// Add a job without a name, it is managed by its id.
+    pub async fn add(&self, job: AnJob) -> Result<Uuid, SchedulerError> {
+        self.add_with_name("", job).await
+    }
+
+    /// Add a job and manage it under `name`, it starts right away when the manager is
+    /// already started.
+    pub async fn add_with_name(
+        &self,
+        name: impl Into<String>,
+        job: AnJob,
+    ) -> Result<Uuid, SchedulerError> {
+        let mut name = name.into();
+        if name.is_empty() {
+            name = Uuid::new_v4().to_string();
+        }
+
+        let mut jobs = self.jobs.write().await;
+        if jobs.contains_key(&name) {
+            return Err(SchedulerError::DuplicateJob(name));
+        }
+
+        let job = Arc::new(ScheduledJob::new(name.clone(), job, self.lock_provider.clone())?);
+        if self.started.load(Ordering::Acquire) {
+            job.start();
+        }
+        let id = job.id();
+        jobs.insert(name, job);
+
+        Ok(id)
+    }
 
     pub async fn remove(&self, guid: Vec<u8>) {
-        match guid.try_into() {
-            Ok(uid) => self.scheduler.remove(&uid).await.ok(),
-            Err(e) => {
-                warn!("JobSchedulerManager failed to remove job, error: {}", e);
-                None
+        match Uuid::from_slice(&guid) {
+            Ok(uid) => {
+                let mut jobs = self.jobs.write().await;
+                let name = jobs
+                    .values()
+                    .find(|job| job.id() == uid)
+                    .map(|job| job.name().to_string());
+                if let Some(job) = name.and_then(|name| jobs.remove(&name)) {
+                    job.stop();
+                }
             }
+            Err(e) => warn!("JobSchedulerManager failed to remove job, error: {}", e),
         };
     }
 
+    /// Stop and remove the job, a running execution is not interrupted.
+    pub async fn remove_job(&self, name: &str) -> Result<(), SchedulerError> {
+        let job = self
+            .jobs
+            .write()
+            .await
+            .remove(name)
+            .ok_or_else(|| SchedulerError::JobNotFound(name.to_string()))?;
+        job.stop();
+        Ok(())
+    }
+
     pub async fn exists(&self, uid: u128) -> bool {
-        self.ids.read().await.contains(&uid)
+        self.jobs
+            .read()
+            .await
+            .values()
+            .any(|job| job.id().as_u128() == uid)
     }
 
     pub async fn count(&self) -> usize {
-        self.ids.read().await.len()
+        self.jobs.read().await.len()
+    }
+
+    /// All jobs currently managed.
+    pub async fn jobs(&self) -> Vec<ScheduledJobInfo> {
+        self.jobs.read().await.values().map(|job| job.info()).collect()
+    }
+
+    pub async fn job(&self, name: &str) -> Option<ScheduledJobInfo> {
+        self.jobs.read().await.get(name).map(|job| job.info())
+    }
+
+    /// The time the job will run next, `None` when it will not run anymore.
+    pub async fn next_tick(&self, id: Uuid) -> Option<chrono::DateTime<chrono::Utc>> {
+        self.jobs
+            .read()
+            .await
+            .values()
+            .find(|job| job.id() == id)
+            .and_then(|job| job.info().next_fire_time)
+    }
+
+    /// Stop running the job until it is resumed, a running execution is not interrupted.
+    pub async fn pause(&self, name: &str) -> Result<(), SchedulerError> {
+        self.get(name).await?.pause();
+        Ok(())
+    }
+
+    /// Resume a paused job, the fire times missed in the meantime are skipped.
+    pub async fn resume(&self, name: &str) -> Result<(), SchedulerError> {
+        self.get(name).await?.resume();
+        Ok(())
+    }
+
+    /// Run the job now, in the background, its overlap policy and lock still apply.
+    pub async fn trigger(&self, name: &str) -> Result<(), SchedulerError> {
+        self.get(name).await?.trigger();
+        Ok(())
+    }
+
+    /// Replace the schedule of the job, e.g. a new cron expression.
+    pub async fn reschedule(&self, name: &str, schedule: ScheduleType) -> Result<(), SchedulerError> {
+        self.get(name).await?.reschedule(schedule)
+    }
+
+    /// Last executions of the job, the most recent first.
+    pub async fn history(&self, name: &str) -> Result<Vec<JobExecution>, SchedulerError> {
+        Ok(self.get(name).await?.history())
     }
 
     pub async fn start(&mut self) {
-        // Add code to be run during/after shutdown
-        self.scheduler.set_shutdown_handler(Box::new(|| {
-            Box::pin(async move {
-                println!("Scheduler Done.");
-            })
-        }));
-
-        // Start the scheduler
-        self.scheduler.start().await.unwrap();
-    }
-
-    fn pack(job: AnJob) -> Result<Job, JobSchedulerError> {
-        match job {
-            AnJob::Async((ty, run)) => {
-                let job = match ty {
-                    ScheduleType::Cron(args) => {
-                        let schedule = args.cron.unwrap();
-                        match args.timezone {
-                            Some(timezone) => match schedule.to_lowercase().as_str() {
-                                "local" => Job::new_cron_job_async_tz(
-                                    schedule,
-                                    chrono::Local,
-                                    move |_uid, _lock| run(),
-                                )?,
-                                "utc" => Job::new_cron_job_async_tz(
-                                    schedule,
-                                    chrono::Utc,
-                                    move |_uid, _lock| run(),
-                                )?,
-                                _ => Job::new_cron_job_async_tz(
-                                    schedule,
-                                    timezone.parse::<chrono_tz::Tz>().unwrap_or(chrono_tz::UTC),
-                                    move |_uid, _lock| run(),
-                                )?,
-                            },
-                            None => Job::new_cron_job_async(schedule, move |_uid, _lock| run())?,
-                        }
-                    }
-                    ScheduleType::FixedRate(args) => {
-                        let value = args.fixed_rate.unwrap();
-
-                        let duration = args
-                            .time_unit
-                            .map(|unit| unit.parse::<TimeUnit>().unwrap_or(TimeUnit::Milliseconds))
-                            .map(|unit| unit.to_duration(value))
-                            .unwrap_or(std::time::Duration::from_millis(value));
-
-                        Job::new_repeated_async(duration, move |_uid, _lock| run())?
-                    }
-
-                    ScheduleType::OneShot(args) => {
-                        let value = args.initial_delay.unwrap();
-                        let duration = args
-                            .time_unit
-                            .map(|unit| unit.parse::<TimeUnit>().unwrap_or(TimeUnit::Milliseconds))
-                            .map(|unit| unit.to_duration(value))
-                            .unwrap_or(std::time::Duration::from_millis(value));
-                        Job::new_one_shot_async(duration, move |_uid, _lock| run())?
-                    }
-                };
-
-                Ok(job)
-            }
-            AnJob::Sync((ty, run)) => {
-                let job = match ty {
-                    ScheduleType::Cron(args) => {
-                        let schedule = args.cron.unwrap();
-                        match args.timezone {
-                            Some(timezone) => match schedule.to_lowercase().as_str() {
-                                "local" => {
-                                    Job::new_tz(schedule, chrono::Local, move |_uid, _lock| run())?
-                                }
-                                "utc" => {
-                                    Job::new_tz(schedule, chrono::Utc, move |_uid, _lock| run())?
-                                }
-                                _ => Job::new_tz(
-                                    schedule,
-                                    timezone.parse::<chrono_tz::Tz>().unwrap_or(chrono_tz::UTC),
-                                    move |_uid, _lock| run(),
-                                )?,
-                            },
-                            None => {
-                                Job::new_cron_job::<_, _, ()>(schedule, move |_uid, _lock| run())?
-                            }
-                        }
-                    }
-                    ScheduleType::FixedRate(args) => {
-                        let value = args.fixed_rate.unwrap();
-
-                        let duration = args
-                            .time_unit
-                            .map(|unit| unit.parse::<TimeUnit>().unwrap_or(TimeUnit::Milliseconds))
-                            .map(|unit| unit.to_duration(value))
-                            .unwrap_or(std::time::Duration::from_millis(value));
-
-                        Job::new_repeated(duration, move |_uid, _lock| run())?
-                    }
-
-                    ScheduleType::OneShot(args) => {
-                        let value = args.initial_delay.unwrap();
-                        let duration = args
-                            .time_unit
-                            .map(|unit| unit.parse::<TimeUnit>().unwrap_or(TimeUnit::Milliseconds))
-                            .map(|unit| unit.to_duration(value))
-                            .unwrap_or(std::time::Duration::from_millis(value));
-                        Job::new_one_shot(duration, move |_uid, _lock| run())?
-                    }
-                };
-
-                Ok(job)
-            }
+        if self.started.swap(true, Ordering::AcqRel) {
+            return;
+        }
+
+        for job in self.jobs.read().await.values() {
+            job.start();
+        }
+    }
+
+    /// Stop all jobs, running executions are not interrupted.
+    pub async fn shutdown(&self) {
+        self.started.store(false, Ordering::Release);
+        for job in self.jobs.read().await.values() {
+            job.stop();
         }
     }
 
-    // fn generate_job(
-    //     job: Arc<dyn ScheduledTask>,
-    //     context: JobExecutionContext,
-    // ) -> Result<Job, BoxError> {
-    //     let schedule = job.schedule();
-    //     let jjb = match schedule {
-    //         ScheduleType::Cron(cron) => Job::new_cron_job_async("cron", move |_uid, _lock| {
-    //             Box::pin({
-    //                 let var1 = job.clone();
-    //                 let var2 = context.clone();
-    //                 async move {
-    //                     var1.execute(var2).await;
-    //                 }
-    //             })
-    //         }),
-    //         ScheduleType::FixedRate(interval) => {
-    //             Job::new_repeated_async(std::time::Duration::from_secs(1000), move |_uid, _lock| {
-    //                 Box::pin({
-    //                     let var1 = job.clone();
-    //                     let var2 = context.clone();
-    //                     async move {
-    //                         var1.execute(var2).await;
-    //                     }
-    //                 })
-    //             })
-    //         }
-    //         ScheduleType::OneShot(with_args) => {
-    //             Job::new_one_shot_async(std::time::Duration::from_secs(1000), move |_uid, _lock| {
-    //                 Box::pin({
-    //                     let var1 = job.clone();
-    //                     let var2 = context.clone();
-    //                     async move {
-    //                         var1.execute(var2).await;
-    //                     }
-    //                 })
-    //             })
-    //         }
-    //     };
-
-    //     Ok(jjb?)
-    // }
+    async fn get(&self, name: &str) -> Result<Arc<ScheduledJob>, SchedulerError> {
+        self.jobs
+            .read()
+            .await
+            .get(name)
+            .cloned()
+            .ok_or_else(|| SchedulerError::JobNotFound(name.to_string()))
+    }
+}
+
+impl Default for JobSchedulerManager {
+    fn default() -> Self {
+        Self::new()
+    }
 }
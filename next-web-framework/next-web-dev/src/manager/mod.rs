@@ -1,2 +1,4 @@
 #[cfg(feature = "enable-scheduling")]
-pub mod job_scheduler_manager;
\ No newline at end of file
+pub mod job_scheduler_manager;
+#[cfg(feature = "enable-scheduling")]
+pub mod scheduled_job;
@@ -0,0 +1,769 @@
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Local, Utc};
+use next_web_core::scheduler::lock_configuration::LockConfiguration;
+use next_web_core::scheduler::schedule_type::{MisfirePolicy, OverlapPolicy, ScheduleType, WithArgs};
+use next_web_core::traits::schedule::scheduler_lock_provider::SchedulerLockProvider;
+use next_web_core::util::time::TimeUnit;
+use next_web_utils::cron::cron_util::{CronExpression, CronUtil};
+use thiserror::Error;
+use tokio::sync::Notify;
+use tokio::task::{JoinError, JoinHandle};
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use crate::autoregister::scheduler_autoregister::AnJob;
+
+/// Executions kept in the history of a job
+const HISTORY_SIZE: usize = 20;
+
+/// How late a job can run before it misfired, unless the job sets `misfire_threshold`
+const DEFAULT_MISFIRE_THRESHOLD: Duration = Duration::from_secs(5);
+
+/// The wall clock is checked at least this often, so that clock jumps are noticed
+const MAX_SLEEP: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Error)]
+pub enum SchedulerError {
+    #[error("invalid schedule of job {name}: {message}")]
+    InvalidSchedule { name: String, message: String },
+
+    #[error("job {0} already exists")]
+    DuplicateJob(String),
+
+    #[error("job {0} not found")]
+    JobNotFound(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JobStatus {
+    Scheduled,
+    Paused,
+    /// One shot job that already fired
+    Completed,
+}
+
+impl JobStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JobStatus::Scheduled => "scheduled",
+            JobStatus::Paused => "paused",
+            JobStatus::Completed => "completed",
+        }
+    }
+}
+
+/// Why the job ran.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JobTrigger {
+    Scheduled,
+    /// Ran late, see [`MisfirePolicy`]
+    Misfire,
+    /// Triggered through [`JobSchedulerManager::trigger`](super::job_scheduler_manager::JobSchedulerManager::trigger)
+    Manual,
+}
+
+impl JobTrigger {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JobTrigger::Scheduled => "scheduled",
+            JobTrigger::Misfire => "misfire",
+            JobTrigger::Manual => "manual",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JobOutcome {
+    Success,
+    /// The job panicked
+    Failed(String),
+    /// The job didn't run, e.g. the previous execution was still running
+    Skipped(String),
+}
+
+/// 任务执行记录
+///
+/// One execution of a job.
+#[derive(Debug, Clone)]
+pub struct JobExecution {
+    pub trigger: JobTrigger,
+    /// Time the job was due, `None` when it was triggered manually
+    pub fire_time: Option<DateTime<Utc>>,
+    pub started_at: DateTime<Utc>,
+    pub duration: Duration,
+    pub outcome: JobOutcome,
+}
+
+/// Description of a job added to the [`JobSchedulerManager`](super::job_scheduler_manager::JobSchedulerManager).
+#[derive(Debug, Clone)]
+pub struct ScheduledJobInfo {
+    pub id: Uuid,
+    pub name: String,
+    pub schedule: ScheduleType,
+    pub status: JobStatus,
+    /// `None` when the job is paused or will not run anymore
+    pub next_fire_time: Option<DateTime<Utc>>,
+    /// Executions currently running
+    pub running: usize,
+    pub last_execution: Option<JobExecution>,
+}
+
+type JobFuture = Arc<dyn Fn() -> Pin<Box<dyn Future<Output = ()> + Send>> + Send + Sync>;
+type JobFunction = Arc<dyn Fn() + Send + Sync>;
+
+enum JobRun {
+    Async(JobFuture),
+    /// Run on the blocking pool
+    Sync(JobFunction),
+}
+
+/// Next fire time of a job.
+#[derive(Debug, Clone, Copy)]
+enum NextFire {
+    /// Cron jobs follow the wall clock
+    At(DateTime<Utc>),
+    /// Fixed rate and one shot jobs follow the monotonic clock, they are not affected by clock jumps
+    After(Instant),
+}
+
+impl NextFire {
+    /// How late the job is, `None` when it is not due yet.
+    fn lateness(&self) -> Option<Duration> {
+        match self {
+            NextFire::At(time) => (Utc::now() - *time).to_std().ok(),
+            NextFire::After(instant) => Instant::now().checked_duration_since(*instant),
+        }
+    }
+
+    fn remaining(&self) -> Duration {
+        match self {
+            NextFire::At(time) => (*time - Utc::now()).to_std().unwrap_or_default(),
+            NextFire::After(instant) => instant.saturating_duration_since(Instant::now()),
+        }
+    }
+
+    fn to_utc(self) -> DateTime<Utc> {
+        match self {
+            NextFire::At(time) => time,
+            NextFire::After(instant) => {
+                let now = Instant::now();
+                match now.checked_duration_since(instant) {
+                    Some(late) => Utc::now() - late,
+                    None => Utc::now() + instant.saturating_duration_since(now),
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum CronZone {
+    Local,
+    Tz(chrono_tz::Tz),
+}
+
+/// [`ScheduleType`] ready to compute fire times.
+#[derive(Debug, Clone)]
+enum Schedule {
+    Cron { expression: CronExpression, zone: CronZone },
+    FixedRate { interval: Duration, initial_delay: Option<Duration> },
+    OneShot { delay: Duration },
+}
+
+impl Schedule {
+    fn new(schedule: &ScheduleType) -> Result<Self, String> {
+        match schedule {
+            ScheduleType::Cron(args) => {
+                let cron = args.cron.ok_or("cron is not set")?;
+                let expression = CronUtil::parse(cron).map_err(|error| error.to_string())?;
+                // UTC unless the job opts into the host time zone, so a job fires at the
+                // same instant on every instance
+                let zone = match args.timezone {
+                    None => CronZone::Tz(chrono_tz::UTC),
+                    Some(timezone) if timezone.eq_ignore_ascii_case("local") => CronZone::Local,
+                    Some(timezone) if timezone.eq_ignore_ascii_case("utc") => CronZone::Tz(chrono_tz::UTC),
+                    Some(timezone) => CronZone::Tz(
+                        timezone
+                            .parse::<chrono_tz::Tz>()
+                            .map_err(|error| error.to_string())?,
+                    ),
+                };
+                // Fails when the expression never fires, e.g. February 30th
+                let schedule = Schedule::Cron { expression, zone };
+                schedule.next_from_now().ok_or("the cron expression never fires")?;
+                Ok(schedule)
+            }
+            ScheduleType::FixedRate(args) => {
+                let interval = duration(args, args.fixed_rate.ok_or("fixed_rate is not set")?)?;
+                if interval.is_zero() {
+                    return Err(String::from("fixed_rate must be greater than 0"));
+                }
+                let initial_delay = args.initial_delay.map(|delay| duration(args, delay)).transpose()?;
+                Ok(Schedule::FixedRate { interval, initial_delay })
+            }
+            ScheduleType::OneShot(args) => Ok(Schedule::OneShot {
+                delay: duration(args, args.initial_delay.ok_or("initial_delay is not set")?)?,
+            }),
+        }
+    }
+
+    fn first(&self) -> Option<NextFire> {
+        match self {
+            Schedule::Cron { .. } => self.next_from_now(),
+            Schedule::FixedRate { interval, initial_delay } => {
+                Some(NextFire::After(Instant::now() + initial_delay.unwrap_or(*interval)))
+            }
+            Schedule::OneShot { delay } => Some(NextFire::After(Instant::now() + *delay)),
+        }
+    }
+
+    /// Fire time following `previous`.
+    fn next(&self, previous: NextFire) -> Option<NextFire> {
+        match (self, previous) {
+            (Schedule::Cron { .. }, NextFire::At(time)) => self.cron_after(time),
+            (Schedule::FixedRate { interval, .. }, NextFire::After(instant)) => {
+                Some(NextFire::After(instant + *interval))
+            }
+            (Schedule::OneShot { .. }, _) => None,
+            // The schedule changed since
+            _ => self.next_from_now(),
+        }
+    }
+
+    /// First fire time from now on, skipping the ones that were missed.
+    fn next_from_now(&self) -> Option<NextFire> {
+        match self {
+            Schedule::Cron { .. } => self.cron_after(Utc::now()),
+            Schedule::FixedRate { interval, .. } => Some(NextFire::After(Instant::now() + *interval)),
+            Schedule::OneShot { .. } => None,
+        }
+    }
+
+    fn cron_after(&self, time: DateTime<Utc>) -> Option<NextFire> {
+        let Schedule::Cron { expression, zone } = self else {
+            return None;
+        };
+        let next = match zone {
+            CronZone::Local => expression
+                .next_after(&time.with_timezone(&Local))
+                .map(|next| next.with_timezone(&Utc)),
+            CronZone::Tz(tz) => expression
+                .next_after(&time.with_timezone(tz))
+                .map(|next| next.with_timezone(&Utc)),
+        };
+        next.map(NextFire::At)
+    }
+}
+
+fn duration(args: &WithArgs, value: u64) -> Result<Duration, String> {
+    let unit = match args.time_unit {
+        Some(unit) => unit.parse::<TimeUnit>()?,
+        None => TimeUnit::Milliseconds,
+    };
+    Ok(unit.to_duration(value))
+}
+
+struct JobState {
+    schedule_type: ScheduleType,
+    schedule: Schedule,
+    status: JobStatus,
+    next: Option<NextFire>,
+}
+
+/// 定时任务
+///
+/// A job with its own driver task, which waits for the fire times and runs the job
+/// according to its overlap and misfire policies.
+pub(crate) struct ScheduledJob {
+    id: Uuid,
+    name: String,
+    run: JobRun,
+    state: Mutex<JobState>,
+    lock_provider: Arc<dyn SchedulerLockProvider>,
+    /// Wakes the driver when the state changed
+    notify: Notify,
+    driver: Mutex<Option<JoinHandle<()>>>,
+
+    running: AtomicUsize,
+    /// Held by the running execution unless the overlap policy is concurrent
+    serial: tokio::sync::Mutex<()>,
+    queued: AtomicBool,
+    history: Mutex<VecDeque<JobExecution>>,
+}
+
+impl ScheduledJob {
+    pub(crate) fn new(
+        name: String,
+        job: AnJob,
+        lock_provider: Arc<dyn SchedulerLockProvider>,
+    ) -> Result<Self, SchedulerError> {
+        let (schedule_type, run) = match job {
+            AnJob::Async((schedule_type, run)) => (schedule_type, JobRun::Async(Arc::from(run))),
+            AnJob::Sync((schedule_type, run)) => (schedule_type, JobRun::Sync(Arc::from(run))),
+        };
+        let schedule = Schedule::new(&schedule_type).map_err(|message| SchedulerError::InvalidSchedule {
+            name: name.clone(),
+            message,
+        })?;
+
+        Ok(Self {
+            id: Uuid::new_v4(),
+            name,
+            run,
+            state: Mutex::new(JobState {
+                schedule_type,
+                schedule,
+                status: JobStatus::Scheduled,
+                next: None,
+            }),
+            lock_provider,
+            notify: Notify::new(),
+            driver: Mutex::new(None),
+            running: AtomicUsize::new(0),
+            serial: tokio::sync::Mutex::new(()),
+            queued: AtomicBool::new(false),
+            history: Mutex::new(VecDeque::with_capacity(HISTORY_SIZE)),
+        })
+    }
+
+    pub(crate) fn id(&self) -> Uuid {
+        self.id
+    }
+
+    pub(crate) fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Compute the first fire time and spawn the driver.
+    pub(crate) fn start(self: &Arc<Self>) {
+        {
+            let mut state = self.state.lock().unwrap();
+            state.next = state.schedule.first();
+        }
+
+        let job = self.clone();
+        let driver = tokio::spawn(async move { job.drive().await });
+        if let Some(previous) = self.driver.lock().unwrap().replace(driver) {
+            previous.abort();
+        }
+    }
+
+    pub(crate) fn stop(&self) {
+        if let Some(driver) = self.driver.lock().unwrap().take() {
+            driver.abort();
+        }
+    }
+
+    pub(crate) fn pause(&self) {
+        let mut state = self.state.lock().unwrap();
+        if state.status == JobStatus::Scheduled {
+            state.status = JobStatus::Paused;
+        }
+    }
+
+    /// Resume the job, the fire times missed while it was paused are skipped.
+    pub(crate) fn resume(&self) {
+        let mut state = self.state.lock().unwrap();
+        if state.status != JobStatus::Paused {
+            return;
+        }
+        state.status = JobStatus::Scheduled;
+        // A one shot job keeps its fire time, it runs right away when it was missed
+        if let Some(next) = state.schedule.next_from_now() {
+            state.next = Some(next);
+        }
+        drop(state);
+        self.notify.notify_one();
+    }
+
+    pub(crate) fn reschedule(&self, schedule_type: ScheduleType) -> Result<(), SchedulerError> {
+        let schedule = Schedule::new(&schedule_type).map_err(|message| SchedulerError::InvalidSchedule {
+            name: self.name.clone(),
+            message,
+        })?;
+
+        let mut state = self.state.lock().unwrap();
+        state.next = schedule.first();
+        state.schedule = schedule;
+        state.schedule_type = schedule_type;
+        if state.status == JobStatus::Completed {
+            state.status = JobStatus::Scheduled;
+        }
+        drop(state);
+        self.notify.notify_one();
+        Ok(())
+    }
+
+    /// Run the job now, in the background.
+    pub(crate) fn trigger(self: &Arc<Self>) {
+        tokio::spawn(self.clone().execute(JobTrigger::Manual, None));
+    }
+
+    pub(crate) fn info(&self) -> ScheduledJobInfo {
+        let state = self.state.lock().unwrap();
+        ScheduledJobInfo {
+            id: self.id,
+            name: self.name.clone(),
+            schedule: state.schedule_type.clone(),
+            status: state.status,
+            next_fire_time: match state.status {
+                JobStatus::Scheduled => state.next.map(NextFire::to_utc),
+                _ => None,
+            },
+            running: self.running.load(Ordering::Relaxed),
+            last_execution: self.history.lock().unwrap().back().cloned(),
+        }
+    }
+
+    /// Executions, the most recent first.
+    pub(crate) fn history(&self) -> Vec<JobExecution> {
+        self.history.lock().unwrap().iter().rev().cloned().collect()
+    }
+
+    async fn drive(self: Arc<Self>) {
+        loop {
+            let (status, next) = {
+                let state = self.state.lock().unwrap();
+                (state.status, state.next)
+            };
+
+            let next = match (status, next) {
+                (JobStatus::Scheduled, Some(next)) => next,
+                // Nothing to do until the job is resumed or rescheduled
+                _ => {
+                    self.notify.notified().await;
+                    continue;
+                }
+            };
+
+            if next.lateness().is_none() {
+                tokio::select! {
+                    _ = tokio::time::sleep(next.remaining().min(MAX_SLEEP)) => {}
+                    _ = self.notify.notified() => {}
+                }
+                continue;
+            }
+
+            self.fire();
+        }
+    }
+
+    /// The job is due, advance to the next fire time and start the execution.
+    fn fire(self: &Arc<Self>) {
+        let mut state = self.state.lock().unwrap();
+        let Some(fire_time) = state.next else {
+            return;
+        };
+        let Some(lateness) = fire_time.lateness() else {
+            return;
+        };
+
+        let args = state.schedule_type.args();
+        let threshold = args
+            .misfire_threshold
+            .map(Duration::from_millis)
+            .unwrap_or(DEFAULT_MISFIRE_THRESHOLD);
+        let misfire_policy = args.misfire;
+        let misfired = lateness > threshold;
+
+        state.next = match misfired {
+            true => state.schedule.next_from_now(),
+            false => state.schedule.next(fire_time),
+        };
+        if state.next.is_none() {
+            state.status = JobStatus::Completed;
+        }
+        drop(state);
+
+        let fire_time = fire_time.to_utc();
+        let trigger = match (misfired, misfire_policy) {
+            (false, _) => JobTrigger::Scheduled,
+            (true, MisfirePolicy::FireOnce) => {
+                warn!(
+                    "Job {} misfired by {:.3}s, it is run once now",
+                    self.name,
+                    lateness.as_secs_f64()
+                );
+                JobTrigger::Misfire
+            }
+            (true, MisfirePolicy::Skip) => {
+                warn!(
+                    "Job {} misfired by {:.3}s, the execution is skipped",
+                    self.name,
+                    lateness.as_secs_f64()
+                );
+                self.record(JobExecution {
+                    trigger: JobTrigger::Misfire,
+                    fire_time: Some(fire_time),
+                    started_at: Utc::now(),
+                    duration: Duration::ZERO,
+                    outcome: JobOutcome::Skipped(String::from("misfired")),
+                });
+                return;
+            }
+        };
+
+        tokio::spawn(self.clone().execute(trigger, Some(fire_time)));
+    }
+
+    async fn execute(self: Arc<Self>, trigger: JobTrigger, fire_time: Option<DateTime<Utc>>) {
+        let started_at = Utc::now();
+        let (overlap, lock) = {
+            let state = self.state.lock().unwrap();
+            let args = state.schedule_type.args();
+            let lock = args.lock_at_most_for.map(|lock_at_most_for| {
+                LockConfiguration::new(
+                    args.lock_name.unwrap_or(&self.name),
+                    Duration::from_millis(lock_at_most_for),
+                    Duration::from_millis(args.lock_at_least_for.unwrap_or_default()),
+                )
+            });
+            (args.overlap, lock)
+        };
+
+        let skipped = |reason: &str| JobExecution {
+            trigger,
+            fire_time,
+            started_at,
+            duration: Duration::ZERO,
+            outcome: JobOutcome::Skipped(reason.to_string()),
+        };
+
+        let _guard = match overlap {
+            OverlapPolicy::Concurrent => None,
+            OverlapPolicy::Skip => match self.serial.try_lock() {
+                Ok(guard) => Some(guard),
+                Err(_) => {
+                    info!("Skipped execution of job {}, the previous one is still running", self.name);
+                    self.record(skipped("the previous execution is still running"));
+                    return;
+                }
+            },
+            OverlapPolicy::Queue => match self.serial.try_lock() {
+                Ok(guard) => Some(guard),
+                Err(_) => {
+                    if self.queued.swap(true, Ordering::AcqRel) {
+                        info!("Skipped execution of job {}, an execution is already queued", self.name);
+                        self.record(skipped("an execution is already queued"));
+                        return;
+                    }
+                    let guard = self.serial.lock().await;
+                    self.queued.store(false, Ordering::Release);
+                    Some(guard)
+                }
+            },
+        };
+
+        // Only one instance runs a locked job at a time
+        if let Some(configuration) = lock.as_ref() {
+            match self.lock_provider.lock(configuration).await {
+                Ok(true) => {}
+                Ok(false) => {
+                    info!("Skipped execution of job {}, the lock is already held", self.name);
+                    self.record(skipped("the lock is held elsewhere"));
+                    return;
+                }
+                Err(error) => {
+                    warn!(
+                        "Skipped execution of job {}, failed to acquire the lock: {}",
+                        self.name, error
+                    );
+                    self.record(skipped(&format!("failed to acquire the lock: {}", error)));
+                    return;
+                }
+            }
+        }
+
+        self.running.fetch_add(1, Ordering::AcqRel);
+        let started_at = Utc::now();
+        let start = Instant::now();
+        let result = match &self.run {
+            JobRun::Async(run) => tokio::spawn(run()).await,
+            JobRun::Sync(run) => {
+                let run = run.clone();
+                tokio::task::spawn_blocking(move || run()).await
+            }
+        };
+        let duration = start.elapsed();
+        self.running.fetch_sub(1, Ordering::AcqRel);
+
+        if let Some(configuration) = lock.as_ref() {
+            if let Err(error) = self.lock_provider.unlock(configuration).await {
+                warn!("Failed to release the lock of job {}: {}", self.name, error);
+            }
+        }
+
+        let outcome = match result {
+            Ok(()) => JobOutcome::Success,
+            Err(error) => {
+                let message = panic_message(error);
+                error!("Job {} failed: {}", self.name, message);
+                JobOutcome::Failed(message)
+            }
+        };
+        self.record(JobExecution {
+            trigger,
+            fire_time,
+            started_at,
+            duration,
+            outcome,
+        });
+    }
+
+    fn record(&self, execution: JobExecution) {
+        let mut history = self.history.lock().unwrap();
+        if history.len() == HISTORY_SIZE {
+            history.pop_front();
+        }
+        history.push_back(execution);
+    }
+}
+
+fn panic_message(error: JoinError) -> String {
+    match error.try_into_panic() {
+        Ok(panic) => match panic.downcast::<String>() {
+            Ok(message) => *message,
+            Err(panic) => match panic.downcast::<&'static str>() {
+                Ok(message) => message.to_string(),
+                Err(_) => String::from("panicked"),
+            },
+        },
+        Err(error) => error.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use next_web_core::scheduler::in_memory_lock_provider::InMemoryLockProvider;
+
+    use super::*;
+
+    fn job(args: WithArgs, run: impl Fn() + Send + Sync + 'static) -> Arc<ScheduledJob> {
+        let job = AnJob::Sync((ScheduleType::FixedRate(args), Box::new(run)));
+        Arc::new(ScheduledJob::new("job".into(), job, Arc::new(InMemoryLockProvider::new())).unwrap())
+    }
+
+    #[test]
+    fn test_invalid_schedule() {
+        let schedule = ScheduleType::Cron(WithArgs {
+            cron: Some("0 0 0 30 2 *"),
+            ..Default::default()
+        });
+        assert!(Schedule::new(&schedule).is_err());
+
+        let schedule = ScheduleType::FixedRate(WithArgs {
+            fixed_rate: Some(0),
+            ..Default::default()
+        });
+        assert!(Schedule::new(&schedule).is_err());
+    }
+
+    #[test]
+    fn test_cron_zone() {
+        let zone = |timezone| match Schedule::new(&ScheduleType::Cron(WithArgs {
+            cron: Some("0 0 3 * * *"),
+            timezone,
+            ..Default::default()
+        })) {
+            Ok(Schedule::Cron { zone, .. }) => zone,
+            _ => panic!("cron schedule expected"),
+        };
+
+        assert!(matches!(zone(None), CronZone::Tz(chrono_tz::UTC)));
+        assert!(matches!(zone(Some("local")), CronZone::Local));
+        assert!(matches!(zone(Some("Asia/Shanghai")), CronZone::Tz(chrono_tz::Asia::Shanghai)));
+    }
+
+    #[test]
+    fn test_next_fire_time() {
+        let schedule = Schedule::new(&ScheduleType::FixedRate(WithArgs {
+            fixed_rate: Some(2),
+            initial_delay: Some(500),
+            time_unit: Some("ms"),
+            ..Default::default()
+        }))
+        .unwrap();
+
+        let first = schedule.first().unwrap();
+        assert!(first.remaining() <= Duration::from_millis(500));
+        let NextFire::After(first_instant) = first else {
+            panic!("fixed rate follows the monotonic clock");
+        };
+        let NextFire::After(second) = schedule.next(first).unwrap() else {
+            panic!("fixed rate follows the monotonic clock");
+        };
+        assert_eq!(second - first_instant, Duration::from_millis(2));
+    }
+
+    #[tokio::test]
+    async fn test_overlap_skip() {
+        let job = job(
+            WithArgs {
+                fixed_rate: Some(60),
+                time_unit: Some("s"),
+                overlap: OverlapPolicy::Skip,
+                ..Default::default()
+            },
+            || std::thread::sleep(Duration::from_millis(200)),
+        );
+
+        let first = tokio::spawn(job.clone().execute(JobTrigger::Manual, None));
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        job.clone().execute(JobTrigger::Manual, None).await;
+        first.await.unwrap();
+
+        let history = job.history();
+        assert_eq!(history.len(), 2);
+        assert!(matches!(history[1].outcome, JobOutcome::Skipped(_)));
+        assert_eq!(history[0].outcome, JobOutcome::Success);
+    }
+
+    #[tokio::test]
+    async fn test_failed_execution() {
+        let job = job(
+            WithArgs {
+                fixed_rate: Some(60),
+                time_unit: Some("s"),
+                ..Default::default()
+            },
+            || panic!("settlement failed"),
+        );
+
+        job.clone().execute(JobTrigger::Manual, None).await;
+        let execution = job.info().last_execution.unwrap();
+        assert_eq!(execution.outcome, JobOutcome::Failed(String::from("settlement failed")));
+        assert_eq!(execution.trigger, JobTrigger::Manual);
+    }
+
+    #[tokio::test]
+    async fn test_misfire() {
+        let job = job(
+            WithArgs {
+                fixed_rate: Some(60),
+                time_unit: Some("s"),
+                misfire: MisfirePolicy::Skip,
+                misfire_threshold: Some(100),
+                ..Default::default()
+            },
+            || {},
+        );
+
+        // Due since a second, as after a stall
+        job.state.lock().unwrap().next = Some(NextFire::After(Instant::now() - Duration::from_secs(1)));
+        job.fire();
+
+        let execution = job.info().last_execution.unwrap();
+        assert_eq!(execution.trigger, JobTrigger::Misfire);
+        assert!(matches!(execution.outcome, JobOutcome::Skipped(_)));
+
+        // The missed fire times are not replayed
+        let next = job.info().next_fire_time.unwrap();
+        assert!(next > Utc::now() + chrono::Duration::seconds(59));
+    }
+}
@@ -0,0 +1,169 @@
+use std::sync::Arc;
+
+use axum::extract::{Request, State};
+use axum::http::header::{
+    ACCESS_CONTROL_ALLOW_CREDENTIALS, ACCESS_CONTROL_ALLOW_HEADERS, ACCESS_CONTROL_ALLOW_METHODS,
+    ACCESS_CONTROL_ALLOW_ORIGIN, ACCESS_CONTROL_EXPOSE_HEADERS, ACCESS_CONTROL_MAX_AGE,
+    ACCESS_CONTROL_REQUEST_HEADERS, ACCESS_CONTROL_REQUEST_METHOD, HOST, ORIGIN, VARY,
+};
+use axum::http::{HeaderMap, HeaderValue, Method, StatusCode};
+use axum::middleware::{from_fn_with_state, Next};
+use axum::response::{IntoResponse, Response};
+use axum::Router;
+use next_web_core::autoconfigure::context::cors_properties::{CorsConfiguration, CorsProperties};
+use next_web_core::http::cors_registry::CorsRegistry;
+use next_web_core::traits::http::cors_configurer::CorsConfigurer;
+use tracing::error;
+
+/// 跨域中间件
+///
+/// Applies the [`CorsRegistry`] built from the `next.server.http.cors` properties and
+/// every [`CorsConfigurer`].
+///
+/// Requests without an `Origin` header, same origin requests and paths without a
+/// configuration are passed through untouched, a cross origin request that is not
+/// allowed by the matching configuration is rejected with `403`.
+pub struct CorsMiddleware;
+
+impl CorsMiddleware {
+    /// Build the registry, configurations that are invalid are logged and skipped.
+    pub fn registry(
+        properties: Option<&CorsProperties>,
+        configurers: &[Box<dyn CorsConfigurer>],
+    ) -> CorsRegistry {
+        let mut registry = properties
+            .map(CorsRegistry::from_properties)
+            .unwrap_or_default();
+
+        for configurer in configurers {
+            configurer.configure(&mut registry);
+        }
+
+        if let Some(properties) = properties {
+            if !properties.defaults().allowed_origins().is_empty() {
+                registry.add_mapping("/**", properties.defaults().clone());
+            }
+        }
+
+        registry.retain(|(path, configuration)| match configuration.validate() {
+            Ok(_) => true,
+            Err(message) => {
+                error!("Invalid cors configuration of [{}] is ignored: {}", path, message);
+                false
+            }
+        });
+        registry
+    }
+
+    /// Add the middleware to the router.
+    pub fn layer(router: Router, registry: CorsRegistry) -> Router {
+        router.layer(from_fn_with_state(Arc::new(registry), cors))
+    }
+}
+
+async fn cors(State(registry): State<Arc<CorsRegistry>>, request: Request, next: Next) -> Response {
+    let origin = match request.headers().get(ORIGIN).and_then(|value| value.to_str().ok()) {
+        Some(origin) if !is_same_origin(origin, request.headers()) => origin.to_string(),
+        _ => return next.run(request).await,
+    };
+
+    let configuration = match registry.get(request.uri().path()) {
+        Some(configuration) => configuration,
+        None => return next.run(request).await,
+    };
+
+    let allow_origin = match configuration.check_origin(&origin) {
+        Some(allow_origin) => allow_origin,
+        None => return reject(),
+    };
+
+    let preflight = request.method() == Method::OPTIONS
+        && request.headers().contains_key(ACCESS_CONTROL_REQUEST_METHOD);
+    if preflight {
+        return preflight_response(configuration, allow_origin, request.headers())
+            .unwrap_or_else(reject);
+    }
+
+    let mut response = next.run(request).await;
+    let headers = response.headers_mut();
+    insert(headers, ACCESS_CONTROL_ALLOW_ORIGIN, &allow_origin);
+    if configuration.allow_credentials() {
+        insert(headers, ACCESS_CONTROL_ALLOW_CREDENTIALS, "true");
+    }
+    if !configuration.exposed_headers().is_empty() {
+        insert(
+            headers,
+            ACCESS_CONTROL_EXPOSE_HEADERS,
+            &configuration.exposed_headers().join(", "),
+        );
+    }
+    headers.append(VARY, HeaderValue::from_static("Origin"));
+    response
+}
+
+fn preflight_response(
+    configuration: &CorsConfiguration,
+    allow_origin: String,
+    request_headers: &HeaderMap,
+) -> Option<Response> {
+    let method = request_headers
+        .get(ACCESS_CONTROL_REQUEST_METHOD)
+        .and_then(|value| value.to_str().ok())?;
+    if !configuration.check_method(method) {
+        return None;
+    }
+
+    let requested = request_headers
+        .get_all(ACCESS_CONTROL_REQUEST_HEADERS)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(str::trim)
+        .filter(|header| !header.is_empty())
+        .collect::<Vec<_>>();
+    let allow_headers = configuration.check_headers(&requested)?;
+
+    let allow_methods = match configuration.allowed_methods().contains(&"*") {
+        true => method.to_string(),
+        false => configuration.allowed_methods().join(", "),
+    };
+
+    let mut response = StatusCode::OK.into_response();
+    let headers = response.headers_mut();
+    insert(headers, ACCESS_CONTROL_ALLOW_ORIGIN, &allow_origin);
+    insert(headers, ACCESS_CONTROL_ALLOW_METHODS, &allow_methods);
+    if !allow_headers.is_empty() {
+        insert(headers, ACCESS_CONTROL_ALLOW_HEADERS, &allow_headers.join(", "));
+    }
+    if configuration.allow_credentials() {
+        insert(headers, ACCESS_CONTROL_ALLOW_CREDENTIALS, "true");
+    }
+    insert(headers, ACCESS_CONTROL_MAX_AGE, &configuration.max_age().to_string());
+    headers.insert(
+        VARY,
+        HeaderValue::from_static("Origin, Access-Control-Request-Method, Access-Control-Request-Headers"),
+    );
+    Some(response)
+}
+
+/// The browser also sends `Origin` for same origin requests, e.g. `POST`.
+fn is_same_origin(origin: &str, headers: &HeaderMap) -> bool {
+    let host = match headers.get(HOST).and_then(|value| value.to_str().ok()) {
+        Some(host) => host,
+        None => return false,
+    };
+    origin
+        .split_once("://")
+        .map(|(_, authority)| authority.trim_end_matches('/').eq_ignore_ascii_case(host))
+        .unwrap_or(false)
+}
+
+fn insert(headers: &mut HeaderMap, name: axum::http::HeaderName, value: &str) {
+    if let Ok(value) = HeaderValue::from_str(value) {
+        headers.insert(name, value);
+    }
+}
+
+fn reject() -> Response {
+    (StatusCode::FORBIDDEN, "Invalid CORS request").into_response()
+}
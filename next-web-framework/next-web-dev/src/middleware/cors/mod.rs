@@ -0,0 +1 @@
+pub mod cors_middleware;
@@ -1,4 +1,6 @@
+pub mod cors;
 pub mod redis;
+pub mod response;
 pub mod database;
 
 pub use axum::middleware::*;
\ No newline at end of file
@@ -0,0 +1,102 @@
+use axum::body::{Body, HttpBody};
+use axum::extract::Request;
+use axum::http::header::{CONTENT_LENGTH, CONTENT_TYPE, ETAG, IF_NONE_MATCH};
+use axum::http::{HeaderValue, Method, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use http_body_util::BodyExt;
+use sha2::{Digest, Sha256};
+
+/// Bodies larger than this, or of unknown size, are passed through without an `ETag`.
+const MAX_BUFFERED_SIZE: u64 = 8 * 1024 * 1024;
+
+/// Adds an `ETag` to successful `GET` and `HEAD` responses and answers a matching
+/// `If-None-Match` with `304 Not Modified`.
+///
+/// An `ETag` set by the handler is kept, otherwise it is the digest of the body, weak as the
+/// same tag is sent for every content encoding.
+pub(crate) async fn etag(request: Request, next: Next) -> Response {
+    if request.method() != Method::GET && request.method() != Method::HEAD {
+        return next.run(request).await;
+    }
+
+    let if_none_match = request.headers().get(IF_NONE_MATCH).cloned();
+    let response = next.run(request).await;
+    if response.status() != StatusCode::OK {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let (etag, body) = match parts.headers.get(ETAG).cloned() {
+        Some(etag) => (etag, body),
+        None => {
+            match body.size_hint().exact() {
+                Some(size) if size <= MAX_BUFFERED_SIZE => {}
+                _ => return Response::from_parts(parts, body),
+            }
+
+            let bytes = match body.collect().await {
+                Ok(collected) => collected.to_bytes(),
+                Err(_) => return StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+            };
+            let etag = digest(&bytes);
+            parts.headers.insert(ETAG, etag.clone());
+            (etag, Body::from(bytes))
+        }
+    };
+
+    let not_modified = if_none_match
+        .as_ref()
+        .and_then(|value| value.to_str().ok())
+        .zip(etag.to_str().ok())
+        .is_some_and(|(if_none_match, etag)| matches(if_none_match, etag));
+    if not_modified {
+        parts.status = StatusCode::NOT_MODIFIED;
+        parts.headers.remove(CONTENT_LENGTH);
+        parts.headers.remove(CONTENT_TYPE);
+        return Response::from_parts(parts, Body::empty());
+    }
+
+    Response::from_parts(parts, body)
+}
+
+fn digest(bytes: &[u8]) -> HeaderValue {
+    let hash = Sha256::digest(bytes);
+    let hex = hash[..16]
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect::<String>();
+
+    // Only hex digits, the value is always valid
+    HeaderValue::from_str(&format!("W/\"{}\"", hex)).unwrap()
+}
+
+/// `If-None-Match` uses the weak comparison, `W/"a"` matches `"a"`.
+fn matches(if_none_match: &str, etag: &str) -> bool {
+    let etag = etag.trim_start_matches("W/");
+    if_none_match
+        .split(',')
+        .map(str::trim)
+        .any(|tag| tag == "*" || tag.trim_start_matches("W/") == etag)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches() {
+        assert!(matches("\"a\"", "\"a\""));
+        assert!(matches("W/\"a\"", "\"a\""));
+        assert!(matches("\"b\", \"a\"", "W/\"a\""));
+        assert!(matches("*", "\"a\""));
+        assert!(!matches("\"b\"", "\"a\""));
+    }
+
+    #[test]
+    fn test_digest() {
+        assert_eq!(digest(b"next-web"), digest(b"next-web"));
+        assert_ne!(digest(b"next-web"), digest(b"next-web!"));
+        assert_eq!(digest(b"").len(), 36);
+    }
+}
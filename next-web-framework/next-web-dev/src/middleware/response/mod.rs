@@ -0,0 +1,2 @@
+mod etag;
+pub mod response_middleware;
@@ -0,0 +1,228 @@
+use std::sync::Arc;
+use std::time::Instant;
+
+use axum::body::HttpBody;
+use axum::extract::{Request, State};
+use axum::http::header::{CONTENT_LENGTH, CONTENT_TYPE, SERVER};
+use axum::http::{HeaderName, HeaderValue};
+use axum::middleware::{from_fn, from_fn_with_state, Next};
+use axum::response::Response;
+use axum::Router;
+use next_web_core::autoconfigure::context::http_properties::{
+    CompressionAlgorithm, CompressionProperties, ResponseProperties,
+};
+use tower_http::compression::predicate::Predicate;
+use tower_http::compression::CompressionLayer;
+use tracing::warn;
+
+use super::etag::etag;
+
+/// 响应中间件
+///
+/// Applies the `next.server.http.response` properties: `ETag` and conditional `GET`,
+/// the `Server`, security and response time headers, and compression.
+///
+/// The `ETag` is computed on the uncompressed body, so it does not change with the
+/// encoding negotiated by the client.
+pub struct ResponseMiddleware;
+
+impl ResponseMiddleware {
+    pub fn layer(mut router: Router, properties: &ResponseProperties) -> Router {
+        if properties.etag() {
+            router = router.layer(from_fn(etag));
+        }
+
+        let headers = ResponseHeaders::new(properties);
+        if !headers.is_empty() {
+            router = router.layer(from_fn_with_state(Arc::new(headers), response_headers));
+        }
+
+        if let Some(compression) = properties.compression() {
+            let algorithms = compression.algorithms();
+            router = router.layer(
+                CompressionLayer::new()
+                    .gzip(algorithms.contains(&CompressionAlgorithm::Gzip))
+                    .br(algorithms.contains(&CompressionAlgorithm::Br))
+                    .zstd(algorithms.contains(&CompressionAlgorithm::Zstd))
+                    .compress_when(MinSize(compression.min_size()).and(MimeTypes::new(compression))),
+            );
+        }
+
+        router
+    }
+}
+
+/// Headers added to every response.
+struct ResponseHeaders {
+    /// Only added when the handler didn't set them
+    defaults: Vec<(HeaderName, HeaderValue)>,
+    server: Option<HeaderValue>,
+    response_time: Option<HeaderName>,
+}
+
+impl ResponseHeaders {
+    fn new(properties: &ResponseProperties) -> Self {
+        let defaults = properties
+            .security_headers()
+            .map(|security_headers| security_headers.headers())
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|(name, value)| match HeaderValue::from_str(value) {
+                Ok(value) => Some((HeaderName::from_static(name), value)),
+                Err(_) => {
+                    warn!("Invalid value of the {} security header is ignored: {}", name, value);
+                    None
+                }
+            })
+            .collect();
+
+        let server = properties
+            .server_header()
+            .and_then(|server| HeaderValue::from_str(server).ok());
+
+        let response_time = match properties.response_time() {
+            true => match HeaderName::try_from(properties.response_time_header()) {
+                Ok(name) => Some(name),
+                Err(_) => {
+                    warn!("Invalid response time header name: {}", properties.response_time_header());
+                    None
+                }
+            },
+            false => None,
+        };
+
+        Self {
+            defaults,
+            server,
+            response_time,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.defaults.is_empty() && self.server.is_none() && self.response_time.is_none()
+    }
+}
+
+async fn response_headers(
+    State(headers): State<Arc<ResponseHeaders>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let start = Instant::now();
+    let mut response = next.run(request).await;
+
+    let response_headers = response.headers_mut();
+    for (name, value) in headers.defaults.iter() {
+        if !response_headers.contains_key(name) {
+            response_headers.insert(name.clone(), value.clone());
+        }
+    }
+    if let Some(server) = headers.server.as_ref() {
+        response_headers.insert(SERVER, server.clone());
+    }
+    if let Some(name) = headers.response_time.as_ref() {
+        let elapsed = format!("{:.3}ms", start.elapsed().as_secs_f64() * 1000.0);
+        if let Ok(value) = HeaderValue::from_str(&elapsed) {
+            response_headers.insert(name.clone(), value);
+        }
+    }
+    response
+}
+
+/// Compress only the responses of at least `min_size` bytes, or of unknown size.
+///
+/// Unlike [`SizeAbove`](tower_http::compression::predicate::SizeAbove), the threshold is not
+/// limited to `u16`.
+#[derive(Clone, Copy)]
+struct MinSize(u32);
+
+impl Predicate for MinSize {
+    fn should_compress<B>(&self, response: &axum::http::Response<B>) -> bool
+    where
+        B: HttpBody,
+    {
+        let size = response.body().size_hint().exact().or_else(|| {
+            response
+                .headers()
+                .get(CONTENT_LENGTH)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.parse().ok())
+        });
+
+        size.is_none_or(|size| size >= u64::from(self.0))
+    }
+}
+
+/// Compress only the configured content types, `text/*` matches every subtype.
+///
+/// `text/event-stream` is never compressed, the encoder would buffer the events.
+#[derive(Clone)]
+struct MimeTypes(Arc<Vec<String>>);
+
+impl MimeTypes {
+    fn new(properties: &CompressionProperties) -> Self {
+        Self(Arc::new(
+            properties
+                .mime_types()
+                .into_iter()
+                .map(|mime| mime.to_ascii_lowercase())
+                .collect(),
+        ))
+    }
+
+    fn matches(&self, content_type: &str) -> bool {
+        let essence = content_type
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase();
+        if essence == "text/event-stream" {
+            return false;
+        }
+
+        self.0.iter().any(|mime| match mime.strip_suffix("/*") {
+            Some(kind) => essence
+                .split_once('/')
+                .is_some_and(|(essence_kind, _)| essence_kind == kind),
+            None => *mime == essence,
+        })
+    }
+}
+
+impl Predicate for MimeTypes {
+    fn should_compress<B>(&self, response: &axum::http::Response<B>) -> bool
+    where
+        B: HttpBody,
+    {
+        response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|content_type| self.matches(content_type))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mime_types() {
+        let mime_types = MimeTypes(Arc::new(vec!["text/*".into(), "application/json".into()]));
+
+        assert!(mime_types.matches("text/html; charset=utf-8"));
+        assert!(mime_types.matches("Application/JSON"));
+        assert!(!mime_types.matches("text/event-stream"));
+        assert!(!mime_types.matches("image/png"));
+    }
+
+    #[test]
+    fn test_min_size() {
+        let response = |size: usize| axum::http::Response::new(axum::body::Body::from(vec![0u8; size]));
+        let min_size = MinSize(100_000);
+
+        assert!(min_size.should_compress(&response(100_000)));
+        assert!(!min_size.should_compress(&response(99_999)));
+    }
+}
@@ -0,0 +1,122 @@
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use next_web_core::context::properties::ApplicationProperties;
+use next_web_core::state::application_state::ApplicationState;
+use next_web_core::util::singleton::SingletonUtil;
+use next_web_utils::file::watch::{Error, Event, EventKind, Watcher};
+use tracing::{error, info};
+
+use super::context_refresher::ContextRefresher;
+
+/// Events arriving within this window are handled as a single refresh.
+const DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// 配置文件监听器
+///
+/// Watches the application configuration files and triggers [`ContextRefresher`] on change.
+pub struct ConfigFileWatcher;
+
+impl ConfigFileWatcher {
+    /// Start watching the configuration files the application properties were loaded from,
+    /// including the `--config-location` file.
+    pub async fn spawn(state: ApplicationState) {
+        let files = Arc::new(RwLock::new(config_files(&state).await));
+
+        let (tx, rx) = flume::unbounded::<()>();
+        let watched = files.clone();
+        let watcher = Watcher::new(move |event: Result<Event, Error>| match event {
+            Ok(event) => {
+                let watched = watched.read().unwrap_or_else(|e| e.into_inner());
+                if is_config_event(&event, &watched) {
+                    let _ = tx.send(());
+                }
+            }
+            Err(e) => error!("Configuration file watcher error: {}", e),
+        });
+
+        let mut watcher = match watcher {
+            Ok(watcher) => watcher,
+            Err(e) => {
+                error!("Failed to create configuration file watcher: {}", e);
+                return;
+            }
+        };
+
+        let mut directories = HashSet::new();
+        watch_directories(
+            &mut watcher,
+            &mut directories,
+            &files.read().unwrap_or_else(|e| e.into_inner()),
+        );
+
+        let refresher = ContextRefresher::new();
+        tokio::spawn(async move {
+            // The watcher stops when dropped
+            let mut watcher = watcher;
+
+            while rx.recv_async().await.is_ok() {
+                tokio::time::sleep(DEBOUNCE).await;
+                rx.drain();
+
+                if let Err(e) = refresher.refresh(state.context()).await {
+                    error!("Failed to refresh application properties: {}", e);
+                }
+
+                // The refreshed configuration may activate other profiles and load other files
+                let current = config_files(&state).await;
+                watch_directories(&mut watcher, &mut directories, &current);
+                *files.write().unwrap_or_else(|e| e.into_inner()) = current;
+            }
+        });
+    }
+}
+
+/// The absolute paths of the files the current application properties were loaded from.
+async fn config_files(state: &ApplicationState) -> HashSet<PathBuf> {
+    state
+        .context()
+        .read()
+        .await
+        .get_single_option_with_name::<ApplicationProperties>(
+            SingletonUtil::name::<ApplicationProperties>(),
+        )
+        .map(|properties| {
+            properties
+                .config_files()
+                .iter()
+                .filter_map(|file| std::path::absolute(file).ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Watch the parent directory of every file, editors usually replace the file instead of writing it.
+fn watch_directories(
+    watcher: &mut Watcher,
+    directories: &mut HashSet<PathBuf>,
+    files: &HashSet<PathBuf>,
+) {
+    for directory in files.iter().filter_map(|file| file.parent()) {
+        if directories.contains(directory) || !directory.exists() {
+            continue;
+        }
+
+        match watcher.watch(directory, false) {
+            Ok(_) => {
+                info!("Watching configuration files in: {}", directory.display());
+                directories.insert(directory.to_path_buf());
+            }
+            Err(e) => error!("Failed to watch {}: {}", directory.display(), e),
+        }
+    }
+}
+
+fn is_config_event(event: &Event, files: &HashSet<PathBuf>) -> bool {
+    matches!(
+        event.kind,
+        EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+    ) && event.paths.iter().any(|path| files.contains(path))
+}
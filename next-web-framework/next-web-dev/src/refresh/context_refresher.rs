@@ -0,0 +1,126 @@
+use std::sync::Arc;
+
+use next_web_core::context::application_args::ApplicationArgs;
+use next_web_core::context::application_resources::ApplicationResources;
+use next_web_core::context::properties::{ApplicationProperties, Properties};
+use next_web_core::context::properties_validator::PropertiesValidator;
+use next_web_core::context::property_sources::changed_keys;
+use next_web_core::error::properties_validation_error::PropertiesValidationError;
+use next_web_core::error::BoxError;
+use next_web_core::traits::event::application_event_publisher::ApplicationEventPublisher;
+use next_web_core::traits::properties_post_processor::PropertiesPostProcessor;
+use next_web_core::util::singleton::SingletonUtil;
+use next_web_core::ApplicationContext;
+use serde_yaml::Value;
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+
+use crate::event::default_application_event_publisher::DefaultApplicationEventPublisher;
+use crate::event::environment_change_event::EnvironmentChangeEvent;
+use crate::util::logging_level::LoggingLevel;
+
+const LOGGING_LEVEL_KEY: &str = "next.logging.level";
+
+/// 配置刷新器
+///
+/// Reloads the application properties, re-binds the `#[Properties(refreshable)]` structs
+/// and publishes an [`EnvironmentChangeEvent`] listing the changed keys.
+#[derive(Clone, Default)]
+pub struct ContextRefresher;
+
+impl ContextRefresher {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Refresh the application properties, returns the changed keys.
+    pub async fn refresh(
+        &self,
+        context: &Arc<RwLock<ApplicationContext>>,
+    ) -> Result<Vec<String>, BoxError> {
+        let properties_name = SingletonUtil::name::<ApplicationProperties>();
+
+        // Resolving the post processors may create them, the lock is released before reading the files
+        let (args, mut post_processors) = {
+            let mut ctx = context.write().await;
+            let args = ctx
+                .get_single_option_with_name::<ApplicationArgs>(SingletonUtil::name::<ApplicationArgs>())
+                .cloned()
+                .unwrap_or_default();
+            (args, ctx.resolve_by_type::<Box<dyn PropertiesPostProcessor>>())
+        };
+        post_processors.sort_by_key(|item| item.order());
+
+        // The files are read without holding the context lock
+        let properties = tokio::task::spawn_blocking(move || {
+            let resources = ApplicationResources::default();
+            let mut properties = ApplicationProperties::try_from((&args, &resources))?;
+
+            post_processors
+                .iter_mut()
+                .for_each(|item| item.post_process_properties(properties.mapping_mut()));
+            properties.rebind()?;
+            Ok::<_, PropertiesValidationError>(properties)
+        })
+        .await
+        .map_err(|_| "Failed to reload the application properties")??;
+
+        let mut ctx = context.write().await;
+        let old = ctx
+            .get_single_option_with_name::<ApplicationProperties>(properties_name.clone())
+            .cloned()
+            .unwrap_or_default();
+
+        // An invalid configuration is rejected and the current one is kept
+        let items = ctx.resolve_by_type::<Box<dyn Properties>>();
+        PropertiesValidator::validate(&properties, &items)?;
+
+        let keys = changed_keys(
+            old.mapping().unwrap_or(&Value::Null),
+            properties.mapping().unwrap_or(&Value::Null),
+        );
+        if keys.is_empty() {
+            return Ok(keys);
+        }
+
+        ctx.replace_singleton_with_name(properties.clone(), properties_name);
+
+        for item in items {
+            if !item.refreshable() {
+                continue;
+            }
+
+            if let Err(error) = item.rebind(&mut ctx, &properties) {
+                warn!(
+                    "Failed to rebind properties [{}]: {}",
+                    item.registered_name(),
+                    error
+                );
+            }
+        }
+
+        if keys.iter().any(|key| key == LOGGING_LEVEL_KEY) {
+            let level = properties
+                .next()
+                .logging()
+                .map(|logging| logging.level())
+                .unwrap_or(tracing::Level::INFO);
+            LoggingLevel::set(level);
+        }
+
+        let publisher = ctx
+            .get_single_option_with_name::<DefaultApplicationEventPublisher>(
+                SingletonUtil::name::<DefaultApplicationEventPublisher>(),
+            )
+            .cloned();
+        drop(ctx);
+
+        info!("Application properties refreshed, changed keys: {:?}", keys);
+
+        if let Some(publisher) = publisher {
+            publisher.publish_event("", EnvironmentChangeEvent::new(keys.clone()))?;
+        }
+
+        Ok(keys)
+    }
+}
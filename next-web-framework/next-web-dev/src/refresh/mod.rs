@@ -0,0 +1,4 @@
+pub mod context_refresher;
+
+#[cfg(feature = "enable-refresh")]
+pub mod config_file_watcher;
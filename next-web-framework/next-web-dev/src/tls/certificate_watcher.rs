@@ -0,0 +1,85 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use axum_server::tls_rustls::RustlsConfig;
+use next_web_core::autoconfigure::context::ssl_properties::SslProperties;
+use next_web_utils::file::watch::{Error, Event, EventKind, Watcher};
+use tracing::{error, info};
+
+use super::server_tls_config::ServerTlsConfig;
+
+/// Events arriving within this window are handled as a single reload.
+const DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// 证书文件监听器
+///
+/// Watches the directories of the `next.server.ssl` files and reloads the server
+/// configuration on change, new connections use the new certificates.
+///
+/// Every change in the directories triggers a reload, mounted secrets are usually
+/// replaced through a symlink swap that doesn't touch the files themselves.
+/// When the new files cannot be loaded the current configuration is kept.
+pub struct CertificateWatcher;
+
+impl CertificateWatcher {
+    pub fn spawn(config: RustlsConfig, ssl: SslProperties) {
+        let dirs = ssl
+            .files()
+            .into_iter()
+            .filter_map(|file| {
+                Path::new(file)
+                    .parent()
+                    .filter(|parent| !parent.as_os_str().is_empty())
+                    .map(Path::to_path_buf)
+                    .or_else(|| Some(PathBuf::from(".")))
+            })
+            .collect::<HashSet<_>>();
+
+        let (tx, rx) = flume::unbounded::<()>();
+        let watcher = Watcher::new(move |event: Result<Event, Error>| match event {
+            Ok(event) => {
+                if matches!(
+                    event.kind,
+                    EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+                ) {
+                    let _ = tx.send(());
+                }
+            }
+            Err(e) => error!("Certificate watcher error: {}", e),
+        });
+
+        let mut watcher = match watcher {
+            Ok(watcher) => watcher,
+            Err(e) => {
+                error!("Failed to create certificate watcher: {}", e);
+                return;
+            }
+        };
+
+        for dir in dirs.iter().filter(|dir| dir.exists()) {
+            match watcher.watch(dir, false) {
+                Ok(_) => info!("Watching certificates in: {}", dir.display()),
+                Err(e) => error!("Failed to watch {}: {}", dir.display(), e),
+            }
+        }
+
+        tokio::spawn(async move {
+            // The watcher stops when dropped
+            let _watcher = watcher;
+
+            while rx.recv_async().await.is_ok() {
+                tokio::time::sleep(DEBOUNCE).await;
+                rx.drain();
+
+                match ServerTlsConfig::load(&ssl) {
+                    Ok(server_config) => {
+                        config.reload_from_config(server_config);
+                        info!("Certificates reloaded");
+                    }
+                    Err(e) => error!("Failed to reload certificates, keeping the current ones: {}", e),
+                }
+            }
+        });
+    }
+}
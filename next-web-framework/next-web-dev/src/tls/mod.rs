@@ -0,0 +1,2 @@
+pub mod certificate_watcher;
+pub mod server_tls_config;
@@ -0,0 +1,186 @@
+use std::fs::File;
+use std::io::BufReader;
+use std::sync::Arc;
+
+use axum_server::tls_rustls::RustlsConfig;
+use next_web_core::autoconfigure::context::ssl_properties::{ClientAuth, SslProperties, TlsVersion};
+use p12_keystore::{KeyStore, Pkcs12ImportPolicy};
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, PrivatePkcs8KeyDer};
+use rustls::server::danger::ClientCertVerifier;
+use rustls::server::WebPkiClientVerifier;
+use rustls::{RootCertStore, ServerConfig};
+use thiserror::Error;
+
+use super::certificate_watcher::CertificateWatcher;
+
+#[derive(Debug, Error)]
+pub enum TlsConfigError {
+    #[error("failed to read {path}: {source}")]
+    Io {
+        path: String,
+        source: std::io::Error,
+    },
+
+    #[error("no certificate found in {0}")]
+    NoCertificate(String),
+
+    #[error("no private key found in {0}")]
+    NoPrivateKey(String),
+
+    #[error("invalid key store {path}: {message}")]
+    KeyStore { path: String, message: String },
+
+    #[error("the tls-rustls feature is enabled but `next.server.ssl` is not configured, configure it or set `next.server.ssl.enabled: false` to serve plain http")]
+    NotConfigured,
+
+    #[error("either certificate and certificate_private_key or key_store must be set")]
+    NoIdentity,
+
+    #[error("trust_certificate must be set when client_auth is enabled")]
+    NoTrustCertificate,
+
+    #[error("invalid client certificate verifier: {0}")]
+    ClientVerifier(String),
+
+    #[error(transparent)]
+    Rustls(#[from] rustls::Error),
+}
+
+/// 服务端 TLS 配置
+///
+/// Builds the rustls [`ServerConfig`] from the `next.server.ssl` properties.
+pub struct ServerTlsConfig;
+
+impl ServerTlsConfig {
+    /// Resolve the configuration the server is bound with, `None` to serve plain http.
+    ///
+    /// The `tls-rustls` feature requires an explicit `next.server.ssl` section, set
+    /// `enabled: false` in it to serve plain http.
+    pub async fn resolve(ssl: Option<&SslProperties>) -> Result<Option<RustlsConfig>, TlsConfigError> {
+        let ssl = match ssl {
+            Some(ssl) if ssl.enabled() => ssl,
+            Some(_) => return Ok(None),
+            None => return Err(TlsConfigError::NotConfigured),
+        };
+
+        let config = RustlsConfig::from_config(Self::load(ssl)?);
+        if ssl.reload() {
+            CertificateWatcher::spawn(config.clone(), ssl.clone());
+        }
+        Ok(Some(config))
+    }
+
+    pub fn load(ssl: &SslProperties) -> Result<Arc<ServerConfig>, TlsConfigError> {
+        let versions: &[&'static rustls::SupportedProtocolVersion] = match ssl.protocol() {
+            TlsVersion::Tls12 => &[&rustls::version::TLS13, &rustls::version::TLS12],
+            TlsVersion::Tls13 => &[&rustls::version::TLS13],
+        };
+
+        let builder = ServerConfig::builder_with_protocol_versions(versions);
+        let builder = match Self::client_verifier(ssl)? {
+            Some(verifier) => builder.with_client_cert_verifier(verifier),
+            None => builder.with_no_client_auth(),
+        };
+
+        let (certs, key) = Self::identity(ssl)?;
+        let mut config = builder.with_single_cert(certs, key)?;
+        config.alpn_protocols = ssl
+            .alpn()
+            .into_iter()
+            .map(|protocol| protocol.as_bytes().to_vec())
+            .collect();
+
+        Ok(Arc::new(config))
+    }
+
+    fn identity(
+        ssl: &SslProperties,
+    ) -> Result<(Vec<CertificateDer<'static>>, PrivateKeyDer<'static>), TlsConfigError> {
+        if let Some(path) = ssl.key_store() {
+            return Self::read_key_store(path, ssl.key_store_password());
+        }
+
+        match (ssl.certificate(), ssl.certificate_private_key()) {
+            (Some(cert), Some(key)) => Ok((Self::read_certs(cert)?, Self::read_private_key(key)?)),
+            _ => Err(TlsConfigError::NoIdentity),
+        }
+    }
+
+    fn client_verifier(
+        ssl: &SslProperties,
+    ) -> Result<Option<Arc<dyn ClientCertVerifier>>, TlsConfigError> {
+        if ssl.client_auth() == ClientAuth::None {
+            return Ok(None);
+        }
+
+        let path = ssl.trust_certificate().ok_or(TlsConfigError::NoTrustCertificate)?;
+        let mut roots = RootCertStore::empty();
+        for cert in Self::read_certs(path)? {
+            roots.add(cert)?;
+        }
+
+        let builder = WebPkiClientVerifier::builder(Arc::new(roots));
+        let builder = match ssl.client_auth() {
+            ClientAuth::Want => builder.allow_unauthenticated(),
+            _ => builder,
+        };
+        builder
+            .build()
+            .map(Some)
+            .map_err(|error| TlsConfigError::ClientVerifier(error.to_string()))
+    }
+
+    fn read_certs(path: &str) -> Result<Vec<CertificateDer<'static>>, TlsConfigError> {
+        let mut reader = Self::open(path)?;
+        let certs = rustls_pemfile::certs(&mut reader)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|source| TlsConfigError::Io { path: path.to_string(), source })?;
+
+        match certs.is_empty() {
+            true => Err(TlsConfigError::NoCertificate(path.to_string())),
+            false => Ok(certs),
+        }
+    }
+
+    fn read_private_key(path: &str) -> Result<PrivateKeyDer<'static>, TlsConfigError> {
+        let mut reader = Self::open(path)?;
+        rustls_pemfile::private_key(&mut reader)
+            .map_err(|source| TlsConfigError::Io { path: path.to_string(), source })?
+            .ok_or_else(|| TlsConfigError::NoPrivateKey(path.to_string()))
+    }
+
+    fn read_key_store(
+        path: &str,
+        password: &str,
+    ) -> Result<(Vec<CertificateDer<'static>>, PrivateKeyDer<'static>), TlsConfigError> {
+        let data = std::fs::read(path)
+            .map_err(|source| TlsConfigError::Io { path: path.to_string(), source })?;
+        let key_store = KeyStore::from_pkcs12(&data, password, Pkcs12ImportPolicy::Strict).map_err(
+            |error| TlsConfigError::KeyStore {
+                path: path.to_string(),
+                message: error.to_string(),
+            },
+        )?;
+
+        let (_, chain) = key_store
+            .private_key_chain()
+            .ok_or_else(|| TlsConfigError::NoPrivateKey(path.to_string()))?;
+        if chain.certs().is_empty() {
+            return Err(TlsConfigError::NoCertificate(path.to_string()));
+        }
+
+        let certs = chain
+            .certs()
+            .iter()
+            .map(|cert| CertificateDer::from(cert.as_der().to_vec()))
+            .collect();
+        let key = PrivatePkcs8KeyDer::from(chain.key().as_der().to_vec()).into();
+        Ok((certs, key))
+    }
+
+    fn open(path: &str) -> Result<BufReader<File>, TlsConfigError> {
+        File::open(path)
+            .map(BufReader::new)
+            .map_err(|source| TlsConfigError::Io { path: path.to_string(), source })
+    }
+}
@@ -0,0 +1,33 @@
+use std::sync::OnceLock;
+
+use tracing_subscriber::{filter::LevelFilter, reload::Handle, Registry};
+
+static LEVEL_HANDLE: OnceLock<Handle<LevelFilter, Registry>> = OnceLock::new();
+
+/// Change the max level of the global logger at runtime.
+pub struct LoggingLevel;
+
+impl LoggingLevel {
+    pub(crate) fn install(handle: Handle<LevelFilter, Registry>) {
+        let _ = LEVEL_HANDLE.set(handle);
+    }
+
+    /// Set the max level, returns `false` if the logger was not initialized by the application.
+    pub fn set(level: tracing::Level) -> bool {
+        LEVEL_HANDLE
+            .get()
+            .map(|handle| {
+                handle
+                    .modify(|filter| *filter = LevelFilter::from_level(level))
+                    .is_ok()
+            })
+            .unwrap_or(false)
+    }
+
+    /// Get the current max level.
+    pub fn current() -> Option<LevelFilter> {
+        LEVEL_HANDLE
+            .get()
+            .and_then(|handle| handle.clone_current())
+    }
+}
@@ -1,6 +1,7 @@
 pub mod desensitized;
 pub mod hash_slot;
 pub mod local_date_time;
+pub mod logging_level;
 pub mod domain;
 pub mod thread;
 
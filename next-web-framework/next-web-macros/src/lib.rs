@@ -4,8 +4,11 @@ use crate::data::builder::impl_macro_builder;
 use crate::data::constructor::impl_macro_required_args_constructor;
 use crate::data::field_name::impl_macro_field_name;
 use crate::data::get_set::impl_macro_get_set;
+use crate::web::api_schema::impl_macro_api_schema;
 use crate::web::idempotency::impl_macro_idempotency;
-use crate::web::pre_authorize::impl_macro_pre_authorize;
+use crate::web::pre_authorize::{
+    impl_macro_post_authorize, impl_macro_pre_authorize, impl_macro_secured,
+};
 use crate::web::properties::impl_macro_properties;
 use crate::web::retry::impl_macro_retry;
 use crate::web::scheduled::impl_macro_scheduled;
@@ -283,6 +286,35 @@ pub fn desensitized(input: TokenStream) -> TokenStream {
     impl_macro_desensitized(&input)
 }
 
+/// 为结构体或枚举生成 OpenAPI 模型描述。
+///
+/// 字段的文档注释作为描述，遵循 `serde` 的 `rename`、`rename_all`、`skip`、`default` 与
+/// `flatten` 属性，`Option` 字段为非必填。
+///
+/// Generate the OpenAPI schema of a struct or an enum, used by the routing macros to document
+/// request and response bodies.
+///
+/// The doc comments become descriptions, the `serde` attributes `rename`, `rename_all`, `skip`,
+/// `default` and `flatten` are honoured, `Option` fields are not required.
+/// Enums with data are documented in the externally tagged representation.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// #[derive(Deserialize, ApiSchema)]
+/// #[serde(rename_all = "camelCase")]
+/// struct CreateOrder {
+///     /// Product to order
+///     product_id: u64,
+///     quantity: Option<u32>,
+/// }
+/// ```
+#[proc_macro_derive(ApiSchema)]
+pub fn api_schema(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    impl_macro_api_schema(&input)
+}
+
 // =============================== Web ===============================
 //
 #[doc = ""]
@@ -329,17 +361,30 @@ method_macro!(AnyMapping, Any);
          - **Fixed-rate execution**: via the `fixed_rate` parameter (executes repeatedly at fixed intervals).\n\
          - **One-shot execution**: when `one_shot = true`, the task runs once after an optional `initial_delay`.\n\n\
          # Parameters\n\n\
-         - `cron`: A cron expression in 6-field format (seconds, minutes, hours, day-of-month, month, day-of-week). \n\
+         - `name`: Stable name of the job, used to manage it, defaults to the module path and \n\
+           the function name, e.g. `my_app::jobs::daily_cleanup`. Startup fails when two jobs have the same name.\n\
+         - `cron`: A cron expression in 6-field format (seconds, minutes, hours, day-of-month, month, day-of-week) \n\
+           or 5-field format without the seconds. The year field and the `L`, `W` and `#` syntax are not supported. \n\
            Mutually exclusive with `fixed_rate`.\n\
          - `fixed_rate`: Interval between executions (as a positive integer literal). \n\
            Mutually exclusive with `cron`.\n\
          - `initial_delay`: Delay before the first execution (in units specified by `time_unit`).\n\
          - `timezone`: IANA time zone ID (e.g., `\"Asia/Shanghai\"`, `\"UTC\"`). \n\
-           If empty or omitted, the scheduler's default time zone is used.\n\
+           Defaults to UTC, `\"local\"` uses the time zone of the host.\n\
          - `time_unit`: Time unit for `fixed_rate` and `initial_delay` (e.g., `\"ms\"`, `\"s\"`, `\"m\"`). \n\
            Interpretation depends on the underlying scheduler.\n\
          - `one_shot`: If `true`, the task runs exactly once (typically after `initial_delay`). \n\
-           In this mode, `cron` and `fixed_rate` are ignored.\n\n\
+           In this mode, `cron` and `fixed_rate` are ignored.\n\
+         - `lock_at_most_for`: Lock the job so that only one instance runs it at a time, e.g. `\"10m\"`. \n\
+           The lock is released after this duration even if the instance died while running the job.\n\
+         - `lock_at_least_for`: Keep the lock at least this long, e.g. `\"30s\"`, \n\
+           prevents other instances from running the job again right after it finished.\n\
+         - `lock_name`: Name of the lock, defaults to the name of the job.\n\
+         - `overlap`: What to do when the previous execution is still running: \n\
+           `\"concurrent\"` (default), `\"skip\"` or `\"queue\"`.\n\
+         - `misfire`: What to do when the job is late, e.g. after a clock jump: \n\
+           `\"fire_once\"` (default) or `\"skip\"`.\n\
+         - `misfire_threshold`: How late the job can be before it misfired, `\"5s\"` by default.\n\n\
          # Examples\n\n\
          ```rust\n\
          #[Scheduled(cron = \"0 0 3 * * *\", timezone = \"UTC\")]\n\
@@ -347,7 +392,9 @@ method_macro!(AnyMapping, Any);
          #[scheduled(fixed_rate = 30, time_unit = \"s\", initial_delay = 5)]\n\
          fn heartbeat() { /* ... */ }\n\n\
          #[Scheduled(one_shot = true, initial_delay = 10, time_unit = \"s\")]\n\
-         fn delayed_init() { /* ... */ }\n\
+         fn delayed_init() { /* ... */ }\n\n\
+         #[Scheduled(cron = \"0 0 1 * * *\", lock_at_most_for = \"30m\", lock_at_least_for = \"1m\")]\n\
+         async fn nightly_settlement() { /* ... */ }\n\
          ```"]
 #[proc_macro_attribute]
 #[allow(non_snake_case)]
@@ -407,7 +454,55 @@ pub fn Idempotency(attr: TokenStream, item: TokenStream) -> TokenStream {
     impl_macro_idempotency(attr, item_fn)
 }
 
-#[doc = ""]
+/// 方法调用前的权限校验
+///
+/// # 属性参数说明
+/// - 第一个参数或 `value`: 可选字符串字面量，安全表达式，例如 `hasRole('ADMIN') and #id == principal.id`
+/// - `role`:       可选字符串数组，需要的角色
+/// - `permission`: 可选字符串数组，需要的权限
+/// - `mode`:       可选 `"And"` 或 `"Or"`，`role` 与 `permission` 的组合方式，默认为 `"And"`
+/// - `ignore`:     可选布尔值，为 `true` 时不做校验
+/// - `basic`:      可选字符串字面量 `"username:password"`，使用 HTTP Basic 认证
+///
+/// 表达式中的 `#name` 引用同名的处理函数参数，参数需实现 `Serialize`。
+/// 未认证返回 401，认证但无权限返回 403，可注册 `Arc<dyn AccessDeniedHandler>` 自定义响应。
+/// 当前用户由 `Arc<dyn AuthenticationResolver>` 单例解析，例如 `BearerTokenAuthenticationResolver`。
+///
+/// # 注意
+/// 需要放在 `GetMapping` 等映射宏的上方
+///
+/// # 示例
+/// ```rust,ignore
+/// #[PreAuthorize("hasRole('ADMIN') or #id == principal.id")]
+/// #[GetMapping(path = "/users/{id}")]
+/// async fn get_user(Path(id): Path<u64>) -> impl IntoResponse {
+///     "Ok"
+/// }
+///
+/// #[PreAuthorize(role = ["ADMIN", "OPS"], mode = "Or")]
+/// #[DeleteMapping(path = "/cache")]
+/// async fn clear_cache() -> impl IntoResponse {
+///     "Ok"
+/// }
+/// ```
+///
+/// Authorizes a handler before it runs
+///
+/// # Description of Attribute Parameters
+/// - first argument or `value`: optional string literal, a security expression such as `hasRole('ADMIN') and #id == principal.id`
+/// - `role`: optional string array, the required roles
+/// - `permission`: optional string array, the required permissions
+/// - `mode`: optional `"And"` or `"Or"`, how `role` and `permission` combine, default is `"And"`
+/// - `ignore`: optional bool, skips the check when `true`
+/// - `basic`: optional string literal `"username:password"`, checks HTTP Basic credentials instead
+///
+/// `#name` in the expression reads the handler parameter of the same name, which must implement `Serialize`.
+/// Anonymous requests get a 401 and authenticated ones without access a 403, register an
+/// `Arc<dyn AccessDeniedHandler>` to change the responses. The subject is resolved by the
+/// `Arc<dyn AuthenticationResolver>` singleton, e.g. `BearerTokenAuthenticationResolver`.
+///
+/// # Attention
+/// Place it above the mapping macro such as `GetMapping`
 #[allow(non_snake_case)]
 #[proc_macro_attribute]
 pub fn PreAuthorize(attr: TokenStream, item: TokenStream) -> TokenStream {
@@ -415,6 +510,60 @@ pub fn PreAuthorize(attr: TokenStream, item: TokenStream) -> TokenStream {
     impl_macro_pre_authorize(attr, item_fn)
 }
 
+/// 方法返回后的权限校验
+///
+/// 表达式可以通过 `returnObject` 读取返回值，`Result` 取 `Ok` 中的值(`Err` 不校验)，`Json` 取其内部的值。
+/// 返回类型不能是 `impl Trait`。
+///
+/// # 示例
+/// ```rust,ignore
+/// #[PostAuthorize("returnObject.owner == principal.sub")]
+/// #[GetMapping(path = "/orders/{id}")]
+/// async fn get_order(Path(id): Path<u64>) -> Result<Json<Order>, ApiError> {
+///     todo!()
+/// }
+/// ```
+///
+/// Authorizes a handler after it returned
+///
+/// The expression reads the returned value through `returnObject`, the `Ok` value of a
+/// `Result` (an `Err` is returned unchecked) and the inner value of `Json`.
+/// The return type must not be `impl Trait`.
+///
+/// # Attention
+/// Place it above the mapping macro such as `GetMapping`
+#[allow(non_snake_case)]
+#[proc_macro_attribute]
+pub fn PostAuthorize(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let item_fn = parse_macro_input!(item as ItemFn);
+    impl_macro_post_authorize(attr, item_fn)
+}
+
+/// 按角色或权限校验
+///
+/// 拥有任意一个给定的角色或权限即可访问，等同于 `#[PreAuthorize("hasAnyAuthority(...)")]`。
+///
+/// # 示例
+/// ```rust,ignore
+/// #[Secured("ADMIN", "order:write")]
+/// #[PostMapping(path = "/orders")]
+/// async fn create_order() -> impl IntoResponse {
+///     "Ok"
+/// }
+/// ```
+///
+/// Grants access to subjects holding any of the given roles or permissions, the same as
+/// `#[PreAuthorize("hasAnyAuthority(...)")]`.
+///
+/// # Attention
+/// Place it above the mapping macro such as `GetMapping`
+#[allow(non_snake_case)]
+#[proc_macro_attribute]
+pub fn Secured(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let item_fn = parse_macro_input!(item as ItemFn);
+    impl_macro_secured(attr, item_fn)
+}
+
 /// 实现可重试逻辑的过程宏
 ///
 /// # 属性参数说明
@@ -0,0 +1,206 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{Expr, FnArg, GenericArgument, ItemFn, Lit, LitStr, PathArguments, ReturnType, Type};
+
+use crate::util::field_type::FieldType;
+
+use super::api_schema::doc_comment;
+
+/// The `ApiOperation` of a handler, built from its doc comments and signature.
+///
+/// Must run before the signature is rewritten, the return type is replaced by
+/// `impl IntoResponse`.
+pub(crate) fn api_operation(item_fn: &ItemFn, path: &Expr, headers: &[LitStr]) -> TokenStream2 {
+    let mut calls = Vec::new();
+
+    if let Some(doc) = doc_comment(&item_fn.attrs) {
+        let (summary, description) = match doc.split_once('\n') {
+            Some((summary, description)) => (summary.to_string(), description.trim().to_string()),
+            None => (doc, String::new()),
+        };
+        calls.push(quote! { .summary(#summary) });
+        if !description.is_empty() {
+            calls.push(quote! { .description(#description) });
+        }
+    }
+    if item_fn
+        .attrs
+        .iter()
+        .any(|attr| attr.path().is_ident("deprecated"))
+    {
+        calls.push(quote! { .deprecated(true) });
+    }
+
+    let arg_types = item_fn
+        .sig
+        .inputs
+        .iter()
+        .filter_map(|arg| match arg {
+            FnArg::Typed(pat_type) => Some(pat_type.ty.as_ref()),
+            FnArg::Receiver(_) => None,
+        })
+        .collect::<Vec<_>>();
+
+    // Path parameters, typed by the `Path<T>` extractor when there is one
+    let path_params = match path {
+        Expr::Lit(expr_lit) => match &expr_lit.lit {
+            Lit::Str(lit_str) => path_params(&lit_str.value()),
+            _ => Vec::new(),
+        },
+        _ => Vec::new(),
+    };
+    let path_type = arg_types
+        .iter()
+        .find_map(|ty| extractor(ty, &["Path"]).map(|(_, inner)| inner));
+    let path_types = match path_type {
+        Some(Type::Tuple(tuple)) if tuple.elems.len() == path_params.len() => {
+            tuple.elems.iter().map(Some).collect()
+        }
+        Some(ty) if path_params.len() == 1 => vec![Some(ty)],
+        _ => vec![None; path_params.len()],
+    };
+    for (name, ty) in path_params.iter().zip(path_types) {
+        let schema = match ty {
+            Some(ty) => schema_fn(ty),
+            None => schema_fn(&syn::parse_quote!(String)),
+        };
+        calls.push(quote! { .path_param(#name, #schema) });
+    }
+
+    for header in headers {
+        let name = kebab_case(header.value().trim());
+        if !name.is_empty() {
+            calls.push(quote! { .header(#name, true) });
+        }
+    }
+
+    for ty in &arg_types {
+        match extractor(ty, &["Json", "Validated", "Data", "Form", "Query"]) {
+            Some(("Query", inner)) => {
+                let schema = schema_fn(inner);
+                calls.push(quote! { .query(#schema) });
+            }
+            Some((name, inner)) => {
+                let content_type = match name {
+                    "Form" => "application/x-www-form-urlencoded",
+                    _ => "application/json",
+                };
+                let schema = schema_fn(inner);
+                calls.push(quote! { .request_body(#content_type, #schema) });
+            }
+            None => {}
+        }
+    }
+
+    if let ReturnType::Type(_, ty) = &item_fn.sig.output {
+        if let Some(response) = response(ty) {
+            calls.push(response);
+        }
+    }
+
+    quote! {
+        fn operation(&self) -> ::std::option::Option<::next_web_dev::openapi::ApiOperation> {
+            ::std::option::Option::Some(::next_web_dev::openapi::ApiOperation::new() #(#calls)*)
+        }
+    }
+}
+
+fn response(ty: &Type) -> Option<TokenStream2> {
+    if FieldType::is_result(ty) {
+        let (_, inner) = generic_argument(ty)?;
+        return response(inner);
+    }
+
+    let argument = generic_argument(ty);
+    match argument
+        .as_ref()
+        .map(|(name, inner)| (name.as_str(), *inner))
+    {
+        Some(("ApiResponse", data)) => {
+            let data = schema_fn(data);
+            Some(quote! {
+                .response("application/json", {
+                    fn schema(registry: &mut ::next_web_dev::openapi::SchemaRegistry) -> ::next_web_dev::openapi::serde_json::Value {
+                        ::next_web_dev::openapi::api_response_schema(registry, #data)
+                    }
+                    ::std::option::Option::Some(schema as ::next_web_dev::openapi::SchemaFn)
+                })
+            })
+        }
+        Some(("Json", inner)) => {
+            let schema = schema_fn(inner);
+            Some(quote! { .response("application/json", #schema) })
+        }
+        _ if is_text(ty) => {
+            let schema = schema_fn(&syn::parse_quote!(String));
+            Some(quote! { .response("text/plain", #schema) })
+        }
+        _ => None,
+    }
+}
+
+/// `String`, `&str` and `&'static str`.
+fn is_text(ty: &Type) -> bool {
+    match ty {
+        Type::Path(type_path) => type_path.path.is_ident("String"),
+        Type::Reference(reference) => {
+            matches!(reference.elem.as_ref(), Type::Path(type_path) if type_path.path.is_ident("str"))
+        }
+        _ => false,
+    }
+}
+
+/// `Some(SchemaFn)` when the type implements `ApiSchema`, `None` otherwise.
+fn schema_fn(ty: &Type) -> TokenStream2 {
+    quote! {
+        {
+            #[allow(unused_imports)]
+            use ::next_web_dev::openapi::{ViaApiSchema as _, ViaUnknown as _};
+            (&&::next_web_dev::openapi::SchemaProbe::<#ty>::new()).schema_fn()
+        }
+    }
+}
+
+/// The extractor name and its type argument, e.g. `Json` and `User` for `Json<User>`.
+fn extractor<'a>(ty: &'a Type, names: &[&'static str]) -> Option<(&'static str, &'a Type)> {
+    let (name, inner) = generic_argument(ty)?;
+    names
+        .iter()
+        .find(|candidate| **candidate == name.as_str())
+        .map(|candidate| (*candidate, inner))
+}
+
+/// The last segment of a path type and its first type argument.
+fn generic_argument(ty: &Type) -> Option<(String, &Type)> {
+    let Type::Path(type_path) = ty else {
+        return None;
+    };
+    let segment = type_path.path.segments.last()?;
+    let PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+        return None;
+    };
+    arguments.args.iter().find_map(|argument| match argument {
+        GenericArgument::Type(inner) => Some((segment.ident.to_string(), inner)),
+        _ => None,
+    })
+}
+
+/// `/users/{id}/files/{*path}` to `["id", "path"]`.
+fn path_params(path: &str) -> Vec<String> {
+    path.split('/')
+        .filter_map(|segment| segment.strip_prefix('{')?.strip_suffix('}'))
+        .map(|name| name.trim_start_matches('*').to_string())
+        .collect()
+}
+
+/// `ContentType` to `content-type`.
+fn kebab_case(name: &str) -> String {
+    let mut kebab = String::new();
+    for (index, ch) in name.char_indices() {
+        if index > 0 && ch.is_uppercase() {
+            kebab.push('-');
+        }
+        kebab.push(ch.to_ascii_lowercase());
+    }
+    kebab
+}
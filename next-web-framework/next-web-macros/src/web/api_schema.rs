@@ -0,0 +1,366 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::meta::ParseNestedMeta;
+use syn::spanned::Spanned;
+use syn::{Attribute, Data, DeriveInput, Expr, Fields, GenericParam, Lit, LitStr, Meta};
+
+use crate::util::field_type::FieldType;
+
+pub(crate) fn impl_macro_api_schema(input: &DeriveInput) -> TokenStream {
+    match expand(input) {
+        Ok(stream) => stream.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+fn expand(input: &DeriveInput) -> Result<TokenStream2, syn::Error> {
+    let name = &input.ident;
+    let container = SerdeAttrs::parse(&input.attrs)?;
+
+    let mut generics = input.generics.clone();
+    for param in generics.params.iter_mut() {
+        if let GenericParam::Type(param) = param {
+            param
+                .bounds
+                .push(syn::parse_quote!(::next_web_dev::openapi::ApiSchema));
+        }
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let body = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(_) => object_schema(&data.fields, &container)?,
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                // Newtype structs are transparent, keep the name of the inner type
+                let ty = &fields.unnamed[0].ty;
+                return Ok(quote! {
+                    impl #impl_generics ::next_web_dev::openapi::ApiSchema for #name #ty_generics #where_clause {
+                        fn schema_name() -> ::std::option::Option<::std::borrow::Cow<'static, str>> {
+                            <#ty as ::next_web_dev::openapi::ApiSchema>::schema_name()
+                        }
+
+                        fn schema(registry: &mut ::next_web_dev::openapi::SchemaRegistry) -> ::next_web_dev::openapi::serde_json::Value {
+                            <#ty as ::next_web_dev::openapi::ApiSchema>::schema(registry)
+                        }
+                    }
+                });
+            }
+            Fields::Unnamed(fields) => tuple_schema(fields.unnamed.iter().map(|field| &field.ty)),
+            Fields::Unit => {
+                quote! { ::next_web_dev::openapi::serde_json::json!({ "type": "null" }) }
+            }
+        },
+        Data::Enum(data) => {
+            let mut variants = Vec::new();
+            let mut unit_only = true;
+            for variant in &data.variants {
+                let attrs = SerdeAttrs::parse(&variant.attrs)?;
+                if attrs.skip {
+                    continue;
+                }
+                let variant_name = attrs.rename.clone().unwrap_or_else(|| {
+                    rename_variant(&variant.ident.to_string(), container.rename_all.as_deref())
+                });
+                let schema = match &variant.fields {
+                    Fields::Unit => None,
+                    Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                        let ty = &fields.unnamed[0].ty;
+                        Some(quote! { registry.reference::<#ty>() })
+                    }
+                    Fields::Unnamed(fields) => {
+                        Some(tuple_schema(fields.unnamed.iter().map(|field| &field.ty)))
+                    }
+                    Fields::Named(_) => Some(object_schema(&variant.fields, &attrs)?),
+                };
+                unit_only &= schema.is_none();
+                variants.push((variant_name, schema));
+            }
+
+            if unit_only {
+                let names = variants.iter().map(|(name, _)| name);
+                quote! {
+                    ::next_web_dev::openapi::serde_json::json!({ "type": "string", "enum": [#(#names),*] })
+                }
+            } else {
+                // Externally tagged, the representation serde uses by default
+                let variants = variants.iter().map(|(name, schema)| match schema {
+                    Some(schema) => quote! {
+                        ::next_web_dev::openapi::serde_json::json!({
+                            "type": "object",
+                            "properties": { #name: #schema },
+                            "required": [#name],
+                            "additionalProperties": false,
+                        })
+                    },
+                    None => quote! {
+                        ::next_web_dev::openapi::serde_json::json!({ "const": #name })
+                    },
+                });
+                quote! {
+                    ::next_web_dev::openapi::serde_json::json!({ "oneOf": [#(#variants),*] })
+                }
+            }
+        }
+        Data::Union(_) => {
+            return Err(syn::Error::new(
+                input.span(),
+                "#[derive(ApiSchema)] is not supported for unions",
+            ))
+        }
+    };
+
+    let description = doc_comment(&input.attrs);
+    let description = description.map(|description| {
+        quote! {
+            if let ::next_web_dev::openapi::serde_json::Value::Object(map) = &mut schema {
+                map.insert("description".into(), #description.into());
+            }
+        }
+    });
+
+    // Generic types are inlined, one name can't describe every instantiation
+    let schema_name = match input.generics.type_params().next() {
+        Some(_) => quote! { ::std::option::Option::None },
+        None => {
+            let schema_name = container.rename.clone().unwrap_or_else(|| name.to_string());
+            quote! { ::std::option::Option::Some(::std::borrow::Cow::Borrowed(#schema_name)) }
+        }
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::next_web_dev::openapi::ApiSchema for #name #ty_generics #where_clause {
+            fn schema_name() -> ::std::option::Option<::std::borrow::Cow<'static, str>> {
+                #schema_name
+            }
+
+            #[allow(unused_variables, unused_mut)]
+            fn schema(registry: &mut ::next_web_dev::openapi::SchemaRegistry) -> ::next_web_dev::openapi::serde_json::Value {
+                let mut schema = #body;
+                #description
+                schema
+            }
+        }
+    })
+}
+
+/// The schema of the named fields, as an expression using `registry`.
+fn object_schema(fields: &Fields, container: &SerdeAttrs) -> Result<TokenStream2, syn::Error> {
+    let mut statements = Vec::new();
+
+    for field in fields {
+        let attrs = SerdeAttrs::parse(&field.attrs)?;
+        if attrs.skip {
+            continue;
+        }
+        let ty = &field.ty;
+
+        if attrs.flatten {
+            statements.push(quote! {
+                {
+                    let flattened = registry.reference::<#ty>();
+                    let flattened = registry.resolve(&flattened).clone();
+                    if let Some(flattened) = flattened.get("properties").and_then(|value| value.as_object()) {
+                        properties.extend(flattened.clone());
+                    }
+                    if let Some(flattened) = flattened.get("required").and_then(|value| value.as_array()) {
+                        required.extend(flattened.iter().cloned());
+                    }
+                }
+            });
+            continue;
+        }
+
+        let ident = field.ident.as_ref().map(|ident| ident.to_string());
+        let field_name = match attrs.rename {
+            Some(rename) => rename,
+            None => rename_field(
+                ident
+                    .as_deref()
+                    .unwrap_or_default()
+                    .trim_start_matches("r#"),
+                container.rename_all.as_deref(),
+            ),
+        };
+
+        let description = doc_comment(&field.attrs).map(|description| {
+            quote! {
+                if let ::next_web_dev::openapi::serde_json::Value::Object(map) = &mut property {
+                    map.insert("description".into(), #description.into());
+                }
+            }
+        });
+        let is_required = !(attrs.default || container.default || FieldType::is_option(ty));
+        let push_required = is_required.then(|| {
+            quote! { required.push(#field_name.into()); }
+        });
+
+        statements.push(quote! {
+            {
+                let mut property = registry.reference::<#ty>();
+                #description
+                properties.insert(#field_name.into(), property);
+                #push_required
+            }
+        });
+    }
+
+    Ok(quote! {
+        {
+            let mut properties = ::next_web_dev::openapi::serde_json::Map::new();
+            let mut required: ::std::vec::Vec<::next_web_dev::openapi::serde_json::Value> = ::std::vec::Vec::new();
+            #(#statements)*
+
+            let mut object = ::next_web_dev::openapi::serde_json::json!({ "type": "object", "properties": properties });
+            if !required.is_empty() {
+                object["required"] = ::next_web_dev::openapi::serde_json::Value::Array(required);
+            }
+            object
+        }
+    })
+}
+
+fn tuple_schema<'a>(types: impl Iterator<Item = &'a syn::Type>) -> TokenStream2 {
+    let items = types
+        .map(|ty| quote! { registry.reference::<#ty>() })
+        .collect::<Vec<_>>();
+    let len = items.len();
+    quote! {
+        ::next_web_dev::openapi::serde_json::json!({
+            "type": "array",
+            "prefixItems": [#(#items),*],
+            "minItems": #len,
+            "maxItems": #len,
+        })
+    }
+}
+
+/// The doc comments joined into a description.
+pub(crate) fn doc_comment(attrs: &[Attribute]) -> Option<String> {
+    let lines = attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc"))
+        .filter_map(|attr| match &attr.meta {
+            Meta::NameValue(name_value) => match &name_value.value {
+                Expr::Lit(expr_lit) => match &expr_lit.lit {
+                    Lit::Str(lit_str) => Some(lit_str.value().trim().to_string()),
+                    _ => None,
+                },
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    let description = lines.join("\n").trim().to_string();
+    (!description.is_empty()).then_some(description)
+}
+
+/// The `serde` attributes changing the shape of the schema.
+#[derive(Default)]
+struct SerdeAttrs {
+    rename: Option<String>,
+    rename_all: Option<String>,
+    skip: bool,
+    default: bool,
+    flatten: bool,
+}
+
+impl SerdeAttrs {
+    fn parse(attrs: &[Attribute]) -> Result<Self, syn::Error> {
+        let mut result = Self::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("serde")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") && meta.input.peek(syn::Token![=]) {
+                    result.rename = Some(meta.value()?.parse::<LitStr>()?.value());
+                } else if meta.path.is_ident("rename_all") && meta.input.peek(syn::Token![=]) {
+                    result.rename_all = Some(meta.value()?.parse::<LitStr>()?.value());
+                } else if meta.path.is_ident("skip") {
+                    result.skip = true;
+                } else if meta.path.is_ident("flatten") {
+                    result.flatten = true;
+                } else if meta.path.is_ident("default") {
+                    result.default = true;
+                    skip_meta(&meta)?;
+                } else {
+                    skip_meta(&meta)?;
+                }
+                Ok(())
+            })?;
+        }
+        Ok(result)
+    }
+}
+
+/// Consume the value of a `serde` attribute the schema doesn't depend on.
+fn skip_meta(meta: &ParseNestedMeta) -> Result<(), syn::Error> {
+    if meta.input.peek(syn::Token![=]) {
+        meta.value()?.parse::<Expr>()?;
+    } else if meta.input.peek(syn::token::Paren) {
+        meta.parse_nested_meta(|nested| skip_meta(&nested))?;
+    }
+    Ok(())
+}
+
+/// `rename_all` applied to a `snake_case` field, as serde does.
+fn rename_field(field: &str, rule: Option<&str>) -> String {
+    match rule {
+        Some("lowercase") | Some("snake_case") => field.to_string(),
+        Some("UPPERCASE") | Some("SCREAMING_SNAKE_CASE") => field.to_ascii_uppercase(),
+        Some("PascalCase") => pascal_case(field),
+        Some("camelCase") => {
+            let pascal = pascal_case(field);
+            let mut chars = pascal.chars();
+            match chars.next() {
+                Some(first) => first.to_lowercase().chain(chars).collect(),
+                None => pascal,
+            }
+        }
+        Some("kebab-case") => field.replace('_', "-"),
+        Some("SCREAMING-KEBAB-CASE") => field.to_ascii_uppercase().replace('_', "-"),
+        _ => field.to_string(),
+    }
+}
+
+/// `rename_all` applied to a `PascalCase` variant, as serde does.
+fn rename_variant(variant: &str, rule: Option<&str>) -> String {
+    let snake = || {
+        let mut snake = String::new();
+        for (index, ch) in variant.char_indices() {
+            if index > 0 && ch.is_uppercase() {
+                snake.push('_');
+            }
+            snake.push(ch.to_ascii_lowercase());
+        }
+        snake
+    };
+    match rule {
+        Some("lowercase") => variant.to_ascii_lowercase(),
+        Some("UPPERCASE") => variant.to_ascii_uppercase(),
+        Some("camelCase") => {
+            let mut chars = variant.chars();
+            match chars.next() {
+                Some(first) => first.to_lowercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        }
+        Some("snake_case") => snake(),
+        Some("SCREAMING_SNAKE_CASE") => snake().to_ascii_uppercase(),
+        Some("kebab-case") => snake().replace('_', "-"),
+        Some("SCREAMING-KEBAB-CASE") => snake().to_ascii_uppercase().replace('_', "-"),
+        _ => variant.to_string(),
+    }
+}
+
+fn pascal_case(field: &str) -> String {
+    field
+        .split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect::<String>(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
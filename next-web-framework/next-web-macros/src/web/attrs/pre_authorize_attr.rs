@@ -4,15 +4,25 @@ use syn::LitStr;
 #[derive(FromAttr)]
 #[attribute(idents = [value])]
 pub struct PreAuthorizeAttr {
+    // security expression, may also be given as the first positional argument
+    #[attribute(conflicts = [ignore, basic])]
+    pub value: Option<LitStr>,
     pub role: Option<Vec<LitStr>>,
     pub permission: Option<Vec<LitStr>>,
     // Or And
     pub mode: Option<LitStr>,
 
-    #[attribute(conflicts = [role, permission, mode, basic])]
+    #[attribute(conflicts = [value, role, permission, mode, basic])]
     pub ignore: Option<bool>,
 
     // http basic auth
-    #[attribute(conflicts = [ignore, role, permission, mode])]
+    #[attribute(conflicts = [value, ignore, role, permission, mode])]
     pub basic: Option<LitStr>,
-}
\ No newline at end of file
+}
+
+#[derive(FromAttr)]
+#[attribute(idents = [value])]
+pub struct PostAuthorizeAttr {
+    // security expression, may also be given as the first positional argument
+    pub value: Option<LitStr>,
+}
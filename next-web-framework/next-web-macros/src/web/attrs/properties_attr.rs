@@ -9,6 +9,10 @@ pub(crate) struct PropertiesAttr {
     pub(crate) prefix: Expr,
 
     pub(crate) dynamic: bool,
+
+    pub(crate) refreshable: bool,
+
+    pub(crate) validate: bool,
 }
 
 fn default_name() -> Expr {
@@ -20,6 +24,8 @@ impl Default for PropertiesAttr {
         Self {
             prefix: default_name(),
             dynamic: false,
+            refreshable: false,
+            validate: false,
         }
     }
 }
\ No newline at end of file
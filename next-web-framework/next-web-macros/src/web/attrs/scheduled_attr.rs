@@ -5,6 +5,8 @@ use syn::{LitInt, LitStr};
 #[derive(FromAttr)]
 #[attribute(idents = [find])]
 pub struct ScheduledAttr {
+    pub name: Option<LitStr>,
+
     #[attribute(conflicts = [fixed_rate])]
     pub cron: Option<LitStr>,
     #[attribute(conflicts = [cron])]
@@ -15,4 +17,12 @@ pub struct ScheduledAttr {
     pub time_unit: Option<LitStr>,
 
     pub one_shot: bool,
+
+    pub lock_name: Option<LitStr>,
+    pub lock_at_most_for: Option<LitStr>,
+    pub lock_at_least_for: Option<LitStr>,
+
+    pub overlap: Option<LitStr>,
+    pub misfire: Option<LitStr>,
+    pub misfire_threshold: Option<LitStr>,
 }
\ No newline at end of file
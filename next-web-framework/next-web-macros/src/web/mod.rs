@@ -1,3 +1,5 @@
+pub mod api_operation;
+pub mod api_schema;
 mod attrs;
 pub mod idempotency;
 pub mod pre_authorize;
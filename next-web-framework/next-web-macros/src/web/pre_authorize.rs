@@ -1,45 +1,448 @@
 use from_attr::FromAttr;
 use proc_macro::TokenStream;
-use proc_macro2::TokenStream as TokenStream2;
-use syn::{spanned::Spanned, ItemFn};
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::{
+    parse::{ParseStream, Parser},
+    punctuated::Punctuated,
+    spanned::Spanned,
+    GenericArgument, Ident, ItemFn, LitStr, PathArguments, Token, Type,
+};
 
-use crate::util::logic::Logic;
+use crate::util::{id::unique_id, logic::Logic};
 
-use super::attrs::pre_authorize_attr::PreAuthorizeAttr;
+use super::attrs::pre_authorize_attr::{PostAuthorizeAttr, PreAuthorizeAttr};
 
-pub fn impl_macro_pre_authorize(attrs: TokenStream, item_fn: ItemFn) -> TokenStream {
-    let expanded = Logic::generate(|| {
-        let vis = &item_fn.vis;
-        let sig = &item_fn.sig;
-        let name = &sig.ident;
-
-        if sig.asyncness.is_none() {
-            return Err(syn::Error::new(
-                sig.span(),
-                "Function must be declared as async",
-            ));
-        }
-
-        if matches!(sig.output, syn::ReturnType::Default) {
-            return Err(syn::Error::new(
-                sig.output.span(),
-                "Function has no return type. Cannot be used as handler",
-            ));
-        }
+pub fn impl_macro_pre_authorize(attrs: TokenStream, mut item_fn: ItemFn) -> TokenStream {
+    Logic::generate(|| {
+        valid_handler(&item_fn, "PreAuthorize")?;
 
+        let (positional, rest) = positional_expression.parse2(attrs.clone().into())?;
         let PreAuthorizeAttr {
+            value,
             role,
             permission,
             mode,
             ignore,
             basic,
-        } = match PreAuthorizeAttr::from_tokens(attrs.clone().into()) {
-            Ok(attr) => attr,
-            Err(error) => return Err(error),
+        } = PreAuthorizeAttr::from_tokens(rest)?;
+
+        if ignore.unwrap_or_default() {
+            return Ok(quote! { #item_fn });
+        }
+
+        let value = match (positional, value) {
+            (Some(positional), Some(_)) => {
+                return Err(syn::Error::new(
+                    positional.span(),
+                    "The expression is given twice, use either the first argument or `value`",
+                ))
+            }
+            (positional, value) => positional.or(value),
+        };
+
+        let (parts, extension) = guard_args(&mut item_fn);
+
+        if let Some(basic) = basic {
+            if !basic.value().contains(':') {
+                return Err(syn::Error::new(
+                    basic.span(),
+                    "basic must be given as \"username:password\"",
+                ));
+            }
+            let block = quote! {
+                if let Some(__security_response) =
+                    ::next_web_dev::security::web::method::method_security::MethodSecurity::check_basic(&#parts, #basic)
+                {
+                    return __security_response;
+                }
+            };
+            add_guard(&mut item_fn, block);
+            return Ok(quote! { #item_fn });
+        }
+
+        let mut conditions = Vec::new();
+        if let Some(value) = value {
+            validate_expression(&value, false)?;
+            conditions.push(format!("({})", value.value()));
+        }
+
+        let mut authorities = Vec::new();
+        for role in role.unwrap_or_default() {
+            authorities.push(format!("hasRole({})", quote_literal(&role.value())));
+        }
+        for permission in permission.unwrap_or_default() {
+            authorities.push(format!(
+                "hasPermission({})",
+                quote_literal(&permission.value())
+            ));
+        }
+        if !authorities.is_empty() {
+            let operator = match mode.as_ref().map(|mode| mode.value().to_lowercase()) {
+                None => " and ",
+                Some(mode) if mode == "and" => " and ",
+                Some(mode) if mode == "or" => " or ",
+                Some(_) => {
+                    return Err(syn::Error::new(
+                        mode.span(),
+                        "mode must be \"And\" or \"Or\"",
+                    ))
+                }
+            };
+            conditions.push(format!("({})", authorities.join(operator)));
+        }
+
+        let source = if conditions.is_empty() {
+            "isAuthenticated()".to_string()
+        } else {
+            conditions.join(" and ")
         };
+        let source = LitStr::new(&source, Span::call_site());
+        let variables = validate_expression(&source, false)?;
+
+        let context = evaluation_context(&parts, &extension, &variables);
+        let check = check(&extension, &source);
+        add_guard(&mut item_fn, quote! { #context #check });
+
+        Ok(quote! { #item_fn })
+    })
+}
+
+pub fn impl_macro_post_authorize(attrs: TokenStream, mut item_fn: ItemFn) -> TokenStream {
+    Logic::generate(|| {
+        valid_handler(&item_fn, "PostAuthorize")?;
+
+        let (positional, rest) = positional_expression.parse2(attrs.clone().into())?;
+        let PostAuthorizeAttr { value } = PostAuthorizeAttr::from_tokens(rest)?;
+        let source = match (positional, value) {
+            (Some(source), None) | (None, Some(source)) => source,
+            (Some(positional), Some(_)) => {
+                return Err(syn::Error::new(
+                    positional.span(),
+                    "The expression is given twice, use either the first argument or `value`",
+                ))
+            }
+            (None, None) => {
+                return Err(syn::Error::new(
+                    Span::call_site(),
+                    "PostAuthorize requires an expression, e.g. #[PostAuthorize(\"returnObject.owner == principal.sub\")]",
+                ))
+            }
+        };
+        let variables = validate_expression(&source, true)?;
+
+        let return_type = match &item_fn.sig.output {
+            syn::ReturnType::Type(_, ty) if !matches!(ty.as_ref(), Type::ImplTrait(_)) => {
+                ty.as_ref().clone()
+            }
+            output => {
+                return Err(syn::Error::new(
+                    output.span(),
+                    "PostAuthorize needs a concrete return type to read `returnObject`",
+                ))
+            }
+        };
+
+        let (parts, extension) = guard_args(&mut item_fn);
+        let context = evaluation_context(&parts, &extension, &variables);
+        let check = check(&extension, &source);
+
+        let stmts = &item_fn.block.stmts;
+        let (is_result, is_json) = inspect_return_type(&return_type);
+        let value = if is_json {
+            quote! { &__security_value.0 }
+        } else {
+            quote! { __security_value }
+        };
+        let verify = if is_result {
+            quote! {
+                if let Ok(__security_value) = &__security_return {
+                    __security_context.set_return_object(#value);
+                    #check
+                }
+            }
+        } else {
+            quote! {
+                {
+                    let __security_value = &__security_return;
+                    __security_context.set_return_object(#value);
+                    #check
+                }
+            }
+        };
+
+        *item_fn.block = syn::parse2(quote! {
+            {
+                #context
+
+                let __security_return: #return_type =
+                    ::next_web_dev::security::web::method::method_security::MethodSecurity::invoke::<#return_type, _>(
+                        async move { #(#stmts)* }
+                    )
+                    .await;
+
+                #verify
+
+                __security_return
+            }
+        })?;
+
+        Ok(quote! { #item_fn })
+    })
+}
+
+pub fn impl_macro_secured(attrs: TokenStream, mut item_fn: ItemFn) -> TokenStream {
+    Logic::generate(|| {
+        valid_handler(&item_fn, "Secured")?;
 
-        Ok(TokenStream2::new())
+        let authorities =
+            Punctuated::<LitStr, Token![,]>::parse_terminated.parse2(attrs.clone().into())?;
+        if authorities.is_empty() {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                "Secured requires at least one authority, e.g. #[Secured(\"ROLE_ADMIN\")]",
+            ));
+        }
+
+        let source = format!(
+            "hasAnyAuthority({})",
+            authorities
+                .iter()
+                .map(|authority| quote_literal(&authority.value()))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        let source = LitStr::new(&source, Span::call_site());
+
+        let (parts, extension) = guard_args(&mut item_fn);
+        let context = evaluation_context(&parts, &extension, &[]);
+        let check = check(&extension, &source);
+        add_guard(&mut item_fn, quote! { #context #check });
+
+        Ok(quote! { #item_fn })
+    })
+}
+
+fn valid_handler(item_fn: &ItemFn, name: &str) -> syn::Result<()> {
+    Logic::valid_method_handler(item_fn)?;
+
+    // The guard returns early with a `Response`, which only type checks once the mapping
+    // macro has unified the return type to `impl IntoResponse`.
+    let mapped = item_fn.attrs.iter().any(|attr| {
+        attr.path()
+            .segments
+            .last()
+            .map(|segment| segment.ident.to_string().ends_with("Mapping"))
+            .unwrap_or(false)
     });
+    if !mapped {
+        return Err(syn::Error::new(
+            item_fn.sig.ident.span(),
+            format!(
+                "{} must be placed above a mapping attribute such as #[GetMapping]",
+                name
+            ),
+        ));
+    }
+    Ok(())
+}
+
+/// Splits `"expression", key = value, ..` into the leading string literal and the rest.
+fn positional_expression(input: ParseStream) -> syn::Result<(Option<LitStr>, TokenStream2)> {
+    if input.peek(LitStr) {
+        let expression = input.parse::<LitStr>()?;
+        if !input.is_empty() {
+            input.parse::<Token![,]>()?;
+        }
+        Ok((Some(expression), input.parse()?))
+    } else {
+        Ok((None, input.parse()?))
+    }
+}
+
+/// Checks quotes and parentheses and returns the `#variable` names the expression reads.
+fn validate_expression(expression: &LitStr, allow_return_object: bool) -> syn::Result<Vec<String>> {
+    let source = expression.value();
+    let error = |message: &str| syn::Error::new(expression.span(), message);
+
+    if source.trim().is_empty() {
+        return Err(error("The security expression is empty"));
+    }
+
+    let chars = source.chars().collect::<Vec<_>>();
+    let mut variables: Vec<String> = Vec::new();
+    let mut depth = 0usize;
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            quote @ ('\'' | '"') => match chars[i + 1..].iter().position(|c| *c == quote) {
+                Some(end) => i += end + 1,
+                None => return Err(error("Unterminated string in security expression")),
+            },
+            '(' => depth += 1,
+            ')' => {
+                depth = depth
+                    .checked_sub(1)
+                    .ok_or_else(|| error("Unbalanced ')' in security expression"))?
+            }
+            '#' => {
+                let name = chars[i + 1..]
+                    .iter()
+                    .take_while(|c| c.is_alphanumeric() || **c == '_')
+                    .collect::<String>();
+                if name.is_empty() {
+                    return Err(error("Expected a parameter name after '#'"));
+                }
+                i += name.chars().count();
+                if !variables.contains(&name) {
+                    variables.push(name);
+                }
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let word = chars[i..]
+                    .iter()
+                    .take_while(|c| c.is_alphanumeric() || **c == '_')
+                    .collect::<String>();
+                if word == "returnObject" && !allow_return_object {
+                    return Err(error("returnObject is only available in #[PostAuthorize]"));
+                }
+                i += word.chars().count() - 1;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    if depth != 0 {
+        return Err(error("Unbalanced '(' in security expression"));
+    }
+
+    Ok(variables)
+}
+
+fn quote_literal(value: &str) -> String {
+    if value.contains('\'') {
+        format!("\"{}\"", value)
+    } else {
+        format!("'{}'", value)
+    }
+}
+
+/// Adds the request parts and application state extractors the guards need.
+fn guard_args(item_fn: &mut ItemFn) -> (Ident, Ident) {
+    let id = unique_id();
+    let parts = Ident::new(&format!("__security_parts_{}", id), Span::call_site());
+    let extension = Ident::new(&format!("__security_extension_{}", id), Span::call_site());
+
+    Logic::add_args(
+        item_fn,
+        [
+            quote! { #parts: ::next_web_dev::http::request::Parts },
+            quote! {
+                ::next_web_dev::extract::Extension( #extension ) :
+                ::next_web_dev::extract::Extension< ::next_web_dev::state::application_state::ApplicationState >
+            },
+        ]
+        .into_iter(),
+    );
+
+    (parts, extension)
+}
+
+fn evaluation_context(parts: &Ident, extension: &Ident, variables: &[String]) -> TokenStream2 {
+    let variables = variables.iter().map(|name| {
+        let ident = Ident::new(name, Span::call_site());
+        quote! { __security_context.set_variable(#name, &#ident); }
+    });
+
+    quote! {
+        #[allow(unused_mut)]
+        let mut __security_context =
+            ::next_web_dev::security::web::method::method_security::MethodSecurity::context(&#extension, &#parts).await;
+        #(#variables)*
+    }
+}
+
+fn check(extension: &Ident, source: &LitStr) -> TokenStream2 {
+    quote! {
+        static __SECURITY_EXPRESSION: ::std::sync::OnceLock<
+            ::std::result::Result<
+                ::next_web_dev::security::core::authz::expression::security_expression::SecurityExpression,
+                ::next_web_dev::security::core::authz::expression::expression_error::ExpressionError,
+            >,
+        > = ::std::sync::OnceLock::new();
+        let __security_expression = __SECURITY_EXPRESSION.get_or_init(|| {
+            ::next_web_dev::security::core::authz::expression::security_expression::SecurityExpression::parse(#source)
+        });
+        if let Some(__security_response) =
+            ::next_web_dev::security::web::method::method_security::MethodSecurity::check(
+                &#extension,
+                &__security_context,
+                __security_expression.as_ref(),
+            )
+            .await
+        {
+            return __security_response;
+        }
+    }
+}
+
+/// Prepends the guard, a body with a top-level `return` is moved into its own future so
+/// the mapping macro keeps the guard's early return on the handler itself.
+fn add_guard(item_fn: &mut ItemFn, guard: TokenStream2) {
+    let has_return = item_fn
+        .block
+        .stmts
+        .iter()
+        .any(|stmt| matches!(stmt, syn::Stmt::Expr(syn::Expr::Return(_), _)));
+    if !has_return {
+        Logic::add_block(item_fn, guard);
+        return;
+    }
+
+    let stmts = &item_fn.block.stmts;
+    let invoke = match &item_fn.sig.output {
+        syn::ReturnType::Type(_, ty) if !matches!(ty.as_ref(), Type::ImplTrait(_)) => {
+            quote! { invoke::<#ty, _> }
+        }
+        _ => quote! { invoke },
+    };
+    *item_fn.block = syn::parse2(quote! {
+        {
+            #guard
+
+            ::next_web_dev::security::web::method::method_security::MethodSecurity::#invoke(
+                async move { #(#stmts)* }
+            )
+            .await
+        }
+    })
+    .unwrap();
+}
+
+/// Whether the return type is a `Result` and whether the value is wrapped in `Json`.
+fn inspect_return_type(ty: &Type) -> (bool, bool) {
+    fn last_segment(ty: &Type) -> Option<&syn::PathSegment> {
+        match ty {
+            Type::Path(type_path) => type_path.path.segments.last(),
+            _ => None,
+        }
+    }
+
+    let Some(segment) = last_segment(ty) else {
+        return (false, false);
+    };
+    if segment.ident != "Result" {
+        return (false, segment.ident == "Json");
+    }
 
-    expanded
+    let is_json = match &segment.arguments {
+        PathArguments::AngleBracketed(arguments) => match arguments.args.first() {
+            Some(GenericArgument::Type(ok)) => last_segment(ok)
+                .map(|segment| segment.ident == "Json")
+                .unwrap_or(false),
+            _ => false,
+        },
+        _ => false,
+    };
+    (true, is_json)
 }
@@ -36,6 +36,7 @@ pub fn impl_macro_properties(attr: TokenStream, mut item_struct: ItemStruct) ->
 
         let prefix = prefix_expr.to_token_stream().to_string().replace("\"", "");
         let dynamic = attr.dynamic;
+        let refreshable = attr.refreshable;
 
         let fields = match &item_struct.fields {
             Fields::Named(fields_named) => &fields_named.named,
@@ -87,7 +88,7 @@ pub fn impl_macro_properties(attr: TokenStream, mut item_struct: ItemStruct) ->
 
                 // 构建最终 key
                 let key = if prefix.is_empty() {
-                    key_name
+                    key_name.clone()
                 } else {
                     format!("{}.{}", prefix, key_name)
                 };
@@ -139,10 +140,75 @@ pub fn impl_macro_properties(attr: TokenStream, mut item_struct: ItemStruct) ->
                     }
                 };
 
-                Some(field_init)
+                // 校验字段：缺失或类型错误
+                // Validate the field: missing or invalid value
+                let type_name = LitStr::new(
+                    &inner_type.to_token_stream().to_string().replace(' ', ""),
+                    field_name.span(),
+                );
+                let field_check = quote! {
+                    if properties.contains_key(#key_str) {
+                        if (#extract_value_expr).is_none() {
+                            violations.push(::next_web_core::error::properties_validation_error::PropertyViolation::new(
+                                #key_str,
+                                ::std::format!("invalid value, expected {}", #type_name),
+                            ));
+                        }
+                    } else if !#is_option {
+                        violations.push(::next_web_core::error::properties_validation_error::PropertyViolation::new(
+                            #key_str,
+                            "required property is missing",
+                        ));
+                    }
+                };
+
+                Some((field_init, field_check, key_name, field_name.to_string(), key_str))
             })
             .collect::<Vec<_>>();
 
+        let field_checks = common_fields.iter().map(|(_, check, ..)| check).collect::<Vec<_>>();
+        let property_keys = common_fields.iter().map(|(_, _, key, ..)| key).collect::<Vec<_>>();
+        let field_names = common_fields.iter().map(|(.., name, _)| name).collect::<Vec<_>>();
+        let field_keys = common_fields.iter().map(|(.., key_str)| key_str).collect::<Vec<_>>();
+        let common_fields = common_fields.iter().map(|(init, ..)| init).collect::<Vec<_>>();
+
+        let prefix_lit = LitStr::new(&prefix, Span::call_site());
+
+        let property_keys_expr = if dynamic {
+            quote! { ::std::option::Option::None }
+        } else {
+            quote! { ::std::option::Option::Some(&[#(#property_keys),*]) }
+        };
+
+        // validator 约束校验
+        // Constraints declared with `validator::Validate`
+        let validator_check = if attr.validate {
+            quote! {
+                if let ::std::option::Option::Some(instance) = Self::bind_properties(properties) {
+                    if let ::std::result::Result::Err(errors) = ::next_web_core::validator::Validate::validate(&instance) {
+                        for (field, errors) in errors.field_errors() {
+                            let key = match field.as_ref() {
+                                #(#field_names => ::std::string::String::from(#field_keys),)*
+                                other => ::std::format!("{}.{}", #prefix_lit, other),
+                            };
+                            for error in errors.iter() {
+                                violations.push(::next_web_core::error::properties_validation_error::PropertyViolation::new(
+                                    key.clone(),
+                                    error
+                                        .message
+                                        .as_ref()
+                                        .map(|message| message.to_string())
+                                        .unwrap_or_else(|| ::std::format!("constraint [{}] violated", error.code)),
+                                ));
+                            }
+                        }
+                    }
+                }
+            }
+        } else {
+            quote! {}
+        };
+
         // dynamic_field
         let dynamic_field = if dynamic {
             quote! {
@@ -186,6 +252,12 @@ pub fn impl_macro_properties(attr: TokenStream, mut item_struct: ItemStruct) ->
                     if !binds.contains("into_properties") {
                         return Err(syn::Error::new(Span::call_site(), "Singleton or SingleOwner macro must contain ::into_properties"));
                     }
+                    return Ok(());
+                }
+
+                // 跳过其他参数的值, 例如 `on_property = "..."`
+                if meta.input.peek(syn::Token![=]) {
+                    meta.value()?.parse::<syn::Expr>()?;
                 }
                 Ok(())
             });
@@ -216,18 +288,11 @@ pub fn impl_macro_properties(attr: TokenStream, mut item_struct: ItemStruct) ->
                     ctx: &mut ::next_web_core::context::application_context::ApplicationContext,
                     properties: & ::next_web_core::context::properties::ApplicationProperties,
                 ) -> ::std::result::Result<(), ::std::boxed::Box<dyn ::std::error::Error>> {
-                    let mut noting = false;
-
-                    let instance = Self {
-                        #dynamic_field
-
-                        #(#common_fields)*
+                    let instance = match Self::bind_properties(properties) {
+                        Some(instance) => instance,
+                        None => panic!("\nIncorrect assembly of properties! Struct: {} \n", stringify!(#struct_ident)),
                     };
 
-                    if noting {
-                        panic!("\nIncorrect assembly of properties! Struct: {} \n", stringify!(#struct_ident));
-                    }
-
                     ctx.insert_singleton_with_name(instance, #singleton_name);
                     Ok(())
                 }
@@ -237,12 +302,68 @@ pub fn impl_macro_properties(attr: TokenStream, mut item_struct: ItemStruct) ->
                 }
             }
 
-            impl ::next_web_core::context::properties::Properties for #struct_ident {}
+            impl ::next_web_core::context::properties::Properties for #struct_ident {
+                fn prefix(&self) -> &'static str {
+                    #prefix_lit
+                }
+
+                fn property_keys(&self) -> ::std::option::Option<&'static [&'static str]> {
+                    #property_keys_expr
+                }
+
+                fn validate(
+                    &self,
+                    properties: & ::next_web_core::context::properties::ApplicationProperties,
+                ) -> ::std::vec::Vec<::next_web_core::error::properties_validation_error::PropertyViolation> {
+                    let mut violations = ::std::vec::Vec::new();
+
+                    #(#field_checks)*
+
+                    #validator_check
+
+                    violations
+                }
+
+                fn refreshable(&self) -> bool {
+                    #refreshable
+                }
+
+                fn rebind(
+                    &self,
+                    ctx: &mut ::next_web_core::context::application_context::ApplicationContext,
+                    properties: & ::next_web_core::context::properties::ApplicationProperties,
+                ) -> ::std::result::Result<(), ::std::boxed::Box<dyn ::std::error::Error>> {
+                    let instance = Self::bind_properties(properties).ok_or_else(|| {
+                        format!("Incorrect assembly of properties! Struct: {}", stringify!(#struct_ident))
+                    })?;
+
+                    ctx.replace_singleton_with_name(instance, #singleton_name);
+                    Ok(())
+                }
+            }
 
             impl #struct_ident {
                 fn into_properties(self) -> ::std::boxed::Box<dyn ::next_web_core::context::properties::Properties> {
                     ::std::boxed::Box::new(self)
                 }
+
+                fn bind_properties(
+                    properties: & ::next_web_core::context::properties::ApplicationProperties,
+                ) -> ::std::option::Option<Self> {
+                    let mut noting = false;
+
+                    let instance = Self {
+                        #dynamic_field
+
+                        #(#common_fields)*
+                    };
+
+                    if noting {
+                        return None;
+                    }
+
+                    Some(instance)
+                }
             }
         };
 
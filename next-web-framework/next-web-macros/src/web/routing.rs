@@ -11,6 +11,7 @@ use syn::{parse_quote, Expr, FnArg, Ident, Item, ItemImpl, Lit, Meta, Type};
 use crate::util::field_type::FieldType;
 use crate::util::logic::Logic;
 
+use super::api_operation::api_operation;
 use super::attrs::request_mapping_attr::RequestMappingAttr;
 
 pub fn with_method(method: Option<Method>, attr: TokenStream, input: TokenStream) -> TokenStream {
@@ -45,6 +46,9 @@ fn impl_item_fn(
         Err(error) => return Err(error),
     };
 
+    // Taken before the signature is rewritten
+    let operation = api_operation(&item_fn, &path, &headers);
+
     let headers = headers.into_iter()
     .filter(|header| !header.value().trim().is_empty() )
     .enumerate()
@@ -107,6 +111,7 @@ fn impl_item_fn(
 
     let vis = &item_fn.vis;
     let name = &item_fn.sig.ident;
+    let method_name = method.to_string().to_uppercase();
 
     let stream = quote! {
         #(#doc_attributes)*
@@ -122,6 +127,20 @@ fn impl_item_fn(
 
                 __router.route(#path, ::next_web_dev::routing::#method(#name))
             }
+
+            fn name(&self) -> &'static str {
+                stringify!(#name)
+            }
+
+            fn method(&self) -> &'static str {
+                #method_name
+            }
+
+            fn path(&self) -> &'static str {
+                #path
+            }
+
+            #operation
         }
 
         ::next_web_dev::submit_handler!(#name);
@@ -23,6 +23,7 @@ pub(crate) fn impl_macro_scheduled(attr: TokenStream, item: TokenStream) -> Toke
 
     let is_async = sig.asyncness.is_some();
     let ScheduledAttr {
+        name: job_name,
         cron,
         fixed_rate,
         initial_delay,
@@ -30,11 +31,38 @@ pub(crate) fn impl_macro_scheduled(attr: TokenStream, item: TokenStream) -> Toke
         time_unit,
 
         one_shot,
+
+        lock_name,
+        lock_at_most_for,
+        lock_at_least_for,
+
+        overlap,
+        misfire,
+        misfire_threshold,
     } = match ScheduledAttr::from_tokens(attr.into()) {
         Ok(attr) => attr,
         Err(error) => return error.to_compile_error().into(),
     };
 
+    let job_name = match job_name {
+        Some(lit) if lit.value().trim().is_empty() => {
+            return syn::Error::new(lit.span(), "name must not be empty")
+                .to_compile_error()
+                .into();
+        }
+        Some(lit) => quote! { #lit },
+        None => quote! { concat!(module_path!(), "::", stringify!(#name)) },
+    };
+
+    let lock = match lock_args(&job_name, lock_name, lock_at_most_for, lock_at_least_for) {
+        Ok(lock) => lock,
+        Err(error) => return error.to_compile_error().into(),
+    };
+    let policies = match policy_args(overlap, misfire, misfire_threshold) {
+        Ok(policies) => policies,
+        Err(error) => return error.to_compile_error().into(),
+    };
+
     let args: Vec<Box<syn::Pat>> = sig
         .inputs
         .iter()
@@ -109,6 +137,8 @@ pub(crate) fn impl_macro_scheduled(attr: TokenStream, item: TokenStream) -> Toke
                     initial_delay:  Some(#initial_delay),
                     timezone:       #timezone,
                     time_unit:      #time_unit,
+                    #lock
+                    #policies
                     ..Default::default()
             }
         )}
@@ -120,6 +150,8 @@ pub(crate) fn impl_macro_scheduled(attr: TokenStream, item: TokenStream) -> Toke
                 ::next_web_dev::scheduler::schedule_type::WithArgs {
                     cron:           Some(#cron),
                     timezone:       #timezone,
+                    #lock
+                    #policies
                     ..Default::default()
                 }
             )}
@@ -130,6 +162,8 @@ pub(crate) fn impl_macro_scheduled(attr: TokenStream, item: TokenStream) -> Toke
                 ::next_web_dev::scheduler::schedule_type::WithArgs {
                         fixed_rate: Some(#fixed_rate),
                         time_unit: #time_unit,
+                        #lock
+                        #policies
                         ..Default::default()
                 }
             )}
@@ -141,7 +175,7 @@ pub(crate) fn impl_macro_scheduled(attr: TokenStream, item: TokenStream) -> Toke
             ::std::boxed::Box::new(move || { #(#cloneds)* ::std::boxed::Box::pin(#name(#(#args),*)) })
         }
     } else {
-        quote! {::std::boxed::Box::new(#name(#(#args),*))}
+        quote! {::std::boxed::Box::new(move || { #(#cloneds)* #name(#(#args),*) })}
     };
 
     let doc_attributes: Vec<syn::Attribute> = item_fn
@@ -165,6 +199,10 @@ pub(crate) fn impl_macro_scheduled(attr: TokenStream, item: TokenStream) -> Toke
 
                 ::next_web_dev::autoregister::scheduler_autoregister::AnJob::#mark ( (#schedule , #run) )
             }
+
+            fn name(&self) -> &'static str {
+                #job_name
+            }
         }
 
         ::next_web_dev::submit_scheduler!(#name);
@@ -174,3 +212,142 @@ pub(crate) fn impl_macro_scheduled(attr: TokenStream, item: TokenStream) -> Toke
 
     expanded.into()
 }
+
+/// Fields of the scheduler lock, durations are converted to milliseconds.
+fn lock_args(
+    job_name: &TokenStream2,
+    lock_name: Option<syn::LitStr>,
+    lock_at_most_for: Option<syn::LitStr>,
+    lock_at_least_for: Option<syn::LitStr>,
+) -> syn::Result<TokenStream2> {
+    let lock_at_most_for = match lock_at_most_for {
+        Some(lit) => lit,
+        None => {
+            if let Some(lit) = lock_name.as_ref().or(lock_at_least_for.as_ref()) {
+                return Err(syn::Error::new(
+                    lit.span(),
+                    "lock_name and lock_at_least_for must be set with lock_at_most_for",
+                ));
+            }
+            return Ok(TokenStream2::new());
+        }
+    };
+
+    let at_most = parse_duration(&lock_at_most_for)?;
+    if at_most == 0 {
+        return Err(syn::Error::new(
+            lock_at_most_for.span(),
+            "lock_at_most_for must be greater than 0",
+        ));
+    }
+    let at_least = match lock_at_least_for.as_ref() {
+        Some(lit) => {
+            let at_least = parse_duration(lit)?;
+            if at_least > at_most {
+                return Err(syn::Error::new(
+                    lit.span(),
+                    "lock_at_least_for must not be greater than lock_at_most_for",
+                ));
+            }
+            at_least
+        }
+        None => 0,
+    };
+
+    let lock_name = match lock_name {
+        Some(lit) if lit.value().trim().is_empty() => {
+            return Err(syn::Error::new(lit.span(), "lock_name must not be empty"));
+        }
+        Some(lit) => quote! { #lit },
+        None => job_name.clone(),
+    };
+
+    Ok(quote! {
+        lock_name:          Some(#lock_name),
+        lock_at_most_for:   Some(#at_most),
+        lock_at_least_for:  Some(#at_least),
+    })
+}
+
+/// Fields of the overlap and misfire policies.
+fn policy_args(
+    overlap: Option<syn::LitStr>,
+    misfire: Option<syn::LitStr>,
+    misfire_threshold: Option<syn::LitStr>,
+) -> syn::Result<TokenStream2> {
+    let mut tokens = TokenStream2::new();
+
+    if let Some(lit) = overlap {
+        let policy = match lit.value().to_lowercase().as_str() {
+            "concurrent" => quote! { Concurrent },
+            "skip" => quote! { Skip },
+            "queue" => quote! { Queue },
+            _ => {
+                return Err(syn::Error::new(
+                    lit.span(),
+                    "overlap must be one of concurrent, skip or queue",
+                ))
+            }
+        };
+        tokens.extend(quote! {
+            overlap: ::next_web_dev::scheduler::schedule_type::OverlapPolicy::#policy,
+        });
+    }
+
+    if let Some(lit) = misfire {
+        let policy = match lit.value().to_lowercase().as_str() {
+            "fire_once" => quote! { FireOnce },
+            "skip" => quote! { Skip },
+            _ => {
+                return Err(syn::Error::new(
+                    lit.span(),
+                    "misfire must be one of fire_once or skip",
+                ))
+            }
+        };
+        tokens.extend(quote! {
+            misfire: ::next_web_dev::scheduler::schedule_type::MisfirePolicy::#policy,
+        });
+    }
+
+    if let Some(lit) = misfire_threshold {
+        let threshold = parse_duration(&lit)?;
+        tokens.extend(quote! {
+            misfire_threshold: Some(#threshold),
+        });
+    }
+
+    Ok(tokens)
+}
+
+/// Parse a duration like `"30s"`, `"10m"` or `"1h"` into milliseconds, a plain number is
+/// in milliseconds.
+fn parse_duration(lit: &syn::LitStr) -> syn::Result<u64> {
+    let value = lit.value();
+    let value = value.trim();
+    let split = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    let (number, unit) = value.split_at(split);
+
+    let number: u64 = number.parse().map_err(|_| {
+        syn::Error::new(lit.span(), "expected a duration like \"30s\", \"10m\" or \"1h\"")
+    })?;
+    let factor = match unit.trim() {
+        "" | "ms" => 1,
+        "s" => 1_000,
+        "m" => 60_000,
+        "h" => 3_600_000,
+        "d" => 86_400_000,
+        unit => {
+            return Err(syn::Error::new(
+                lit.span(),
+                format!("unsupported time unit `{}`, expected ms, s, m, h or d", unit),
+            ))
+        }
+    };
+
+    number
+        .checked_mul(factor)
+        .ok_or_else(|| syn::Error::new(lit.span(), "duration is too large"))
+}
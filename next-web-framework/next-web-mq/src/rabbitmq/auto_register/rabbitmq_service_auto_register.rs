@@ -1,11 +1,20 @@
 use next_web_core::{
-    async_trait, context::properties::ApplicationProperties, traits::singleton::Singleton,
+    async_trait,
+    context::properties::ApplicationProperties,
+    traits::{
+        health::health_indicator::HealthIndicator,
+        lifecycle::{disposable_bean::DisposableBean, lifecycle_bean::Lifecycle},
+        singleton::Singleton,
+    },
     ApplicationContext, AutoRegister,
 };
 use rudi_dev::SingleOwner;
 
 use crate::rabbitmq::{
-    core::{bind_exchange::BindExchangeBuilder, listener::rabbit_listener::RabbitListener},
+    core::{
+        bind_exchange::BindExchangeBuilder,
+        listener::{rabbit_listener::RabbitListener, rabbit_listener_container::RabbitListenerContainer},
+    },
     properties::rabbitmq_properties::RabbitMQClientProperties,
     service::rabbitmq_service::RabbitmqService,
 };
@@ -36,9 +45,41 @@ impl AutoRegister for RabbitmqServiceAutoRegister {
 
         let rabbitmq_service = RabbitmqService::new(properties, bind_exchange).await;
 
-        let consumer: Vec<Box<dyn RabbitListener>> = ctx.resolve_by_type::<Box<dyn RabbitListener>>();
+        // The listeners consume once the application starts, before it is ready
+        let listeners: Vec<Box<dyn RabbitListener>> = ctx.resolve_by_type::<Box<dyn RabbitListener>>();
+        let container: Box<dyn Lifecycle> = Box::new(RabbitListenerContainer::new(
+            rabbitmq_service.clone(),
+            listeners,
+        ));
+        ctx.insert_singleton_with_name(
+            container,
+            format!("{}ListenerContainer", rabbitmq_service.singleton_name()),
+        );
+        let disposable: Box<dyn DisposableBean> = Box::new(rabbitmq_service.clone());
+        ctx.insert_singleton_with_name(
+            disposable,
+            format!("{}DisposableBean", rabbitmq_service.singleton_name()),
+        );
+        let health_indicator: Box<dyn HealthIndicator> = Box::new(rabbitmq_service.clone());
+        ctx.insert_singleton_with_name(
+            health_indicator,
+            format!("{}HealthIndicator", rabbitmq_service.singleton_name()),
+        );
+        // Relay the outbox messages of the rabbitmq destinations
+        #[cfg(feature = "outbox")]
+        {
+            use next_web_core::traits::event::outbox_publisher::OutboxPublisher;
 
-        rabbitmq_service.spawn_consumer(consumer).await;
+            let outbox_publisher: Box<dyn OutboxPublisher> = Box::new(
+                crate::rabbitmq::core::outbox::rabbitmq_outbox_publisher::RabbitmqOutboxPublisher::new(
+                    rabbitmq_service.clone(),
+                ),
+            );
+            ctx.insert_singleton_with_name(
+                outbox_publisher,
+                format!("{}OutboxPublisher", rabbitmq_service.singleton_name()),
+            );
+        }
         ctx.insert_singleton_with_name(rabbitmq_service, self.singleton_name());
         Ok(())
     }
@@ -1 +1,2 @@
 pub mod rabbit_listener;
+pub mod rabbit_listener_container;
@@ -0,0 +1,107 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use amqprs::channel::{BasicCancelArguments, BasicConsumeArguments};
+use next_web_core::async_trait;
+use next_web_core::error::BoxError;
+use next_web_core::traits::lifecycle::lifecycle_bean::Lifecycle;
+use tokio::task::JoinHandle;
+use tracing::warn;
+
+use crate::rabbitmq::core::listener::rabbit_listener::RabbitListener;
+use crate::rabbitmq::service::rabbitmq_service::RabbitmqService;
+
+/// Runs the [`RabbitListener`]s.
+///
+/// The consumers start before the server is ready, a queue that cannot be consumed fails the
+/// startup. On shutdown they are cancelled and the messages already delivered are handled
+/// before the connection closes.
+#[derive(Clone)]
+pub struct RabbitListenerContainer {
+    service: RabbitmqService,
+    listeners: Arc<Mutex<Option<Vec<Box<dyn RabbitListener>>>>>,
+    consumers: Arc<Mutex<Vec<(String, JoinHandle<()>)>>>,
+    running: Arc<AtomicBool>,
+}
+
+impl RabbitListenerContainer {
+    /// The phase of the consumers, after the lifecycle beans of the application with the
+    /// default phase: they start last and stop first
+    pub const PHASE: i32 = 1000;
+
+    pub fn new(service: RabbitmqService, listeners: Vec<Box<dyn RabbitListener>>) -> Self {
+        Self {
+            service,
+            listeners: Arc::new(Mutex::new(Some(listeners))),
+            consumers: Default::default(),
+            running: Default::default(),
+        }
+    }
+}
+
+#[async_trait]
+impl Lifecycle for RabbitListenerContainer {
+    async fn start(&mut self) -> Result<(), BoxError> {
+        let Some(listeners) = self.listeners.lock().unwrap().take() else {
+            return Err("the rabbitmq listeners can only be started once".into());
+        };
+
+        let mut failures = Vec::new();
+        for mut listener in listeners {
+            let arguments = BasicConsumeArguments::new(&listener.queue(), &listener.consumer_tag());
+            match self.service.add_consumer(arguments).await {
+                Ok((consumer_tag, mut rx)) => {
+                    let consumer = tokio::spawn(async move {
+                        while let Some(msg) = rx.recv().await {
+                            listener.on_message(msg).await;
+                        }
+                    });
+                    self.consumers.lock().unwrap().push((consumer_tag, consumer));
+                }
+                Err(err) => failures.push(format!("{}: {}", listener.queue(), err)),
+            }
+        }
+
+        if !failures.is_empty() {
+            // Do not leave some of the queues consumed
+            self.stop().await;
+            return Err(format!(
+                "failed to consume the rabbitmq queues [{}]",
+                failures.join(", ")
+            )
+            .into());
+        }
+
+        self.running.store(true, Ordering::SeqCst);
+        Ok(())
+    }
+
+    async fn stop(&mut self) {
+        self.running.store(false, Ordering::SeqCst);
+        let consumers = std::mem::take(&mut *self.consumers.lock().unwrap());
+
+        for (consumer_tag, consumer) in consumers {
+            if let Err(err) = self
+                .service
+                .channel()
+                .basic_cancel(BasicCancelArguments::new(&consumer_tag))
+                .await
+            {
+                warn!("Failed to cancel the rabbitmq consumer {}: {}", consumer_tag, err);
+                consumer.abort();
+                continue;
+            }
+
+            // The receiver closes once the consumer is cancelled
+            let _ = consumer.await;
+        }
+    }
+
+    fn is_running(&self) -> bool {
+        self.running.load(Ordering::SeqCst)
+    }
+
+    fn phase(&self) -> i32 {
+        Self::PHASE
+    }
+}
@@ -1,2 +1,4 @@
 pub mod listener;
-pub mod bind_exchange;
\ No newline at end of file
+pub mod bind_exchange;
+#[cfg(feature = "outbox")]
+pub mod outbox;
@@ -0,0 +1,45 @@
+use amqprs::BasicProperties;
+use next_web_core::async_trait;
+use next_web_core::error::BoxError;
+use next_web_core::event::outbox_message::OutboxMessage;
+use next_web_core::traits::event::outbox_publisher::OutboxPublisher;
+
+use crate::rabbitmq::service::rabbitmq_service::RabbitmqService;
+
+/// RabbitMQ 发件箱消息发布者
+///
+/// Publishes the outbox messages of the `rabbitmq:<exchange>/<routing key>` destinations as
+/// persistent messages, with the id of the message as `message_id` and its event type as
+/// `type`. Without an exchange, `rabbitmq:<routing key>`, the default exchange is used.
+#[derive(Clone)]
+pub struct RabbitmqOutboxPublisher {
+    service: RabbitmqService,
+}
+
+impl RabbitmqOutboxPublisher {
+    pub fn new(service: RabbitmqService) -> Self {
+        Self { service }
+    }
+}
+
+#[async_trait]
+impl OutboxPublisher for RabbitmqOutboxPublisher {
+    fn scheme(&self) -> &'static str {
+        "rabbitmq"
+    }
+
+    async fn publish(&self, message: &OutboxMessage) -> Result<(), BoxError> {
+        let (exchange, routing_key) = message.target().split_once('/').unwrap_or(("", message.target()));
+        let properties = BasicProperties::default()
+            .with_message_id(&message.id)
+            .with_message_type(&message.event_type)
+            .with_content_type("application/json")
+            .with_persistence(true)
+            .finish();
+
+        self.service
+            .send_message_with_properties(exchange, routing_key, message.payload.as_bytes(), properties)
+            .await?;
+        Ok(())
+    }
+}
@@ -1,6 +1,5 @@
 
 use crate::rabbitmq::core::bind_exchange::BindExchange;
-use crate::rabbitmq::core::listener::rabbit_listener::RabbitListener;
 use amqprs::callbacks::DefaultChannelCallback;
 use amqprs::callbacks::DefaultConnectionCallback;
 use amqprs::channel::BasicConsumeArguments;
@@ -12,6 +11,10 @@ use amqprs::channel::QueueDeclareArguments;
 use amqprs::connection::Connection;
 use amqprs::connection::OpenConnectionArguments;
 use amqprs::BasicProperties;
+use next_web_core::async_trait;
+use next_web_core::health::health_status::Health;
+use next_web_core::traits::health::health_indicator::HealthIndicator;
+use next_web_core::traits::lifecycle::disposable_bean::DisposableBean;
 use next_web_core::traits::service::Service;
 
 use next_web_core::traits::singleton::Singleton;
@@ -23,6 +26,7 @@ use crate::rabbitmq::properties::rabbitmq_properties::RabbitMQClientProperties;
 #[derive(Clone)]
 pub struct RabbitmqService {
     properties: RabbitMQClientProperties,
+    connection: Connection,
     channel: Channel,
 }
 
@@ -30,33 +34,60 @@ pub struct RabbitmqService {
 impl Singleton  for RabbitmqService {}
 impl Service    for RabbitmqService {}
 
+#[async_trait]
+impl HealthIndicator for RabbitmqService {
+    fn name(&self) -> &str {
+        "rabbitmq"
+    }
+
+    async fn health(&self) -> Health {
+        let health = if !self.channel.is_connection_open() {
+            Health::down().with_detail("error", "connection closed")
+        } else if !self.channel.is_open() {
+            Health::down().with_detail("error", "channel closed")
+        } else {
+            Health::up()
+        };
+
+        health.with_detail(
+            "address",
+            format!(
+                "{}:{}",
+                self.properties.host().unwrap_or("localhost"),
+                self.properties.port().unwrap_or(5672)
+            ),
+        )
+    }
+}
+
+/// Closes the channel and the connection last, once the consumers are stopped and the shutdown
+/// hooks have run.
+#[async_trait]
+impl DisposableBean for RabbitmqService {
+    async fn destroy(&mut self) {
+        if let Err(err) = self.channel.clone().close().await {
+            error!("Failed to close the rabbitmq channel: {}", err);
+        }
+        if let Err(err) = self.connection.clone().close().await {
+            error!("Failed to close the rabbitmq connection: {}", err);
+        }
+    }
+}
+
 impl RabbitmqService {
     pub async fn new(properties: RabbitMQClientProperties, binds: Vec<BindExchange>) -> Self {
-        let channel = Self::build_channel(&properties, &binds).await;
+        let (connection, channel) = Self::build_channel(&properties, &binds).await;
         Self {
             properties,
+            connection,
             channel,
         }
     }
 
-    pub(crate) async fn spawn_consumer(&self, consumer: Vec<Box<dyn RabbitListener>>) {
-        for mut item in consumer {
-            let basic_consume_arguments =
-                BasicConsumeArguments::new(&item.queue(), &item.consumer_tag());
-            if let Ok((_ctag, mut rx)) = self.add_consumer(basic_consume_arguments).await {
-                tokio::spawn(async move {
-                    while let Some(msg) = rx.recv().await {
-                        item.on_message(msg).await;
-                    }
-                });
-            }
-        }
-    }
-
     async fn build_channel(
         options: &RabbitMQClientProperties,
         binds: &[BindExchange],
-    ) -> Channel {
+    ) -> (Connection, Channel) {
         // open a connection to RabbitMQ server
         let mut properties = OpenConnectionArguments::new(
             options.host().unwrap_or("localhost"),
@@ -118,7 +149,7 @@ impl RabbitmqService {
                 }
             }
         }
-        channel
+        (connection, channel)
     }
 
     /// basic_publish
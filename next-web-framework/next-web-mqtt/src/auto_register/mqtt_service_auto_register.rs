@@ -2,7 +2,12 @@ use std::sync::Arc;
 
 use hashbrown::HashMap;
 use next_web_core::{
-    async_trait, context::properties::ApplicationProperties, traits::singleton::Singleton,
+    async_trait,
+    context::properties::ApplicationProperties,
+    traits::{
+        health::health_indicator::HealthIndicator, lifecycle::lifecycle_bean::Lifecycle,
+        singleton::Singleton,
+    },
     ApplicationContext, AutoRegister,
 };
 use rudi_dev::Singleton;
@@ -16,7 +21,12 @@ use crate::{
     service::mqtt_service::MQTTService,
 };
 
-#[Singleton(binds = [Self::into_auto_register])]
+/// Switched off with `next.mqtt.enabled: false`, a user provided `MQTTService` replaces it.
+#[Singleton(
+    on_property = "next.mqtt.enabled!=false",
+    on_missing_bean = MQTTService,
+    binds = [Self::into_auto_register]
+)]
 #[derive(Clone)]
 pub struct MQTTServiceAutoRegister(pub MQTTClientProperties);
 
@@ -84,6 +94,21 @@ impl AutoRegister for MQTTServiceAutoRegister {
         // 将 MQTT 服务插入上下文，并命名为单例名称
         // Insert the MQTT service into the context and name it with the singleton name
         let singleton_name = mqtt_service.singleton_name();
+        let health_indicator: Box<dyn HealthIndicator> = Box::new(mqtt_service.clone());
+        ctx.insert_singleton_with_name(health_indicator, format!("{}HealthIndicator", singleton_name));
+        // The subscribed topics are consumed once the application starts, before it is ready
+        let lifecycle: Box<dyn Lifecycle> = Box::new(mqtt_service.clone());
+        ctx.insert_singleton_with_name(lifecycle, format!("{}Lifecycle", singleton_name));
+        // Relay the outbox messages of the mqtt destinations
+        #[cfg(feature = "outbox")]
+        {
+            use next_web_core::traits::event::outbox_publisher::OutboxPublisher;
+
+            let outbox_publisher: Box<dyn OutboxPublisher> = Box::new(
+                crate::core::outbox::mqtt_outbox_publisher::MQTTOutboxPublisher::new(mqtt_service.clone()),
+            );
+            ctx.insert_singleton_with_name(outbox_publisher, format!("{}OutboxPublisher", singleton_name));
+        }
         ctx.insert_singleton_with_name(mqtt_service, singleton_name);
 
         Ok(())
@@ -1,3 +1,5 @@
 pub mod route;
 pub mod topic;
-pub mod interceptor;
\ No newline at end of file
+pub mod interceptor;
+#[cfg(feature = "outbox")]
+pub mod outbox;
@@ -0,0 +1,34 @@
+use next_web_core::async_trait;
+use next_web_core::error::BoxError;
+use next_web_core::event::outbox_message::OutboxMessage;
+use next_web_core::traits::event::outbox_publisher::OutboxPublisher;
+
+use crate::service::mqtt_service::MQTTService;
+
+/// MQTT 发件箱消息发布者
+///
+/// Publishes the outbox messages of the `mqtt:<topic>` destinations with QoS 1
+#[derive(Clone)]
+pub struct MQTTOutboxPublisher {
+    service: MQTTService,
+}
+
+impl MQTTOutboxPublisher {
+    pub fn new(service: MQTTService) -> Self {
+        Self { service }
+    }
+}
+
+#[async_trait]
+impl OutboxPublisher for MQTTOutboxPublisher {
+    fn scheme(&self) -> &'static str {
+        "mqtt"
+    }
+
+    async fn publish(&self, message: &OutboxMessage) -> Result<(), BoxError> {
+        self.service
+            .publish(message.target(), message.payload.as_bytes())
+            .await?;
+        Ok(())
+    }
+}
@@ -4,7 +4,7 @@ use rudi_dev::Singleton;
 /// MQTT Client Configuration Properties
 ///
 /// MQTT客户端配置属性
-#[Singleton(default, binds=[Self::into_properties])]
+#[Singleton(default, on_property = "next.mqtt.enabled!=false", binds=[Self::into_properties])]
 #[Properties(prefix = "next.mqtt")]
 #[derive(Debug, Clone, Default, PartialEq, serde::Deserialize)]
 pub struct MQTTClientProperties {
@@ -55,6 +55,11 @@ pub struct MQTTClientProperties {
     ///
     /// 清除会话标志(可选)
     clean_session: Option<bool>,
+
+    /// Whether the client is registered, `false` switches it off (optional, defaults to true)
+    ///
+    /// 是否注册客户端, `false` 关闭(可选，默认为true)
+    enabled: Option<bool>,
 }
 
 impl MQTTClientProperties {
@@ -125,6 +130,13 @@ impl MQTTClientProperties {
         self.clean_session
     }
 
+    /// Returns whether the client is registered
+    ///
+    /// 返回是否注册客户端
+    pub fn enabled(&self) -> bool {
+        self.enabled.unwrap_or(true)
+    }
+
     pub fn set_client_id(&mut self, client_id: impl ToString) {
         self.client_id = Some(client_id.to_string());
     }
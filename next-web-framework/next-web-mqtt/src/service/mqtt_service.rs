@@ -1,4 +1,8 @@
+use std::future::Future;
 use std::ops::Deref;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
 
 use crate::{
     core::{
@@ -9,14 +13,29 @@ use crate::{
     properties::mqtt_properties::MQTTClientProperties,
 };
 
+use bytes::Bytes;
 use hashbrown::HashMap;
+use next_web_core::async_trait;
+use next_web_core::error::BoxError;
+use next_web_core::health::health_status::Health;
+use next_web_core::traits::health::health_indicator::HealthIndicator;
+use next_web_core::traits::lifecycle::lifecycle_bean::Lifecycle;
 use next_web_core::traits::{service::Service, singleton::Singleton};
 use rumqttc::{
     AsyncClient, ConnectReturnCode, Event, MqttOptions, NetworkOptions, Packet, QoS,
     SubscribeFilter,
 };
+use tokio::task::JoinHandle;
 use tracing::{error, warn};
 
+type Consumer = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// Messages received but not consumed yet, the ones received before the consumer starts
+/// included. Once the buffer is full the event loop stops reading from the broker until
+/// the consumer catches up, so a slow consumer applies back pressure instead of growing
+/// the memory without bound.
+const MAX_PENDING_MESSAGES: usize = 1024;
+
 /// MQTT Service
 /// This struct provides MQTT client functionality including:
 /// - Connection management
@@ -43,12 +62,60 @@ pub struct MQTTService {
     ///
     /// 异步MQTT客户端实例
     client: AsyncClient,
+
+    /// Whether the client is connected to the broker
+    ///
+    /// 客户端是否已连接到服务器
+    connected: Arc<AtomicBool>,
+
+    /// The task polling the event loop, it connects to the broker and sends the outgoing
+    /// messages from the start
+    ///
+    /// 轮询事件循环的任务，创建后即连接服务器并发送消息
+    event_loop: Arc<Mutex<Option<JoinHandle<()>>>>,
+
+    /// The consumer of the incoming messages, spawned once started
+    ///
+    /// 接收消息的消费者，启动后才开始消费
+    consumer: Arc<Mutex<Option<Consumer>>>,
+
+    /// The task running the consumer while running
+    ///
+    /// 运行中执行消费者的任务
+    task: Arc<Mutex<Option<JoinHandle<()>>>>,
 }
 
 impl Singleton  for MQTTService {}
 impl Service    for MQTTService {}
 
+#[async_trait]
+impl HealthIndicator for MQTTService {
+    fn name(&self) -> &str {
+        "mqtt"
+    }
+
+    async fn health(&self) -> Health {
+        let health = match self.connected.load(Ordering::Relaxed) {
+            true => Health::up(),
+            false => Health::down().with_detail("error", "not connected to the broker"),
+        };
+
+        health.with_detail(
+            "address",
+            format!(
+                "{}:{}",
+                self.properties.host().unwrap_or("127.0.0.1"),
+                self.properties.port().unwrap_or(1883)
+            ),
+        )
+    }
+}
+
 impl MQTTService {
+    /// The phase of the consumer, after the lifecycle beans of the application with the
+    /// default phase: it starts last and stops first
+    pub const PHASE: i32 = 1000;
+
     /// Creates a new MQTTService instance
     ///
     /// # Arguments
@@ -76,8 +143,22 @@ impl MQTTService {
         route: Vec<TopicRoute>,
         interceptor: Box<dyn MessageInterceptor>,
     ) -> Self {
-        let client = Self::build_client(&properties, route_map, route, interceptor);
-        Self { properties, client }
+        let connected = Arc::new(AtomicBool::new(false));
+        let (client, event_loop, consumer) = Self::build_client(
+            &properties,
+            route_map,
+            route,
+            interceptor,
+            connected.clone(),
+        );
+        Self {
+            properties,
+            client,
+            connected,
+            event_loop: Arc::new(Mutex::new(Some(tokio::spawn(event_loop)))),
+            consumer: Arc::new(Mutex::new(Some(consumer))),
+            task: Default::default(),
+        }
     }
 
     /// Builds and configures the MQTT client
@@ -88,7 +169,8 @@ impl MQTTService {
     /// - `Message event loop`
     ///
     /// # Returns
-    /// Async MQTT client instance
+    /// Async MQTT client instance, its event loop and the consumer of the incoming messages,
+    /// started with the service
     ///
     /// 构建并配置MQTT客户端
     ///
@@ -98,14 +180,15 @@ impl MQTTService {
     /// - `Message event loop` 消息事件循环
     ///
     /// # 返回值
-    /// 异步MQTT客户端实例
+    /// 异步MQTT客户端实例、事件循环及随服务启动的消息消费者
     ///
     fn build_client(
         properties: &MQTTClientProperties,
         mut route_map: HashMap<String, Box<dyn BaseTopic>>,
         mut route: Vec<TopicRoute>,
         interceptor: Box<dyn MessageInterceptor>,
-    ) -> AsyncClient {
+        connected: Arc<AtomicBool>,
+    ) -> (AsyncClient, impl Future<Output = ()> + Send + 'static, Consumer) {
         let mut options = MqttOptions::new(
             properties.client_id().unwrap_or_default(),
             properties.host().unwrap_or("127.0.0.1"),
@@ -147,51 +230,61 @@ impl MQTTService {
             .collect::<Vec<_>>();
         client.try_subscribe_many(need_subscribe_topics).unwrap();
 
-        tokio::spawn(async move {
-            loop {
-                match eventloop.poll().await {
-                    Ok(Event::Incoming(Packet::Publish(packet))) => {
-                        let message = packet.payload;
-                        let topic = packet.topic;
-                        if !interceptor.message_entry(&topic, &message).await {
-                            continue;
-                        }
+        let (incoming, mut received) = tokio::sync::mpsc::channel::<(String, Bytes)>(MAX_PENDING_MESSAGES);
+        let consumer = Box::pin(async move {
+            while let Some((topic, message)) = received.recv().await {
+                if !interceptor.message_entry(&topic, &message).await {
+                    continue;
+                }
 
-                        if let Some(basic) = route_map.get_mut(&topic) {
-                            basic.consume(&topic, &message).await;
-                        }
+                if let Some(basic) = route_map.get_mut(&topic) {
+                    basic.consume(&topic, &message).await;
+                }
 
-                        for item in route.iter_mut() {
-                            match item.match_type {
-                                MacthType::Anything => {
-                                    item.base_topic.consume(&topic, &message).await;
-                                }
+                for item in route.iter_mut() {
+                    match item.match_type {
+                        MacthType::Anything => {
+                            item.base_topic.consume(&topic, &message).await;
+                        }
 
-                                MacthType::Multilayer(index) => {
-                                    if topic[0..index].eq(&item.topic[0..index]) {
-                                        item.base_topic.consume(&topic, &message).await;
-                                    }
-                                }
+                        MacthType::Multilayer(index) => {
+                            if topic[0..index].eq(&item.topic[0..index]) {
+                                item.base_topic.consume(&topic, &message).await;
+                            }
+                        }
 
-                                MacthType::Singlelayer(left_index, right_index) => {
-                                    let len = topic.len();
-                                    if topic[0..left_index].eq(&item.topic[0..left_index]) {
-                                        if right_index != 0 {
-                                            if !topic[(len - right_index)..]
-                                                .eq(&item.topic[right_index..])
-                                            {
-                                                continue;
-                                            }
-                                        }
-                                        item.base_topic.consume(&topic, &message).await;
+                        MacthType::Singlelayer(left_index, right_index) => {
+                            let len = topic.len();
+                            if topic[0..left_index].eq(&item.topic[0..left_index]) {
+                                if right_index != 0 {
+                                    if !topic[(len - right_index)..]
+                                        .eq(&item.topic[right_index..])
+                                    {
+                                        continue;
                                     }
                                 }
+                                item.base_topic.consume(&topic, &message).await;
                             }
                         }
                     }
+                }
+            }
+        });
+
+        let event_loop = async move {
+            loop {
+                match eventloop.poll().await {
+                    Ok(Event::Incoming(Packet::Publish(packet))) => {
+                        // Waits while the consumer is behind, or not started yet
+                        if incoming.send((packet.topic, packet.payload)).await.is_err() {
+                            // The consumer was dropped on stop
+                            break;
+                        }
+                    }
 
                     Ok(Event::Incoming(Packet::ConnAck(ack))) => {
                         // This generally refers to the need to receive ack information and re subscribe to the topic after reconnection
+                        connected.store(ack.code == ConnectReturnCode::Success, Ordering::Relaxed);
                         match ack.code {
                             ConnectReturnCode::Success => {
                                 for t in topics.iter() {
@@ -204,6 +297,7 @@ impl MQTTService {
                     }
 
                     Err(e) => {
+                        connected.store(false, Ordering::Relaxed);
                         error!("Mqtt eventloop error, connection error case: {:?}", e);
                         tokio::time::sleep(std::time::Duration::from_millis(500)).await;
                     }
@@ -213,9 +307,9 @@ impl MQTTService {
                     }
                 }
             }
-        });
+        };
 
-        client
+        (client, event_loop, consumer)
     }
 
     /// Publishes a message to a topic with default QoS (AtLeastOnce)
@@ -332,6 +426,42 @@ impl MQTTService {
     }
 }
 
+/// Connects to the broker once created, so messages can be published during startup, and
+/// consumes the subscribed topics once the application starts. Disconnects on shutdown.
+#[async_trait]
+impl Lifecycle for MQTTService {
+    async fn start(&mut self) -> Result<(), BoxError> {
+        let Some(consumer) = self.consumer.lock().unwrap().take() else {
+            return Err("the consumer of the mqtt client can only be started once".into());
+        };
+
+        *self.task.lock().unwrap() = Some(tokio::spawn(consumer));
+        Ok(())
+    }
+
+    async fn stop(&mut self) {
+        if let Err(e) = self.client.disconnect().await {
+            warn!("Failed to disconnect from the mqtt broker: {:?}", e);
+        }
+
+        if let Some(task) = self.task.lock().unwrap().take() {
+            task.abort();
+        }
+        if let Some(event_loop) = self.event_loop.lock().unwrap().take() {
+            event_loop.abort();
+        }
+        self.connected.store(false, Ordering::Relaxed);
+    }
+
+    fn is_running(&self) -> bool {
+        self.task.lock().unwrap().is_some()
+    }
+
+    fn phase(&self) -> i32 {
+        MQTTService::PHASE
+    }
+}
+
 impl Deref for MQTTService {
     type Target = AsyncClient;
 
@@ -0,0 +1,71 @@
+use argon2::{
+    password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
+    Algorithm, Argon2, Params, Version,
+};
+use next_web_core::error::BoxError;
+
+use crate::crypto::password::password_encoder::PasswordEncoder;
+
+/// Argon2id 密码编码器
+///
+/// Hashes are PHC strings (`$argon2id$v=19$m=19456,t=2,p=1$...`), verification reads the
+/// parameters back from the hash. Hashes made with weaker parameters or another Argon2
+/// variant are reported by `upgrade_encoding`.
+#[derive(Clone)]
+pub struct Argon2PasswordEncoder {
+    params: Params,
+}
+
+impl Argon2PasswordEncoder {
+    /// `memory_cost` in KiB, `iterations` passes over memory, `parallelism` lanes.
+    pub fn new(memory_cost: u32, iterations: u32, parallelism: u32) -> Result<Self, BoxError> {
+        let params =
+            Params::new(memory_cost, iterations, parallelism, None).map_err(|e| e.to_string())?;
+        Ok(Self { params })
+    }
+
+    fn argon2(&self) -> Argon2<'static> {
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, self.params.clone())
+    }
+}
+
+impl Default for Argon2PasswordEncoder {
+    fn default() -> Self {
+        Self {
+            params: Params::default(),
+        }
+    }
+}
+
+impl PasswordEncoder for Argon2PasswordEncoder {
+    fn encode(&self, raw_password: &str) -> Result<String, BoxError> {
+        let salt = SaltString::generate(&mut rand::rngs::OsRng);
+        self.argon2()
+            .hash_password(raw_password.as_bytes(), &salt)
+            .map(|hash| hash.to_string())
+            .map_err(|e| e.to_string().into())
+    }
+
+    fn matches(&self, raw_password: &str, encoded_password: &str) -> bool {
+        PasswordHash::new(encoded_password)
+            .and_then(|hash| Argon2::default().verify_password(raw_password.as_bytes(), &hash))
+            .is_ok()
+    }
+
+    fn upgrade_encoding(&self, encoded_password: &str) -> bool {
+        let Ok(hash) = PasswordHash::new(encoded_password) else {
+            return false;
+        };
+        if hash.algorithm != Algorithm::Argon2id.ident() {
+            return true;
+        }
+        match Params::try_from(&hash) {
+            Ok(params) => {
+                params.m_cost() < self.params.m_cost()
+                    || params.t_cost() < self.params.t_cost()
+                    || params.p_cost() < self.params.p_cost()
+            }
+            Err(_) => false,
+        }
+    }
+}
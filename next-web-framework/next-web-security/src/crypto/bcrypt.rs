@@ -0,0 +1,51 @@
+use crate::crypto::password::password_encoder::PasswordEncoder;
+use bcrypt::{hash, verify, DEFAULT_COST};
+use next_web_core::error::BoxError;
+
+/// BCrypt 密码编码器
+///
+/// `cost` is the log2 of the number of rounds, between 4 and 31, defaults to 12. Hashes
+/// created with a lower cost are reported by `upgrade_encoding`.
+#[derive(Clone)]
+pub struct BCryptPasswordEncoder {
+    cost: u32,
+}
+
+impl BCryptPasswordEncoder {
+    pub fn new(cost: u32) -> Result<Self, BoxError> {
+        if !(4..=31).contains(&cost) {
+            return Err(format!("BCrypt cost must be between 4 and 31, got {}", cost).into());
+        }
+        Ok(Self { cost })
+    }
+
+    pub fn cost(&self) -> u32 {
+        self.cost
+    }
+}
+
+impl Default for BCryptPasswordEncoder {
+    fn default() -> Self {
+        Self { cost: DEFAULT_COST }
+    }
+}
+
+impl PasswordEncoder for BCryptPasswordEncoder {
+    fn encode(&self, raw_password: &str) -> Result<String, BoxError> {
+        hash(raw_password, self.cost).map_err(Into::into)
+    }
+
+    fn matches(&self, raw_password: &str, encoded_password: &str) -> bool {
+        verify(raw_password, encoded_password).unwrap_or(false)
+    }
+
+    fn upgrade_encoding(&self, encoded_password: &str) -> bool {
+        // $2b$12$<salt and hash>
+        encoded_password
+            .split('$')
+            .nth(2)
+            .and_then(|cost| cost.parse::<u32>().ok())
+            .map(|cost| cost < self.cost)
+            .unwrap_or(false)
+    }
+}
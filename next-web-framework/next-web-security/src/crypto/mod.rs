@@ -1,2 +1,4 @@
+pub mod argon2;
 pub mod bcrypt;
 pub mod password;
+pub mod scrypt;
@@ -0,0 +1,189 @@
+use std::{collections::HashMap, sync::Arc};
+
+use next_web_core::error::BoxError;
+
+use crate::crypto::{
+    argon2::Argon2PasswordEncoder,
+    bcrypt::BCryptPasswordEncoder,
+    password::{password_encoder::PasswordEncoder, pbkdf2_password_encoder::Pbkdf2PasswordEncoder},
+    scrypt::SCryptPasswordEncoder,
+};
+
+/// 委托密码编码器
+///
+/// New passwords are encoded with the encoder registered under `id_for_encode` and stored
+/// as `{id}hash`, stored hashes are matched by the encoder named in their prefix. A hash
+/// whose id is not `id_for_encode`, or whose parameters are weaker than the current ones,
+/// needs `upgrade_encoding` so it can be re-hashed after a successful login.
+///
+/// ```rust,ignore
+/// let encoder = DelegatingPasswordEncoder::create_default();
+/// let hash = encoder.encode("secret")?; // {bcrypt}$2b$12$...
+/// assert!(encoder.matches("secret", &hash));
+/// ```
+#[derive(Clone)]
+pub struct DelegatingPasswordEncoder {
+    id_for_encode: String,
+    encoders: HashMap<String, Arc<dyn PasswordEncoder>>,
+    default_for_matches: Option<Arc<dyn PasswordEncoder>>,
+}
+
+impl DelegatingPasswordEncoder {
+    const PREFIX: char = '{';
+    const SUFFIX: char = '}';
+
+    pub fn new(
+        id_for_encode: impl Into<String>,
+        encoders: HashMap<String, Arc<dyn PasswordEncoder>>,
+    ) -> Self {
+        let id_for_encode = id_for_encode.into();
+        assert!(
+            encoders.contains_key(&id_for_encode),
+            "id_for_encode {} is not found in encoders",
+            id_for_encode
+        );
+        Self {
+            id_for_encode,
+            encoders,
+            default_for_matches: None,
+        }
+    }
+
+    /// BCrypt for new passwords, with Argon2id, SCrypt and PBKDF2 hashes still accepted.
+    pub fn create_default() -> Self {
+        let mut encoders: HashMap<String, Arc<dyn PasswordEncoder>> = HashMap::new();
+        encoders.insert("bcrypt".into(), Arc::new(BCryptPasswordEncoder::default()));
+        encoders.insert("argon2".into(), Arc::new(Argon2PasswordEncoder::default()));
+        encoders.insert("scrypt".into(), Arc::new(SCryptPasswordEncoder::default()));
+        encoders.insert("pbkdf2".into(), Arc::new(Pbkdf2PasswordEncoder::default()));
+        Self::new("bcrypt", encoders)
+    }
+
+    /// Encoder used for hashes without an `{id}` prefix, such hashes never match otherwise.
+    pub fn set_default_password_encoder_for_matches(&mut self, encoder: Arc<dyn PasswordEncoder>) {
+        self.default_for_matches = Some(encoder);
+    }
+
+    pub fn get_id_for_encode(&self) -> &str {
+        &self.id_for_encode
+    }
+
+    /// `{bcrypt}$2b$...` to `(Some("bcrypt"), "$2b$...")`.
+    fn split(encoded_password: &str) -> (Option<&str>, &str) {
+        encoded_password
+            .strip_prefix(Self::PREFIX)
+            .and_then(|rest| rest.split_once(Self::SUFFIX))
+            .map(|(id, encoded)| (Some(id), encoded))
+            .unwrap_or((None, encoded_password))
+    }
+}
+
+impl PasswordEncoder for DelegatingPasswordEncoder {
+    fn encode(&self, raw_password: &str) -> Result<String, BoxError> {
+        let encoded = self.encoders[&self.id_for_encode].encode(raw_password)?;
+        Ok(format!(
+            "{}{}{}{}",
+            Self::PREFIX,
+            self.id_for_encode,
+            Self::SUFFIX,
+            encoded
+        ))
+    }
+
+    fn matches(&self, raw_password: &str, encoded_password: &str) -> bool {
+        match Self::split(encoded_password) {
+            (Some(id), encoded) => match self.encoders.get(id) {
+                Some(encoder) => encoder.matches(raw_password, encoded),
+                None => false,
+            },
+            (None, encoded) => match &self.default_for_matches {
+                Some(encoder) => encoder.matches(raw_password, encoded),
+                None => false,
+            },
+        }
+    }
+
+    fn upgrade_encoding(&self, encoded_password: &str) -> bool {
+        match Self::split(encoded_password) {
+            (Some(id), encoded) if id == self.id_for_encode => {
+                self.encoders[id].upgrade_encoding(encoded)
+            }
+            _ => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encoder() -> DelegatingPasswordEncoder {
+        let mut encoders: HashMap<String, Arc<dyn PasswordEncoder>> = HashMap::new();
+        encoders.insert("bcrypt".into(), Arc::new(BCryptPasswordEncoder::new(5).unwrap()));
+        encoders.insert(
+            "argon2".into(),
+            Arc::new(Argon2PasswordEncoder::new(1024, 1, 1).unwrap()),
+        );
+        encoders.insert(
+            "scrypt".into(),
+            Arc::new(SCryptPasswordEncoder::new(4, 8, 1).unwrap()),
+        );
+        encoders.insert("pbkdf2".into(), Arc::new(Pbkdf2PasswordEncoder::new(1000)));
+        DelegatingPasswordEncoder::new("argon2", encoders)
+    }
+
+    #[test]
+    fn test_encoders_round_trip() {
+        let delegating = encoder();
+        for (id, encoder) in &delegating.encoders {
+            let encoded = encoder.encode("secret").unwrap();
+            assert!(encoder.matches("secret", &encoded), "{}", id);
+            assert!(!encoder.matches("wrong", &encoded), "{}", id);
+            assert!(!encoder.upgrade_encoding(&encoded), "{}", id);
+        }
+
+        let encoded = delegating.encode("secret").unwrap();
+        assert!(encoded.starts_with("{argon2}$argon2id$"));
+        assert!(delegating.matches("secret", &encoded));
+        assert!(!delegating.matches("wrong", &encoded));
+    }
+
+    #[test]
+    fn test_upgrade_encoding() {
+        let delegating = encoder();
+
+        // Stored with another encoder
+        let legacy = format!(
+            "{{pbkdf2}}{}",
+            Pbkdf2PasswordEncoder::new(1000).encode("secret").unwrap()
+        );
+        assert!(delegating.matches("secret", &legacy));
+        assert!(delegating.upgrade_encoding(&legacy));
+
+        // Stored with weaker parameters
+        let weak = format!(
+            "{{argon2}}{}",
+            Argon2PasswordEncoder::new(512, 1, 1)
+                .unwrap()
+                .encode("secret")
+                .unwrap()
+        );
+        assert!(delegating.matches("secret", &weak));
+        assert!(delegating.upgrade_encoding(&weak));
+        let bcrypt = BCryptPasswordEncoder::new(5).unwrap();
+        assert!(BCryptPasswordEncoder::new(6)
+            .unwrap()
+            .upgrade_encoding(&bcrypt.encode("secret").unwrap()));
+        assert!(BCryptPasswordEncoder::new(3).is_err());
+
+        // Unknown or missing ids never match
+        assert!(!delegating.matches("secret", "{md5}5ebe2294ecd0e0f08eab7690d2a6ee69"));
+        let unprefixed = bcrypt.encode("secret").unwrap();
+        assert!(!delegating.matches("secret", &unprefixed));
+
+        let mut delegating = delegating;
+        delegating.set_default_password_encoder_for_matches(Arc::new(bcrypt));
+        assert!(delegating.matches("secret", &unprefixed));
+        assert!(delegating.upgrade_encoding(&unprefixed));
+    }
+}
@@ -0,0 +1,3 @@
+pub mod delegating_password_encoder;
+pub mod password_encoder;
+pub mod pbkdf2_password_encoder;
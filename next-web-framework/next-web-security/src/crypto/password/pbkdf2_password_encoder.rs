@@ -0,0 +1,76 @@
+use next_web_core::error::BoxError;
+use pbkdf2::{
+    password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
+    Algorithm, Params, Pbkdf2,
+};
+
+use crate::crypto::password::password_encoder::PasswordEncoder;
+
+/// PBKDF2 密码编码器
+///
+/// HMAC-SHA256 by default, hashes are PHC strings (`$pbkdf2-sha256$i=600000,l=32$...`).
+/// Hashes made with fewer iterations or another digest are reported by `upgrade_encoding`.
+#[derive(Clone)]
+pub struct Pbkdf2PasswordEncoder {
+    algorithm: Algorithm,
+    iterations: u32,
+}
+
+impl Pbkdf2PasswordEncoder {
+    pub fn new(iterations: u32) -> Self {
+        Self {
+            algorithm: Algorithm::Pbkdf2Sha256,
+            iterations,
+        }
+    }
+
+    /// Uses HMAC-SHA512 instead of HMAC-SHA256.
+    pub fn sha512(mut self) -> Self {
+        self.algorithm = Algorithm::Pbkdf2Sha512;
+        self
+    }
+}
+
+impl Default for Pbkdf2PasswordEncoder {
+    fn default() -> Self {
+        Self::new(Params::RECOMMENDED_ROUNDS as u32)
+    }
+}
+
+impl PasswordEncoder for Pbkdf2PasswordEncoder {
+    fn encode(&self, raw_password: &str) -> Result<String, BoxError> {
+        let salt = SaltString::generate(&mut rand::rngs::OsRng);
+        let params = Params {
+            rounds: self.iterations,
+            ..Default::default()
+        };
+        Pbkdf2
+            .hash_password_customized(
+                raw_password.as_bytes(),
+                Some(self.algorithm.ident()),
+                None,
+                params,
+                &salt,
+            )
+            .map(|hash| hash.to_string())
+            .map_err(|e| e.to_string().into())
+    }
+
+    fn matches(&self, raw_password: &str, encoded_password: &str) -> bool {
+        PasswordHash::new(encoded_password)
+            .and_then(|hash| Pbkdf2.verify_password(raw_password.as_bytes(), &hash))
+            .is_ok()
+    }
+
+    fn upgrade_encoding(&self, encoded_password: &str) -> bool {
+        let Ok(hash) = PasswordHash::new(encoded_password) else {
+            return false;
+        };
+        if hash.algorithm != self.algorithm.ident() {
+            return true;
+        }
+        Params::try_from(&hash)
+            .map(|params| params.rounds < self.iterations)
+            .unwrap_or(false)
+    }
+}
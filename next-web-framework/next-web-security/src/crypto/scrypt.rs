@@ -0,0 +1,63 @@
+use next_web_core::error::BoxError;
+use scrypt::{
+    password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
+    Params, Scrypt,
+};
+
+use crate::crypto::password::password_encoder::PasswordEncoder;
+
+/// SCrypt 密码编码器
+///
+/// Hashes are PHC strings (`$scrypt$ln=17,r=8,p=1$...`), verification reads the parameters
+/// back from the hash. Hashes made with a lower cost are reported by `upgrade_encoding`.
+#[derive(Clone)]
+pub struct SCryptPasswordEncoder {
+    params: Params,
+}
+
+impl SCryptPasswordEncoder {
+    /// `log_n` is the log2 of the CPU/memory cost, `r` the block size, `p` the parallelism.
+    pub fn new(log_n: u8, r: u32, p: u32) -> Result<Self, BoxError> {
+        let params =
+            Params::new(log_n, r, p, Params::RECOMMENDED_LEN).map_err(|e| e.to_string())?;
+        Ok(Self { params })
+    }
+}
+
+impl Default for SCryptPasswordEncoder {
+    fn default() -> Self {
+        Self {
+            params: Params::recommended(),
+        }
+    }
+}
+
+impl PasswordEncoder for SCryptPasswordEncoder {
+    fn encode(&self, raw_password: &str) -> Result<String, BoxError> {
+        let salt = SaltString::generate(&mut rand::rngs::OsRng);
+        Scrypt
+            .hash_password_customized(raw_password.as_bytes(), None, None, self.params, &salt)
+            .map(|hash| hash.to_string())
+            .map_err(|e| e.to_string().into())
+    }
+
+    fn matches(&self, raw_password: &str, encoded_password: &str) -> bool {
+        PasswordHash::new(encoded_password)
+            .and_then(|hash| Scrypt.verify_password(raw_password.as_bytes(), &hash))
+            .is_ok()
+    }
+
+    fn upgrade_encoding(&self, encoded_password: &str) -> bool {
+        let Ok(hash) = PasswordHash::new(encoded_password) else {
+            return false;
+        };
+        match Params::try_from(&hash) {
+            Ok(params) => {
+                params.log_n() < self.params.log_n()
+                    || params.r() < self.params.r()
+                    || params.p() < self.params.p()
+            }
+            Err(_) => false,
+        }
+    }
+}
@@ -1,3 +1,5 @@
+pub mod crypto;
+
 #[cfg(feature = "user-friendly")]
 mod shiro;
 #[cfg(feature = "user-friendly")]
@@ -1,13 +1,16 @@
+use next_web_core::async_trait;
+
 use super::{
     authentication_error::AuthenticationError, authentication_info::AuthenticationInfo,
     authentication_token::AuthenticationToken,
 };
 
+#[async_trait]
 pub trait Authenticator
 where
     Self: Send + Sync,
 {
-    fn authenticate(
+    async fn authenticate(
         &self,
         authentication_token: &dyn AuthenticationToken,
     ) -> Result<Box<dyn AuthenticationInfo>, AuthenticationError>;
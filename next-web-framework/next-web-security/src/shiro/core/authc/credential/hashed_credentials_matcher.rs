@@ -0,0 +1,172 @@
+use std::sync::Arc;
+
+use tracing::warn;
+
+use crate::{
+    core::{
+        authc::{
+            authentication_info::AuthenticationInfo, authentication_token::AuthenticationToken,
+            credential::credentials_matcher::CredentialsMatcher,
+        },
+        util::object::Object,
+    },
+    crypto::password::{
+        delegating_password_encoder::DelegatingPasswordEncoder, password_encoder::PasswordEncoder,
+    },
+};
+
+/// Receives the primary principal and the new hash of a password whose stored hash is outdated.
+pub type PasswordUpgradeListener = Arc<dyn Fn(&Object, String) + Send + Sync>;
+
+/// 哈希凭证匹配器
+///
+/// Matches the submitted password against the hash stored in the account with a
+/// [`PasswordEncoder`]. When the stored hash needs `upgrade_encoding`, the password is
+/// re-hashed after a successful match and handed to the upgrade listener, which persists it.
+///
+/// ```rust,ignore
+/// let mut matcher = HashedCredentialsMatcher::default();
+/// matcher.set_password_upgrade_listener(Arc::new(move |principal, hash| {
+///     user_store.update_password(principal.to_string(), hash);
+/// }));
+/// ```
+#[derive(Clone)]
+pub struct HashedCredentialsMatcher {
+    password_encoder: Arc<dyn PasswordEncoder>,
+    password_upgrade_listener: Option<PasswordUpgradeListener>,
+}
+
+impl HashedCredentialsMatcher {
+    pub fn new(password_encoder: Arc<dyn PasswordEncoder>) -> Self {
+        Self {
+            password_encoder,
+            password_upgrade_listener: None,
+        }
+    }
+
+    pub fn get_password_encoder(&self) -> &Arc<dyn PasswordEncoder> {
+        &self.password_encoder
+    }
+
+    pub fn set_password_upgrade_listener(&mut self, listener: PasswordUpgradeListener) {
+        self.password_upgrade_listener = Some(listener);
+    }
+
+    fn upgrade(&self, raw_password: &str, info: &dyn AuthenticationInfo) {
+        let Some(listener) = &self.password_upgrade_listener else {
+            return;
+        };
+        let Some(principal) = info
+            .get_principals()
+            .and_then(|principals| principals.get_primary_principal())
+        else {
+            return;
+        };
+        match self.password_encoder.encode(raw_password) {
+            Ok(encoded) => listener(principal, encoded),
+            Err(error) => warn!("Failed to upgrade password encoding: {}", error),
+        }
+    }
+}
+
+impl Default for HashedCredentialsMatcher {
+    fn default() -> Self {
+        Self::new(Arc::new(DelegatingPasswordEncoder::create_default()))
+    }
+}
+
+impl CredentialsMatcher for HashedCredentialsMatcher {
+    fn do_credentials_match(
+        &self,
+        token: &dyn AuthenticationToken,
+        info: &dyn AuthenticationInfo,
+    ) -> bool {
+        let Some(Object::Str(raw_password)) = token.get_credentials() else {
+            return false;
+        };
+        let Some(Object::Str(encoded_password)) = info.get_credentials() else {
+            return false;
+        };
+
+        if !self
+            .password_encoder
+            .matches(&raw_password, encoded_password)
+        {
+            return false;
+        }
+        if self.password_encoder.upgrade_encoding(encoded_password) {
+            self.upgrade(&raw_password, info);
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fmt::Display, sync::Mutex};
+
+    use super::*;
+    use crate::{
+        core::{
+            authc::username_password_token::UsernamePasswordToken,
+            subject::{
+                principal_collection::PrincipalCollection,
+                simple_principal_collection::SimplePrincipalCollection,
+            },
+        },
+        crypto::password::pbkdf2_password_encoder::Pbkdf2PasswordEncoder,
+    };
+
+    #[derive(Clone)]
+    struct Account {
+        principals: Arc<dyn PrincipalCollection>,
+        password: Object,
+    }
+
+    impl AuthenticationInfo for Account {
+        fn get_principals(&self) -> Option<&Arc<dyn PrincipalCollection>> {
+            Some(&self.principals)
+        }
+
+        fn get_credentials(&self) -> Option<&Object> {
+            Some(&self.password)
+        }
+    }
+
+    impl Display for Account {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "Account [{}]", self.principals)
+        }
+    }
+
+    #[test]
+    fn test_upgrade_legacy_hash() {
+        let legacy = Pbkdf2PasswordEncoder::new(1000).encode("secret").unwrap();
+        let account = Account {
+            principals: Arc::new(SimplePrincipalCollection::new(
+                Object::Str("alice".into()),
+                "test",
+            )),
+            password: Object::Str(format!("{{pbkdf2}}{}", legacy)),
+        };
+
+        let upgraded = Arc::new(Mutex::new(None));
+        let mut matcher = HashedCredentialsMatcher::default();
+        let sink = upgraded.clone();
+        matcher.set_password_upgrade_listener(Arc::new(move |principal, hash| {
+            *sink.lock().unwrap() = Some((principal.to_string(), hash));
+        }));
+
+        let wrong = UsernamePasswordToken::new("alice".into(), "wrong".into(), false, None);
+        assert!(!matcher.do_credentials_match(&wrong, &account));
+        assert!(upgraded.lock().unwrap().is_none());
+
+        let token = UsernamePasswordToken::new("alice".into(), "secret".into(), false, None);
+        assert!(matcher.do_credentials_match(&token, &account));
+
+        let (principal, hash) = upgraded.lock().unwrap().take().unwrap();
+        assert_eq!(principal, "alice");
+        assert!(hash.starts_with("{bcrypt}"));
+        assert!(matcher.get_password_encoder().matches("secret", &hash));
+    }
+}
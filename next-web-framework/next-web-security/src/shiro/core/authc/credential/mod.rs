@@ -1,2 +1,3 @@
-pub mod simple_credentials_matcher;
 pub mod credentials_matcher;
+pub mod hashed_credentials_matcher;
+pub mod simple_credentials_matcher;
@@ -1,22 +1,77 @@
-use crate::core::authc::credential::credentials_matcher::CredentialsMatcher;
+use subtle::{Choice, ConstantTimeEq};
 
-pub struct SimpleCredentialsMatcher {}
+use crate::core::{
+    authc::{
+        authentication_info::AuthenticationInfo, authentication_token::AuthenticationToken,
+        credential::credentials_matcher::CredentialsMatcher,
+    },
+    util::object::Object,
+};
 
+/// Compares the submitted credentials with the stored ones as plain values, in constant
+/// time so the comparison does not reveal how much of a secret matched.
+pub struct SimpleCredentialsMatcher {}
 
+impl SimpleCredentialsMatcher {
+    pub fn equals(token_credentials: &Object, account_credentials: &Object) -> bool {
+        let equal = match (token_credentials, account_credentials) {
+            (Object::Str(a), Object::Str(b)) => a.as_bytes().ct_eq(b.as_bytes()),
+            (Object::Int(a), Object::Int(b)) => a.ct_eq(b),
+            (Object::Bool(a), Object::Bool(b)) => (*a as u8).ct_eq(&(*b as u8)),
+            (Object::ListStr(a), Object::ListStr(b)) if a.len() == b.len() => a
+                .iter()
+                .zip(b)
+                .fold(Choice::from(1), |equal, (a, b)| {
+                    equal & a.as_bytes().ct_eq(b.as_bytes())
+                }),
+            _ => Choice::from(0),
+        };
+        equal.into()
+    }
+}
 
 impl CredentialsMatcher for SimpleCredentialsMatcher {
     fn do_credentials_match(
         &self,
-        token: &dyn crate::core::authc::authentication_token::AuthenticationToken,
-        info: &dyn crate::core::authc::authentication_info::AuthenticationInfo,
+        token: &dyn AuthenticationToken,
+        info: &dyn AuthenticationInfo,
     ) -> bool {
-        todo!()
+        match (token.get_credentials(), info.get_credentials()) {
+            (Some(token_credentials), Some(account_credentials)) => {
+                Self::equals(&token_credentials, account_credentials)
+            }
+            _ => false,
+        }
     }
 }
 
-
 impl Default for SimpleCredentialsMatcher {
     fn default() -> Self {
-        Self {  }
+        Self {}
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_equals() {
+        let secret = Object::Str("secret".into());
+        assert!(SimpleCredentialsMatcher::equals(&secret, &Object::Str("secret".into())));
+        assert!(!SimpleCredentialsMatcher::equals(&secret, &Object::Str("secreT".into())));
+        assert!(!SimpleCredentialsMatcher::equals(&secret, &Object::Str("secrets".into())));
+        assert!(!SimpleCredentialsMatcher::equals(&secret, &Object::Int(1)));
+
+        let list = Object::ListStr(vec!["a".into(), "b".into()]);
+        assert!(SimpleCredentialsMatcher::equals(
+            &list,
+            &Object::ListStr(vec!["a".into(), "b".into()])
+        ));
+        assert!(!SimpleCredentialsMatcher::equals(
+            &list,
+            &Object::ListStr(vec!["a".into()])
+        ));
+        assert!(SimpleCredentialsMatcher::equals(&Object::Int(7), &Object::Int(7)));
+    }
+}
@@ -0,0 +1,253 @@
+use std::{
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+
+use jsonwebtoken::jwk::{Jwk, JwkSet};
+use tokio::sync::{Mutex, MutexGuard, RwLock};
+use tracing::{debug, warn};
+
+use super::jwt_error::JwtError;
+
+/// JWKS 来源
+#[derive(Debug, Clone)]
+pub enum JwkSetLocation {
+    File(PathBuf),
+    Url(String),
+}
+
+impl JwkSetLocation {
+    /// `http://` and `https://` locations are fetched, anything else is read from disk.
+    pub fn parse(location: &str) -> Self {
+        if location.starts_with("http://") || location.starts_with("https://") {
+            JwkSetLocation::Url(location.to_string())
+        } else {
+            JwkSetLocation::File(PathBuf::from(location.trim_start_matches("file://")))
+        }
+    }
+}
+
+struct CachedJwkSet {
+    jwk_set: JwkSet,
+    loaded_at: Instant,
+    attempted_at: Instant,
+}
+
+impl CachedJwkSet {
+    fn needs_refresh(&self, found: bool, ttl: Duration, min_refresh_interval: Duration) -> bool {
+        if self.attempted_at.elapsed() < min_refresh_interval {
+            return false;
+        }
+        !found || self.loaded_at.elapsed() >= ttl
+    }
+}
+
+/// The cached lookup of a key.
+enum CachedKey {
+    /// The set is up to date.
+    Valid(Result<Jwk, JwtError>),
+    /// The key is known but the set has to be refreshed.
+    Expired(Jwk),
+    /// The key is unknown or no set is loaded yet, the set has to be refreshed first.
+    Missing,
+}
+
+/// JWK 密钥集
+///
+/// Keys are cached and looked up by `kid`. The set is reloaded once `ttl` has passed, and
+/// also when a token carries an unknown `kid` so rotated keys are picked up, at most once
+/// per `min_refresh_interval`.
+///
+/// A single task fetches the set at a time, outside the cache lock so lookups keep being
+/// served meanwhile. Once `ttl` has passed the known keys are still served to the other
+/// tasks, only lookups of unknown keys wait for the refresh. When a refresh fails the keys
+/// loaded before are kept and served until the next refresh succeeds.
+pub struct JwkSetSource {
+    location: JwkSetLocation,
+    ttl: Duration,
+    min_refresh_interval: Duration,
+    client: reqwest::Client,
+    cache: RwLock<Option<CachedJwkSet>>,
+    refreshing: Mutex<()>,
+}
+
+impl JwkSetSource {
+    pub fn new(location: JwkSetLocation) -> Self {
+        Self {
+            location,
+            ttl: Duration::from_secs(300),
+            min_refresh_interval: Duration::from_secs(30),
+            client: Self::client(Duration::from_secs(5), Duration::from_secs(10)),
+            cache: RwLock::new(None),
+            refreshing: Mutex::new(()),
+        }
+    }
+
+    fn client(connect_timeout: Duration, timeout: Duration) -> reqwest::Client {
+        reqwest::Client::builder()
+            .connect_timeout(connect_timeout)
+            .timeout(timeout)
+            .build()
+            .expect("failed to build the JWK set http client")
+    }
+
+    pub fn from_file(path: impl Into<PathBuf>) -> Self {
+        Self::new(JwkSetLocation::File(path.into()))
+    }
+
+    pub fn from_url(url: impl Into<String>) -> Self {
+        Self::new(JwkSetLocation::Url(url.into()))
+    }
+
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    pub fn min_refresh_interval(mut self, interval: Duration) -> Self {
+        self.min_refresh_interval = interval;
+        self
+    }
+
+    /// Connect and whole request timeouts of the fetch, default to 5 and 10 seconds.
+    pub fn timeouts(mut self, connect_timeout: Duration, timeout: Duration) -> Self {
+        self.client = Self::client(connect_timeout, timeout);
+        self
+    }
+
+    pub fn location(&self) -> &JwkSetLocation {
+        &self.location
+    }
+
+    /// The key for `kid`, a token without `kid` matches a set holding a single key.
+    pub async fn get_key(&self, kid: Option<&str>) -> Result<Jwk, JwtError> {
+        match self.cached_key(kid).await {
+            CachedKey::Valid(result) => result,
+            CachedKey::Expired(jwk) => match self.refreshing.try_lock() {
+                Ok(refreshing) => self.refresh(kid, refreshing).await,
+                // Another task is refreshing the set
+                Err(_) => Ok(jwk),
+            },
+            CachedKey::Missing => {
+                let refreshing = self.refreshing.lock().await;
+                self.refresh(kid, refreshing).await
+            }
+        }
+    }
+
+    async fn refresh(
+        &self,
+        kid: Option<&str>,
+        _refreshing: MutexGuard<'_, ()>,
+    ) -> Result<Jwk, JwtError> {
+        // Another task may have refreshed the set while we waited for the lock
+        if let CachedKey::Valid(result) = self.cached_key(kid).await {
+            return result;
+        }
+
+        debug!("Loading JWK set from {:?}", self.location);
+        let loaded = self.load().await;
+
+        let mut cache = self.cache.write().await;
+        let now = Instant::now();
+        match loaded {
+            Ok(jwk_set) => {
+                let jwk = Self::find(&jwk_set, kid);
+                *cache = Some(CachedJwkSet {
+                    jwk_set,
+                    loaded_at: now,
+                    attempted_at: now,
+                });
+                jwk.ok_or_else(|| JwtError::KeyNotFound(kid.map(ToString::to_string)))
+            }
+            Err(error) => match cache.as_mut() {
+                Some(cached) => {
+                    warn!(
+                        "Failed to refresh the JWK set from {:?}, serving the keys loaded before: {}",
+                        self.location, error
+                    );
+                    cached.attempted_at = now;
+                    Self::find(&cached.jwk_set, kid).ok_or(error)
+                }
+                None => Err(error),
+            },
+        }
+    }
+
+    async fn cached_key(&self, kid: Option<&str>) -> CachedKey {
+        let cache = self.cache.read().await;
+        let Some(cached) = cache.as_ref() else {
+            return CachedKey::Missing;
+        };
+
+        let jwk = Self::find(&cached.jwk_set, kid);
+        let needs_refresh =
+            cached.needs_refresh(jwk.is_some(), self.ttl, self.min_refresh_interval);
+        match (jwk, needs_refresh) {
+            (Some(jwk), true) => CachedKey::Expired(jwk),
+            (None, true) => CachedKey::Missing,
+            (jwk, false) => CachedKey::Valid(
+                jwk.ok_or_else(|| JwtError::KeyNotFound(kid.map(ToString::to_string))),
+            ),
+        }
+    }
+
+    fn find(jwk_set: &JwkSet, kid: Option<&str>) -> Option<Jwk> {
+        match kid {
+            Some(kid) => jwk_set.find(kid).cloned(),
+            None if jwk_set.keys.len() == 1 => jwk_set.keys.first().cloned(),
+            None => None,
+        }
+    }
+
+    async fn load(&self) -> Result<JwkSet, JwtError> {
+        let content = match &self.location {
+            JwkSetLocation::File(path) => std::fs::read_to_string(path)
+                .map_err(|e| JwtError::JwkSet(format!("{}: {}", path.display(), e)))?,
+            JwkSetLocation::Url(url) => self
+                .client
+                .get(url)
+                .send()
+                .await
+                .and_then(|resp| resp.error_for_status())
+                .map_err(|e| JwtError::JwkSet(e.to_string()))?
+                .text()
+                .await
+                .map_err(|e| JwtError::JwkSet(e.to_string()))?,
+        };
+
+        serde_json::from_str(&content).map_err(|e| JwtError::JwkSet(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_expired_keys_served_while_refreshing() {
+        let path = std::env::temp_dir().join(format!("jwks-{}.json", uuid::Uuid::new_v4()));
+        std::fs::write(
+            &path,
+            r#"{"keys":[{"kty":"oct","kid":"k1","alg":"HS256","k":"Zmlyc3Q"}]}"#,
+        )
+        .unwrap();
+
+        let source = JwkSetSource::from_file(&path)
+            .ttl(Duration::ZERO)
+            .min_refresh_interval(Duration::ZERO);
+        assert!(source.get_key(Some("k1")).await.is_ok());
+
+        // Another task is refreshing: the known key is served, an unknown one waits for it
+        let refreshing = source.refreshing.lock().await;
+        let timeout = Duration::from_millis(100);
+        let known = tokio::time::timeout(timeout, source.get_key(Some("k1"))).await;
+        assert!(matches!(known, Ok(Ok(_))));
+        let unknown = tokio::time::timeout(timeout, source.get_key(Some("k2"))).await;
+        assert!(unknown.is_err());
+
+        drop(refreshing);
+        assert!(source.get_key(Some("k1")).await.is_ok());
+        std::fs::remove_file(&path).ok();
+    }
+}
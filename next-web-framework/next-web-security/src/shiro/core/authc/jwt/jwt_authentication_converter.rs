@@ -0,0 +1,97 @@
+use serde_json::Value;
+
+use super::{jwt_claims::JwtClaims, jwt_error::JwtError};
+
+/// JWT 声明转换器
+///
+/// Maps verified claims to a principal, roles and permissions. Authority claims may be a
+/// space separated string (`"scope": "orders:read orders:write"`) or an array of strings.
+///
+/// By default the principal is `sub`, roles come from `roles` and permissions from `scope`
+/// and `scp`, all without prefix.
+#[derive(Debug, Clone)]
+pub struct JwtAuthenticationConverter {
+    principal_claim: String,
+    role_claims: Vec<String>,
+    permission_claims: Vec<String>,
+    role_prefix: String,
+    permission_prefix: String,
+}
+
+impl Default for JwtAuthenticationConverter {
+    fn default() -> Self {
+        Self {
+            principal_claim: "sub".to_string(),
+            role_claims: vec!["roles".to_string()],
+            permission_claims: vec!["scope".to_string(), "scp".to_string()],
+            role_prefix: String::new(),
+            permission_prefix: String::new(),
+        }
+    }
+}
+
+impl JwtAuthenticationConverter {
+    pub fn principal_claim(mut self, claim: impl Into<String>) -> Self {
+        self.principal_claim = claim.into();
+        self
+    }
+
+    pub fn role_claims(mut self, claims: Vec<String>) -> Self {
+        self.role_claims = claims;
+        self
+    }
+
+    pub fn permission_claims(mut self, claims: Vec<String>) -> Self {
+        self.permission_claims = claims;
+        self
+    }
+
+    /// Prefix added to every role, e.g. `ROLE_`.
+    pub fn role_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.role_prefix = prefix.into();
+        self
+    }
+
+    /// Prefix added to every permission, e.g. `SCOPE_`.
+    pub fn permission_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.permission_prefix = prefix.into();
+        self
+    }
+
+    pub fn principal(&self, claims: &JwtClaims) -> Result<String, JwtError> {
+        match claims.get(&self.principal_claim) {
+            Some(Value::String(principal)) if !principal.is_empty() => Ok(principal),
+            Some(Value::Number(principal)) => Ok(principal.to_string()),
+            _ => Err(JwtError::MissingPrincipal(self.principal_claim.clone())),
+        }
+    }
+
+    pub fn roles(&self, claims: &JwtClaims) -> Vec<String> {
+        Self::authorities(claims, &self.role_claims, &self.role_prefix)
+    }
+
+    pub fn permissions(&self, claims: &JwtClaims) -> Vec<String> {
+        Self::authorities(claims, &self.permission_claims, &self.permission_prefix)
+    }
+
+    fn authorities(claims: &JwtClaims, names: &[String], prefix: &str) -> Vec<String> {
+        let mut authorities = Vec::new();
+        for value in names.iter().filter_map(|name| claims.get(name)) {
+            let values = match value {
+                Value::String(value) => value.split_whitespace().map(str::to_string).collect(),
+                Value::Array(values) => values
+                    .into_iter()
+                    .filter_map(|value| value.as_str().map(str::to_string))
+                    .collect(),
+                _ => Vec::new(),
+            };
+            for value in values {
+                let authority = format!("{}{}", prefix, value);
+                if !authorities.contains(&authority) {
+                    authorities.push(authority);
+                }
+            }
+        }
+        authorities
+    }
+}
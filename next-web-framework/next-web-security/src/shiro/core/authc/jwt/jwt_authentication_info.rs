@@ -0,0 +1,83 @@
+use std::{fmt::Display, sync::Arc};
+
+use crate::core::{
+    authc::authentication_info::AuthenticationInfo,
+    authz::{authorization_info::AuthorizationInfo, permission::Permission},
+    subject::principal_collection::PrincipalCollection,
+    util::object::Object,
+};
+
+use super::jwt_claims::JwtClaims;
+
+/// JWT 认证信息
+///
+/// The result of a verified bearer token, carries the principals, the token itself as
+/// credentials, the claims and the authorities mapped from them.
+#[derive(Clone)]
+pub struct JwtAuthenticationInfo {
+    principals: Arc<dyn PrincipalCollection>,
+    credentials: Object,
+    claims: JwtClaims,
+    roles: Vec<String>,
+    permissions: Vec<String>,
+}
+
+impl JwtAuthenticationInfo {
+    pub fn new(
+        principals: Arc<dyn PrincipalCollection>,
+        token: impl Into<String>,
+        claims: JwtClaims,
+        roles: Vec<String>,
+        permissions: Vec<String>,
+    ) -> Self {
+        Self {
+            principals,
+            credentials: Object::Str(token.into()),
+            claims,
+            roles,
+            permissions,
+        }
+    }
+
+    pub fn get_claims(&self) -> &JwtClaims {
+        &self.claims
+    }
+
+    pub fn get_token(&self) -> &str {
+        self.credentials.as_str().unwrap_or_default()
+    }
+}
+
+impl AuthenticationInfo for JwtAuthenticationInfo {
+    fn get_principals(&self) -> Option<&Arc<dyn PrincipalCollection>> {
+        Some(&self.principals)
+    }
+
+    fn get_credentials(&self) -> Option<&Object> {
+        Some(&self.credentials)
+    }
+}
+
+impl AuthorizationInfo for JwtAuthenticationInfo {
+    fn get_roles(&self) -> Vec<String> {
+        self.roles.clone()
+    }
+
+    fn get_permissions(&self) -> Vec<String> {
+        self.permissions.clone()
+    }
+
+    fn get_dyn_permissions(&self) -> Vec<Box<dyn Permission>> {
+        Vec::new()
+    }
+}
+
+impl Display for JwtAuthenticationInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "JwtAuthenticationInfo [principals: {}, roles: {:?}, permissions: {:?}]",
+            self.principals, self.roles, self.permissions
+        )
+    }
+}
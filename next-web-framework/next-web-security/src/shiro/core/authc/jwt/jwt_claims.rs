@@ -0,0 +1,114 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// JWT 声明集
+///
+/// Registered claims are typed, every other claim is kept in `extra`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct JwtClaims {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub iss: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sub: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub aud: Option<Audience>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exp: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nbf: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub iat: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub jti: Option<String>,
+
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// `aud` 声明，可以是单个值或数组
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Audience {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl Audience {
+    pub fn contains(&self, audience: &str) -> bool {
+        match self {
+            Audience::One(aud) => aud == audience,
+            Audience::Many(auds) => auds.iter().any(|aud| aud == audience),
+        }
+    }
+}
+
+impl JwtClaims {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn issuer(mut self, iss: impl Into<String>) -> Self {
+        self.iss = Some(iss.into());
+        self
+    }
+
+    pub fn subject(mut self, sub: impl Into<String>) -> Self {
+        self.sub = Some(sub.into());
+        self
+    }
+
+    pub fn audience(mut self, aud: impl Into<String>) -> Self {
+        self.aud = Some(Audience::One(aud.into()));
+        self
+    }
+
+    pub fn audiences(mut self, aud: Vec<String>) -> Self {
+        self.aud = Some(Audience::Many(aud));
+        self
+    }
+
+    /// Sets `iat` to now and `exp` to now plus the given duration.
+    pub fn expires_in(mut self, duration: std::time::Duration) -> Self {
+        let now = chrono::Utc::now().timestamp();
+        self.iat = Some(now);
+        self.exp = Some(now + duration.as_secs() as i64);
+        self
+    }
+
+    pub fn expires_at(mut self, exp: i64) -> Self {
+        self.exp = Some(exp);
+        self
+    }
+
+    pub fn not_before(mut self, nbf: i64) -> Self {
+        self.nbf = Some(nbf);
+        self
+    }
+
+    pub fn jwt_id(mut self, jti: impl Into<String>) -> Self {
+        self.jti = Some(jti.into());
+        self
+    }
+
+    pub fn claim(mut self, name: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.extra.insert(name.into(), value.into());
+        self
+    }
+
+    /// Looks up any claim by name, registered claims included.
+    pub fn get(&self, name: &str) -> Option<Value> {
+        match name {
+            "iss" => self.iss.clone().map(Value::from),
+            "sub" => self.sub.clone().map(Value::from),
+            "aud" => self
+                .aud
+                .as_ref()
+                .and_then(|aud| serde_json::to_value(aud).ok()),
+            "exp" => self.exp.map(Value::from),
+            "nbf" => self.nbf.map(Value::from),
+            "iat" => self.iat.map(Value::from),
+            "jti" => self.jti.clone().map(Value::from),
+            _ => self.extra.get(name).cloned(),
+        }
+    }
+}
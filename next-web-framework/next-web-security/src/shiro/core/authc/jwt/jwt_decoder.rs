@@ -0,0 +1,337 @@
+use std::sync::Arc;
+
+use jsonwebtoken::{jwk::Jwk, Algorithm, DecodingKey, Validation};
+
+use super::{
+    jwk_set_source::{JwkSetLocation, JwkSetSource},
+    jwt_claims::JwtClaims,
+    jwt_error::JwtError,
+    jwt_properties::JwtProperties,
+};
+
+#[derive(Clone)]
+enum KeySource {
+    Static {
+        algorithm: Algorithm,
+        key: DecodingKey,
+    },
+    JwkSet(Arc<JwkSetSource>),
+}
+
+/// JWT 校验器
+///
+/// Verifies the signature, then `exp`, `nbf`, `iss` and `aud` with the configured leeway.
+/// `exp` is required. Keys come either from a single static key or from a JWK set, in which
+/// case the key is selected by the `kid` header.
+///
+/// ```rust,ignore
+/// let decoder = JwtDecoder::with_jwk_set(JwkSetSource::from_url("https://idp.example.com/jwks.json"))
+///     .issuer("https://idp.example.com")
+///     .audience("orders")
+///     .leeway(30);
+/// let claims = decoder.decode(token).await?;
+/// ```
+#[derive(Clone)]
+pub struct JwtDecoder {
+    key_source: KeySource,
+    algorithms: Vec<Algorithm>,
+    issuer: Option<String>,
+    audiences: Vec<String>,
+    leeway: u64,
+}
+
+impl JwtDecoder {
+    fn new(key_source: KeySource, algorithms: Vec<Algorithm>) -> Self {
+        Self {
+            key_source,
+            algorithms,
+            issuer: None,
+            audiences: Vec::new(),
+            leeway: 60,
+        }
+    }
+
+    pub fn with_key(algorithm: Algorithm, key: DecodingKey) -> Self {
+        Self::new(KeySource::Static { algorithm, key }, vec![algorithm])
+    }
+
+    pub fn hs256(secret: impl AsRef<[u8]>) -> Self {
+        Self::with_key(Algorithm::HS256, DecodingKey::from_secret(secret.as_ref()))
+    }
+
+    pub fn rs256_pem(public_key: impl AsRef<[u8]>) -> Result<Self, JwtError> {
+        Ok(Self::with_key(
+            Algorithm::RS256,
+            DecodingKey::from_rsa_pem(public_key.as_ref())?,
+        ))
+    }
+
+    pub fn es256_pem(public_key: impl AsRef<[u8]>) -> Result<Self, JwtError> {
+        Ok(Self::with_key(
+            Algorithm::ES256,
+            DecodingKey::from_ec_pem(public_key.as_ref())?,
+        ))
+    }
+
+    pub fn ed_dsa_pem(public_key: impl AsRef<[u8]>) -> Result<Self, JwtError> {
+        Ok(Self::with_key(
+            Algorithm::EdDSA,
+            DecodingKey::from_ed_pem(public_key.as_ref())?,
+        ))
+    }
+
+    /// Keys are looked up by `kid` in the set, only asymmetric algorithms are accepted
+    /// unless [`algorithms`](Self::algorithms) says otherwise.
+    pub fn with_jwk_set(source: JwkSetSource) -> Self {
+        Self::new(
+            KeySource::JwkSet(Arc::new(source)),
+            vec![
+                Algorithm::RS256,
+                Algorithm::RS384,
+                Algorithm::RS512,
+                Algorithm::PS256,
+                Algorithm::PS384,
+                Algorithm::PS512,
+                Algorithm::ES256,
+                Algorithm::ES384,
+                Algorithm::EdDSA,
+            ],
+        )
+    }
+
+    /// Builds a decoder from `jwk_set_uri`, `public_key` or `secret`, in that order.
+    pub fn from_properties(properties: &JwtProperties) -> Result<Self, JwtError> {
+        let algorithm = properties.algorithm()?;
+        let mut decoder = if let Some(uri) = properties.jwk_set_uri() {
+            let decoder = Self::with_jwk_set(JwkSetSource::new(JwkSetLocation::parse(uri)));
+            match algorithm {
+                Some(algorithm) => decoder.algorithms(vec![algorithm]),
+                None => decoder,
+            }
+        } else if let Some(public_key) = properties.public_key() {
+            let pem = public_key.as_bytes();
+            let algorithm = algorithm.unwrap_or(Algorithm::RS256);
+            let key = match algorithm {
+                Algorithm::ES256 | Algorithm::ES384 => DecodingKey::from_ec_pem(pem)?,
+                Algorithm::EdDSA => DecodingKey::from_ed_pem(pem)?,
+                _ => DecodingKey::from_rsa_pem(pem)?,
+            };
+            Self::with_key(algorithm, key)
+        } else if let Some(secret) = properties.secret() {
+            Self::with_key(
+                algorithm.unwrap_or(Algorithm::HS256),
+                DecodingKey::from_secret(secret.as_bytes()),
+            )
+        } else {
+            return Err(JwtError::InvalidKey(
+                "one of jwk_set_uri, public_key or secret is required".to_string(),
+            ));
+        };
+
+        if let Some(issuer) = properties.issuer() {
+            decoder = decoder.issuer(issuer);
+        }
+        for audience in properties.audiences() {
+            decoder = decoder.audience(audience);
+        }
+        Ok(decoder.leeway(properties.leeway()))
+    }
+
+    pub fn algorithms(mut self, algorithms: Vec<Algorithm>) -> Self {
+        self.algorithms = algorithms;
+        self
+    }
+
+    pub fn issuer(mut self, issuer: impl Into<String>) -> Self {
+        self.issuer = Some(issuer.into());
+        self
+    }
+
+    /// Accepted audience, a token is valid when its `aud` contains any of them.
+    pub fn audience(mut self, audience: impl Into<String>) -> Self {
+        self.audiences.push(audience.into());
+        self
+    }
+
+    /// Clock skew tolerated on `exp` and `nbf`, in seconds.
+    pub fn leeway(mut self, leeway: u64) -> Self {
+        self.leeway = leeway;
+        self
+    }
+
+    pub async fn decode(&self, token: &str) -> Result<JwtClaims, JwtError> {
+        let header = jsonwebtoken::decode_header(token)?;
+        if !self.algorithms.contains(&header.alg) {
+            return Err(JwtError::UnsupportedAlgorithm(format!("{:?}", header.alg)));
+        }
+
+        let key = match &self.key_source {
+            KeySource::Static { algorithm, key } => {
+                if *algorithm != header.alg {
+                    return Err(JwtError::UnsupportedAlgorithm(format!("{:?}", header.alg)));
+                }
+                key.clone()
+            }
+            KeySource::JwkSet(source) => {
+                let jwk = source.get_key(header.kid.as_deref()).await?;
+                Self::check_jwk_algorithm(&jwk, header.alg)?;
+                DecodingKey::from_jwk(&jwk)?
+            }
+        };
+
+        let mut validation = Validation::new(header.alg);
+        validation.leeway = self.leeway;
+        validation.validate_nbf = true;
+        validation.validate_aud = !self.audiences.is_empty();
+        if !self.audiences.is_empty() {
+            validation.set_audience(&self.audiences);
+        }
+        if let Some(issuer) = &self.issuer {
+            validation.set_issuer(&[issuer]);
+        }
+
+        Ok(jsonwebtoken::decode::<JwtClaims>(token, &key, &validation)?.claims)
+    }
+
+    /// A key that names its algorithm may only verify tokens signed with it.
+    fn check_jwk_algorithm(jwk: &Jwk, algorithm: Algorithm) -> Result<(), JwtError> {
+        match jwk.common.key_algorithm {
+            Some(key_algorithm) if format!("{:?}", key_algorithm) != format!("{:?}", algorithm) => {
+                Err(JwtError::UnsupportedAlgorithm(format!(
+                    "{:?} with a {:?} key",
+                    algorithm, key_algorithm
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use base64::Engine;
+
+    use super::*;
+    use crate::core::authc::jwt::jwt_encoder::JwtEncoder;
+
+    fn claims() -> JwtClaims {
+        JwtClaims::new()
+            .issuer("https://idp.example.com")
+            .audience("orders")
+            .subject("alice")
+            .expires_in(Duration::from_secs(60))
+    }
+
+    fn oct_key(kid: &str, secret: &str) -> String {
+        let k = base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(secret);
+        format!(
+            r#"{{"kty":"oct","kid":"{}","alg":"HS256","k":"{}"}}"#,
+            kid, k
+        )
+    }
+
+    #[tokio::test]
+    async fn test_decode_validates_claims() {
+        let token = JwtEncoder::hs256("secret").encode(&claims()).unwrap();
+        let decoder = JwtDecoder::hs256("secret")
+            .issuer("https://idp.example.com")
+            .audience("orders");
+
+        let decoded = decoder.decode(&token).await.unwrap();
+        assert_eq!(decoded.sub.as_deref(), Some("alice"));
+
+        let wrong_key = JwtDecoder::hs256("other").decode(&token).await;
+        assert!(matches!(wrong_key, Err(JwtError::InvalidToken(_))));
+
+        let wrong_issuer = JwtDecoder::hs256("secret").issuer("https://evil.example.com");
+        assert!(matches!(
+            wrong_issuer.decode(&token).await,
+            Err(JwtError::InvalidIssuer)
+        ));
+
+        let wrong_audience = JwtDecoder::hs256("secret").audience("billing");
+        assert!(matches!(
+            wrong_audience.decode(&token).await,
+            Err(JwtError::InvalidAudience)
+        ));
+
+        let now = chrono::Utc::now().timestamp();
+        let expired = JwtEncoder::hs256("secret")
+            .encode(&claims().expires_at(now - 30))
+            .unwrap();
+        assert!(matches!(
+            decoder.clone().leeway(0).decode(&expired).await,
+            Err(JwtError::Expired)
+        ));
+        assert!(decoder.leeway(60).decode(&expired).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_jwk_set_key_rotation() {
+        let path = std::env::temp_dir().join(format!("jwks-{}.json", uuid::Uuid::new_v4()));
+        std::fs::write(&path, format!(r#"{{"keys":[{}]}}"#, oct_key("k1", "first"))).unwrap();
+
+        let source = JwkSetSource::from_file(&path).min_refresh_interval(Duration::ZERO);
+        let decoder = JwtDecoder::with_jwk_set(source).algorithms(vec![Algorithm::HS256]);
+
+        let first = JwtEncoder::hs256("first")
+            .kid("k1")
+            .encode(&claims())
+            .unwrap();
+        assert!(decoder.decode(&first).await.is_ok());
+
+        // A token signed with a key that is not published yet
+        let second = JwtEncoder::hs256("second")
+            .kid("k2")
+            .encode(&claims())
+            .unwrap();
+        assert!(matches!(
+            decoder.decode(&second).await,
+            Err(JwtError::KeyNotFound(_))
+        ));
+
+        // Rotated keys are picked up on the next unknown kid
+        let keys = format!(
+            r#"{{"keys":[{},{}]}}"#,
+            oct_key("k1", "first"),
+            oct_key("k2", "second")
+        );
+        std::fs::write(&path, keys).unwrap();
+        assert!(decoder.decode(&second).await.is_ok());
+        assert!(decoder.decode(&first).await.is_ok());
+
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[tokio::test]
+    async fn test_jwk_set_serves_stale_keys() {
+        let path = std::env::temp_dir().join(format!("jwks-{}.json", uuid::Uuid::new_v4()));
+        std::fs::write(&path, format!(r#"{{"keys":[{}]}}"#, oct_key("k1", "first"))).unwrap();
+
+        let source = JwkSetSource::from_file(&path)
+            .ttl(Duration::ZERO)
+            .min_refresh_interval(Duration::ZERO);
+        let decoder = JwtDecoder::with_jwk_set(source).algorithms(vec![Algorithm::HS256]);
+
+        let first = JwtEncoder::hs256("first")
+            .kid("k1")
+            .encode(&claims())
+            .unwrap();
+        assert!(decoder.decode(&first).await.is_ok());
+
+        // The set can no longer be loaded, the expired keys are still served
+        std::fs::remove_file(&path).unwrap();
+        assert!(decoder.decode(&first).await.is_ok());
+
+        let second = JwtEncoder::hs256("second")
+            .kid("k2")
+            .encode(&claims())
+            .unwrap();
+        assert!(matches!(
+            decoder.decode(&second).await,
+            Err(JwtError::JwkSet(_))
+        ));
+    }
+}
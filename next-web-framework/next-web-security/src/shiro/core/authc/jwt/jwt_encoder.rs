@@ -0,0 +1,69 @@
+use jsonwebtoken::{Algorithm, EncodingKey, Header};
+
+use super::{jwt_claims::JwtClaims, jwt_error::JwtError};
+
+/// JWT 签发器
+///
+/// Signs claims with one key, `kid` is written to the header when set so verifiers backed
+/// by a JWK set can pick the matching key.
+///
+/// ```rust,ignore
+/// let encoder = JwtEncoder::hs256(b"secret").kid("2024-01");
+/// let token = encoder.encode(&JwtClaims::new().subject("alice").expires_in(Duration::from_secs(3600)))?;
+/// ```
+#[derive(Clone)]
+pub struct JwtEncoder {
+    algorithm: Algorithm,
+    key: EncodingKey,
+    kid: Option<String>,
+}
+
+impl JwtEncoder {
+    pub fn new(algorithm: Algorithm, key: EncodingKey) -> Self {
+        Self {
+            algorithm,
+            key,
+            kid: None,
+        }
+    }
+
+    pub fn hs256(secret: impl AsRef<[u8]>) -> Self {
+        Self::new(Algorithm::HS256, EncodingKey::from_secret(secret.as_ref()))
+    }
+
+    pub fn rs256_pem(private_key: impl AsRef<[u8]>) -> Result<Self, JwtError> {
+        Ok(Self::new(
+            Algorithm::RS256,
+            EncodingKey::from_rsa_pem(private_key.as_ref())?,
+        ))
+    }
+
+    pub fn es256_pem(private_key: impl AsRef<[u8]>) -> Result<Self, JwtError> {
+        Ok(Self::new(
+            Algorithm::ES256,
+            EncodingKey::from_ec_pem(private_key.as_ref())?,
+        ))
+    }
+
+    pub fn ed_dsa_pem(private_key: impl AsRef<[u8]>) -> Result<Self, JwtError> {
+        Ok(Self::new(
+            Algorithm::EdDSA,
+            EncodingKey::from_ed_pem(private_key.as_ref())?,
+        ))
+    }
+
+    pub fn kid(mut self, kid: impl Into<String>) -> Self {
+        self.kid = Some(kid.into());
+        self
+    }
+
+    pub fn algorithm(&self) -> Algorithm {
+        self.algorithm
+    }
+
+    pub fn encode(&self, claims: &JwtClaims) -> Result<String, JwtError> {
+        let mut header = Header::new(self.algorithm);
+        header.kid = self.kid.clone();
+        Ok(jsonwebtoken::encode(&header, claims, &self.key)?)
+    }
+}
@@ -0,0 +1,80 @@
+use std::fmt::Display;
+
+use crate::core::authc::authentication_error::AuthenticationError;
+
+#[derive(Debug)]
+pub enum JwtError {
+    /// 令牌格式错误或签名无效
+    InvalidToken(String),
+    /// 令牌已过期
+    Expired,
+    /// 令牌尚未生效
+    NotYetValid,
+    /// 签发者不匹配
+    InvalidIssuer,
+    /// 受众不匹配
+    InvalidAudience,
+    /// 不允许的签名算法
+    UnsupportedAlgorithm(String),
+    /// 找不到 `kid` 对应的密钥
+    KeyNotFound(Option<String>),
+    /// 密钥无效或无法加载
+    InvalidKey(String),
+    /// JWKS 加载失败
+    JwkSet(String),
+    /// 缺少主体声明
+    MissingPrincipal(String),
+}
+
+impl From<jsonwebtoken::errors::Error> for JwtError {
+    fn from(error: jsonwebtoken::errors::Error) -> Self {
+        use jsonwebtoken::errors::ErrorKind;
+
+        match error.kind() {
+            ErrorKind::ExpiredSignature => JwtError::Expired,
+            ErrorKind::ImmatureSignature => JwtError::NotYetValid,
+            ErrorKind::InvalidIssuer => JwtError::InvalidIssuer,
+            ErrorKind::InvalidAudience => JwtError::InvalidAudience,
+            ErrorKind::InvalidAlgorithm => JwtError::UnsupportedAlgorithm(error.to_string()),
+            ErrorKind::InvalidKeyFormat
+            | ErrorKind::InvalidRsaKey(_)
+            | ErrorKind::InvalidEcdsaKey
+            | ErrorKind::InvalidAlgorithmName => JwtError::InvalidKey(error.to_string()),
+            _ => JwtError::InvalidToken(error.to_string()),
+        }
+    }
+}
+
+impl From<JwtError> for AuthenticationError {
+    fn from(error: JwtError) -> Self {
+        match error {
+            JwtError::InvalidKey(_) | JwtError::JwkSet(_) => {
+                AuthenticationError::Custom(error.to_string())
+            }
+            _ => AuthenticationError::InvalidCredentials,
+        }
+    }
+}
+
+impl Display for JwtError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JwtError::InvalidToken(msg) => write!(f, "Invalid token: {}", msg),
+            JwtError::Expired => write!(f, "Token has expired"),
+            JwtError::NotYetValid => write!(f, "Token is not valid yet"),
+            JwtError::InvalidIssuer => write!(f, "Token issuer is not trusted"),
+            JwtError::InvalidAudience => write!(f, "Token audience is not accepted"),
+            JwtError::UnsupportedAlgorithm(alg) => write!(f, "Unsupported algorithm: {}", alg),
+            JwtError::KeyNotFound(kid) => write!(
+                f,
+                "No verification key found for kid: {}",
+                kid.as_deref().unwrap_or("<none>")
+            ),
+            JwtError::InvalidKey(msg) => write!(f, "Invalid key: {}", msg),
+            JwtError::JwkSet(msg) => write!(f, "Failed to load JWK set: {}", msg),
+            JwtError::MissingPrincipal(claim) => write!(f, "Missing principal claim: {}", claim),
+        }
+    }
+}
+
+impl std::error::Error for JwtError {}
@@ -0,0 +1,68 @@
+use std::str::FromStr;
+
+use jsonwebtoken::Algorithm;
+use serde::Deserialize;
+
+use super::jwt_error::JwtError;
+
+/// JWT 校验配置
+///
+/// ```yaml
+/// jwt:
+///   jwk_set_uri: https://idp.example.com/.well-known/jwks.json
+///   issuer: https://idp.example.com
+///   audiences: [orders]
+///   leeway: 30
+/// ```
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct JwtProperties {
+    /// JWKS 地址或本地文件路径
+    jwk_set_uri: Option<String>,
+    /// PEM 格式公钥
+    public_key: Option<String>,
+    /// HMAC 密钥
+    secret: Option<String>,
+    /// 签名算法，如 `RS256`
+    algorithm: Option<String>,
+    issuer: Option<String>,
+    #[serde(default)]
+    audiences: Vec<String>,
+    /// 允许的时钟偏差（秒），默认 60
+    leeway: Option<u64>,
+}
+
+impl JwtProperties {
+    pub fn jwk_set_uri(&self) -> Option<&str> {
+        self.jwk_set_uri.as_deref()
+    }
+
+    pub fn public_key(&self) -> Option<&str> {
+        self.public_key.as_deref()
+    }
+
+    pub fn secret(&self) -> Option<&str> {
+        self.secret.as_deref()
+    }
+
+    pub fn algorithm(&self) -> Result<Option<Algorithm>, JwtError> {
+        self.algorithm
+            .as_deref()
+            .map(|algorithm| {
+                Algorithm::from_str(algorithm)
+                    .map_err(|_| JwtError::UnsupportedAlgorithm(algorithm.to_string()))
+            })
+            .transpose()
+    }
+
+    pub fn issuer(&self) -> Option<&str> {
+        self.issuer.as_deref()
+    }
+
+    pub fn audiences(&self) -> &[String] {
+        &self.audiences
+    }
+
+    pub fn leeway(&self) -> u64 {
+        self.leeway.unwrap_or(60)
+    }
+}
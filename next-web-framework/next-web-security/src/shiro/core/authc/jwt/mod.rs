@@ -0,0 +1,10 @@
+pub mod jwk_set_source;
+pub mod jwt_authentication_converter;
+pub mod jwt_authentication_info;
+pub mod jwt_claims;
+pub mod jwt_decoder;
+pub mod jwt_encoder;
+pub mod jwt_error;
+pub mod jwt_properties;
+
+pub use jsonwebtoken::Algorithm;
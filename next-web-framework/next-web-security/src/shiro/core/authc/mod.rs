@@ -5,6 +5,7 @@ pub mod authenticator;
 pub mod bearer_token;
 pub mod credential;
 pub mod host_authentication_token;
+pub mod jwt;
 pub mod logout_aware;
 pub mod pam;
 pub mod remember_me_authentication_token;
@@ -1,39 +1,103 @@
+use std::sync::Arc;
+
+use next_web_core::async_trait;
+use tracing::debug;
+
 use crate::core::{
-    authc::{authenticator::Authenticator, logout_aware::LogoutAware},
+    authc::{
+        authentication_error::AuthenticationError, authentication_info::AuthenticationInfo,
+        authentication_token::AuthenticationToken, authenticator::Authenticator,
+        logout_aware::LogoutAware,
+    },
     realm::Realm,
+    subject::principal_collection::PrincipalCollection,
 };
 
-#[derive(Clone)]
+/// 多 Realm 认证器
+///
+/// Delegates authentication to the configured realms. With a single realm its result is
+/// returned as is, with several realms the first realm that supports the token and
+/// authenticates it wins.
+#[derive(Clone, Default)]
 pub struct ModularRealmAuthenticator {
-    // TODO: implement this
+    realms: Vec<Arc<dyn Realm>>,
 }
 
 impl ModularRealmAuthenticator {
-    pub fn set_realms<T: Realm>(&mut self, realms: Vec<T>) {}
-}
-impl Default for ModularRealmAuthenticator {
-    fn default() -> Self {
-        Self {}
+    pub fn set_realms<T: Realm + 'static>(&mut self, realms: Vec<T>) {
+        self.realms = realms
+            .into_iter()
+            .map(|realm| Arc::new(realm) as Arc<dyn Realm>)
+            .collect();
+    }
+
+    pub fn add_realm(&mut self, realm: Arc<dyn Realm>) {
+        self.realms.push(realm);
+    }
+
+    pub fn get_realms(&self) -> &Vec<Arc<dyn Realm>> {
+        &self.realms
+    }
+
+    async fn do_single_realm_authentication(
+        &self,
+        realm: &dyn Realm,
+        token: &dyn AuthenticationToken,
+    ) -> Result<Box<dyn AuthenticationInfo>, AuthenticationError> {
+        if !realm.supports(token) {
+            return Err(AuthenticationError::Custom(format!(
+                "Realm [{}] does not support authentication token [{}]",
+                realm.get_name(),
+                token
+            )));
+        }
+
+        realm
+            .get_authentication_info(token)
+            .await
+            .ok_or(AuthenticationError::InvalidCredentials)
+    }
+
+    async fn do_multi_realm_authentication(
+        &self,
+        token: &dyn AuthenticationToken,
+    ) -> Result<Box<dyn AuthenticationInfo>, AuthenticationError> {
+        for realm in self.realms.iter().filter(|realm| realm.supports(token)) {
+            match realm.get_authentication_info(token).await {
+                Some(info) => return Ok(info),
+                None => debug!(
+                    "Realm [{}] could not authenticate the token",
+                    realm.get_name()
+                ),
+            }
+        }
+
+        Err(AuthenticationError::InvalidCredentials)
     }
 }
 
+#[async_trait]
 impl Authenticator for ModularRealmAuthenticator {
-    fn authenticate(
+    async fn authenticate(
         &self,
-        authentication_token: &dyn crate::core::authc::authentication_token::AuthenticationToken,
-    ) -> Result<
-        Box<dyn crate::core::authc::authentication_info::AuthenticationInfo>,
-        crate::core::authc::authentication_error::AuthenticationError,
-    > {
-        todo!()
+        authentication_token: &dyn AuthenticationToken,
+    ) -> Result<Box<dyn AuthenticationInfo>, AuthenticationError> {
+        match self.realms.as_slice() {
+            [] => Err(AuthenticationError::Custom(
+                "Configuration error: no realms have been configured".to_string(),
+            )),
+            [realm] => {
+                self.do_single_realm_authentication(realm.as_ref(), authentication_token)
+                    .await
+            }
+            _ => {
+                self.do_multi_realm_authentication(authentication_token)
+                    .await
+            }
+        }
     }
 }
 
 impl LogoutAware for ModularRealmAuthenticator {
-    fn on_logout(
-        &self,
-        principals: &dyn crate::core::subject::principal_collection::PrincipalCollection,
-    ) {
-        todo!()
-    }
+    fn on_logout(&self, _principals: &dyn PrincipalCollection) {}
 }
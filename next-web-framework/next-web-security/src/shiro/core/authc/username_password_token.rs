@@ -34,11 +34,11 @@ impl UsernamePasswordToken {
 
 impl AuthenticationToken for UsernamePasswordToken {
     fn get_principal(&self) -> Object {
-        todo!()
+        Object::Str(self.username.clone())
     }
 
     fn get_credentials(&self) -> Option<Object> {
-        todo!()
+        Some(Object::Str(self.password.clone()))
     }
 }
 
@@ -4,6 +4,8 @@ use std::{error::Error, fmt::Display};
 pub enum AuthorizationError {
     Unauthorized(String),
     Forbidden(String),
+    /// 权限字符串格式错误
+    InvalidPermission(String),
 }
 
 impl Error for AuthorizationError {}
@@ -11,8 +13,9 @@ impl Error for AuthorizationError {}
 impl Display for AuthorizationError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            AuthorizationError::Unauthorized(_) => todo!(),
-            AuthorizationError::Forbidden(_) => todo!(),
+            AuthorizationError::Unauthorized(msg) => write!(f, "Unauthorized: {}", msg),
+            AuthorizationError::Forbidden(msg) => write!(f, "Forbidden: {}", msg),
+            AuthorizationError::InvalidPermission(msg) => write!(f, "Invalid permission: {}", msg),
         }
     }
-}
\ No newline at end of file
+}
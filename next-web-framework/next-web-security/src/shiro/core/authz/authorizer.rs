@@ -60,4 +60,8 @@ where
         roles: &[&str],
     ) -> Result<(), AuthorizationError>;
 
+    // === 缓存 ===
+    /// Drops the cached grants of the principals, every cached grant with `None`. Called on
+    /// logout, call it whenever the roles or permissions of a subject change.
+    fn clear_cached_authorization(&self, _principals: Option<&dyn PrincipalCollection>) {}
 }
@@ -0,0 +1,51 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use super::security_expression_root::SecurityExpressionRoot;
+
+/// 表达式求值上下文
+///
+/// The root object plus the `#name` variables and, after the method returned,
+/// `returnObject`. Values are kept as JSON so expressions can reach into fields.
+#[derive(Debug, Clone)]
+pub struct EvaluationContext {
+    root: SecurityExpressionRoot,
+    variables: HashMap<String, Value>,
+    return_object: Option<Value>,
+}
+
+impl EvaluationContext {
+    pub fn new(root: SecurityExpressionRoot) -> Self {
+        Self {
+            root,
+            variables: HashMap::new(),
+            return_object: None,
+        }
+    }
+
+    pub fn get_root(&self) -> &SecurityExpressionRoot {
+        &self.root
+    }
+
+    /// Sets `#name`, values that fail to serialize become `null`.
+    pub fn set_variable<T: Serialize + ?Sized>(&mut self, name: impl Into<String>, value: &T) {
+        self.variables.insert(
+            name.into(),
+            serde_json::to_value(value).unwrap_or(Value::Null),
+        );
+    }
+
+    pub fn get_variable(&self, name: &str) -> Option<&Value> {
+        self.variables.get(name)
+    }
+
+    pub fn set_return_object<T: Serialize + ?Sized>(&mut self, value: &T) {
+        self.return_object = Some(serde_json::to_value(value).unwrap_or(Value::Null));
+    }
+
+    pub fn get_return_object(&self) -> Option<&Value> {
+        self.return_object.as_ref()
+    }
+}
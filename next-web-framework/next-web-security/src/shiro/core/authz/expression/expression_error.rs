@@ -0,0 +1,37 @@
+use std::fmt::Display;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExpressionError {
+    /// 表达式语法错误，附带出错位置
+    Syntax { position: usize, message: String },
+    /// 未知的函数
+    UnknownFunction(String),
+    /// 未提供的变量
+    UnknownVariable(String),
+    /// 参数或运算数类型不匹配
+    Type(String),
+}
+
+impl ExpressionError {
+    pub(crate) fn syntax(position: usize, message: impl Into<String>) -> Self {
+        ExpressionError::Syntax {
+            position,
+            message: message.into(),
+        }
+    }
+}
+
+impl Display for ExpressionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExpressionError::Syntax { position, message } => {
+                write!(f, "Syntax error at {}: {}", position, message)
+            }
+            ExpressionError::UnknownFunction(name) => write!(f, "Unknown function: {}", name),
+            ExpressionError::UnknownVariable(name) => write!(f, "Unknown variable: #{}", name),
+            ExpressionError::Type(msg) => write!(f, "Type error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for ExpressionError {}
@@ -0,0 +1,4 @@
+pub mod evaluation_context;
+pub mod expression_error;
+pub mod security_expression;
+pub mod security_expression_root;
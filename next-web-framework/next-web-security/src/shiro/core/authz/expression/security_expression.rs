@@ -0,0 +1,649 @@
+use std::cmp::Ordering;
+
+use serde_json::{Number, Value};
+
+use super::{evaluation_context::EvaluationContext, expression_error::ExpressionError};
+
+/// 安全表达式
+///
+/// A small expression language for method security, parsed once and evaluated against an
+/// [`EvaluationContext`] per call.
+///
+/// - Functions: `hasRole('ADMIN')`, `hasAnyRole('A', 'B')`, `hasPermission('order:write')`,
+///   `hasAnyPermission(..)`, `hasAuthority(..)`, `hasAnyAuthority(..)`, `isAuthenticated()`,
+///   `isAnonymous()`, `permitAll`, `denyAll`
+/// - Values: `'text'`, `"text"`, numbers, `true`, `false`, `null`, `#arg.field`,
+///   `principal.field`, `returnObject.field`
+/// - Operators: `and`/`&&`, `or`/`||`, `not`/`!`, `==`, `!=`, `<`, `<=`, `>`, `>=`, parentheses
+///
+/// Numbers and strings compare equal when their text is the same, so `#id == principal.id`
+/// holds for a numeric path parameter and a string claim. Integers compare exactly, only
+/// floating point numbers are compared as `f64`.
+///
+/// A comparison involving `null`, such as a missing field, is always false, unless one side
+/// is the `null` literal: `principal == null` and `#order.owner != null` test for presence.
+///
+/// ```rust,ignore
+/// let expression = SecurityExpression::parse("hasRole('ADMIN') or #id == principal.id")?;
+/// let allowed = expression.evaluate(&context)?;
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct SecurityExpression {
+    source: String,
+    node: Node,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    Literal(Value),
+    Variable(String, Vec<String>),
+    Principal(Vec<String>),
+    ReturnObject(Vec<String>),
+    Call(String, Vec<Node>),
+    Not(Box<Node>),
+    And(Box<Node>, Box<Node>),
+    Or(Box<Node>, Box<Node>),
+    Compare(CompareOp, Box<Node>, Box<Node>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Str(String),
+    Num(Value),
+    Ident(String),
+    Hash,
+    Dot,
+    LParen,
+    RParen,
+    Comma,
+    And,
+    Or,
+    Not,
+    Compare(CompareOp),
+}
+
+impl SecurityExpression {
+    pub fn parse(source: &str) -> Result<Self, ExpressionError> {
+        let tokens = tokenize(source)?;
+        let mut parser = Parser { tokens, index: 0 };
+        let node = parser.parse_or()?;
+        if let Some((position, token)) = parser.tokens.get(parser.index) {
+            return Err(ExpressionError::syntax(
+                *position,
+                format!("unexpected {:?}", token),
+            ));
+        }
+        Ok(Self {
+            source: source.to_string(),
+            node,
+        })
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Whether the expression reads `returnObject`, only possible after the method returned.
+    pub fn uses_return_object(&self) -> bool {
+        fn visit(node: &Node) -> bool {
+            match node {
+                Node::ReturnObject(_) => true,
+                Node::Call(_, args) => args.iter().any(visit),
+                Node::Not(node) => visit(node),
+                Node::And(left, right) | Node::Or(left, right) | Node::Compare(_, left, right) => {
+                    visit(left) || visit(right)
+                }
+                _ => false,
+            }
+        }
+        visit(&self.node)
+    }
+
+    pub fn evaluate(&self, context: &EvaluationContext) -> Result<bool, ExpressionError> {
+        match evaluate(&self.node, context)? {
+            Value::Bool(value) => Ok(value),
+            other => Err(ExpressionError::Type(format!(
+                "expression must be a boolean, found {}",
+                other
+            ))),
+        }
+    }
+}
+
+fn tokenize(source: &str) -> Result<Vec<(usize, Token)>, ExpressionError> {
+    let chars = source.char_indices().collect::<Vec<_>>();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let (position, ch) = chars[i];
+        let next = chars.get(i + 1).map(|(_, ch)| *ch);
+        let token = match ch {
+            c if c.is_whitespace() => {
+                i += 1;
+                continue;
+            }
+            '\'' | '"' => {
+                let mut text = String::new();
+                let mut closed = false;
+                i += 1;
+                while let Some((_, c)) = chars.get(i) {
+                    i += 1;
+                    if *c == ch {
+                        closed = true;
+                        break;
+                    }
+                    text.push(*c);
+                }
+                if !closed {
+                    return Err(ExpressionError::syntax(position, "unterminated string"));
+                }
+                tokens.push((position, Token::Str(text)));
+                continue;
+            }
+            c if c.is_ascii_digit() => {
+                let start = i;
+                while chars
+                    .get(i)
+                    .is_some_and(|(_, c)| c.is_ascii_digit() || *c == '.')
+                {
+                    i += 1;
+                }
+                let text = chars[start..i].iter().map(|(_, c)| c).collect::<String>();
+                let number = match (text.parse::<i64>(), text.parse::<u64>()) {
+                    (Ok(number), _) => Value::from(number),
+                    (_, Ok(number)) => Value::from(number),
+                    _ => text
+                        .parse::<f64>()
+                        .map(Value::from)
+                        .map_err(|_| ExpressionError::syntax(position, "invalid number"))?,
+                };
+                tokens.push((position, Token::Num(number)));
+                continue;
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let start = i;
+                while chars
+                    .get(i)
+                    .is_some_and(|(_, c)| c.is_alphanumeric() || *c == '_')
+                {
+                    i += 1;
+                }
+                let ident = chars[start..i].iter().map(|(_, c)| c).collect::<String>();
+                let token = match ident.to_ascii_lowercase().as_str() {
+                    "and" => Token::And,
+                    "or" => Token::Or,
+                    "not" => Token::Not,
+                    _ => Token::Ident(ident),
+                };
+                tokens.push((position, token));
+                continue;
+            }
+            '#' => Token::Hash,
+            '.' => Token::Dot,
+            '(' => Token::LParen,
+            ')' => Token::RParen,
+            ',' => Token::Comma,
+            '&' if next == Some('&') => {
+                i += 1;
+                Token::And
+            }
+            '|' if next == Some('|') => {
+                i += 1;
+                Token::Or
+            }
+            '=' if next == Some('=') => {
+                i += 1;
+                Token::Compare(CompareOp::Eq)
+            }
+            '!' if next == Some('=') => {
+                i += 1;
+                Token::Compare(CompareOp::Ne)
+            }
+            '!' => Token::Not,
+            '<' if next == Some('=') => {
+                i += 1;
+                Token::Compare(CompareOp::Le)
+            }
+            '<' => Token::Compare(CompareOp::Lt),
+            '>' if next == Some('=') => {
+                i += 1;
+                Token::Compare(CompareOp::Ge)
+            }
+            '>' => Token::Compare(CompareOp::Gt),
+            c => {
+                return Err(ExpressionError::syntax(
+                    position,
+                    format!("unexpected character '{}'", c),
+                ))
+            }
+        };
+        tokens.push((position, token));
+        i += 1;
+    }
+
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<(usize, Token)>,
+    index: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.index).map(|(_, token)| token)
+    }
+
+    fn position(&self) -> usize {
+        self.tokens
+            .get(self.index)
+            .or(self.tokens.last())
+            .map(|(position, _)| *position)
+            .unwrap_or_default()
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.index).map(|(_, token)| token.clone());
+        self.index += 1;
+        token
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<(), ExpressionError> {
+        let position = self.position();
+        match self.next() {
+            Some(token) if token == expected => Ok(()),
+            Some(token) => Err(ExpressionError::syntax(
+                position,
+                format!("expected {:?}, found {:?}", expected, token),
+            )),
+            None => Err(ExpressionError::syntax(
+                position,
+                format!("expected {:?}, found end of expression", expected),
+            )),
+        }
+    }
+
+    fn parse_or(&mut self) -> Result<Node, ExpressionError> {
+        let mut node = self.parse_and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.next();
+            node = Node::Or(Box::new(node), Box::new(self.parse_and()?));
+        }
+        Ok(node)
+    }
+
+    fn parse_and(&mut self) -> Result<Node, ExpressionError> {
+        let mut node = self.parse_not()?;
+        while self.peek() == Some(&Token::And) {
+            self.next();
+            node = Node::And(Box::new(node), Box::new(self.parse_not()?));
+        }
+        Ok(node)
+    }
+
+    fn parse_not(&mut self) -> Result<Node, ExpressionError> {
+        if self.peek() == Some(&Token::Not) {
+            self.next();
+            return Ok(Node::Not(Box::new(self.parse_not()?)));
+        }
+        self.parse_compare()
+    }
+
+    fn parse_compare(&mut self) -> Result<Node, ExpressionError> {
+        let left = self.parse_primary()?;
+        if let Some(Token::Compare(op)) = self.peek() {
+            let op = *op;
+            self.next();
+            let right = self.parse_primary()?;
+            return Ok(Node::Compare(op, Box::new(left), Box::new(right)));
+        }
+        Ok(left)
+    }
+
+    fn parse_primary(&mut self) -> Result<Node, ExpressionError> {
+        let position = self.position();
+        match self.next() {
+            Some(Token::Str(text)) => Ok(Node::Literal(Value::String(text))),
+            Some(Token::Num(number)) => Ok(Node::Literal(number)),
+            Some(Token::LParen) => {
+                let node = self.parse_or()?;
+                self.expect(Token::RParen)?;
+                Ok(node)
+            }
+            Some(Token::Hash) => match self.next() {
+                Some(Token::Ident(name)) => Ok(Node::Variable(name, self.parse_path()?)),
+                _ => Err(ExpressionError::syntax(
+                    position,
+                    "expected a variable name after '#'",
+                )),
+            },
+            Some(Token::Ident(ident)) => match ident.as_str() {
+                "true" => Ok(Node::Literal(Value::Bool(true))),
+                "false" => Ok(Node::Literal(Value::Bool(false))),
+                "null" => Ok(Node::Literal(Value::Null)),
+                "principal" => Ok(Node::Principal(self.parse_path()?)),
+                "returnObject" => Ok(Node::ReturnObject(self.parse_path()?)),
+                _ if self.peek() == Some(&Token::LParen) => {
+                    self.next();
+                    let mut args = Vec::new();
+                    if self.peek() != Some(&Token::RParen) {
+                        args.push(self.parse_or()?);
+                        while self.peek() == Some(&Token::Comma) {
+                            self.next();
+                            args.push(self.parse_or()?);
+                        }
+                    }
+                    self.expect(Token::RParen)?;
+                    Ok(Node::Call(ident, args))
+                }
+                // `permitAll` and `denyAll` read as properties
+                _ => Ok(Node::Call(ident, Vec::new())),
+            },
+            Some(token) => Err(ExpressionError::syntax(
+                position,
+                format!("unexpected {:?}", token),
+            )),
+            None => Err(ExpressionError::syntax(
+                position,
+                "unexpected end of expression",
+            )),
+        }
+    }
+
+    fn parse_path(&mut self) -> Result<Vec<String>, ExpressionError> {
+        let mut path = Vec::new();
+        while self.peek() == Some(&Token::Dot) {
+            self.next();
+            let position = self.position();
+            match self.next() {
+                Some(Token::Ident(field)) => path.push(field),
+                Some(Token::Num(index)) => path.push(index.to_string()),
+                _ => return Err(ExpressionError::syntax(position, "expected a field name")),
+            }
+        }
+        Ok(path)
+    }
+}
+
+fn evaluate(node: &Node, context: &EvaluationContext) -> Result<Value, ExpressionError> {
+    match node {
+        Node::Literal(value) => Ok(value.clone()),
+        Node::Variable(name, path) => context
+            .get_variable(name)
+            .map(|value| member(value, path))
+            .ok_or_else(|| ExpressionError::UnknownVariable(name.clone())),
+        Node::Principal(path) => Ok(context
+            .get_root()
+            .get_principal()
+            .map(|principal| member(principal, path))
+            .unwrap_or(Value::Null)),
+        Node::ReturnObject(path) => context
+            .get_return_object()
+            .map(|value| member(value, path))
+            .ok_or_else(|| ExpressionError::UnknownVariable("returnObject".to_string())),
+        Node::Not(node) => Ok(Value::Bool(!boolean(evaluate(node, context)?)?)),
+        Node::And(left, right) => Ok(Value::Bool(
+            boolean(evaluate(left, context)?)? && boolean(evaluate(right, context)?)?,
+        )),
+        Node::Or(left, right) => Ok(Value::Bool(
+            boolean(evaluate(left, context)?)? || boolean(evaluate(right, context)?)?,
+        )),
+        Node::Compare(op, left, right) => {
+            let null_check = is_null_literal(left) || is_null_literal(right);
+            let left = evaluate(left, context)?;
+            let right = evaluate(right, context)?;
+            compare(*op, &left, &right, null_check).map(Value::Bool)
+        }
+        Node::Call(name, args) => {
+            let args = args
+                .iter()
+                .map(|arg| match evaluate(arg, context)? {
+                    Value::String(text) => Ok(text),
+                    other => Err(ExpressionError::Type(format!(
+                        "{} expects string arguments, found {}",
+                        name, other
+                    ))),
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            call(name, &args, context).map(Value::Bool)
+        }
+    }
+}
+
+fn call(name: &str, args: &[String], context: &EvaluationContext) -> Result<bool, ExpressionError> {
+    let root = context.get_root();
+    let arity = |expected: usize| {
+        if args.len() == expected {
+            Ok(())
+        } else {
+            Err(ExpressionError::Type(format!(
+                "{} expects {} argument(s), found {}",
+                name,
+                expected,
+                args.len()
+            )))
+        }
+    };
+    let any = |check: &dyn Fn(&str) -> bool| {
+        if args.is_empty() {
+            return Err(ExpressionError::Type(format!(
+                "{} expects at least one argument",
+                name
+            )));
+        }
+        Ok(args.iter().any(|arg| check(arg)))
+    };
+
+    match name {
+        "hasRole" => arity(1).map(|_| root.has_role(&args[0])),
+        "hasAnyRole" => any(&|role| root.has_role(role)),
+        "hasPermission" => arity(1).map(|_| root.has_permission(&args[0])),
+        "hasAnyPermission" => any(&|permission| root.has_permission(permission)),
+        "hasAuthority" => arity(1).map(|_| root.has_authority(&args[0])),
+        "hasAnyAuthority" => any(&|authority| root.has_authority(authority)),
+        "isAuthenticated" => arity(0).map(|_| root.is_authenticated()),
+        "isAnonymous" => arity(0).map(|_| !root.is_authenticated()),
+        "permitAll" => arity(0).map(|_| true),
+        "denyAll" => arity(0).map(|_| false),
+        _ => Err(ExpressionError::UnknownFunction(name.to_string())),
+    }
+}
+
+fn member(value: &Value, path: &[String]) -> Value {
+    let mut current = value;
+    for field in path {
+        let next = match current {
+            Value::Object(map) => map.get(field),
+            Value::Array(items) => field.parse::<usize>().ok().and_then(|i| items.get(i)),
+            _ => None,
+        };
+        match next {
+            Some(next) => current = next,
+            None => return Value::Null,
+        }
+    }
+    current.clone()
+}
+
+fn boolean(value: Value) -> Result<bool, ExpressionError> {
+    match value {
+        Value::Bool(value) => Ok(value),
+        other => Err(ExpressionError::Type(format!(
+            "expected a boolean, found {}",
+            other
+        ))),
+    }
+}
+
+fn is_null_literal(node: &Node) -> bool {
+    matches!(node, Node::Literal(Value::Null))
+}
+
+fn compare(
+    op: CompareOp,
+    left: &Value,
+    right: &Value,
+    null_check: bool,
+) -> Result<bool, ExpressionError> {
+    if left.is_null() || right.is_null() {
+        // A missing value never satisfies a comparison, it can only be tested against `null`
+        return Ok(match op {
+            CompareOp::Eq if null_check => left == right,
+            CompareOp::Ne if null_check => left != right,
+            _ => false,
+        });
+    }
+
+    let equal = match (left, right) {
+        (Value::Number(a), Value::Number(b)) => compare_numbers(a, b) == Some(Ordering::Equal),
+        (Value::Number(number), Value::String(text))
+        | (Value::String(text), Value::Number(number)) => number.to_string() == *text,
+        (a, b) => a == b,
+    };
+    match op {
+        CompareOp::Eq => return Ok(equal),
+        CompareOp::Ne => return Ok(!equal),
+        _ => {}
+    }
+
+    let ordering = match (left, right) {
+        (Value::Number(a), Value::Number(b)) => compare_numbers(a, b),
+        (Value::String(a), Value::String(b)) => Some(a.cmp(b)),
+        _ => None,
+    }
+    .ok_or_else(|| ExpressionError::Type(format!("cannot compare {} with {}", left, right)))?;
+
+    Ok(match op {
+        CompareOp::Lt => ordering == Ordering::Less,
+        CompareOp::Le => ordering != Ordering::Greater,
+        CompareOp::Gt => ordering == Ordering::Greater,
+        CompareOp::Ge => ordering != Ordering::Less,
+        CompareOp::Eq | CompareOp::Ne => unreachable!(),
+    })
+}
+
+fn compare_numbers(a: &Number, b: &Number) -> Option<Ordering> {
+    let integer = |number: &Number| {
+        number
+            .as_i64()
+            .map(i128::from)
+            .or_else(|| number.as_u64().map(i128::from))
+    };
+    match (integer(a), integer(b)) {
+        (Some(a), Some(b)) => Some(a.cmp(&b)),
+        _ => a.as_f64()?.partial_cmp(&b.as_f64()?),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::core::authz::expression::security_expression_root::SecurityExpressionRoot;
+
+    fn context() -> EvaluationContext {
+        let root = SecurityExpressionRoot::authenticated(
+            json!({ "id": "42", "name": "alice" }),
+            vec!["ADMIN".into()],
+            vec!["order:read".into(), "invoice:*".into()],
+        );
+        let mut context = EvaluationContext::new(root);
+        context.set_variable("id", &42u64);
+        context.set_variable("order", &json!({ "owner": "alice", "total": 120 }));
+        context
+    }
+
+    fn eval(source: &str) -> Result<bool, ExpressionError> {
+        SecurityExpression::parse(source)?.evaluate(&context())
+    }
+
+    #[test]
+    fn test_evaluate() {
+        assert_eq!(
+            eval("hasRole('ADMIN') and hasPermission('order:read')"),
+            Ok(true)
+        );
+        assert_eq!(
+            eval("hasRole('USER') || hasAnyRole('OPS', 'ADMIN')"),
+            Ok(true)
+        );
+        assert_eq!(eval("not hasPermission('order:write')"), Ok(true));
+        assert_eq!(eval("hasPermission('invoice:refund:7')"), Ok(true));
+        assert_eq!(eval("#id == principal.id"), Ok(true));
+        assert_eq!(
+            eval("#order.owner == principal.name && #order.total <= 100"),
+            Ok(false)
+        );
+        assert_eq!(
+            eval("(permitAll or denyAll) and isAuthenticated()"),
+            Ok(true)
+        );
+
+        let anonymous = EvaluationContext::new(SecurityExpressionRoot::anonymous());
+        let expression = SecurityExpression::parse("isAnonymous() and principal == null").unwrap();
+        assert_eq!(expression.evaluate(&anonymous), Ok(true));
+    }
+
+    #[test]
+    fn test_compare_null_and_numbers() {
+        // Missing fields are null, they never match each other nor anything else
+        assert_eq!(eval("#order.missing == principal.missing"), Ok(false));
+        assert_eq!(eval("#order.missing != principal.name"), Ok(false));
+        assert_eq!(eval("#order.missing < 1"), Ok(false));
+        assert_eq!(eval("#order.missing == null"), Ok(true));
+        assert_eq!(eval("#order.owner != null"), Ok(true));
+        assert_eq!(eval("null == #order.owner"), Ok(false));
+
+        let mut context = context();
+        context.set_variable("big", &u64::MAX);
+        context.set_variable("near", &(u64::MAX - 1));
+        context.set_variable("large", &(i64::MAX - 1));
+        let evaluate = |source: &str| SecurityExpression::parse(source)?.evaluate(&context);
+        assert_eq!(evaluate("#big == #near"), Ok(false));
+        assert_eq!(evaluate("#near < #big"), Ok(true));
+        assert_eq!(evaluate("#large == 9223372036854775807"), Ok(false));
+        assert_eq!(evaluate("#big == 18446744073709551615"), Ok(true));
+        assert_eq!(evaluate("#near == 18446744073709551615"), Ok(false));
+        assert_eq!(evaluate("#order.total == 120.0"), Ok(true));
+        assert_eq!(evaluate("#order.total < 120.5"), Ok(true));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(matches!(
+            SecurityExpression::parse("hasRole('ADMIN'"),
+            Err(ExpressionError::Syntax { .. })
+        ));
+        assert!(matches!(
+            SecurityExpression::parse("hasRole('ADMIN) "),
+            Err(ExpressionError::Syntax { .. })
+        ));
+        assert_eq!(
+            eval("#missing == 1"),
+            Err(ExpressionError::UnknownVariable("missing".into()))
+        );
+        assert_eq!(
+            eval("hasGroup('x')"),
+            Err(ExpressionError::UnknownFunction("hasGroup".into()))
+        );
+        assert!(matches!(eval("#id"), Err(ExpressionError::Type(_))));
+
+        let expression = SecurityExpression::parse("returnObject.owner == principal.name").unwrap();
+        assert!(expression.uses_return_object());
+        let mut context = context();
+        context.set_return_object(&json!({ "owner": "alice" }));
+        assert_eq!(expression.evaluate(&context), Ok(true));
+    }
+}
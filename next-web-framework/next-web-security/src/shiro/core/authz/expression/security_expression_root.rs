@@ -0,0 +1,74 @@
+use serde_json::Value;
+
+use crate::core::authz::{
+    authorization_info::AuthorizationInfo,
+    permission::{wildcard_permission::WildcardPermission, Permission},
+};
+
+/// 安全表达式根对象
+///
+/// The current subject as seen by security expressions: the principal, its roles and its
+/// permissions. A root without principal is anonymous.
+#[derive(Debug, Clone, Default)]
+pub struct SecurityExpressionRoot {
+    principal: Option<Value>,
+    roles: Vec<String>,
+    permissions: Vec<String>,
+}
+
+impl SecurityExpressionRoot {
+    pub fn anonymous() -> Self {
+        Self::default()
+    }
+
+    pub fn authenticated(principal: Value, roles: Vec<String>, permissions: Vec<String>) -> Self {
+        Self {
+            principal: Some(principal),
+            roles,
+            permissions,
+        }
+    }
+
+    /// Roles and permissions are taken from the authorization info.
+    pub fn from_authorization_info(principal: Value, info: &dyn AuthorizationInfo) -> Self {
+        Self::authenticated(principal, info.get_roles(), info.get_permissions())
+    }
+
+    pub fn get_principal(&self) -> Option<&Value> {
+        self.principal.as_ref()
+    }
+
+    pub fn get_roles(&self) -> &[String] {
+        &self.roles
+    }
+
+    pub fn get_permissions(&self) -> &[String] {
+        &self.permissions
+    }
+
+    pub fn is_authenticated(&self) -> bool {
+        self.principal.is_some()
+    }
+
+    pub fn has_role(&self, role: &str) -> bool {
+        self.roles.iter().any(|r| r == role)
+    }
+
+    /// Granted permissions are matched as wildcard permissions, so `order:*` grants
+    /// `order:read`. Strings that are not valid wildcard permissions must match exactly.
+    pub fn has_permission(&self, permission: &str) -> bool {
+        let required = WildcardPermission::new(permission).ok();
+        self.permissions.iter().any(|p| {
+            p == permission
+                || match (WildcardPermission::new(p), required.as_ref()) {
+                    (Ok(granted), Some(required)) => granted.implies(required),
+                    _ => false,
+                }
+        })
+    }
+
+    /// A role or a permission.
+    pub fn has_authority(&self, authority: &str) -> bool {
+        self.has_role(authority) || self.has_permission(authority)
+    }
+}
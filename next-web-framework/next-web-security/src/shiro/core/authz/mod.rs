@@ -2,4 +2,6 @@ pub mod modular_realm_authorizer;
 pub mod authorization_info;
 pub mod permission;
 pub mod authorizer;
+pub mod expression;
 pub mod authorization_error;
+pub mod simple_authorization_info;
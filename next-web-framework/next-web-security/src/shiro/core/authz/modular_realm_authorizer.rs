@@ -1,10 +1,240 @@
-use crate::core::{authz::{authorization_error::AuthorizationError, authorizer::Authorizer, permission::Permission}, realm::Realm, subject::principal_collection::PrincipalCollection};
+use std::{
+    collections::HashSet,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
 
+use dashmap::DashMap;
+use tracing::trace;
+
+use crate::core::{
+    authz::{
+        authorization_error::AuthorizationError,
+        authorizer::Authorizer,
+        permission::{
+            permission_resolver::PermissionResolver,
+            permission_resolver_aware::PermissionResolverAware,
+            role_permission_resolver::RolePermissionResolver,
+            role_permission_resolver_aware::RolePermissionResolverAware,
+            wildcard_permission_resolver::WildcardPermissionResolver, Permission,
+        },
+    },
+    realm::Realm,
+    subject::principal_collection::PrincipalCollection,
+};
+
+/// 多 Realm 授权器
+///
+/// Aggregates the authorization info every realm returns for the principals. Roles are
+/// united, permission strings are resolved by the [`PermissionResolver`] (wildcard
+/// permissions by default) and roles are expanded by the optional
+/// [`RolePermissionResolver`]. A subject is permitted when any granted permission implies
+/// the requested one.
+///
+/// The aggregate is cached per realm and primary principal for at most `cache_ttl`, ten
+/// minutes by default. The entry of a subject is dropped on logout, call
+/// [`clear_cached_authorization`](Authorizer::clear_cached_authorization) when the grants
+/// of a principal change. Expired entries are swept at most once per `cache_ttl`.
 #[derive(Clone)]
-pub struct ModularRealmAuthorizer {}
+pub struct ModularRealmAuthorizer {
+    realms: Vec<Arc<dyn Realm>>,
+    permission_resolver: Arc<dyn PermissionResolver>,
+    role_permission_resolver: Option<Arc<dyn RolePermissionResolver>>,
+    caching_enabled: bool,
+    cache_ttl: Duration,
+    cache: Arc<DashMap<CacheKey, CachedAuthorization>>,
+    swept_at: Arc<Mutex<Instant>>,
+}
+
+/// The sorted realm names of the principals and the primary principal.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct CacheKey {
+    realms: Vec<String>,
+    principal: String,
+}
+
+struct CachedAuthorization {
+    authorization: Arc<AggregatedAuthorization>,
+    cached_at: Instant,
+}
+
+struct AggregatedAuthorization {
+    roles: HashSet<String>,
+    permissions: Vec<Box<dyn Permission>>,
+}
 
 impl ModularRealmAuthorizer {
-    pub fn set_realms<T: Realm>(&mut self, realms: Vec<T>) {}
+    pub fn set_realms<T: Realm + 'static>(&mut self, realms: Vec<T>) {
+        self.realms = realms
+            .into_iter()
+            .map(|realm| Arc::new(realm) as Arc<dyn Realm>)
+            .collect();
+        self.clear_all_cache();
+    }
+
+    pub fn add_realm(&mut self, realm: Arc<dyn Realm>) {
+        self.realms.push(realm);
+        self.clear_all_cache();
+    }
+
+    pub fn get_realms(&self) -> &Vec<Arc<dyn Realm>> {
+        &self.realms
+    }
+
+    pub fn get_permission_resolver(&self) -> &dyn PermissionResolver {
+        self.permission_resolver.as_ref()
+    }
+
+    pub fn get_role_permission_resolver(&self) -> Option<&dyn RolePermissionResolver> {
+        self.role_permission_resolver.as_deref()
+    }
+
+    pub fn is_caching_enabled(&self) -> bool {
+        self.caching_enabled
+    }
+
+    pub fn set_caching_enabled(&mut self, caching_enabled: bool) {
+        self.caching_enabled = caching_enabled;
+        if !caching_enabled {
+            self.clear_all_cache();
+        }
+    }
+
+    pub fn get_cache_ttl(&self) -> Duration {
+        self.cache_ttl
+    }
+
+    pub fn set_cache_ttl(&mut self, cache_ttl: Duration) {
+        self.cache_ttl = cache_ttl;
+    }
+
+    /// Drops the cached grants of the primary principal.
+    pub fn clear_cache(&self, principals: &dyn PrincipalCollection) {
+        if let Some(key) = Self::cache_key(principals) {
+            self.cache.remove(&key);
+        }
+    }
+
+    pub fn clear_all_cache(&self) {
+        self.cache.clear();
+    }
+
+    fn cache_key(principals: &dyn PrincipalCollection) -> Option<CacheKey> {
+        let principal = principals.get_primary_principal()?.to_string();
+        let mut realms = principals
+            .get_realm_names()
+            .unwrap_or_default()
+            .into_iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        realms.sort();
+        Some(CacheKey { realms, principal })
+    }
+
+    fn get_authorization(
+        &self,
+        principals: Option<&dyn PrincipalCollection>,
+    ) -> Option<Arc<AggregatedAuthorization>> {
+        let principals = principals.filter(|principals| !principals.is_empty())?;
+        let key = Self::cache_key(principals).filter(|_| self.caching_enabled);
+
+        if let Some(key) = key.as_ref() {
+            if let Some(cached) = self.cache.get(key) {
+                if cached.cached_at.elapsed() < self.cache_ttl {
+                    trace!(
+                        "AuthorizationInfo found in cache for principal [{}]",
+                        key.principal
+                    );
+                    return Some(Arc::clone(&cached.authorization));
+                }
+            }
+        }
+
+        let mut roles = HashSet::new();
+        let mut permissions: HashSet<Box<dyn Permission>> = HashSet::new();
+        for realm in self.realms.iter() {
+            let Some(info) = realm.get_authorization_info(principals) else {
+                continue;
+            };
+
+            permissions.extend(info.get_dyn_permissions());
+            for permission in info.get_permissions() {
+                let permission = permission.trim();
+                if !permission.is_empty() {
+                    permissions.insert(self.permission_resolver.resolve_permission(permission));
+                }
+            }
+            for role in info.get_roles() {
+                let role = role.trim();
+                if role.is_empty() {
+                    continue;
+                }
+                if let Some(resolver) = self.role_permission_resolver.as_ref() {
+                    permissions.extend(resolver.resolve_permissions_in_role(role));
+                }
+                roles.insert(role.to_string());
+            }
+        }
+
+        let authorization = Arc::new(AggregatedAuthorization {
+            roles,
+            permissions: permissions.into_iter().collect(),
+        });
+        if let Some(key) = key {
+            trace!("Caching AuthorizationInfo for principal [{}]", key.principal);
+            self.sweep_expired();
+            self.cache.insert(
+                key,
+                CachedAuthorization {
+                    authorization: Arc::clone(&authorization),
+                    cached_at: Instant::now(),
+                },
+            );
+        }
+        Some(authorization)
+    }
+
+    /// Drops the expired entries of principals that are not seen again, the whole cache is
+    /// only walked once per `cache_ttl` and by one caller at a time.
+    fn sweep_expired(&self) {
+        let Ok(mut swept_at) = self.swept_at.try_lock() else {
+            return;
+        };
+        if swept_at.elapsed() < self.cache_ttl {
+            return;
+        }
+
+        self.cache
+            .retain(|_, cached| cached.cached_at.elapsed() < self.cache_ttl);
+        *swept_at = Instant::now();
+    }
+
+    fn resolve_permissions(&self, permissions: &[&str]) -> Vec<Box<dyn Permission>> {
+        permissions
+            .iter()
+            .map(|permission| self.permission_resolver.resolve_permission(permission))
+            .collect()
+    }
+
+    fn permitted(
+        authorization: Option<&AggregatedAuthorization>,
+        permission: &dyn Permission,
+    ) -> bool {
+        authorization
+            .map(|authorization| {
+                authorization
+                    .permissions
+                    .iter()
+                    .any(|granted| granted.implies(permission))
+            })
+            .unwrap_or(false)
+    }
+
+    fn has(authorization: Option<&AggregatedAuthorization>, role: &str) -> bool {
+        authorization
+            .map(|authorization| authorization.roles.contains(role))
+            .unwrap_or(false)
+    }
 }
 
 impl Authorizer for ModularRealmAuthorizer {
@@ -13,7 +243,7 @@ impl Authorizer for ModularRealmAuthorizer {
         principal: Option<&dyn PrincipalCollection>,
         permission: &dyn Permission,
     ) -> bool {
-        todo!()
+        Self::permitted(self.get_authorization(principal).as_deref(), permission)
     }
 
     fn is_permitted_from_str(
@@ -21,7 +251,8 @@ impl Authorizer for ModularRealmAuthorizer {
         principal: Option<&dyn PrincipalCollection>,
         permission: &str,
     ) -> bool {
-        todo!()
+        let permission = self.permission_resolver.resolve_permission(permission);
+        self.is_permitted(principal, permission.as_ref())
     }
 
     fn is_permitted_from_str_list(
@@ -29,7 +260,7 @@ impl Authorizer for ModularRealmAuthorizer {
         principal: Option<&dyn PrincipalCollection>,
         permissions: &[&str],
     ) -> Vec<bool> {
-        todo!()
+        self.is_permitted_from_permission_list(principal, &self.resolve_permissions(permissions))
     }
 
     fn is_permitted_from_permission_list(
@@ -37,7 +268,11 @@ impl Authorizer for ModularRealmAuthorizer {
         principal: Option<&dyn PrincipalCollection>,
         permissions: &[Box<dyn Permission>],
     ) -> Vec<bool> {
-        todo!()
+        let authorization = self.get_authorization(principal);
+        permissions
+            .iter()
+            .map(|permission| Self::permitted(authorization.as_deref(), permission.as_ref()))
+            .collect()
     }
 
     fn is_permitted_all(
@@ -45,7 +280,10 @@ impl Authorizer for ModularRealmAuthorizer {
         principal: Option<&dyn PrincipalCollection>,
         permissions: &[Box<dyn Permission>],
     ) -> bool {
-        todo!()
+        let authorization = self.get_authorization(principal);
+        permissions
+            .iter()
+            .all(|permission| Self::permitted(authorization.as_deref(), permission.as_ref()))
     }
 
     fn is_permitted_all_from_str(
@@ -53,7 +291,7 @@ impl Authorizer for ModularRealmAuthorizer {
         principal: Option<&dyn PrincipalCollection>,
         permissions: &[&str],
     ) -> bool {
-        todo!()
+        self.is_permitted_all(principal, &self.resolve_permissions(permissions))
     }
 
     fn check_permission(
@@ -61,7 +299,14 @@ impl Authorizer for ModularRealmAuthorizer {
         principal: Option<&dyn PrincipalCollection>,
         permission: &dyn Permission,
     ) -> Result<(), AuthorizationError> {
-        todo!()
+        if self.is_permitted(principal, permission) {
+            Ok(())
+        } else {
+            Err(AuthorizationError::Unauthorized(format!(
+                "User is not permitted [{}]",
+                permission
+            )))
+        }
     }
 
     fn check_permission_from_str(
@@ -69,7 +314,8 @@ impl Authorizer for ModularRealmAuthorizer {
         principal: Option<&dyn PrincipalCollection>,
         permission: &str,
     ) -> Result<(), AuthorizationError> {
-        todo!()
+        let permission = self.permission_resolver.resolve_permission(permission);
+        self.check_permission(principal, permission.as_ref())
     }
 
     fn check_permissions(
@@ -77,7 +323,10 @@ impl Authorizer for ModularRealmAuthorizer {
         principals: Option<&dyn PrincipalCollection>,
         permissions: &[Box<dyn Permission>],
     ) -> Result<(), AuthorizationError> {
-        todo!()
+        for permission in permissions {
+            self.check_permission(principals, permission.as_ref())?;
+        }
+        Ok(())
     }
 
     fn check_permissions_from_str(
@@ -85,15 +334,14 @@ impl Authorizer for ModularRealmAuthorizer {
         principals: Option<&dyn PrincipalCollection>,
         permissions: &[&str],
     ) -> Result<(), AuthorizationError> {
-        todo!()
+        self.check_permissions(principals, &self.resolve_permissions(permissions))
     }
 
-    fn has_role(
-        &self,
-        principal: Option<&dyn PrincipalCollection>,
-        role_identifier: &str,
-    ) -> bool {
-        todo!()
+    fn has_role(&self, principal: Option<&dyn PrincipalCollection>, role_identifier: &str) -> bool {
+        Self::has(
+            self.get_authorization(principal).as_deref(),
+            role_identifier,
+        )
     }
 
     fn has_roles(
@@ -101,7 +349,11 @@ impl Authorizer for ModularRealmAuthorizer {
         principal: Option<&dyn PrincipalCollection>,
         role_identifiers: &[&str],
     ) -> Vec<bool> {
-        todo!()
+        let authorization = self.get_authorization(principal);
+        role_identifiers
+            .iter()
+            .map(|role| Self::has(authorization.as_deref(), role))
+            .collect()
     }
 
     fn has_all_roles(
@@ -109,7 +361,10 @@ impl Authorizer for ModularRealmAuthorizer {
         principal: Option<&dyn PrincipalCollection>,
         role_identifiers: &[&str],
     ) -> bool {
-        todo!()
+        let authorization = self.get_authorization(principal);
+        role_identifiers
+            .iter()
+            .all(|role| Self::has(authorization.as_deref(), role))
     }
 
     fn check_role(
@@ -117,7 +372,14 @@ impl Authorizer for ModularRealmAuthorizer {
         principal: Option<&dyn PrincipalCollection>,
         role: &str,
     ) -> Result<(), AuthorizationError> {
-        todo!()
+        if self.has_role(principal, role) {
+            Ok(())
+        } else {
+            Err(AuthorizationError::Unauthorized(format!(
+                "User does not have role [{}]",
+                role
+            )))
+        }
     }
 
     fn check_roles(
@@ -125,11 +387,186 @@ impl Authorizer for ModularRealmAuthorizer {
         principal: Option<&dyn PrincipalCollection>,
         roles: &[&str],
     ) -> Result<(), AuthorizationError> {
-        todo!()
+        for role in roles {
+            self.check_role(principal, role)?;
+        }
+        Ok(())
+    }
+
+    fn clear_cached_authorization(&self, principals: Option<&dyn PrincipalCollection>) {
+        match principals {
+            Some(principals) => self.clear_cache(principals),
+            None => self.clear_all_cache(),
+        }
+    }
+}
+
+impl PermissionResolverAware for ModularRealmAuthorizer {
+    fn set_permission_resolver(&mut self, resolver: impl PermissionResolver + 'static) {
+        self.permission_resolver = Arc::new(resolver);
+        self.clear_all_cache();
     }
 }
+
+impl RolePermissionResolverAware for ModularRealmAuthorizer {
+    fn set_role_permission_resolver(&mut self, rpr: impl RolePermissionResolver + 'static) {
+        self.role_permission_resolver = Some(Arc::new(rpr));
+        self.clear_all_cache();
+    }
+}
+
 impl Default for ModularRealmAuthorizer {
     fn default() -> Self {
-        Self {}
+        Self {
+            realms: Vec::new(),
+            permission_resolver: Arc::new(WildcardPermissionResolver::default()),
+            role_permission_resolver: None,
+            caching_enabled: true,
+            cache_ttl: Duration::from_secs(600),
+            cache: Arc::new(DashMap::new()),
+            swept_at: Arc::new(Mutex::new(Instant::now())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use next_web_core::async_trait;
+
+    use super::*;
+    use crate::core::{
+        authc::{
+            authentication_info::AuthenticationInfo, authentication_token::AuthenticationToken,
+        },
+        authz::{
+            authorization_info::AuthorizationInfo,
+            permission::simple_role_permission_resolver::SimpleRolePermissionResolver,
+            simple_authorization_info::SimpleAuthorizationInfo,
+        },
+        subject::simple_principal_collection::SimplePrincipalCollection,
+        util::object::Object,
+    };
+
+    struct StaticRealm {
+        name: &'static str,
+        info: SimpleAuthorizationInfo,
+        lookups: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl Realm for StaticRealm {
+        fn get_name(&self) -> &str {
+            self.name
+        }
+
+        fn supports(&self, _authentication_token: &dyn AuthenticationToken) -> bool {
+            false
+        }
+
+        async fn get_authentication_info(
+            &self,
+            _token: &dyn AuthenticationToken,
+        ) -> Option<Box<dyn AuthenticationInfo>> {
+            None
+        }
+
+        fn get_authorization_info(
+            &self,
+            principals: &dyn PrincipalCollection,
+        ) -> Option<Box<dyn AuthorizationInfo>> {
+            self.lookups.fetch_add(1, Ordering::SeqCst);
+            let alice = principals
+                .get_primary_principal()
+                .and_then(Object::as_str)
+                .map(|principal| principal == "alice")
+                .unwrap_or(false);
+            alice.then(|| Box::new(self.info.clone()) as Box<dyn AuthorizationInfo>)
+        }
+    }
+
+    #[test]
+    fn test_aggregate_realms() {
+        let lookups = Arc::new(AtomicUsize::new(0));
+        let mut authorizer = ModularRealmAuthorizer::default();
+        authorizer.add_realm(Arc::new(StaticRealm {
+            name: "ldap",
+            info: SimpleAuthorizationInfo::new().role("clerk"),
+            lookups: lookups.clone(),
+        }));
+        authorizer.add_realm(Arc::new(StaticRealm {
+            name: "db",
+            info: SimpleAuthorizationInfo::new().permission("printer:print:lp7200"),
+            lookups: lookups.clone(),
+        }));
+        authorizer.set_role_permission_resolver(
+            SimpleRolePermissionResolver::default().role("clerk", ["orders:view,edit"]),
+        );
+
+        let alice = SimplePrincipalCollection::new(Object::Str("alice".into()), "ldap");
+        let bob = SimplePrincipalCollection::new(Object::Str("bob".into()), "ldap");
+        let alice = Some(&alice as &dyn PrincipalCollection);
+
+        assert!(authorizer.has_role(alice, "clerk"));
+        assert!(authorizer.is_permitted_from_str(alice, "orders:edit:42"));
+        assert!(
+            authorizer.is_permitted_all_from_str(alice, &["printer:print:lp7200", "orders:view"])
+        );
+        assert_eq!(
+            authorizer.is_permitted_from_str_list(alice, &["orders:delete", "printer:print"]),
+            vec![false, false]
+        );
+        assert!(authorizer
+            .check_permission_from_str(alice, "orders:delete")
+            .is_err());
+        assert!(authorizer.check_roles(alice, &["clerk"]).is_ok());
+
+        assert!(!authorizer.is_permitted_from_str(Some(&bob), "orders:view"));
+        assert!(!authorizer.is_permitted_from_str(None, "orders:view"));
+
+        // Two realms per principal, alice was aggregated once
+        assert_eq!(lookups.load(Ordering::SeqCst), 4);
+        authorizer.clear_cached_authorization(alice);
+        assert!(authorizer.has_role(alice, "clerk"));
+        assert_eq!(lookups.load(Ordering::SeqCst), 6);
+    }
+
+    #[test]
+    fn test_cache_key_and_ttl() {
+        let lookups = Arc::new(AtomicUsize::new(0));
+        let mut authorizer = ModularRealmAuthorizer::default();
+        authorizer.add_realm(Arc::new(StaticRealm {
+            name: "ldap",
+            info: SimpleAuthorizationInfo::new().role("clerk"),
+            lookups: lookups.clone(),
+        }));
+
+        // The same name authenticated by another realm is another subject
+        let ldap = SimplePrincipalCollection::new(Object::Str("alice".into()), "ldap");
+        let db = SimplePrincipalCollection::new(Object::Str("alice".into()), "db");
+        assert!(authorizer.has_role(Some(&ldap), "clerk"));
+        assert!(authorizer.has_role(Some(&db), "clerk"));
+        assert!(authorizer.has_role(Some(&ldap), "clerk"));
+        assert_eq!(lookups.load(Ordering::SeqCst), 2);
+
+        authorizer.clear_cached_authorization(Some(&db));
+        assert!(authorizer.has_role(Some(&ldap), "clerk"));
+        assert!(authorizer.has_role(Some(&db), "clerk"));
+        assert_eq!(lookups.load(Ordering::SeqCst), 3);
+
+        authorizer.clear_cached_authorization(None);
+        assert!(authorizer.has_role(Some(&ldap), "clerk"));
+        assert_eq!(lookups.load(Ordering::SeqCst), 4);
+
+        // Expired entries are aggregated again
+        authorizer.set_cache_ttl(Duration::ZERO);
+        assert!(authorizer.has_role(Some(&ldap), "clerk"));
+        assert!(authorizer.has_role(Some(&ldap), "clerk"));
+        assert_eq!(lookups.load(Ordering::SeqCst), 6);
+
+        // The expired entries are swept, only the last one is kept
+        assert!(authorizer.has_role(Some(&db), "clerk"));
+        assert_eq!(authorizer.cache.len(), 1);
     }
 }
@@ -1,18 +1,21 @@
+use std::any::Any;
 use std::fmt::Display;
 use std::hash::Hasher;
 use std::hash::Hash;
 
 
+pub mod wildcard_permission;
 pub mod wildcard_permission_resolver;
 pub mod role_permission_resolver_aware;
 pub mod permission_resolver_aware;
 pub mod role_permission_resolver;
+pub mod simple_role_permission_resolver;
 pub mod permission_resolver;
 
 pub trait Permission
 where 
 Self: Send + Sync,
-Self: Display,
+Self: Any + Display,
 {
     fn implies(&self, p: &dyn Permission) -> bool;
 
@@ -0,0 +1,75 @@
+use std::{collections::HashMap, sync::Arc};
+
+use crate::core::authz::permission::{
+    permission_resolver::PermissionResolver, role_permission_resolver::RolePermissionResolver,
+    wildcard_permission_resolver::WildcardPermissionResolver, Permission,
+};
+
+/// 角色权限解析器
+///
+/// Maps role names to permission strings, resolved by a [`PermissionResolver`] (wildcard
+/// permissions by default). Roles without a mapping grant no permissions.
+///
+/// ```rust,ignore
+/// let resolver = SimpleRolePermissionResolver::default()
+///     .role("admin", ["*"])
+///     .role("clerk", ["orders:view,edit", "customers:view"]);
+/// ```
+#[derive(Clone)]
+pub struct SimpleRolePermissionResolver {
+    roles: HashMap<String, Vec<String>>,
+    permission_resolver: Arc<dyn PermissionResolver>,
+}
+
+impl SimpleRolePermissionResolver {
+    pub fn new(permission_resolver: impl PermissionResolver + 'static) -> Self {
+        Self {
+            roles: HashMap::new(),
+            permission_resolver: Arc::new(permission_resolver),
+        }
+    }
+
+    pub fn role<I, S>(mut self, role: impl Into<String>, permissions: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.add_role(role, permissions);
+        self
+    }
+
+    pub fn add_role<I, S>(&mut self, role: impl Into<String>, permissions: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.roles
+            .entry(role.into())
+            .or_default()
+            .extend(permissions.into_iter().map(Into::into));
+    }
+
+    pub fn get_roles(&self) -> &HashMap<String, Vec<String>> {
+        &self.roles
+    }
+}
+
+impl RolePermissionResolver for SimpleRolePermissionResolver {
+    fn resolve_permissions_in_role(&self, role_string: &str) -> Vec<Box<dyn Permission>> {
+        self.roles
+            .get(role_string)
+            .map(|permissions| {
+                permissions
+                    .iter()
+                    .map(|permission| self.permission_resolver.resolve_permission(permission))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+impl Default for SimpleRolePermissionResolver {
+    fn default() -> Self {
+        Self::new(WildcardPermissionResolver::default())
+    }
+}
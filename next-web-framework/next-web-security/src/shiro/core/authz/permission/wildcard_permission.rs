@@ -0,0 +1,212 @@
+use std::{any::Any, collections::BTreeSet, fmt::Display};
+
+use crate::core::authz::{authorization_error::AuthorizationError, permission::Permission};
+
+/// 通配符权限
+///
+/// Shiro compatible `domain:action:instance` permissions. Parts are divided by `:`, a part
+/// may list several values divided by `,` and `*` matches any value.
+///
+/// A permission implies another when each of its parts is `*` or contains every value of the
+/// other's part. Missing trailing parts act as `*`, so `printer:print` implies
+/// `printer:print:lp7200` but not the other way round.
+///
+/// ```rust,ignore
+/// let granted = WildcardPermission::new("printer:print,query:*")?;
+/// assert!(granted.implies(&WildcardPermission::new("printer:query:lp7200")?));
+/// assert!(!granted.implies(&WildcardPermission::new("printer:manage")?));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WildcardPermission {
+    identifier: String,
+    parts: Vec<BTreeSet<String>>,
+    case_sensitive: bool,
+}
+
+impl WildcardPermission {
+    pub const WILDCARD_TOKEN: &'static str = "*";
+    pub const PART_DIVIDER_TOKEN: &'static str = ":";
+    pub const SUBPART_DIVIDER_TOKEN: &'static str = ",";
+    pub const DEFAULT_CASE_SENSITIVE: bool = false;
+
+    /// Parses a case insensitive permission.
+    pub fn new(wildcard_string: &str) -> Result<Self, AuthorizationError> {
+        Self::with_case_sensitive(wildcard_string, Self::DEFAULT_CASE_SENSITIVE)
+    }
+
+    pub fn with_case_sensitive(
+        wildcard_string: &str,
+        case_sensitive: bool,
+    ) -> Result<Self, AuthorizationError> {
+        let wildcard_string = wildcard_string.trim();
+        if wildcard_string.is_empty() {
+            return Err(AuthorizationError::InvalidPermission(
+                "Wildcard string cannot be empty".to_string(),
+            ));
+        }
+
+        let mut parts = Vec::new();
+        for part in wildcard_string.split(Self::PART_DIVIDER_TOKEN) {
+            let subparts = part
+                .split(Self::SUBPART_DIVIDER_TOKEN)
+                .map(str::trim)
+                .filter(|subpart| !subpart.is_empty())
+                .map(|subpart| {
+                    if case_sensitive {
+                        subpart.to_string()
+                    } else {
+                        subpart.to_lowercase()
+                    }
+                })
+                .collect::<BTreeSet<_>>();
+
+            if subparts.is_empty() {
+                return Err(AuthorizationError::InvalidPermission(format!(
+                    "Wildcard string [{}] cannot contain parts with only dividers",
+                    wildcard_string
+                )));
+            }
+            parts.push(subparts);
+        }
+
+        Ok(Self {
+            identifier: Self::join(&parts),
+            parts,
+            case_sensitive,
+        })
+    }
+
+    /// A permission that implies nothing and is implied by nothing, stands in for strings
+    /// that failed to parse.
+    pub(crate) fn unresolvable(wildcard_string: &str) -> Self {
+        Self {
+            identifier: wildcard_string.to_string(),
+            parts: Vec::new(),
+            case_sensitive: true,
+        }
+    }
+
+    pub fn get_parts(&self) -> &[BTreeSet<String>] {
+        &self.parts
+    }
+
+    pub fn is_case_sensitive(&self) -> bool {
+        self.case_sensitive
+    }
+
+    fn join(parts: &[BTreeSet<String>]) -> String {
+        parts
+            .iter()
+            .map(|part| {
+                part.iter()
+                    .map(String::as_str)
+                    .collect::<Vec<_>>()
+                    .join(Self::SUBPART_DIVIDER_TOKEN)
+            })
+            .collect::<Vec<_>>()
+            .join(Self::PART_DIVIDER_TOKEN)
+    }
+
+    fn implies_parts(&self, other: &[BTreeSet<String>]) -> bool {
+        if self.parts.is_empty() || other.is_empty() {
+            return false;
+        }
+
+        for (index, other_part) in other.iter().enumerate() {
+            // Fewer parts means the rest is implicitly `*`
+            let Some(part) = self.parts.get(index) else {
+                return true;
+            };
+            if part.contains(Self::WILDCARD_TOKEN) {
+                continue;
+            }
+            let covered = other_part.iter().all(|value| {
+                if self.case_sensitive {
+                    part.contains(value)
+                } else {
+                    part.contains(&value.to_lowercase())
+                }
+            });
+            if !covered {
+                return false;
+            }
+        }
+
+        self.parts
+            .iter()
+            .skip(other.len())
+            .all(|part| part.contains(Self::WILDCARD_TOKEN))
+    }
+}
+
+impl Permission for WildcardPermission {
+    fn implies(&self, p: &dyn Permission) -> bool {
+        match (p as &dyn Any).downcast_ref::<WildcardPermission>() {
+            Some(other) => self.implies_parts(&other.parts),
+            // Other permission types are compared by their identifier
+            None => match Self::with_case_sensitive(p.identifier(), self.case_sensitive) {
+                Ok(other) => self.implies_parts(&other.parts),
+                Err(_) => false,
+            },
+        }
+    }
+
+    fn identifier(&self) -> &str {
+        &self.identifier
+    }
+}
+
+impl Display for WildcardPermission {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.identifier)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn implies(granted: &str, required: &str) -> bool {
+        WildcardPermission::new(granted)
+            .unwrap()
+            .implies(&WildcardPermission::new(required).unwrap())
+    }
+
+    #[test]
+    fn test_implies() {
+        assert!(implies("printer", "printer:print:lp7200"));
+        assert!(implies("printer:*", "printer:query"));
+        assert!(implies("printer:print,query", "printer:query"));
+        assert!(implies("printer:print,query", "printer:print,query:lp7200"));
+        assert!(implies("*:view", "orders:view"));
+        assert!(implies("printer:print:*", "printer:print"));
+        assert!(implies("Printer:PRINT", "printer:print"));
+
+        assert!(!implies("printer:print", "printer"));
+        assert!(!implies("printer:print", "printer:query"));
+        assert!(!implies("printer:print,query", "printer:print,manage"));
+        assert!(!implies("printer:print:lp7200", "printer:print:epsoncolor"));
+        assert!(!implies("*:view", "orders:edit"));
+
+        let sensitive = WildcardPermission::with_case_sensitive("Printer:print", true).unwrap();
+        assert!(!sensitive
+            .implies(&WildcardPermission::with_case_sensitive("printer:print", true).unwrap()));
+        assert!(sensitive
+            .implies(&WildcardPermission::with_case_sensitive("Printer:print", true).unwrap()));
+    }
+
+    #[test]
+    fn test_parse() {
+        let permission = WildcardPermission::new(" Orders : Edit , view : 42 ").unwrap();
+        assert_eq!(permission.identifier(), "orders:edit,view:42");
+        assert_eq!(permission.get_parts().len(), 3);
+
+        assert!(WildcardPermission::new("").is_err());
+        assert!(WildcardPermission::new("orders::edit").is_err());
+        assert!(WildcardPermission::new("orders:,").is_err());
+
+        let unresolvable = WildcardPermission::unresolvable("orders::edit");
+        assert!(!unresolvable.implies(&WildcardPermission::new("orders").unwrap()));
+        assert!(!WildcardPermission::new("*").unwrap().implies(&unresolvable));
+    }
+}
@@ -1,17 +1,46 @@
-use crate::core::authz::permission::permission_resolver::PermissionResolver;
+use tracing::warn;
 
+use crate::core::authz::permission::{
+    permission_resolver::PermissionResolver, wildcard_permission::WildcardPermission, Permission,
+};
 
-pub struct WildcardPermissionResolver {}
+/// 通配符权限解析器
+///
+/// Resolves permission strings into [`WildcardPermission`]s. Strings that fail to parse are
+/// logged and resolve to a permission that grants nothing.
+#[derive(Debug, Clone)]
+pub struct WildcardPermissionResolver {
+    case_sensitive: bool,
+}
+
+impl WildcardPermissionResolver {
+    pub fn new(case_sensitive: bool) -> Self {
+        Self { case_sensitive }
+    }
+
+    pub fn is_case_sensitive(&self) -> bool {
+        self.case_sensitive
+    }
 
+    pub fn set_case_sensitive(&mut self, case_sensitive: bool) {
+        self.case_sensitive = case_sensitive;
+    }
+}
 
-impl PermissionResolver  for  WildcardPermissionResolver {
-    fn resolve_permission(&self, permission: &str) -> Box<dyn super::Permission> {
-        todo!()
+impl PermissionResolver for WildcardPermissionResolver {
+    fn resolve_permission(&self, permission: &str) -> Box<dyn Permission> {
+        match WildcardPermission::with_case_sensitive(permission, self.case_sensitive) {
+            Ok(permission) => Box::new(permission),
+            Err(error) => {
+                warn!("Unable to resolve permission [{}]: {}", permission, error);
+                Box::new(WildcardPermission::unresolvable(permission))
+            }
+        }
     }
 }
 
 impl Default for WildcardPermissionResolver {
     fn default() -> Self {
-        Self {  }
+        Self::new(WildcardPermission::DEFAULT_CASE_SENSITIVE)
     }
-}
\ No newline at end of file
+}
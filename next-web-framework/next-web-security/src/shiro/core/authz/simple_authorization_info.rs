@@ -0,0 +1,48 @@
+use crate::core::authz::{authorization_info::AuthorizationInfo, permission::Permission};
+
+/// 简单授权信息
+///
+/// Role names and permission strings, permissions are resolved by the authorizer.
+#[derive(Debug, Clone, Default)]
+pub struct SimpleAuthorizationInfo {
+    roles: Vec<String>,
+    permissions: Vec<String>,
+}
+
+impl SimpleAuthorizationInfo {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn role(mut self, role: impl Into<String>) -> Self {
+        self.add_role(role);
+        self
+    }
+
+    pub fn permission(mut self, permission: impl Into<String>) -> Self {
+        self.add_permission(permission);
+        self
+    }
+
+    pub fn add_role(&mut self, role: impl Into<String>) {
+        self.roles.push(role.into());
+    }
+
+    pub fn add_permission(&mut self, permission: impl Into<String>) {
+        self.permissions.push(permission.into());
+    }
+}
+
+impl AuthorizationInfo for SimpleAuthorizationInfo {
+    fn get_roles(&self) -> Vec<String> {
+        self.roles.clone()
+    }
+
+    fn get_permissions(&self) -> Vec<String> {
+        self.permissions.clone()
+    }
+
+    fn get_dyn_permissions(&self) -> Vec<Box<dyn Permission>> {
+        Vec::new()
+    }
+}
@@ -1,6 +1,6 @@
 use std::any::Any;
 
-use next_web_core::traits::required::Required;
+use next_web_core::{async_trait, traits::required::Required};
 
 use crate::core::{
     authc::{
@@ -45,6 +45,7 @@ where
     }
 }
 
+#[async_trait]
 impl<T, R, C, B> Authenticator for AuthenticatingSecurityManager<T, R, C, B>
 where
     T: Authenticator,
@@ -52,11 +53,11 @@ where
     C: Send + Sync,
     B: Send + Sync,
 {
-    fn authenticate(
+    async fn authenticate(
         &self,
         authentication_token: &dyn AuthenticationToken,
     ) -> Result<Box<dyn AuthenticationInfo>, AuthenticationError> {
-        self.authenticator.authenticate(authentication_token)
+        self.authenticator.authenticate(authentication_token).await
     }
 }
 
@@ -64,7 +65,7 @@ impl<T, R, C, B> AuthenticatingSecurityManager<T, R, C, B>
 where
     T: Authenticator + Any,
     R: Realm + CacheManagerAware<C> + EventBusAware<B>,
-    R: Clone,
+    R: Clone + 'static,
     C: CacheManager + Clone,
     B: EventBus + Clone,
 {
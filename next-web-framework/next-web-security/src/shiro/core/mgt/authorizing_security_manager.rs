@@ -55,7 +55,7 @@ where
     A: Any,
     T: Authenticator + 'static,
     R: Realm + CacheManagerAware<C> + EventBusAware<B>,
-    R: Clone,
+    R: Clone + 'static,
     C: CacheManager + Clone,
     B: EventBus + Clone,
 {
@@ -202,6 +202,10 @@ where
     ) -> Result<(), AuthorizationError> {
         self.authorizer.check_roles(principal, roles)
     }
+
+    fn clear_cached_authorization(&self, principals: Option<&dyn PrincipalCollection>) {
+        self.authorizer.clear_cached_authorization(principals)
+    }
 }
 
 impl<A, T, R, C, B> Destroyable for AuthorizingSecurityManager<A, T, R, C, B>
@@ -611,8 +611,15 @@ where
             .get_object()
             .check_roles(principal, roles)
     }
+
+    fn clear_cached_authorization(&self, principals: Option<&dyn PrincipalCollection>) {
+        self.sessions_security_manager
+            .get_object()
+            .clear_cached_authorization(principals)
+    }
 }
 
+#[async_trait]
 impl<D, F, S, A, T, R, C, B> Authenticator for DefaultSecurityManager<D, F, S, A, T, R, C, B>
 where
     D: Send + Sync,
@@ -625,7 +632,7 @@ where
     C: CacheManager + Clone,
     B: EventBus + Clone,
 {
-    fn authenticate(
+    async fn authenticate(
         &self,
         authentication_token: &dyn AuthenticationToken,
     ) -> Result<Box<dyn AuthenticationInfo>, AuthenticationError> {
@@ -633,6 +640,7 @@ where
             .get_object()
             .get_object()
             .authenticate(authentication_token)
+            .await
     }
 }
 
@@ -662,6 +670,26 @@ where
     async fn get_session(&self, id: &SessionId) -> Result<Arc<dyn Session>, SessionError> {
         self.sessions_security_manager.get_session(id).await
     }
+
+    async fn register_principal(
+        &self,
+        session_id: &SessionId,
+        principal: &str,
+    ) -> Result<(), SessionError> {
+        self.sessions_security_manager
+            .register_principal(session_id, principal)
+            .await
+    }
+
+    async fn check_principal(
+        &self,
+        session_id: Option<&SessionId>,
+        principal: &str,
+    ) -> Result<(), SessionError> {
+        self.sessions_security_manager
+            .check_principal(session_id, principal)
+            .await
+    }
 }
 
 #[async_trait]
@@ -686,7 +714,7 @@ where
         req: &mut dyn HttpRequest,
         resp: &mut dyn HttpResponse,
     ) -> Result<Box<dyn Subject>, AuthenticationError> {
-        let info = match self.authenticate(token) {
+        let info = match self.authenticate(token).await {
             Ok(info) => info,
             Err(error) => {
                 if let Err(err) = self.on_failed_login(token, &error, subject, req, resp) {
@@ -699,10 +727,56 @@ where
             }
         };
 
+        // Enforce the concurrent session limit before anything of the login is saved
+        let principal = info
+            .get_principals()
+            .and_then(|principals| principals.get_primary_principal())
+            .map(ToString::to_string);
+        if let Some(principal) = principal.as_deref() {
+            let session = subject.get_session();
+            if let Err(SessionError::MaximumSessionsExceeded(msg)) = self
+                .sessions_security_manager
+                .check_principal(session.as_ref().map(|session| session.id()), principal)
+                .await
+            {
+                let error = AuthenticationError::Custom(msg);
+                if let Err(err) = self.on_failed_login(token, &error, subject, req, resp) {
+                    info!(
+                        "on_failed_login method threw an error.  Logging and propagating original AuthenticationError. error: {:?}",
+                        err
+                    );
+                }
+                return Err(error);
+            }
+        }
+
         let mut logged_in = self
             ._create_subject(token, info.as_ref(), Some(subject), req, resp)
             .await;
 
+        // Bind the session to the principal, a login racing past the check above is rolled
+        // back here, the session manager has already stopped and deleted the session
+        if let (Some(session), Some(principal)) = (logged_in.get_session(), principal) {
+            match self
+                .sessions_security_manager
+                .register_principal(session.id(), &principal)
+                .await
+            {
+                Ok(()) => {}
+                Err(SessionError::MaximumSessionsExceeded(msg)) => {
+                    self.delete(logged_in.as_ref()).await;
+                    return Err(AuthenticationError::Custom(msg));
+                }
+                Err(error) => warn!(
+                    "Unable to bind session [{}] to principal [{}]: {}",
+                    session.id(),
+                    principal,
+                    error
+                ),
+            }
+        }
+
+        // Remember me only runs once the login can no longer be refused
         self.on_successful_login(token, info.as_ref(), logged_in.as_mut(), req, resp);
 
         Ok(logged_in)
@@ -726,6 +800,9 @@ where
                     .get_authenticator();
 
                 authc.on_logout(principals.as_ref());
+                self.sessions_security_manager
+                    .get_object()
+                    .clear_cached_authorization(Some(principals.as_ref()));
             }
         }
 
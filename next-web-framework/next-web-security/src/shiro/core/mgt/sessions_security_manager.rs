@@ -146,6 +146,26 @@ where
     async fn get_session(&self, id: &SessionId) -> Result<Arc<dyn Session>, SessionError> {
         self.session_manager.get_session(id).await
     }
+
+    async fn register_principal(
+        &self,
+        session_id: &SessionId,
+        principal: &str,
+    ) -> Result<(), SessionError> {
+        self.session_manager
+            .register_principal(session_id, principal)
+            .await
+    }
+
+    async fn check_principal(
+        &self,
+        session_id: Option<&SessionId>,
+        principal: &str,
+    ) -> Result<(), SessionError> {
+        self.session_manager
+            .check_principal(session_id, principal)
+            .await
+    }
 }
 
 impl<S, A, T, R, C, B> Required<AuthorizingSecurityManager<A, T, R, C, B>>
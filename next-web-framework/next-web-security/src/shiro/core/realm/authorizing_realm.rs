@@ -342,7 +342,7 @@ impl AuthorizingRealm {
     ) -> Result<(), AuthorizationError> {
         if !permissions.is_empty() {
             for p in permissions {
-                self._check_permission(p.as_ref(), info).ok();
+                self._check_permission(p.as_ref(), info)?;
             }
         }
 
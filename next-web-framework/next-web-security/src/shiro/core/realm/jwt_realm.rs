@@ -0,0 +1,168 @@
+use std::{any::Any, sync::Arc};
+
+use next_web_core::async_trait;
+use tracing::debug;
+
+use crate::core::{
+    authc::{
+        authentication_info::AuthenticationInfo,
+        authentication_token::AuthenticationToken,
+        bearer_token::BearerToken,
+        jwt::{
+            jwt_authentication_converter::JwtAuthenticationConverter,
+            jwt_authentication_info::JwtAuthenticationInfo, jwt_decoder::JwtDecoder,
+            jwt_error::JwtError,
+        },
+    },
+    realm::Realm,
+    subject::simple_principal_collection::SimplePrincipalCollection,
+    util::object::Object,
+};
+
+/// JWT Realm
+///
+/// Authenticates [`BearerToken`]s carrying a JWT. The token is verified by the decoder and
+/// its claims mapped by the converter, nothing is looked up or stored so requests
+/// authenticate without a session.
+///
+/// ```rust,ignore
+/// let realm = JwtRealm::new(JwtDecoder::hs256(secret).issuer("https://idp.example.com"));
+/// let mut authenticator = ModularRealmAuthenticator::default();
+/// authenticator.add_realm(Arc::new(realm));
+/// ```
+#[derive(Clone)]
+pub struct JwtRealm {
+    name: String,
+    decoder: JwtDecoder,
+    converter: JwtAuthenticationConverter,
+}
+
+impl JwtRealm {
+    pub fn new(decoder: JwtDecoder) -> Self {
+        Self {
+            name: "JwtRealm".to_string(),
+            decoder,
+            converter: JwtAuthenticationConverter::default(),
+        }
+    }
+
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    pub fn converter(mut self, converter: JwtAuthenticationConverter) -> Self {
+        self.converter = converter;
+        self
+    }
+
+    pub fn get_decoder(&self) -> &JwtDecoder {
+        &self.decoder
+    }
+
+    /// Verifies a raw token and maps its claims.
+    pub async fn authenticate_token(&self, token: &str) -> Result<JwtAuthenticationInfo, JwtError> {
+        let claims = self.decoder.decode(token).await?;
+        let principal = self.converter.principal(&claims)?;
+        let roles = self.converter.roles(&claims);
+        let permissions = self.converter.permissions(&claims);
+
+        let principals = SimplePrincipalCollection::new(Object::Str(principal), self.name.as_str());
+        Ok(JwtAuthenticationInfo::new(
+            Arc::new(principals),
+            token,
+            claims,
+            roles,
+            permissions,
+        ))
+    }
+}
+
+#[async_trait]
+impl Realm for JwtRealm {
+    fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    fn supports(&self, authentication_token: &dyn AuthenticationToken) -> bool {
+        (authentication_token as &dyn Any)
+            .downcast_ref::<BearerToken>()
+            .map(|token| !token.get_token().is_empty())
+            .unwrap_or(false)
+    }
+
+    async fn get_authentication_info(
+        &self,
+        token: &dyn AuthenticationToken,
+    ) -> Option<Box<dyn AuthenticationInfo>> {
+        let token = (token as &dyn Any).downcast_ref::<BearerToken>()?;
+        match self.authenticate_token(token.get_token()).await {
+            Ok(info) => Some(Box::new(info)),
+            Err(error) => {
+                debug!("Bearer token rejected: {}", error);
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::core::{
+        authc::{
+            authenticator::Authenticator,
+            jwt::{jwt_claims::JwtClaims, jwt_encoder::JwtEncoder},
+            pam::modular_realm_authenticator::ModularRealmAuthenticator,
+        },
+        authz::authorization_info::AuthorizationInfo,
+        subject::principal_collection::PrincipalCollection,
+    };
+
+    #[tokio::test]
+    async fn test_authenticate_bearer_token() {
+        let realm = JwtRealm::new(JwtDecoder::hs256("secret"))
+            .converter(JwtAuthenticationConverter::default().permission_prefix("SCOPE_"));
+        let mut authenticator = ModularRealmAuthenticator::default();
+        authenticator.add_realm(Arc::new(realm.clone()));
+
+        let claims = JwtClaims::new()
+            .subject("alice")
+            .claim("roles", vec!["admin"])
+            .claim("scope", "orders:read orders:write")
+            .expires_in(Duration::from_secs(60));
+        let token = JwtEncoder::hs256("secret").encode(&claims).unwrap();
+
+        let info = authenticator
+            .authenticate(&BearerToken::new(token.clone(), None))
+            .await
+            .unwrap();
+        let principal = info
+            .get_principals()
+            .unwrap()
+            .get_primary_principal()
+            .cloned();
+        assert_eq!(
+            principal
+                .and_then(|p| p.as_str().map(String::from))
+                .as_deref(),
+            Some("alice")
+        );
+
+        let info = realm.authenticate_token(&token).await.unwrap();
+        assert_eq!(info.get_claims().sub.as_deref(), Some("alice"));
+        assert_eq!(info.get_roles(), vec!["admin"]);
+        assert_eq!(
+            info.get_permissions(),
+            vec!["SCOPE_orders:read", "SCOPE_orders:write"]
+        );
+
+        let forged = JwtEncoder::hs256("other").encode(&claims).unwrap();
+        assert!(authenticator
+            .authenticate(&BearerToken::new(forged, None))
+            .await
+            .is_err());
+    }
+}
@@ -1,10 +1,13 @@
+pub mod jwt_realm;
 pub mod simple_account_realm;
 use std::sync::Arc;
 
 use next_web_core::async_trait;
 
-use crate::core::authc::{
-    authentication_info::AuthenticationInfo, authentication_token::AuthenticationToken,
+use crate::core::{
+    authc::{authentication_info::AuthenticationInfo, authentication_token::AuthenticationToken},
+    authz::authorization_info::AuthorizationInfo,
+    subject::principal_collection::PrincipalCollection,
 };
 
 pub mod authenticating_realm;
@@ -24,6 +27,15 @@ Self: Send + Sync,
         &self,
         token: &dyn AuthenticationToken,
     ) -> Option<Box<dyn AuthenticationInfo>>;
+
+    /// Roles and permissions this realm grants the principals, realms that only
+    /// authenticate keep the default `None`.
+    fn get_authorization_info(
+        &self,
+        _principals: &dyn PrincipalCollection,
+    ) -> Option<Box<dyn AuthorizationInfo>> {
+        None
+    }
 }
 
 #[async_trait]
@@ -42,4 +54,11 @@ impl Realm for Arc<dyn Realm> {
     ) -> Option<Box<dyn AuthenticationInfo>> {
         self.as_ref().get_authentication_info(token).await
     }
+
+    fn get_authorization_info(
+        &self,
+        principals: &dyn PrincipalCollection,
+    ) -> Option<Box<dyn AuthorizationInfo>> {
+        self.as_ref().get_authorization_info(principals)
+    }
 }
\ No newline at end of file
@@ -0,0 +1,234 @@
+use std::sync::Arc;
+
+use tracing::debug;
+
+use crate::core::session::{
+    mgt::eis::session_dao::{SessionDAO, PRINCIPAL_NAME_SESSION_KEY},
+    Session, SessionError, SessionId, SessionValue,
+};
+
+/// 并发会话策略
+///
+/// What to do when a principal signs in once more than the session limit allows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ConcurrentSessionStrategy {
+    /// Stops the least recently used sessions of the principal.
+    #[default]
+    EvictOldest,
+    /// Refuses the new session with [`SessionError::MaximumSessionsExceeded`].
+    Reject,
+}
+
+/// 并发会话控制
+///
+/// Limits the sessions a principal may hold at once. Sessions are found through the
+/// [`SessionDAO`], so with a shared store the limit holds across the cluster. Two sign ins
+/// racing on different instances may briefly exceed it. The limit is enforced when the
+/// security manager binds the session of a successful login.
+///
+/// ```rust,ignore
+/// let mut session_manager = DefaultSessionManager::default();
+/// session_manager.set_concurrent_session_control(
+///     ConcurrentSessionControl::new(1).strategy(ConcurrentSessionStrategy::Reject),
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct ConcurrentSessionControl {
+    maximum_sessions: usize,
+    strategy: ConcurrentSessionStrategy,
+}
+
+impl ConcurrentSessionControl {
+    /// `0` allows any number of sessions.
+    pub fn new(maximum_sessions: usize) -> Self {
+        Self {
+            maximum_sessions,
+            strategy: ConcurrentSessionStrategy::default(),
+        }
+    }
+
+    pub fn strategy(mut self, strategy: ConcurrentSessionStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    pub fn get_maximum_sessions(&self) -> usize {
+        self.maximum_sessions
+    }
+
+    pub fn get_strategy(&self) -> ConcurrentSessionStrategy {
+        self.strategy
+    }
+
+    /// Fails with [`SessionError::MaximumSessionsExceeded`] when the [`Reject`] strategy
+    /// would refuse another session of the principal.
+    ///
+    /// Meant to run before a login creates anything, `session_id` is the session the login
+    /// would reuse.
+    ///
+    /// [`Reject`]: ConcurrentSessionStrategy::Reject
+    pub async fn check(
+        &self,
+        session_dao: &dyn SessionDAO,
+        principal: &str,
+        session_id: Option<&SessionId>,
+    ) -> Result<(), SessionError> {
+        if self.maximum_sessions == 0 || self.strategy != ConcurrentSessionStrategy::Reject {
+            return Ok(());
+        }
+
+        let sessions = self.other_sessions(session_dao, principal, session_id).await?;
+        self.reject(principal, &sessions)
+    }
+
+    /// Binds the session to the principal and enforces the limit.
+    ///
+    /// Returns the sessions that were stopped and deleted to make room, the caller notifies
+    /// the session listeners.
+    pub async fn register(
+        &self,
+        session_dao: &dyn SessionDAO,
+        principal: &str,
+        session: &Arc<dyn Session>,
+    ) -> Result<Vec<Arc<dyn Session>>, SessionError> {
+        let mut sessions = self
+            .other_sessions(session_dao, principal, Some(session.id()))
+            .await?;
+
+        let mut evicted = Vec::new();
+        if self.maximum_sessions > 0 && sessions.len() >= self.maximum_sessions {
+            if self.strategy == ConcurrentSessionStrategy::Reject {
+                self.reject(principal, &sessions)?;
+            }
+
+            sessions.sort_by_key(|other| {
+                other
+                    .last_access_time()
+                    .unwrap_or_else(|| other.start_timestamp())
+            });
+            let excess = sessions.len() + 1 - self.maximum_sessions;
+            for other in sessions.into_iter().take(excess) {
+                debug!(
+                    "Evicting session [{}] of principal [{}], maximum of {} sessions reached",
+                    other.id(),
+                    principal,
+                    self.maximum_sessions
+                );
+                other.stop()?;
+                session_dao.delete(other.as_ref()).await?;
+                evicted.push(other);
+            }
+        }
+
+        session
+            .set_attribute(
+                PRINCIPAL_NAME_SESSION_KEY,
+                SessionValue::String(principal.to_string()),
+            )
+            .await?;
+        session_dao.update(Arc::clone(session)).await?;
+
+        Ok(evicted)
+    }
+
+    async fn other_sessions(
+        &self,
+        session_dao: &dyn SessionDAO,
+        principal: &str,
+        session_id: Option<&SessionId>,
+    ) -> Result<Vec<Arc<dyn Session>>, SessionError> {
+        Ok(session_dao
+            .find_by_principal(principal)
+            .await?
+            .into_iter()
+            .filter(|other| Some(other.id()) != session_id)
+            .collect())
+    }
+
+    fn reject(&self, principal: &str, sessions: &[Arc<dyn Session>]) -> Result<(), SessionError> {
+        if sessions.len() < self.maximum_sessions {
+            return Ok(());
+        }
+
+        Err(SessionError::MaximumSessionsExceeded(format!(
+            "Principal [{}] already has {} active sessions",
+            principal,
+            sessions.len()
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::session::mgt::{
+        eis::memory_session_dao::MemorySessionDAO, simple_session::SimpleSession,
+    };
+
+    async fn sign_in(
+        control: &ConcurrentSessionControl,
+        dao: &MemorySessionDAO,
+        last_access_time: i64,
+    ) -> (
+        Arc<dyn Session>,
+        Result<Vec<Arc<dyn Session>>, SessionError>,
+    ) {
+        let session = SimpleSession::default();
+        session.set_last_access_time(last_access_time);
+        let session: Arc<dyn Session> = Arc::new(session);
+        dao.create(session.clone()).await.unwrap();
+
+        let result = control.register(dao, "alice", &session).await;
+        (session, result)
+    }
+
+    #[tokio::test]
+    async fn test_evict_oldest() {
+        let dao = MemorySessionDAO::default();
+        let control = ConcurrentSessionControl::new(2);
+
+        let (first, _) = sign_in(&control, &dao, 1_000).await;
+        let (second, _) = sign_in(&control, &dao, 2_000).await;
+        let (third, evicted) = sign_in(&control, &dao, 3_000).await;
+
+        let evicted = evicted.unwrap();
+        assert_eq!(evicted.len(), 1);
+        assert_eq!(evicted[0].id(), first.id());
+        assert_eq!(
+            dao.read(first.id()).await.err(),
+            Some(SessionError::NotFound)
+        );
+        assert!(dao.read(second.id()).await.is_ok());
+        assert_eq!(dao.find_by_principal("alice").await.unwrap().len(), 2);
+        assert_eq!(
+            third
+                .get_attribute(PRINCIPAL_NAME_SESSION_KEY)
+                .await
+                .map(|value| match value {
+                    SessionValue::String(name) => name,
+                    _ => String::new(),
+                }),
+            Some("alice".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_reject() {
+        let dao = MemorySessionDAO::default();
+        let control = ConcurrentSessionControl::new(1).strategy(ConcurrentSessionStrategy::Reject);
+
+        let (_, accepted) = sign_in(&control, &dao, 1_000).await;
+        assert!(accepted.unwrap().is_empty());
+
+        let (rejected, result) = sign_in(&control, &dao, 2_000).await;
+        assert!(matches!(
+            result,
+            Err(SessionError::MaximumSessionsExceeded(_))
+        ));
+        assert!(rejected
+            .get_attribute(PRINCIPAL_NAME_SESSION_KEY)
+            .await
+            .is_none());
+        assert_eq!(dao.find_by_principal("alice").await.unwrap().len(), 1);
+    }
+}
@@ -1,5 +1,7 @@
+use chrono::Utc;
 use next_web_core::{
     async_trait,
+    context::scope::SessionIdValidator,
     traits::http::{http_request::HttpRequest, http_response::HttpResponse},
 };
 
@@ -12,8 +14,12 @@ use crate::core::{
     event::{event_bus_aware::EventBusAware, support::default_event_bus::DefaultEventBus},
     session::{
         mgt::{
+            concurrent_session_control::ConcurrentSessionControl,
             default_validating_session_manager::DefaultValidatingSessionManager,
-            eis::{memory_session_dao::MemorySessionDAO, session_dao::SessionDAO},
+            eis::{
+                memory_session_dao::MemorySessionDAO,
+                session_dao::{SessionDAO, PRINCIPAL_NAME_SESSION_KEY},
+            },
             native_session_manager::NativeSessionManagerExt,
             session_context::SessionContext,
             session_factory::SessionFactory,
@@ -31,7 +37,7 @@ use std::{
     ops::{Deref, DerefMut},
     sync::Arc,
 };
-use tracing::trace;
+use tracing::{trace, warn};
 
 #[derive(Clone)]
 pub struct DefaultSessionManager {
@@ -39,6 +45,7 @@ pub struct DefaultSessionManager {
     session_factory: Arc<dyn SessionFactory>,
     cache_manager: Option<Arc<dyn CacheManager>>,
     delete_invalid_sessions: bool,
+    concurrent_session_control: Option<ConcurrentSessionControl>,
 
     pub(crate) validating_session_manager: DefaultValidatingSessionManager,
 }
@@ -75,12 +82,85 @@ impl DefaultSessionManager {
         self.delete_invalid_sessions = delete_invalid_sessions;
     }
 
+    pub fn get_concurrent_session_control(&self) -> Option<&ConcurrentSessionControl> {
+        self.concurrent_session_control.as_ref()
+    }
+
+    pub fn set_concurrent_session_control(&mut self, control: ConcurrentSessionControl) {
+        self.concurrent_session_control = Some(control);
+    }
+
+    /// Checks the session limit of the principal before a login creates its subject.
+    pub async fn check_principal(
+        &self,
+        session_id: Option<&SessionId>,
+        principal: &str,
+    ) -> Result<(), SessionError> {
+        match self.concurrent_session_control.as_ref() {
+            Some(control) => {
+                control
+                    .check(self.session_dao.as_ref(), principal, session_id)
+                    .await
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Binds the session to the principal once it signed in.
+    ///
+    /// With a [`ConcurrentSessionControl`] the session limit is enforced, evicted sessions are
+    /// reported to the session listeners as stopped. Looking the sessions of the principal up
+    /// also drops the ids of the expired ones from the index of the session DAO. A session
+    /// refused by the limit is stopped and deleted.
+    pub async fn register_principal(
+        &self,
+        session_id: &SessionId,
+        principal: &str,
+    ) -> Result<(), SessionError> {
+        let session = self.retrieve_session_from_data_source(session_id).await?;
+        match self.concurrent_session_control.as_ref() {
+            Some(control) => {
+                let evicted = match control
+                    .register(self.session_dao.as_ref(), principal, &session)
+                    .await
+                {
+                    Ok(evicted) => evicted,
+                    Err(error @ SessionError::MaximumSessionsExceeded(_)) => {
+                        // The rejected login must not leave a usable session behind
+                        session.stop()?;
+                        self.delete(session.as_ref()).await;
+                        self.notify_stop(session);
+                        return Err(error);
+                    }
+                    Err(error) => return Err(error),
+                };
+                for session in evicted {
+                    self.notify_stop(session);
+                }
+            }
+            None => {
+                self.session_dao.find_by_principal(principal).await?;
+                session
+                    .set_attribute(
+                        PRINCIPAL_NAME_SESSION_KEY,
+                        SessionValue::String(principal.to_string()),
+                    )
+                    .await?;
+                self.session_dao.update(session).await?;
+            }
+        }
+
+        Ok(())
+    }
+
     pub async fn create_session(&self, ctx: &dyn SessionContext) -> Arc<dyn Session> {
         self.validating_session_manager
             .enable_session_validation_if_necessary();
 
         let session = self.new_session_instance(ctx);
-        self.create(session.clone()).await;
+        if let Err(error) = self.create(session.clone()).await {
+            warn!("Unable to store session [{}]: {}", session.id(), error);
+        }
 
         session
     }
@@ -89,8 +169,8 @@ impl DefaultSessionManager {
         self.session_factory.create_session(ctx)
     }
 
-    pub async fn create(&self, session: Arc<dyn Session>) {
-        self.session_dao.create(session).await;
+    pub async fn create(&self, session: Arc<dyn Session>) -> Result<(), SessionError> {
+        self.session_dao.create(session).await
     }
 
     pub async fn do_get_session(
@@ -101,35 +181,36 @@ impl DefaultSessionManager {
             .enable_session_validation_if_necessary();
         trace!("Attempting to retrieve session with id {}", session_id);
 
-        let session = self.retrieve_session_from_data_source(session_id).await;
-
-        session
-            .map(|s| {
-                // self.validate(&s, session_id);
-                Ok(s.clone())
-            })
-            .unwrap_or(Err(SessionError::NotFound))
+        self.retrieve_session_from_data_source(session_id).await
     }
 
-    async fn _get_session(&self, session_id: &SessionId) -> Option<&Arc<dyn Session>> {
+    async fn _get_session(&self, session_id: &SessionId) -> Option<Arc<dyn Session>> {
         self.validating_session_manager
             .enable_session_validation_if_necessary();
 
-        self.retrieve_session_from_data_source(session_id).await
+        self.retrieve_session_from_data_source(session_id)
+            .await
+            .ok()
     }
     pub async fn after_expired(&self, session: &dyn Session) {
         if self.is_delete_invalid_sessions() {
-            self.session_dao.delete(session).await;
+            self.delete(session).await;
         }
     }
 
     pub async fn retrieve_session_from_data_source(
         &self,
         session_id: &SessionId,
-    ) -> Option<&Arc<dyn Session>> {
+    ) -> Result<Arc<dyn Session>, SessionError> {
         self.session_dao.read(session_id).await
     }
 
+    async fn delete(&self, session: &dyn Session) {
+        if let Err(error) = self.session_dao.delete(session).await {
+            warn!("Unable to delete session [{}]: {}", session.id(), error);
+        }
+    }
+
     fn apply_cache_manager_to_session_dao(&mut self) {
         // todo!()
     }
@@ -177,7 +258,7 @@ impl DefaultSessionManager {
     ) -> Result<(), SessionError> {
         if let Some(s) = self._get_session(session_id).await {
             s.set_timeout(max_idle_time_in_millis)?;
-            self.on_change(s).await;
+            self.on_change(&s).await;
             return Ok(());
         }
 
@@ -187,18 +268,17 @@ impl DefaultSessionManager {
     pub async fn touch(&self, session_id: &SessionId) -> Result<(), SessionError> {
         if let Some(s) = self._get_session(session_id).await {
             s.touch()?;
-            self.on_change(s).await;
+            self.on_change(&s).await;
             return Ok(());
         }
 
         Err(SessionError::Invalid(None))
     }
 
-    pub async fn host(&self, session_id: &SessionId) -> Option<&str> {
+    pub async fn host(&self, session_id: &SessionId) -> Option<String> {
         self._get_session(session_id)
             .await
-            .map(|s| s.host())
-            .unwrap_or_default()
+            .and_then(|s| s.host().map(ToString::to_string))
     }
 
     pub async fn stop(
@@ -209,7 +289,7 @@ impl DefaultSessionManager {
     ) -> Result<(), SessionError> {
         if let Some(s) = self._get_session(session_id).await {
             s.stop()?;
-            self.on_stop(s, req, resp).await;
+            self.on_stop(&s, req, resp).await;
             self.notify_stop(s.clone());
             self.after_stopped(s.as_ref()).await;
             return Ok(());
@@ -258,7 +338,7 @@ impl DefaultSessionManager {
                 if let Some(s) = self._get_session(session_id).await {
                     s.set_attribute(key, session_value).await?;
 
-                    self.on_change(s).await;
+                    self.on_change(&s).await;
                 }
             }
             None => {
@@ -278,7 +358,7 @@ impl DefaultSessionManager {
             let removed = s.remove_attribute(key).await?;
 
             if let Some(value) = removed {
-                self.on_change(s).await;
+                self.on_change(&s).await;
                 return Ok(value);
             }
         }
@@ -309,6 +389,22 @@ impl SessionManager for DefaultSessionManager {
             .await
             .map(|s| Arc::new(self.create_exposed_session(s.as_ref())) as Arc<dyn Session>)
     }
+
+    async fn register_principal(
+        &self,
+        session_id: &SessionId,
+        principal: &str,
+    ) -> Result<(), SessionError> {
+        DefaultSessionManager::register_principal(self, session_id, principal).await
+    }
+
+    async fn check_principal(
+        &self,
+        session_id: Option<&SessionId>,
+        principal: &str,
+    ) -> Result<(), SessionError> {
+        DefaultSessionManager::check_principal(self, session_id, principal).await
+    }
 }
 
 #[async_trait]
@@ -330,12 +426,14 @@ impl NativeSessionManagerExt for DefaultSessionManager {
 
     async fn after_stopped(&self, session: &dyn Session) {
         if self.is_delete_invalid_sessions() {
-            self.session_dao.delete(session).await;
+            self.delete(session).await;
         }
     }
 
     async fn on_change(&self, session: &Arc<dyn Session>) {
-        self.session_dao.update(session.clone()).await.ok();
+        if let Err(error) = self.session_dao.update(session.clone()).await {
+            warn!("Unable to update session [{}]: {}", session.id(), error);
+        }
     }
 }
 
@@ -355,8 +453,14 @@ impl ValidatingSessionManagerExt for DefaultSessionManager {
         self.on_change(session).await;
     }
 
-    async fn get_active_sessions(&self) -> Vec<&Arc<dyn Session>> {
-        self.session_dao.get_active_sessions().await
+    async fn get_active_sessions(&self) -> Vec<Arc<dyn Session>> {
+        self.session_dao
+            .get_active_sessions()
+            .await
+            .unwrap_or_else(|error| {
+                warn!("Unable to load active sessions: {}", error);
+                Vec::new()
+            })
     }
 }
 
@@ -375,6 +479,29 @@ impl EventBusAware<DefaultEventBus> for DefaultSessionManager {
     }
 }
 
+/// Lets a [`SessionScope`](next_web_core::context::scope::SessionScope) hand out beans to the
+/// sessions that exist and did not time out only.
+#[async_trait]
+impl SessionIdValidator for DefaultSessionManager {
+    async fn is_valid(&self, session_id: &str) -> bool {
+        let session_id = SessionId::String(session_id.to_string());
+        let Ok(session) = self.do_get_session(&session_id).await else {
+            return false;
+        };
+
+        match session.timeout() {
+            Ok(timeout) if timeout >= 0 => {
+                let last_access_time = session
+                    .last_access_time()
+                    .unwrap_or_else(|| session.start_timestamp());
+                Utc::now().timestamp_millis() - last_access_time < timeout
+            }
+            Ok(_) => true,
+            Err(_) => false,
+        }
+    }
+}
+
 impl Deref for DefaultSessionManager {
     type Target = DefaultValidatingSessionManager;
 
@@ -396,8 +523,85 @@ impl Default for DefaultSessionManager {
             session_factory: Arc::new(SimpleSessionFactory::default()),
             cache_manager: None,
             delete_invalid_sessions: true,
+            concurrent_session_control: None,
 
             validating_session_manager: Default::default(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{
+        session::mgt::concurrent_session_control::ConcurrentSessionStrategy,
+        subject::support::default_subject_context::DefaultSubjectContext,
+    };
+
+    #[tokio::test]
+    async fn test_session_id_validator() {
+        let session_dao = MemorySessionDAO::default();
+        let mut session_manager = DefaultSessionManager::default();
+        session_manager.set_session_dao(session_dao.clone());
+
+        let live = SimpleSession::default();
+        let idle = SimpleSession::default();
+        idle.set_last_access_time(Utc::now().timestamp_millis() - 31 * 60 * 1000);
+        for session in [live.clone(), idle.clone()] {
+            session_dao.create(Arc::new(session)).await.unwrap();
+        }
+
+        let validator: &dyn SessionIdValidator = &session_manager;
+        assert!(validator.is_valid(&live.id().to_string()).await);
+        assert!(!validator.is_valid(&idle.id().to_string()).await);
+        assert!(!validator.is_valid("forged").await);
+    }
+
+    #[tokio::test]
+    async fn test_rejected_login_leaves_no_session() {
+        let session_dao = MemorySessionDAO::default();
+        let mut session_manager = DefaultSessionManager::default();
+        session_manager.set_session_dao(session_dao.clone());
+        session_manager.set_concurrent_session_control(
+            ConcurrentSessionControl::new(1).strategy(ConcurrentSessionStrategy::Reject),
+        );
+
+        let first = SimpleSession::default();
+        session_dao.create(Arc::new(first.clone())).await.unwrap();
+        session_manager
+            .register_principal(first.id(), "alice")
+            .await
+            .unwrap();
+
+        // Refused before the login saves anything
+        let second = SimpleSession::default();
+        assert!(matches!(
+            session_manager
+                .check_principal(Some(second.id()), "alice")
+                .await,
+            Err(SessionError::MaximumSessionsExceeded(_))
+        ));
+        assert!(session_manager.check_principal(None, "bob").await.is_ok());
+
+        // A login racing past the check loses its authenticated session
+        second
+            .set_attribute(
+                DefaultSubjectContext::AUTHENTICATED_SESSION_KEY,
+                SessionValue::Boolean(true),
+            )
+            .await
+            .unwrap();
+        session_dao.create(Arc::new(second.clone())).await.unwrap();
+        assert!(matches!(
+            session_manager
+                .register_principal(second.id(), "alice")
+                .await,
+            Err(SessionError::MaximumSessionsExceeded(_))
+        ));
+        assert_eq!(
+            session_dao.read(second.id()).await.err(),
+            Some(SessionError::NotFound)
+        );
+        assert_eq!(session_dao.find_by_principal("alice").await.unwrap().len(), 1);
+    }
+}
@@ -164,7 +164,7 @@ impl ValidatingSessionManagerExt for DefaultValidatingSessionManager {
         todo!()
     }
 
-    async fn get_active_sessions(&self) -> Vec<&Arc<dyn Session>> {
+    async fn get_active_sessions(&self) -> Vec<Arc<dyn Session>> {
         todo!()
     }
 }
@@ -1,40 +1,55 @@
 use std::sync::Arc;
 
-use crate::core::session::SessionId;
+use dashmap::DashMap;
 use next_web_core::async_trait;
 
-use crate::core::session::{mgt::eis::session_dao::SessionDAO, Session};
+use crate::core::session::{mgt::eis::session_dao::SessionDAO, Session, SessionError, SessionId};
 
+/// 内存会话存储
+///
+/// Keeps sessions in the local process, suitable for a single instance.
 #[derive(Clone)]
-pub struct MemorySessionDAO {}
-
-impl MemorySessionDAO {
-    fn generate_id(&self) -> String {
-        uuid::Uuid::new_v4().to_string()
-    }
+pub struct MemorySessionDAO {
+    sessions: Arc<DashMap<SessionId, Arc<dyn Session>>>,
 }
 
 #[async_trait]
 impl SessionDAO for MemorySessionDAO {
-    async fn create(&self, session: Arc<dyn Session>) {}
+    async fn create(&self, session: Arc<dyn Session>) -> Result<(), SessionError> {
+        self.sessions.insert(session.id().clone(), session);
+        Ok(())
+    }
 
-    async fn read(&self, session_id: &SessionId) -> Option<&Arc<dyn Session>> {
-        None
+    async fn read(&self, session_id: &SessionId) -> Result<Arc<dyn Session>, SessionError> {
+        self.sessions
+            .get(session_id)
+            .map(|session| Arc::clone(session.value()))
+            .ok_or(SessionError::NotFound)
     }
 
-    async fn update(&self, session: Arc<dyn Session>) -> Result<(), &'static str> {
+    async fn update(&self, session: Arc<dyn Session>) -> Result<(), SessionError> {
+        self.sessions.insert(session.id().clone(), session);
         Ok(())
     }
 
-    async fn delete(&self, session: &dyn Session) {}
+    async fn delete(&self, session: &dyn Session) -> Result<(), SessionError> {
+        self.sessions.remove(session.id());
+        Ok(())
+    }
 
-    async fn get_active_sessions(&self) -> Vec<&Arc<dyn Session>> {
-        vec![]
+    async fn get_active_sessions(&self) -> Result<Vec<Arc<dyn Session>>, SessionError> {
+        Ok(self
+            .sessions
+            .iter()
+            .map(|session| Arc::clone(session.value()))
+            .collect())
     }
 }
 
 impl Default for MemorySessionDAO {
     fn default() -> Self {
-        Self {}
+        Self {
+            sessions: Arc::new(DashMap::new()),
+        }
     }
 }
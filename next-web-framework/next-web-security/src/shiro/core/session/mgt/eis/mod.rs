@@ -1,2 +1,6 @@
 pub mod memory_session_dao;
+#[cfg(feature = "redis-session")]
+pub mod redis_session_dao;
+#[cfg(feature = "redis-session")]
+pub mod redis_session_expiration_listener;
 pub mod session_dao;
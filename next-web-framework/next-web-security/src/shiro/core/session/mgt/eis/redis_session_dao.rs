@@ -0,0 +1,655 @@
+use std::{any::Any, collections::HashMap, sync::Arc};
+
+use chrono::Utc;
+use next_web_core::{async_trait, traits::any_clone::AnyClone};
+use next_web_data_redis::{service::redis_service::RedisService, MultiplexedConnection};
+use serde::{Deserialize, Serialize};
+use tracing::trace;
+
+use crate::core::{
+    session::{
+        mgt::{
+            eis::session_dao::{SessionDAO, PRINCIPAL_NAME_SESSION_KEY},
+            simple_session::SimpleSession,
+        },
+        session_listener::SessionListener,
+        Session, SessionError, SessionId, SessionValue,
+    },
+    subject::{
+        principal_collection::PrincipalCollection,
+        simple_principal_collection::SimplePrincipalCollection,
+    },
+    util::object::Object,
+};
+
+use super::redis_session_expiration_listener::RedisSessionExpirationListener;
+
+/// Redis 会话存储
+///
+/// Shares sessions between instances through Redis. Every session is stored under three
+/// kinds of keys, `next:session:` being the default prefix:
+///
+/// - `next:session:sessions:{id}` the session as JSON, kept five minutes past its timeout so
+///   it can still be read when it expires.
+/// - `next:session:expires:{id}` an empty marker that expires with the session, its keyspace
+///   notification drives [`RedisSessionExpirationListener`].
+/// - `next:session:principals:{name}` the ids of the sessions bound to a principal, it
+///   expires with the longest lived of them and requires Redis 7.
+///
+/// The principal collections of signed in subjects are stored as JSON. Saving a session
+/// holding any other [`SessionValue::Object`] fails, as it could not be read back.
+///
+/// ```rust,ignore
+/// let session_dao = RedisSessionDAO::new(redis_service);
+/// let listener: Box<dyn RedisExpiredKeysEvent> =
+///     Box::new(session_dao.expiration_listener(listeners));
+/// ctx.insert_singleton(listener);
+/// session_manager.set_session_dao(session_dao);
+/// ```
+#[derive(Clone)]
+pub struct RedisSessionDAO {
+    service: RedisService,
+    key_prefix: String,
+}
+
+impl RedisSessionDAO {
+    const EXPIRATION_GRACE_MILLIS: i64 = 5 * 60 * 1000;
+
+    pub fn new(service: RedisService) -> Self {
+        Self {
+            service,
+            key_prefix: String::from("next:session:"),
+        }
+    }
+
+    /// Prefix of the session keys, `next:session:` by default.
+    pub fn with_key_prefix(mut self, key_prefix: impl Into<String>) -> Self {
+        self.key_prefix = key_prefix.into();
+        self
+    }
+
+    pub fn get_key_prefix(&self) -> &str {
+        &self.key_prefix
+    }
+
+    /// Feeds expired sessions to the listeners, register it as the
+    /// `Box<dyn RedisExpiredKeysEvent>` of the context.
+    pub fn expiration_listener(
+        &self,
+        listeners: Vec<Arc<dyn SessionListener>>,
+    ) -> RedisSessionExpirationListener {
+        RedisSessionExpirationListener::new(self.clone(), listeners)
+    }
+
+    fn connection(&self) -> Result<MultiplexedConnection, SessionError> {
+        self.service
+            .get_connection()
+            .ok_or_else(|| SessionError::Storage("No redis connection available".to_string()))
+    }
+
+    fn session_key(&self, id: &str) -> String {
+        format!("{}sessions:{}", self.key_prefix, id)
+    }
+
+    fn expires_key(&self, id: &str) -> String {
+        format!("{}expires:{}", self.key_prefix, id)
+    }
+
+    pub(crate) fn expires_key_prefix(&self) -> String {
+        self.expires_key("")
+    }
+
+    fn principal_key(&self, principal: &str) -> String {
+        format!("{}principals:{}", self.key_prefix, principal)
+    }
+
+    async fn save(&self, session: &dyn Session) -> Result<(), SessionError> {
+        let record = SessionRecord::from_session(session).await?;
+        let id = record.id.to_string();
+        let value = serde_json::to_string(&record).map_err(storage_error)?;
+
+        let mut pipe = redis::pipe();
+        pipe.atomic();
+        if record.timeout >= 0 {
+            pipe.cmd("SET")
+                .arg(self.session_key(&id))
+                .arg(value)
+                .arg("PX")
+                .arg(record.timeout.max(1) + Self::EXPIRATION_GRACE_MILLIS)
+                .ignore();
+            pipe.cmd("SET")
+                .arg(self.expires_key(&id))
+                .arg("")
+                .arg("PX")
+                .arg(record.timeout.max(1))
+                .ignore();
+        } else {
+            pipe.cmd("SET")
+                .arg(self.session_key(&id))
+                .arg(value)
+                .ignore();
+            pipe.cmd("DEL").arg(self.expires_key(&id)).ignore();
+        }
+        if let Some(principal) = record.principal() {
+            let principal_key = self.principal_key(principal);
+            pipe.cmd("SADD").arg(&principal_key).arg(&id).ignore();
+            if record.timeout >= 0 {
+                // Set the first expiry, then only ever extend it
+                let ttl = record.timeout.max(1) + Self::EXPIRATION_GRACE_MILLIS;
+                for condition in ["NX", "GT"] {
+                    pipe.cmd("PEXPIRE")
+                        .arg(&principal_key)
+                        .arg(ttl)
+                        .arg(condition)
+                        .ignore();
+                }
+            } else {
+                pipe.cmd("PERSIST").arg(&principal_key).ignore();
+            }
+        }
+
+        pipe.query_async::<()>(&mut self.connection()?)
+            .await
+            .map_err(storage_error)
+    }
+
+    /// Decodes the stored sessions, leaving out missing and expired ones.
+    async fn load(&self, ids: &[String]) -> Result<Vec<Option<Arc<dyn Session>>>, SessionError> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let keys = ids
+            .iter()
+            .map(|id| self.session_key(id))
+            .collect::<Vec<_>>();
+        let values: Vec<Option<String>> = redis::cmd("MGET")
+            .arg(keys)
+            .query_async(&mut self.connection()?)
+            .await
+            .map_err(storage_error)?;
+
+        let now = Utc::now().timestamp_millis();
+        let mut sessions = Vec::with_capacity(values.len());
+        for value in values {
+            let session = match value {
+                Some(value) => {
+                    let record = SessionRecord::decode(&value)?;
+                    if record.is_expired(now) {
+                        None
+                    } else {
+                        Some(record.into_session().await)
+                    }
+                }
+                None => None,
+            };
+            sessions.push(session);
+        }
+        Ok(sessions)
+    }
+
+    /// Removes the session of an expired marker, only the instance that removed it gets the
+    /// session back so listeners are notified once across the cluster.
+    pub(crate) async fn take_expired(
+        &self,
+        id: &str,
+    ) -> Result<Option<Arc<dyn Session>>, SessionError> {
+        let mut connection = self.connection()?;
+        let value: Option<String> = redis::cmd("GETDEL")
+            .arg(self.session_key(id))
+            .query_async(&mut connection)
+            .await
+            .map_err(storage_error)?;
+        let Some(value) = value else {
+            return Ok(None);
+        };
+
+        let mut record = SessionRecord::decode(&value)?;
+        if let Some(principal) = record.principal() {
+            redis::cmd("SREM")
+                .arg(self.principal_key(principal))
+                .arg(id)
+                .query_async::<()>(&mut connection)
+                .await
+                .map_err(storage_error)?;
+        }
+
+        record.expired = true;
+        Ok(Some(record.into_session().await))
+    }
+}
+
+#[async_trait]
+impl SessionDAO for RedisSessionDAO {
+    async fn create(&self, session: Arc<dyn Session>) -> Result<(), SessionError> {
+        self.save(session.as_ref()).await
+    }
+
+    async fn read(&self, session_id: &SessionId) -> Result<Arc<dyn Session>, SessionError> {
+        self.load(&[session_id.to_string()])
+            .await?
+            .pop()
+            .flatten()
+            .ok_or(SessionError::NotFound)
+    }
+
+    async fn update(&self, session: Arc<dyn Session>) -> Result<(), SessionError> {
+        self.save(session.as_ref()).await
+    }
+
+    async fn delete(&self, session: &dyn Session) -> Result<(), SessionError> {
+        let id = session.id().to_string();
+
+        let mut pipe = redis::pipe();
+        pipe.atomic();
+        pipe.cmd("DEL")
+            .arg(self.session_key(&id))
+            .arg(self.expires_key(&id))
+            .ignore();
+        if let Some(SessionValue::String(principal)) =
+            session.get_attribute(PRINCIPAL_NAME_SESSION_KEY).await
+        {
+            pipe.cmd("SREM")
+                .arg(self.principal_key(&principal))
+                .arg(&id)
+                .ignore();
+        }
+
+        pipe.query_async::<()>(&mut self.connection()?)
+            .await
+            .map_err(storage_error)
+    }
+
+    async fn get_active_sessions(&self) -> Result<Vec<Arc<dyn Session>>, SessionError> {
+        let prefix = self.session_key("");
+        let mut connection = self.connection()?;
+        let mut cmd = redis::cmd("SCAN");
+        cmd.cursor_arg(0)
+            .arg("MATCH")
+            .arg(format!("{}*", prefix))
+            .arg("COUNT")
+            .arg(100);
+        let mut iter = cmd
+            .iter_async::<String>(&mut connection)
+            .await
+            .map_err(storage_error)?;
+
+        let mut ids = Vec::new();
+        while let Some(key) = iter.next_item().await {
+            if let Some(id) = key.strip_prefix(&prefix) {
+                ids.push(id.to_string());
+            }
+        }
+        drop(iter);
+
+        Ok(self.load(&ids).await?.into_iter().flatten().collect())
+    }
+
+    async fn find_by_principal(
+        &self,
+        principal: &str,
+    ) -> Result<Vec<Arc<dyn Session>>, SessionError> {
+        let principal_key = self.principal_key(principal);
+        let ids: Vec<String> = redis::cmd("SMEMBERS")
+            .arg(&principal_key)
+            .query_async(&mut self.connection()?)
+            .await
+            .map_err(storage_error)?;
+
+        let mut sessions = Vec::with_capacity(ids.len());
+        let mut stale = Vec::new();
+        for (id, session) in ids.iter().zip(self.load(&ids).await?) {
+            match session {
+                Some(session) => sessions.push(session),
+                None => stale.push(id),
+            }
+        }
+
+        if !stale.is_empty() {
+            trace!(
+                "Removing {} stale sessions from the index of principal [{}]",
+                stale.len(),
+                principal
+            );
+            redis::cmd("SREM")
+                .arg(&principal_key)
+                .arg(stale)
+                .query_async::<()>(&mut self.connection()?)
+                .await
+                .map_err(storage_error)?;
+        }
+
+        Ok(sessions)
+    }
+}
+
+fn storage_error(error: impl std::fmt::Display) -> SessionError {
+    SessionError::Storage(error.to_string())
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct SessionRecord {
+    id: SessionId,
+    start_time: i64,
+    stop_time: i64,
+    last_access_time: Option<i64>,
+    timeout: i64,
+    expired: bool,
+    host: Option<String>,
+    attributes: HashMap<String, AttributeValue>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value")]
+enum AttributeValue {
+    String(String),
+    Int(i64),
+    Boolean(bool),
+    Bytes(Vec<u8>),
+    Principals(PrincipalsRecord),
+    PrincipalsStack(Vec<PrincipalsRecord>),
+}
+
+impl AttributeValue {
+    fn from_object(key: &str, object: &dyn AnyClone) -> Result<Self, SessionError> {
+        let object = object as &dyn Any;
+        if let Some(principals) = object.downcast_ref::<Arc<dyn PrincipalCollection>>() {
+            return PrincipalsRecord::from_principals(principals.as_ref()).map(Self::Principals);
+        }
+        if let Some(stack) = object.downcast_ref::<Vec<Arc<dyn PrincipalCollection>>>() {
+            return stack
+                .iter()
+                .map(|principals| PrincipalsRecord::from_principals(principals.as_ref()))
+                .collect::<Result<Vec<_>, _>>()
+                .map(Self::PrincipalsStack);
+        }
+        Err(SessionError::Storage(format!(
+            "Session attribute [{}] holds an object that can't be stored in redis",
+            key
+        )))
+    }
+
+    fn into_session_value(self) -> SessionValue {
+        match self {
+            AttributeValue::String(value) => SessionValue::String(value),
+            AttributeValue::Int(value) => SessionValue::Int(value),
+            AttributeValue::Boolean(value) => SessionValue::Boolean(value),
+            AttributeValue::Bytes(value) => SessionValue::Bytes(value),
+            AttributeValue::Principals(record) => {
+                SessionValue::Object(Box::new(record.into_principals()))
+            }
+            AttributeValue::PrincipalsStack(records) => SessionValue::Object(Box::new(
+                records
+                    .into_iter()
+                    .map(PrincipalsRecord::into_principals)
+                    .collect::<Vec<_>>(),
+            )),
+        }
+    }
+}
+
+/// A principal collection, each principal tagged with the name of its realm.
+#[derive(Debug, Serialize, Deserialize)]
+struct PrincipalsRecord {
+    id: String,
+    principals: Vec<(String, PrincipalValue)>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value")]
+enum PrincipalValue {
+    Str(String),
+    Int(i64),
+    Bool(bool),
+    List(Vec<PrincipalValue>),
+    ListStr(Vec<String>),
+    Null,
+}
+
+impl PrincipalsRecord {
+    fn from_principals(principals: &dyn PrincipalCollection) -> Result<Self, SessionError> {
+        let principals_of_realms = principals
+            .get_realm_principals()
+            .into_iter()
+            .map(|(realm, principal)| {
+                PrincipalValue::from_object(principal)
+                    .map(|principal| (realm.to_string(), principal))
+                    .ok_or_else(|| {
+                        SessionError::Storage(format!(
+                            "Principal [{}] of realm [{}] is an object and can't be stored in redis",
+                            principal, realm
+                        ))
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self {
+            id: principals.id().to_string(),
+            principals: principals_of_realms,
+        })
+    }
+
+    fn into_principals(self) -> Arc<dyn PrincipalCollection> {
+        let mut principals = SimplePrincipalCollection::with_id(self.id);
+        for (realm, principal) in self.principals {
+            principals.add(principal.into_object(), realm);
+        }
+        Arc::new(principals)
+    }
+}
+
+impl PrincipalValue {
+    fn from_object(object: &Object) -> Option<Self> {
+        Some(match object {
+            Object::Str(value) => PrincipalValue::Str(value.clone()),
+            Object::Int(value) => PrincipalValue::Int(*value),
+            Object::Bool(value) => PrincipalValue::Bool(*value),
+            Object::List(values) => PrincipalValue::List(
+                values
+                    .iter()
+                    .map(Self::from_object)
+                    .collect::<Option<Vec<_>>>()?,
+            ),
+            Object::ListStr(values) => PrincipalValue::ListStr(values.clone()),
+            Object::Null => PrincipalValue::Null,
+            Object::Obj(_) => return None,
+        })
+    }
+
+    fn into_object(self) -> Object {
+        match self {
+            PrincipalValue::Str(value) => Object::Str(value),
+            PrincipalValue::Int(value) => Object::Int(value),
+            PrincipalValue::Bool(value) => Object::Bool(value),
+            PrincipalValue::List(values) => {
+                Object::List(values.into_iter().map(Self::into_object).collect())
+            }
+            PrincipalValue::ListStr(values) => Object::ListStr(values),
+            PrincipalValue::Null => Object::Null,
+        }
+    }
+}
+
+impl SessionRecord {
+    async fn from_session(session: &dyn Session) -> Result<Self, SessionError> {
+        let (stop_time, expired) = match (session as &dyn Any).downcast_ref::<SimpleSession>() {
+            Some(simple_session) => (
+                simple_session.stop_time_stamp(),
+                simple_session.is_expired(),
+            ),
+            None => (0, false),
+        };
+
+        let mut attributes = HashMap::new();
+        for key in session.attribute_keys().await? {
+            let value = match session.get_attribute(&key).await {
+                Some(SessionValue::String(value)) => AttributeValue::String(value),
+                Some(SessionValue::Int(value)) => AttributeValue::Int(value),
+                Some(SessionValue::Boolean(value)) => AttributeValue::Boolean(value),
+                Some(SessionValue::Bytes(value)) => AttributeValue::Bytes(value),
+                Some(SessionValue::Object(object)) => {
+                    AttributeValue::from_object(&key, object.as_ref())?
+                }
+                Some(SessionValue::Null) | None => continue,
+            };
+            attributes.insert(key, value);
+        }
+
+        Ok(Self {
+            id: session.id().clone(),
+            start_time: session.start_timestamp(),
+            stop_time,
+            last_access_time: session.last_access_time(),
+            timeout: session.timeout()?,
+            expired,
+            host: session.host().map(ToString::to_string),
+            attributes,
+        })
+    }
+
+    fn decode(value: &str) -> Result<Self, SessionError> {
+        serde_json::from_str(value).map_err(storage_error)
+    }
+
+    fn principal(&self) -> Option<&str> {
+        match self.attributes.get(PRINCIPAL_NAME_SESSION_KEY) {
+            Some(AttributeValue::String(principal)) => Some(principal),
+            _ => None,
+        }
+    }
+
+    fn is_expired(&self, now: i64) -> bool {
+        if self.expired {
+            return true;
+        }
+        let last_access_time = self.last_access_time.unwrap_or(self.start_time);
+        self.timeout >= 0 && last_access_time + self.timeout < now
+    }
+
+    async fn into_session(self) -> Arc<dyn Session> {
+        let mut session = SimpleSession::default();
+        session.set_id(self.id);
+        session.set_start_time(self.start_time);
+        session.set_stop_time(self.stop_time);
+        session.set_last_access_time(self.last_access_time.unwrap_or_default());
+        session.set_expired(self.expired);
+        if let Some(host) = self.host {
+            session.set_host(host);
+        }
+        session.set_timeout(self.timeout).ok();
+
+        for (key, value) in self.attributes {
+            session
+                .set_attribute(&key, value.into_session_value())
+                .await
+                .ok();
+        }
+
+        Arc::new(session)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::subject::support::default_subject_context::DefaultSubjectContext;
+
+    #[tokio::test]
+    async fn test_session_record() {
+        let session = SimpleSession::new("10.0.0.7");
+        session.set_timeout(60_000).unwrap();
+        session
+            .set_attribute(
+                PRINCIPAL_NAME_SESSION_KEY,
+                SessionValue::String("alice".into()),
+            )
+            .await
+            .unwrap();
+        session
+            .set_attribute("visits", SessionValue::Int(3))
+            .await
+            .unwrap();
+        let record = SessionRecord::from_session(&session).await.unwrap();
+        let encoded = serde_json::to_string(&record).unwrap();
+        let record = SessionRecord::decode(&encoded).unwrap();
+        assert_eq!(record.principal(), Some("alice"));
+
+        let now = Utc::now().timestamp_millis();
+        assert!(!record.is_expired(now));
+        assert!(record.is_expired(now + 120_000));
+
+        let restored = record.into_session().await;
+        assert_eq!(restored.id(), session.id());
+        assert_eq!(restored.host(), Some("10.0.0.7"));
+        assert_eq!(restored.timeout(), Ok(60_000));
+        assert!(matches!(
+            restored.get_attribute("visits").await,
+            Some(SessionValue::Int(3))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_authenticated_session_record() {
+        let session = SimpleSession::new("10.0.0.7");
+        let mut principals = SimplePrincipalCollection::new(Object::Str("alice".into()), "ldap");
+        principals.add(Object::Int(42), "db");
+        let principals: Arc<dyn PrincipalCollection> = Arc::new(principals);
+        session
+            .set_attribute(
+                DefaultSubjectContext::PRINCIPALS_SESSION_KEY,
+                SessionValue::Object(Box::new(principals.clone())),
+            )
+            .await
+            .unwrap();
+        session
+            .set_attribute(
+                DefaultSubjectContext::AUTHENTICATED_SESSION_KEY,
+                SessionValue::Boolean(true),
+            )
+            .await
+            .unwrap();
+
+        let record = SessionRecord::from_session(&session).await.unwrap();
+        let encoded = serde_json::to_string(&record).unwrap();
+        let restored = SessionRecord::decode(&encoded)
+            .unwrap()
+            .into_session()
+            .await;
+
+        assert!(matches!(
+            restored
+                .get_attribute(DefaultSubjectContext::AUTHENTICATED_SESSION_KEY)
+                .await,
+            Some(SessionValue::Boolean(true))
+        ));
+        let value = restored
+            .get_attribute(DefaultSubjectContext::PRINCIPALS_SESSION_KEY)
+            .await
+            .unwrap();
+        let restored_principals = value.as_object::<Arc<dyn PrincipalCollection>>().unwrap();
+        assert_eq!(restored_principals.id(), principals.id());
+        assert_eq!(
+            restored_principals
+                .get_primary_principal()
+                .and_then(Object::as_str),
+            Some("alice")
+        );
+        assert_eq!(
+            restored_principals
+                .get_realm_principals()
+                .into_iter()
+                .map(|(realm, principal)| format!("{}={}", realm, principal))
+                .collect::<Vec<_>>(),
+            vec!["ldap=alice", "db=42"]
+        );
+
+        // Objects that can't be read back fail the save
+        session
+            .set_attribute("cart", SessionValue::Object(Box::new(vec![1u8, 2])))
+            .await
+            .unwrap();
+        assert!(matches!(
+            SessionRecord::from_session(&session).await,
+            Err(SessionError::Storage(_))
+        ));
+    }
+}
@@ -0,0 +1,57 @@
+use std::sync::Arc;
+
+use next_web_core::async_trait;
+use next_web_data_redis::core::event::expired_keys_event::RedisExpiredKeysEvent;
+use tracing::{trace, warn};
+
+use crate::core::session::session_listener::SessionListener;
+
+use super::redis_session_dao::RedisSessionDAO;
+
+/// Redis 会话过期监听
+///
+/// Turns the expiration of the session markers of a [`RedisSessionDAO`] into
+/// [`SessionListener::on_expiration`] calls. Every instance receives the notification, only
+/// the one that removes the session notifies its listeners.
+#[derive(Clone)]
+pub struct RedisSessionExpirationListener {
+    session_dao: RedisSessionDAO,
+    listeners: Vec<Arc<dyn SessionListener>>,
+}
+
+impl RedisSessionExpirationListener {
+    pub fn new(session_dao: RedisSessionDAO, listeners: Vec<Arc<dyn SessionListener>>) -> Self {
+        Self {
+            session_dao,
+            listeners,
+        }
+    }
+
+    pub fn add_listener(&mut self, listener: Arc<dyn SessionListener>) {
+        self.listeners.push(listener);
+    }
+}
+
+#[async_trait]
+impl RedisExpiredKeysEvent for RedisSessionExpirationListener {
+    async fn on_message(&mut self, message: &[u8], _pattern: &[u8]) {
+        let Ok(key) = std::str::from_utf8(message) else {
+            return;
+        };
+        let prefix = self.session_dao.expires_key_prefix();
+        let Some(id) = key.strip_prefix(prefix.as_str()) else {
+            return;
+        };
+
+        match self.session_dao.take_expired(id).await {
+            Ok(Some(session)) => {
+                trace!("Session with id [{}] has expired in redis.", id);
+                for listener in self.listeners.iter() {
+                    listener.on_expiration(session.as_ref());
+                }
+            }
+            Ok(None) => {}
+            Err(error) => warn!("Unable to handle expiration of session [{}]: {}", id, error),
+        }
+    }
+}
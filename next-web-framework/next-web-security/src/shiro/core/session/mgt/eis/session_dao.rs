@@ -2,20 +2,51 @@ use std::sync::Arc;
 
 use next_web_core::async_trait;
 
-use crate::core::session::{Session, SessionId};
+use crate::core::session::{Session, SessionError, SessionId, SessionValue};
 
+/// Session attribute holding the name of the authenticated principal, sessions are indexed
+/// by it for [`SessionDAO::find_by_principal`] and concurrent session control.
+pub const PRINCIPAL_NAME_SESSION_KEY: &str = "next.security.session.PRINCIPAL_NAME";
+
+/// 会话数据访问对象
+///
+/// Stores sessions for the session manager. Sessions are returned owned so the store may
+/// live in another process, implementations that keep sessions in memory hand out clones of
+/// their `Arc`.
 #[async_trait]
 pub trait SessionDAO
 where
     Self: Send + Sync,
 {
-    async fn create(&self, session: Arc<dyn Session>);
+    async fn create(&self, session: Arc<dyn Session>) -> Result<(), SessionError>;
+
+    /// Returns [`SessionError::NotFound`] for unknown or expired sessions.
+    async fn read(&self, session_id: &SessionId) -> Result<Arc<dyn Session>, SessionError>;
 
-    async fn read(&self, session_id: &SessionId) -> Option<&Arc<dyn Session>>;
+    async fn update(&self, session: Arc<dyn Session>) -> Result<(), SessionError>;
 
-    async fn update(&self, session: Arc<dyn Session>) -> Result<(), &'static str>;
+    async fn delete(&self, session: &dyn Session) -> Result<(), SessionError>;
 
-    async fn delete(&self, session: &dyn Session);
+    async fn get_active_sessions(&self) -> Result<Vec<Arc<dyn Session>>, SessionError>;
 
-    async fn get_active_sessions(&self) -> Vec<&Arc<dyn Session>>;
+    /// The sessions bound to a principal through [`PRINCIPAL_NAME_SESSION_KEY`].
+    ///
+    /// The default implementation scans the active sessions, stores that can index
+    /// attributes should override it.
+    async fn find_by_principal(
+        &self,
+        principal: &str,
+    ) -> Result<Vec<Arc<dyn Session>>, SessionError> {
+        let mut sessions = Vec::new();
+        for session in self.get_active_sessions().await? {
+            if let Some(SessionValue::String(name)) =
+                session.get_attribute(PRINCIPAL_NAME_SESSION_KEY).await
+            {
+                if name == principal {
+                    sessions.push(session);
+                }
+            }
+        }
+        Ok(sessions)
+    }
 }
@@ -1,3 +1,4 @@
+pub mod concurrent_session_control;
 pub mod default_native_session_manager;
 pub mod default_session_context;
 pub mod default_session_manager;
@@ -22,4 +22,24 @@ where
     ) -> Result<Box<dyn Session>, AuthorizationError>;
 
     async fn get_session(&self, id: &SessionId) -> Result<Arc<dyn Session>, SessionError>;
+
+    /// Refuses the login of a principal that may not open another session, called by the
+    /// security manager before the subject of a successful authentication is created.
+    async fn check_principal(
+        &self,
+        _session_id: Option<&SessionId>,
+        _principal: &str,
+    ) -> Result<(), SessionError> {
+        Ok(())
+    }
+
+    /// Binds the session to the principal that signed in, called by the security manager
+    /// after a successful login.
+    async fn register_principal(
+        &self,
+        _session_id: &SessionId,
+        _principal: &str,
+    ) -> Result<(), SessionError> {
+        Ok(())
+    }
 }
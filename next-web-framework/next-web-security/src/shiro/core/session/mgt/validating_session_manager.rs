@@ -43,5 +43,5 @@ where
     ) {
     }
 
-    async fn get_active_sessions(&self) -> Vec<&Arc<dyn Session>>;
+    async fn get_active_sessions(&self) -> Vec<Arc<dyn Session>>;
 }
@@ -4,6 +4,7 @@ pub mod session_listener;
 
 use next_web_core::async_trait;
 use next_web_core::traits::any_clone::AnyClone;
+use serde::{Deserialize, Serialize};
 use std::any::Any;
 use std::error::Error;
 use std::fmt::Display;
@@ -18,9 +19,14 @@ pub enum SessionError {
     Expired(Option<String>),
     Stopped(String),
     NotFound,
+    /// 会话存储不可用
+    Storage(String),
+    /// 超出单个主体允许的会话数
+    MaximumSessionsExceeded(String),
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(untagged)]
 pub enum SessionId {
     /// 字符串 ID
     String(String),
@@ -43,7 +49,7 @@ pub enum SessionValue {
 impl SessionValue {
     pub fn as_object<T: AnyClone>(&self) -> Option<&T> {
         if let SessionValue::Object(ref obj) = self {
-            (obj as &dyn Any).downcast_ref::<T>()
+            (obj.as_ref() as &dyn Any).downcast_ref::<T>()
         } else {
             None
         }
@@ -113,6 +119,10 @@ impl Display for SessionError {
             SessionError::Invalid(msg) => write!(f, "Session invalid: {:?}", msg),
             SessionError::NotFound => write!(f, "Session not found"),
             SessionError::Stopped(msg) => write!(f, "Session stopped: {}", msg),
+            SessionError::Storage(msg) => write!(f, "Session storage error: {}", msg),
+            SessionError::MaximumSessionsExceeded(msg) => {
+                write!(f, "Maximum sessions exceeded: {}", msg)
+            }
         }
     }
 }
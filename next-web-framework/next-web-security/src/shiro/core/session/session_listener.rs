@@ -1,3 +1,5 @@
+use next_web_core::context::scope::SessionScope;
+
 use crate::core::session::Session;
 
 pub trait SessionListener
@@ -10,3 +12,17 @@ where
 
     fn on_expiration(&self, session: &dyn Session);
 }
+
+/// Drops the session scoped beans of a session once it stops or expires, add the
+/// [`SessionScope`] of the context to the listeners of the session manager.
+impl SessionListener for SessionScope {
+    fn on_start(&self, _session: &dyn Session) {}
+
+    fn on_stop(&self, session: &dyn Session) {
+        self.evict(session.id().to_string());
+    }
+
+    fn on_expiration(&self, session: &dyn Session) {
+        self.evict(session.id().to_string());
+    }
+}
@@ -1,4 +1,5 @@
 pub mod principal_collection;
+pub mod simple_principal_collection;
 pub mod subject_context;
 pub mod support;
 
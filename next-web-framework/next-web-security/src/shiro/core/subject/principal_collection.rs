@@ -10,7 +10,7 @@ where
     Self: Display,
     Self: DynClone + Id
 {
-    fn get_primary_principal<'a>(&self) -> Option<&'a Object>;
+    fn get_primary_principal(&self) -> Option<&Object>;
 
     // fn one_by_type<T>(&self) -> Option<& T>;
 
@@ -20,6 +20,9 @@ where
 
     fn get_realm_names(&self) -> Option<HashSet<&str>>;
 
+    /// The principals with the name of the realm that produced each, the primary one first.
+    fn get_realm_principals(&self) -> Vec<(&str, &Object)>;
+
     fn is_empty(&self) -> bool;
 }
 
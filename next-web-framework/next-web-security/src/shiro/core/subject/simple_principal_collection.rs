@@ -0,0 +1,103 @@
+use std::{collections::HashSet, fmt::Display};
+
+use next_web_core::traits::id::Id;
+
+use crate::core::{subject::principal_collection::PrincipalCollection, util::object::Object};
+
+/// 简单身份集合
+///
+/// Principals in the order they were added, each tagged with the name of the realm that
+/// produced it. The first principal is the primary one.
+#[derive(Clone, Default)]
+pub struct SimplePrincipalCollection {
+    id: String,
+    principals: Vec<(String, Object)>,
+    cursor: usize,
+}
+
+impl SimplePrincipalCollection {
+    pub fn new(principal: Object, realm_name: impl Into<String>) -> Self {
+        let mut collection = Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            ..Default::default()
+        };
+        collection.add(principal, realm_name);
+        collection
+    }
+
+    /// An empty collection keeping the id of a collection that was stored before.
+    pub fn with_id(id: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn add(&mut self, principal: Object, realm_name: impl Into<String>) {
+        self.principals.push((realm_name.into(), principal));
+    }
+
+    pub fn from_realm(&self, realm_name: &str) -> Vec<&Object> {
+        self.principals
+            .iter()
+            .filter(|(name, _)| name == realm_name)
+            .map(|(_, principal)| principal)
+            .collect()
+    }
+}
+
+impl PrincipalCollection for SimplePrincipalCollection {
+    fn get_primary_principal(&self) -> Option<&Object> {
+        self.principals.first().map(|(_, principal)| principal)
+    }
+
+    fn get_realm_names(&self) -> Option<HashSet<&str>> {
+        if self.principals.is_empty() {
+            return None;
+        }
+        Some(
+            self.principals
+                .iter()
+                .map(|(name, _)| name.as_str())
+                .collect(),
+        )
+    }
+
+    fn get_realm_principals(&self) -> Vec<(&str, &Object)> {
+        self.principals
+            .iter()
+            .map(|(name, principal)| (name.as_str(), principal))
+            .collect()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.principals.is_empty()
+    }
+}
+
+impl Iterator for SimplePrincipalCollection {
+    type Item = Object;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (_, principal) = self.principals.get(self.cursor)?;
+        self.cursor += 1;
+        Some(principal.clone())
+    }
+}
+
+impl Id for SimplePrincipalCollection {
+    fn id(&self) -> &str {
+        &self.id
+    }
+}
+
+impl Display for SimplePrincipalCollection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let principals = self
+            .principals
+            .iter()
+            .map(|(_, principal)| principal.to_string())
+            .collect::<Vec<_>>();
+        write!(f, "SimplePrincipalCollection {:?}", principals)
+    }
+}
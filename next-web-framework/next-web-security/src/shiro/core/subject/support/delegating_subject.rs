@@ -103,8 +103,8 @@ impl DelegatingSubject {
     }
 
     pub fn get_primary_principal<'a>(
-        &'a self,
-        principals: Option<&dyn PrincipalCollection>,
+        &self,
+        principals: Option<&'a dyn PrincipalCollection>,
     ) -> Option<&'a Object> {
         if let Some(principals) = principals {
             if !principals.is_empty() {
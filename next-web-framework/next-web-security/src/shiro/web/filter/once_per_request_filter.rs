@@ -6,6 +6,7 @@ use next_web_core::{
         filter::{http_filter::HttpFilter, http_filter_chain::HttpFilterChain},
         http::{http_request::HttpRequest, http_response::HttpResponse},
         named::Named,
+        ordered::Ordered,
         required::Required,
     },
 };
@@ -85,6 +86,13 @@ impl<T: Named> Named for HttpFilterWrapper<T> {
     }
 }
 
+impl<T> Ordered for HttpFilterWrapper<T> {
+    /// The wrapped filters run in the order of their path chain.
+    fn order(&self) -> i32 {
+        0
+    }
+}
+
 #[async_trait]
 impl<T> HttpFilter for HttpFilterWrapper<T>
 where
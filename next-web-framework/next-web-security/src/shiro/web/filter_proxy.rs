@@ -30,6 +30,7 @@ use next_web_core::{
         filter::{http_filter::HttpFilter, http_filter_chain::HttpFilterChain},
         http::{http_request::HttpRequest, http_response::HttpResponse},
         named::Named,
+        ordered::Ordered,
     },
 };
 use tracing::error;
@@ -287,6 +288,13 @@ impl Named for FilterProxy {
     }
 }
 
+impl Ordered for FilterProxy {
+    /// Runs before the application filters, they usually rely on the subject.
+    fn order(&self) -> i32 {
+        -100
+    }
+}
+
 #[async_trait]
 impl HttpFilter for FilterProxy {
     async fn do_filter(
@@ -0,0 +1,35 @@
+use axum::{
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+};
+
+/// 访问拒绝处理器
+///
+/// Builds the responses returned by `#[PreAuthorize]`, `#[PostAuthorize]` and `#[Secured]`
+/// guards. Register an `Arc<dyn AccessDeniedHandler>` singleton to replace the default.
+pub trait AccessDeniedHandler: Send + Sync {
+    /// No subject could be resolved from the request.
+    fn unauthorized(&self) -> Response;
+
+    /// The subject is known but the expression denied access.
+    fn forbidden(&self) -> Response;
+}
+
+/// Plain `401 Unauthorized` and `403 Forbidden` responses, the 401 asks for a bearer token.
+#[derive(Debug, Clone, Default)]
+pub struct DefaultAccessDeniedHandler;
+
+impl AccessDeniedHandler for DefaultAccessDeniedHandler {
+    fn unauthorized(&self) -> Response {
+        (
+            StatusCode::UNAUTHORIZED,
+            [(header::WWW_AUTHENTICATE, "Bearer")],
+            "Unauthorized",
+        )
+            .into_response()
+    }
+
+    fn forbidden(&self) -> Response {
+        (StatusCode::FORBIDDEN, "Forbidden").into_response()
+    }
+}
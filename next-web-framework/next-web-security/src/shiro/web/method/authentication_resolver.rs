@@ -0,0 +1,13 @@
+use axum::http::request::Parts;
+use next_web_core::async_trait;
+
+use crate::core::authz::expression::security_expression_root::SecurityExpressionRoot;
+
+/// 认证解析器
+///
+/// Resolves the current subject of a request for method security. Register an
+/// `Arc<dyn AuthenticationResolver>` singleton, `None` means the request is anonymous.
+#[async_trait]
+pub trait AuthenticationResolver: Send + Sync {
+    async fn resolve(&self, parts: &Parts) -> Option<SecurityExpressionRoot>;
+}
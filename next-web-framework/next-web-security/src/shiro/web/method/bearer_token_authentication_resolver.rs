@@ -0,0 +1,68 @@
+use axum::http::{header, request::Parts};
+use next_web_core::async_trait;
+use serde_json::Value;
+use tracing::debug;
+
+use crate::core::{
+    authz::{
+        authorization_info::AuthorizationInfo,
+        expression::security_expression_root::SecurityExpressionRoot,
+    },
+    realm::jwt_realm::JwtRealm,
+};
+
+use super::authentication_resolver::AuthenticationResolver;
+
+/// Bearer Token 认证解析器
+///
+/// Reads `Authorization: Bearer <jwt>` and verifies it with a [`JwtRealm`]. The principal seen
+/// by expressions is the claim set, with `id` set to the subject when the token has none.
+///
+/// ```rust,ignore
+/// let resolver: Arc<dyn AuthenticationResolver> =
+///     Arc::new(BearerTokenAuthenticationResolver::new(JwtRealm::new(decoder)));
+/// ```
+#[derive(Clone)]
+pub struct BearerTokenAuthenticationResolver {
+    realm: JwtRealm,
+}
+
+impl BearerTokenAuthenticationResolver {
+    pub fn new(realm: JwtRealm) -> Self {
+        Self { realm }
+    }
+
+    pub fn get_realm(&self) -> &JwtRealm {
+        &self.realm
+    }
+}
+
+#[async_trait]
+impl AuthenticationResolver for BearerTokenAuthenticationResolver {
+    async fn resolve(&self, parts: &Parts) -> Option<SecurityExpressionRoot> {
+        let token = parts
+            .headers
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .map(str::trim)
+            .filter(|token| !token.is_empty())?;
+
+        let info = match self.realm.authenticate_token(token).await {
+            Ok(info) => info,
+            Err(error) => {
+                debug!("Bearer token rejected: {}", error);
+                return None;
+            }
+        };
+
+        let mut principal = serde_json::to_value(info.get_claims()).unwrap_or(Value::Null);
+        if let (Value::Object(map), Some(sub)) = (&mut principal, &info.get_claims().sub) {
+            map.entry("id")
+                .or_insert_with(|| Value::String(sub.clone()));
+        }
+        Some(SecurityExpressionRoot::from_authorization_info(
+            principal, &info,
+        ))
+    }
+}
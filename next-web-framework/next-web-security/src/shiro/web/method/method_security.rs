@@ -0,0 +1,216 @@
+use std::{future::Future, sync::Arc};
+
+use axum::{
+    http::{header, request::Parts, StatusCode},
+    response::{IntoResponse, Response},
+};
+use base64::Engine;
+use next_web_core::state::application_state::ApplicationState;
+use subtle::ConstantTimeEq;
+use tracing::warn;
+
+use crate::core::authz::expression::{
+    evaluation_context::EvaluationContext, expression_error::ExpressionError,
+    security_expression::SecurityExpression, security_expression_root::SecurityExpressionRoot,
+};
+
+use super::{
+    access_denied_handler::{AccessDeniedHandler, DefaultAccessDeniedHandler},
+    authentication_resolver::AuthenticationResolver,
+};
+
+/// 方法安全
+///
+/// The runtime behind the `#[PreAuthorize]`, `#[PostAuthorize]` and `#[Secured]` guards.
+///
+/// The subject is taken from a [`SecurityExpressionRoot`] request extension when a middleware
+/// already authenticated the request, otherwise from the first registered
+/// `Arc<dyn AuthenticationResolver>`, otherwise the request is anonymous.
+pub struct MethodSecurity;
+
+impl MethodSecurity {
+    pub async fn resolve(state: &ApplicationState, parts: &Parts) -> SecurityExpressionRoot {
+        if let Some(root) = parts.extensions.get::<SecurityExpressionRoot>() {
+            return root.clone();
+        }
+
+        let resolver = state
+            .context()
+            .read()
+            .await
+            .get_singles_by_type::<Arc<dyn AuthenticationResolver>>()
+            .first()
+            .map(|resolver| Arc::clone(resolver));
+
+        match resolver {
+            Some(resolver) => resolver
+                .resolve(parts)
+                .await
+                .unwrap_or_else(SecurityExpressionRoot::anonymous),
+            None => SecurityExpressionRoot::anonymous(),
+        }
+    }
+
+    pub async fn context(state: &ApplicationState, parts: &Parts) -> EvaluationContext {
+        EvaluationContext::new(Self::resolve(state, parts).await)
+    }
+
+    /// Returns the response to send when access is denied, `None` when the call may proceed.
+    ///
+    /// Anonymous subjects get a 401, authenticated ones a 403. Expressions that failed to
+    /// parse or evaluate deny access.
+    pub async fn check(
+        state: &ApplicationState,
+        context: &EvaluationContext,
+        expression: Result<&SecurityExpression, &ExpressionError>,
+    ) -> Option<Response> {
+        let allowed = match expression {
+            Ok(expression) => expression.evaluate(context).unwrap_or_else(|error| {
+                warn!(
+                    "Security expression `{}` failed: {}",
+                    expression.source(),
+                    error
+                );
+                false
+            }),
+            Err(error) => {
+                warn!("Invalid security expression: {}", error);
+                false
+            }
+        };
+        if allowed {
+            return None;
+        }
+
+        let handler = Self::access_denied_handler(state).await;
+        if context.get_root().is_authenticated() {
+            Some(handler.forbidden())
+        } else {
+            Some(handler.unauthorized())
+        }
+    }
+
+    /// Checks HTTP Basic credentials against `username:password`.
+    pub fn check_basic(parts: &Parts, credentials: &str) -> Option<Response> {
+        let matched = parts
+            .headers
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Basic "))
+            .and_then(|value| {
+                base64::engine::general_purpose::STANDARD
+                    .decode(value.trim())
+                    .ok()
+            })
+            .map(|decoded| bool::from(decoded.ct_eq(credentials.as_bytes())))
+            .unwrap_or(false);
+
+        if matched {
+            None
+        } else {
+            Some(
+                (
+                    StatusCode::UNAUTHORIZED,
+                    [(header::WWW_AUTHENTICATE, "Basic realm=\"next-web\"")],
+                    "Unauthorized",
+                )
+                    .into_response(),
+            )
+        }
+    }
+
+    /// Awaits a handler body wrapped by a guard, the explicit `T` types the body's `?` and
+    /// `return` statements.
+    pub async fn invoke<T, F>(body: F) -> T
+    where
+        F: Future<Output = T>,
+    {
+        body.await
+    }
+
+    async fn access_denied_handler(state: &ApplicationState) -> Arc<dyn AccessDeniedHandler> {
+        state
+            .context()
+            .read()
+            .await
+            .get_singles_by_type::<Arc<dyn AccessDeniedHandler>>()
+            .first()
+            .map(|handler| Arc::clone(handler))
+            .unwrap_or_else(|| Arc::new(DefaultAccessDeniedHandler))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use axum::http::Request;
+    use next_web_core::ApplicationContext;
+
+    use super::*;
+    use crate::{
+        core::{
+            authc::jwt::{jwt_claims::JwtClaims, jwt_decoder::JwtDecoder, jwt_encoder::JwtEncoder},
+            realm::jwt_realm::JwtRealm,
+        },
+        web::method::bearer_token_authentication_resolver::BearerTokenAuthenticationResolver,
+    };
+
+    fn parts(authorization: Option<String>) -> Parts {
+        let mut builder = Request::builder().uri("/orders/42");
+        if let Some(authorization) = authorization {
+            builder = builder.header(header::AUTHORIZATION, authorization);
+        }
+        builder.body(()).unwrap().into_parts().0
+    }
+
+    #[tokio::test]
+    async fn test_check_with_bearer_token() {
+        let mut context = ApplicationContext::default();
+        let resolver: Arc<dyn AuthenticationResolver> = Arc::new(
+            BearerTokenAuthenticationResolver::new(JwtRealm::new(JwtDecoder::hs256("secret"))),
+        );
+        context.insert_singleton(resolver);
+        let state = ApplicationState::from_context(context);
+
+        let claims = JwtClaims::new()
+            .subject("42")
+            .claim("roles", vec!["USER"])
+            .expires_in(Duration::from_secs(60));
+        let token = JwtEncoder::hs256("secret").encode(&claims).unwrap();
+        let expression =
+            SecurityExpression::parse("hasRole('USER') and #id == principal.id").unwrap();
+
+        let mut evaluation =
+            MethodSecurity::context(&state, &parts(Some(format!("Bearer {}", token)))).await;
+        evaluation.set_variable("id", &42u64);
+        assert!(MethodSecurity::check(&state, &evaluation, Ok(&expression))
+            .await
+            .is_none());
+
+        evaluation.set_variable("id", &7u64);
+        let response = MethodSecurity::check(&state, &evaluation, Ok(&expression))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        let anonymous = MethodSecurity::context(&state, &parts(None)).await;
+        let response = MethodSecurity::check(&state, &anonymous, Ok(&expression))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let basic = format!(
+            "Basic {}",
+            base64::engine::general_purpose::STANDARD.encode("admin:secret")
+        );
+        assert!(MethodSecurity::check_basic(&parts(Some(basic)), "admin:secret").is_none());
+        assert!(MethodSecurity::check_basic(&parts(None), "admin:secret").is_some());
+
+        let wrong = format!(
+            "Basic {}",
+            base64::engine::general_purpose::STANDARD.encode("admin:secreT")
+        );
+        assert!(MethodSecurity::check_basic(&parts(Some(wrong)), "admin:secret").is_some());
+    }
+}
@@ -0,0 +1,4 @@
+pub mod access_denied_handler;
+pub mod authentication_resolver;
+pub mod bearer_token_authentication_resolver;
+pub mod method_security;
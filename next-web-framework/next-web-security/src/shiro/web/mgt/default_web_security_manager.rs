@@ -194,13 +194,15 @@ impl SecurityManager for DefaultWebSecurityManager {
     }
 }
 
+#[async_trait]
 impl Authenticator for DefaultWebSecurityManager {
-    fn authenticate(
+    async fn authenticate(
         &self,
         authentication_token: &dyn AuthenticationToken,
     ) -> Result<Box<dyn AuthenticationInfo>, AuthenticationError> {
         self.default_security_manager
             .authenticate(authentication_token)
+            .await
     }
 }
 
@@ -333,6 +335,10 @@ impl Authorizer for DefaultWebSecurityManager {
     ) -> Result<(), AuthorizationError> {
         self.default_security_manager.check_roles(principal, roles)
     }
+
+    fn clear_cached_authorization(&self, principals: Option<&dyn PrincipalCollection>) {
+        self.default_security_manager.clear_cached_authorization(principals)
+    }
 }
 
 #[async_trait]
@@ -351,6 +357,26 @@ impl SessionManager for DefaultWebSecurityManager {
     async fn get_session(&self, id: &SessionId) -> Result<Arc<dyn Session>, SessionError> {
         self.default_security_manager.get_session(id).await
     }
+
+    async fn register_principal(
+        &self,
+        session_id: &SessionId,
+        principal: &str,
+    ) -> Result<(), SessionError> {
+        self.default_security_manager
+            .register_principal(session_id, principal)
+            .await
+    }
+
+    async fn check_principal(
+        &self,
+        session_id: Option<&SessionId>,
+        principal: &str,
+    ) -> Result<(), SessionError> {
+        self.default_security_manager
+            .check_principal(session_id, principal)
+            .await
+    }
 }
 
 impl Default for DefaultWebSecurityManager {
@@ -5,6 +5,7 @@ use chrono::Utc;
 use next_web_core::traits::http::{http_request::HttpRequest, http_response::HttpResponse};
 use tracing::{debug, warn};
 
+pub mod method;
 pub mod mgt;
 // pub mod web_security_context;
 pub mod filter;
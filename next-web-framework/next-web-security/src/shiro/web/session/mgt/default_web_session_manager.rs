@@ -287,7 +287,7 @@ impl ValidatingSessionManagerExt for DefaultWebSessionManager {
         self._on_invalidation(req, resp);
     }
 
-    async fn get_active_sessions(&self) -> Vec<&Arc<dyn Session>> {
+    async fn get_active_sessions(&self) -> Vec<Arc<dyn Session>> {
         self.default_session_manager.get_active_sessions().await
     }
 }
@@ -306,6 +306,26 @@ impl SessionManager for DefaultWebSessionManager {
     async fn get_session(&self, id: &SessionId) -> Result<Arc<dyn Session>, SessionError> {
         self.default_session_manager.do_get_session(id).await
     }
+
+    async fn register_principal(
+        &self,
+        session_id: &SessionId,
+        principal: &str,
+    ) -> Result<(), SessionError> {
+        self.default_session_manager
+            .register_principal(session_id, principal)
+            .await
+    }
+
+    async fn check_principal(
+        &self,
+        session_id: Option<&SessionId>,
+        principal: &str,
+    ) -> Result<(), SessionError> {
+        self.default_session_manager
+            .check_principal(session_id, principal)
+            .await
+    }
 }
 
 #[async_trait]
@@ -3,6 +3,5 @@ pub mod authentication;
 pub mod authorization;
 pub mod config;
 pub mod core;
-pub mod crypto;
 pub mod permission;
 pub mod web;
@@ -0,0 +1,355 @@
+//! 应用测试
+//!
+//! Run the whole application in-process with [`TestApplication`] and call it with the returned
+//! [`TestClient`], no port is bound.
+
+mod test_application;
+mod test_client;
+mod test_response;
+
+pub use test_application::TestApplication;
+pub use test_client::{TestClient, TestRequest};
+pub use test_response::TestResponse;
+
+#[cfg(test)]
+mod tests {
+    use axum::http::StatusCode;
+    use axum::Router;
+    use next_web_core::{
+        async_trait, context::properties::ApplicationProperties, ApplicationContext,
+    };
+    use next_web_core::context::scope::SessionScope;
+    use next_web_dev::{
+        application::Application,
+        extract::{find_scoped::FindScoped, find_singleton::FindSingleton},
+        Singleton, Transient,
+    };
+    use next_web_dev::response::IntoResponse;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    #[derive(Clone)]
+    struct Greeting(String);
+
+    #[Singleton(name = "greeting")]
+    fn DefaultGreeting() -> Greeting {
+        Greeting("hello".into())
+    }
+
+    #[derive(Clone)]
+    struct Feature;
+
+    #[Singleton(name = "feature", on_property = "app.feature.enabled", on_bean = Greeting)]
+    fn ConditionalFeature() -> Feature {
+        Feature
+    }
+
+    #[derive(Clone)]
+    struct DevTools;
+
+    #[Singleton(name = "devTools", on_profile = "!prod")]
+    fn ConditionalDevTools() -> DevTools {
+        DevTools
+    }
+
+    /// A user of the directory
+    #[derive(serde::Deserialize, serde::Serialize, next_web_dev::ApiSchema)]
+    #[serde(rename_all = "camelCase")]
+    struct NewUser {
+        /// Name shown to the other users
+        display_name: String,
+        age: Option<u32>,
+    }
+
+    /// Rename a user
+    ///
+    /// Returns the renamed user.
+    #[next_web_dev::PutMapping(path = "/users/{id}")]
+    async fn rename_user(
+        next_web_dev::extract::Path(_id): next_web_dev::extract::Path<u64>,
+        next_web_dev::extract::Json(user): next_web_dev::extract::Json<NewUser>,
+    ) -> next_web_dev::extract::Json<NewUser> {
+        next_web_dev::extract::Json(user)
+    }
+
+    #[next_web_dev::PreAuthorize("hasRole('admin') or #id == principal.id")]
+    #[next_web_dev::GetMapping(path = "/accounts/{id}")]
+    async fn get_account(
+        next_web_dev::extract::Path(id): next_web_dev::extract::Path<u64>,
+    ) -> String {
+        format!("account {}", id)
+    }
+
+    #[derive(serde::Serialize)]
+    struct Document {
+        owner: String,
+    }
+
+    #[next_web_dev::PostAuthorize("returnObject.owner == principal.sub")]
+    #[next_web_dev::GetMapping(path = "/documents/{owner}")]
+    async fn get_document(
+        next_web_dev::extract::Path(owner): next_web_dev::extract::Path<String>,
+    ) -> Result<next_web_dev::extract::Json<Document>, StatusCode> {
+        if owner == "missing" {
+            return Err(StatusCode::NOT_FOUND);
+        }
+        Ok(next_web_dev::extract::Json(Document { owner }))
+    }
+
+    #[next_web_dev::Secured("ops")]
+    #[next_web_dev::DeleteMapping(path = "/cache")]
+    async fn clear_cache() -> &'static str {
+        "cleared"
+    }
+
+    static CREATED: AtomicUsize = AtomicUsize::new(0);
+
+    #[derive(Clone)]
+    struct Visit(usize);
+
+    #[Transient(name = "visit")]
+    fn NewVisit() -> Visit {
+        Visit(CREATED.fetch_add(1, Ordering::SeqCst))
+    }
+
+    #[derive(Default, Clone)]
+    struct TestApp;
+
+    #[async_trait]
+    impl Application for TestApp {
+        type ErrorSolve = ();
+
+        async fn init_middleware(&self, _properties: &ApplicationProperties) {}
+
+        async fn application_router(&self, _ctx: &mut ApplicationContext) -> Router {
+            Router::new()
+                .route(
+                    "/greeting",
+                    axum::routing::get(|FindSingleton(Greeting(message)): FindSingleton<Greeting>| async move {
+                        message
+                    }),
+                )
+                .route(
+                    "/visit",
+                    axum::routing::get(|a: FindScoped<Visit>, b: FindScoped<Visit>| async move {
+                        assert_eq!(a.0 .0, b.0 .0);
+                        a.0 .0.to_string()
+                    }),
+                )
+                .route(
+                    "/session-visit",
+                    axum::routing::get(|visit: FindScoped<Visit, SessionScope>| async move {
+                        visit.0 .0.to_string()
+                    }),
+                )
+        }
+    }
+
+    /// Serves the handlers registered by the mapping macros.
+    #[derive(Default, Clone)]
+    struct MappedApp;
+
+    #[async_trait]
+    impl Application for MappedApp {
+        type ErrorSolve = ();
+
+        async fn init_middleware(&self, _properties: &ApplicationProperties) {}
+    }
+
+    #[tokio::test]
+    async fn test_application_pipeline() {
+        let client = TestApplication::<TestApp>::new()
+            .config_yaml("next:\n  server:\n    context_path: /api")
+            .start()
+            .await;
+
+        client.get("/api/greeting").await.assert_ok().assert_text("hello");
+        client
+            .get("/api/missing")
+            .await
+            .assert_status(StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_singleton_replaced_before_startup() {
+        let client = TestApplication::<TestApp>::new()
+            .singleton(Greeting("mocked".into()))
+            .start()
+            .await;
+
+        client.get("/greeting").await.assert_ok().assert_text("mocked");
+        assert_eq!(client.singleton::<Greeting>().await.unwrap().0, "mocked");
+    }
+
+    #[tokio::test]
+    async fn test_conditional_singleton() {
+        let client = TestApplication::<TestApp>::new()
+            .property("app.feature.enabled", "true")
+            .active_profiles(["dev"])
+            .start()
+            .await;
+        let ctx = client.state().context().read().await;
+        assert!(ctx.contains_provider_with_name::<Feature>("feature"));
+        assert!(ctx.contains_provider_with_name::<DevTools>("devTools"));
+        drop(ctx);
+
+        let client = TestApplication::<TestApp>::new()
+            .property("app.feature.enabled", "false")
+            .active_profiles(["prod"])
+            .start()
+            .await;
+        let ctx = client.state().context().read().await;
+        assert!(!ctx.contains_provider_with_name::<Feature>("feature"));
+        assert!(!ctx.contains_provider_with_name::<DevTools>("devTools"));
+    }
+
+    #[tokio::test]
+    async fn test_scoped_beans() {
+        let client = TestApplication::<TestApp>::new().start().await;
+
+        let first = client.get("/visit").await.assert_ok().text();
+        let second = client.get("/visit").await.assert_ok().text();
+        assert_ne!(first, second);
+
+        client
+            .get("/session-visit")
+            .await
+            .assert_status(StatusCode::BAD_REQUEST);
+        let session = client
+            .get("/session-visit")
+            .header("cookie", "JSESSIONID=s1")
+            .await
+            .assert_ok()
+            .text();
+        client
+            .get("/session-visit")
+            .header("cookie", "JSESSIONID=s1")
+            .await
+            .assert_text(&session);
+
+        client.singleton::<SessionScope>().await.unwrap().evict("s1");
+        let renewed = client
+            .get("/session-visit")
+            .header("cookie", "JSESSIONID=s1")
+            .await
+            .assert_ok()
+            .text();
+        assert_ne!(session, renewed);
+    }
+
+    #[test]
+    fn test_api_operation() {
+        use next_web_core::openapi::OpenApiBuilder;
+        use next_web_dev::autoregister::handler_autoregister::HttpHandlerAutoRegister;
+
+        let handler = inventory::iter::<&dyn HttpHandlerAutoRegister>
+            .into_iter()
+            .find(|handler| handler.name() == "rename_user")
+            .unwrap();
+        let operation = handler.operation().unwrap();
+        assert_eq!(operation.summary, Some("Rename a user"));
+        assert_eq!(operation.description, Some("Returns the renamed user."));
+
+        let mut builder = OpenApiBuilder::new("Test", "1.0.0");
+        builder.add_operation(handler.method(), handler.path(), handler.name(), &operation);
+        let document = builder.build();
+
+        let put = &document["paths"]["/users/{id}"]["put"];
+        assert_eq!(put["parameters"][0]["schema"]["format"], "uint64");
+        assert_eq!(
+            put["requestBody"]["content"]["application/json"]["schema"]["$ref"],
+            "#/components/schemas/NewUser"
+        );
+        let user = &document["components"]["schemas"]["NewUser"];
+        assert_eq!(user["description"], "A user of the directory");
+        assert_eq!(
+            user["properties"]["displayName"]["description"],
+            "Name shown to the other users"
+        );
+        assert_eq!(user["required"], serde_json::json!(["displayName"]));
+    }
+
+    #[tokio::test]
+    async fn test_method_security() {
+        use next_web_dev::security::{
+            core::{
+                authc::jwt::{
+                    jwt_claims::JwtClaims, jwt_decoder::JwtDecoder, jwt_encoder::JwtEncoder,
+                },
+                realm::jwt_realm::JwtRealm,
+            },
+            web::method::{
+                authentication_resolver::AuthenticationResolver,
+                bearer_token_authentication_resolver::BearerTokenAuthenticationResolver,
+            },
+        };
+        use std::{sync::Arc, time::Duration};
+
+        let resolver: Arc<dyn AuthenticationResolver> = Arc::new(
+            BearerTokenAuthenticationResolver::new(JwtRealm::new(JwtDecoder::hs256("secret"))),
+        );
+        let client = TestApplication::<MappedApp>::new()
+            .singleton(resolver)
+            .start()
+            .await;
+        let token = |sub: &str, roles: Vec<&str>| {
+            let claims = JwtClaims::new()
+                .subject(sub)
+                .claim("roles", roles)
+                .expires_in(Duration::from_secs(60));
+            JwtEncoder::hs256("secret").encode(&claims).unwrap()
+        };
+        let alice = token("7", vec![]);
+        let admin = token("1", vec!["admin", "ops"]);
+
+        client
+            .get("/accounts/7")
+            .await
+            .assert_status(StatusCode::UNAUTHORIZED);
+        client
+            .get("/accounts/7")
+            .bearer_auth(&alice)
+            .await
+            .assert_ok()
+            .assert_text("account 7");
+        client
+            .get("/accounts/8")
+            .bearer_auth(&alice)
+            .await
+            .assert_status(StatusCode::FORBIDDEN);
+        client
+            .get("/accounts/8")
+            .bearer_auth(&admin)
+            .await
+            .assert_ok();
+
+        client
+            .get("/documents/7")
+            .bearer_auth(&alice)
+            .await
+            .assert_ok();
+        client
+            .get("/documents/1")
+            .bearer_auth(&alice)
+            .await
+            .assert_status(StatusCode::FORBIDDEN);
+        client
+            .get("/documents/missing")
+            .bearer_auth(&alice)
+            .await
+            .assert_status(StatusCode::NOT_FOUND);
+
+        client
+            .delete("/cache")
+            .bearer_auth(&alice)
+            .await
+            .assert_status(StatusCode::FORBIDDEN);
+        client
+            .delete("/cache")
+            .bearer_auth(&admin)
+            .await
+            .assert_ok()
+            .assert_text("cleared");
+    }
+}
@@ -0,0 +1,170 @@
+use std::borrow::Cow;
+use std::time::Duration;
+
+use next_web_core::context::application_args::ApplicationArgs;
+use next_web_core::context::application_context::{ApplicationContext, ApplicationContextOptions};
+use next_web_core::context::application_resources::ApplicationResources;
+use next_web_core::context::properties::{ApplicationProperties, Properties};
+use next_web_core::context::properties_validator::PropertiesValidator;
+use next_web_core::context::property_sources::{PropertySource, PropertySources};
+use next_web_dev::application::Application;
+use next_web_dev::availability::availability_state::AvailabilityState;
+
+use crate::test_client::TestClient;
+
+type ContextOptionsFn = Box<dyn FnOnce(ApplicationContextOptions) -> ApplicationContextOptions>;
+
+/// 测试应用
+///
+/// Runs the same pipeline as [`Application::run`]: properties, context, auto registration,
+/// infrastructure, router and filters, against an in-memory configuration, and returns a
+/// [`TestClient`] calling the router directly instead of binding a port. The lifecycle beans are
+/// started before it returns.
+///
+/// Only the configuration given here is used, the configuration files, the environment and the
+/// command line are not read. Logging is not initialized, the management endpoints served on
+/// their own port are not served.
+pub struct TestApplication<A: Application> {
+    application: A,
+    sources: PropertySources,
+    active_profiles: Vec<String>,
+    options: Vec<ContextOptionsFn>,
+}
+
+impl<A: Application + Default> TestApplication<A> {
+    pub fn new() -> Self {
+        Self::with_application(A::default())
+    }
+}
+
+impl<A: Application + Default> Default for TestApplication<A> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<A: Application> TestApplication<A> {
+    pub fn with_application(application: A) -> Self {
+        Self {
+            application,
+            sources: PropertySources::new(),
+            active_profiles: Vec::new(),
+            options: Vec::new(),
+        }
+    }
+
+    /// Add a yaml document to the configuration, it overrides the ones added before.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the document is not valid yaml.
+    pub fn config_yaml(mut self, yaml: &str) -> Self {
+        let value = serde_yaml::from_str(yaml)
+            .unwrap_or_else(|error| panic!("The test configuration is not valid yaml: {}", error));
+        let name = format!("test-config-{}", self.sources.iter().count());
+        self.sources.add_last(PropertySource::new(name, value));
+        self
+    }
+
+    /// Set a single dotted key, e.g. `property("next.server.context_path", "/api")`.
+    pub fn property(mut self, key: impl AsRef<str>, value: impl AsRef<str>) -> Self {
+        let name = format!("test-property-{}", self.sources.iter().count());
+        let source = PropertySource::from_pairs(name, [(key, value)])
+            .unwrap_or_else(|report| panic!("{}", report));
+        self.sources.add_last(source);
+        self
+    }
+
+    pub fn active_profiles<I, S>(mut self, profiles: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.active_profiles = profiles.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Register the instance under the default name of its type before startup, it replaces
+    /// the singleton of the same type registered by `#[Singleton]`, e.g. a mock.
+    pub fn singleton<T>(self, instance: T) -> Self
+    where
+        T: 'static + Clone + Send + Sync,
+    {
+        self.context_options(move |options| options.singleton_with_default_name(instance))
+    }
+
+    /// Register the instance under `name` before startup, it replaces the singleton with the
+    /// same type and name, e.g. a `Box<dyn Trait>` bound by a `#[Singleton]`.
+    pub fn singleton_with_name<T, N>(self, instance: T, name: N) -> Self
+    where
+        T: 'static + Clone + Send + Sync,
+        N: Into<Cow<'static, str>> + 'static,
+    {
+        self.context_options(move |options| options.singleton_with_name(instance, name))
+    }
+
+    /// Customize the options the context is created with.
+    pub fn context_options<F>(mut self, f: F) -> Self
+    where
+        F: FnOnce(ApplicationContextOptions) -> ApplicationContextOptions + 'static,
+    {
+        self.options.push(Box::new(f));
+        self
+    }
+
+    /// Start the application and return a client calling it.
+    ///
+    /// # Panics
+    ///
+    /// Panics with the validation report if the configuration is invalid.
+    pub async fn start(self) -> TestClient {
+        let Self {
+            application,
+            sources,
+            active_profiles,
+            options,
+        } = self;
+
+        let mut properties = ApplicationProperties::from_sources(&sources, active_profiles)
+            .unwrap_or_else(|report| panic!("{}", report));
+
+        let allow_override = properties
+            .next()
+            .appliation()
+            .map(|s| s.context().allow_override())
+            .unwrap_or(false);
+        let mut ctx = options
+            .into_iter()
+            .fold(
+                ApplicationContext::options()
+                    .allow_override(allow_override)
+                    .properties(properties.clone()),
+                |options, f| f(options),
+            )
+            .auto_register();
+
+        application.post_process_properties(&mut ctx, &mut properties);
+
+        let properties_beans = ctx.resolve_by_type::<Box<dyn Properties>>();
+        if let Err(report) = PropertiesValidator::validate(&properties, &properties_beans) {
+            panic!("{}", report);
+        }
+
+        let args = ApplicationArgs::parse_with_overrides([env!("CARGO_PKG_NAME")]);
+        let resources = ApplicationResources::default();
+
+        application
+            .prepare_context(&mut ctx, &properties, &args, &resources)
+            .await;
+
+        let prepared = application.prepare_application(ctx, &properties).await;
+        let lifecycle_beans = prepared.state().context().write().await.lifecycle_beans();
+        lifecycle_beans
+            .start(Duration::from_secs(30))
+            .await
+            .expect("failed to start the lifecycle beans");
+        prepared.availability().set_state(AvailabilityState::Ready);
+
+        TestClient::new(prepared.router().clone(), prepared.state().clone())
+    }
+}
@@ -0,0 +1,140 @@
+use std::future::{Future, IntoFuture};
+use std::net::SocketAddr;
+use std::pin::Pin;
+
+use axum::body::Body;
+use axum::extract::connect_info::MockConnectInfo;
+use axum::http::header::{AUTHORIZATION, CONTENT_TYPE};
+use axum::http::{HeaderName, HeaderValue, Method, Request};
+use axum::Router;
+use next_web_core::state::application_state::ApplicationState;
+use next_web_core::util::singleton::SingletonUtil;
+use serde::Serialize;
+use tower::ServiceExt;
+
+use crate::test_response::TestResponse;
+
+/// 测试客户端
+///
+/// Calls the router of a started [`TestApplication`](crate::TestApplication) as a
+/// `tower::Service`, requests go through the same middleware and filters as with a server.
+#[derive(Clone)]
+pub struct TestClient {
+    router: Router,
+    state: ApplicationState,
+}
+
+impl TestClient {
+    pub fn new(router: Router, state: ApplicationState) -> Self {
+        // Handlers and filters reading the peer address see a local client
+        let router = router.layer(MockConnectInfo(SocketAddr::from(([127, 0, 0, 1], 0))));
+        Self { router, state }
+    }
+
+    pub fn get(&self, uri: impl AsRef<str>) -> TestRequest {
+        self.request(Method::GET, uri)
+    }
+
+    pub fn post(&self, uri: impl AsRef<str>) -> TestRequest {
+        self.request(Method::POST, uri)
+    }
+
+    pub fn put(&self, uri: impl AsRef<str>) -> TestRequest {
+        self.request(Method::PUT, uri)
+    }
+
+    pub fn patch(&self, uri: impl AsRef<str>) -> TestRequest {
+        self.request(Method::PATCH, uri)
+    }
+
+    pub fn delete(&self, uri: impl AsRef<str>) -> TestRequest {
+        self.request(Method::DELETE, uri)
+    }
+
+    pub fn request(&self, method: Method, uri: impl AsRef<str>) -> TestRequest {
+        TestRequest {
+            router: self.router.clone(),
+            request: Request::builder().method(method).uri(uri.as_ref()),
+            body: Body::empty(),
+        }
+    }
+
+    /// The state of the application, it holds the [`ApplicationContext`](next_web_core::ApplicationContext).
+    pub fn state(&self) -> &ApplicationState {
+        &self.state
+    }
+
+    /// Get the singleton registered under the default name of its type.
+    pub async fn singleton<T>(&self) -> Option<T>
+    where
+        T: 'static + Clone + Send + Sync,
+    {
+        self.state
+            .context()
+            .read()
+            .await
+            .get_single_option_with_name::<T>(SingletonUtil::name::<T>())
+            .cloned()
+    }
+}
+
+/// A request to send with the [`TestClient`], it is sent when awaited.
+pub struct TestRequest {
+    router: Router,
+    request: axum::http::request::Builder,
+    body: Body,
+}
+
+impl TestRequest {
+    /// # Panics
+    ///
+    /// Panics when the request is sent if the name or the value is invalid.
+    pub fn header<K, V>(mut self, name: K, value: V) -> Self
+    where
+        HeaderName: TryFrom<K>,
+        <HeaderName as TryFrom<K>>::Error: Into<axum::http::Error>,
+        HeaderValue: TryFrom<V>,
+        <HeaderValue as TryFrom<V>>::Error: Into<axum::http::Error>,
+    {
+        self.request = self.request.header(name, value);
+        self
+    }
+
+    pub fn bearer_auth(self, token: impl AsRef<str>) -> Self {
+        self.header(AUTHORIZATION, format!("Bearer {}", token.as_ref()))
+    }
+
+    pub fn body(mut self, body: impl Into<Body>) -> Self {
+        self.body = body.into();
+        self
+    }
+
+    /// Send the value as a json body.
+    pub fn json<T: Serialize>(self, value: &T) -> Self {
+        let body = serde_json::to_vec(value).expect("Failed to serialize the json body");
+        self.header(CONTENT_TYPE, "application/json").body(body)
+    }
+
+    pub async fn send(self) -> TestResponse {
+        let request = self
+            .request
+            .body(self.body)
+            .expect("Failed to build the test request");
+
+        let response = match self.router.oneshot(request).await {
+            Ok(response) => response,
+            Err(infallible) => match infallible {},
+        };
+
+        TestResponse::from_response(response).await
+    }
+}
+
+impl IntoFuture for TestRequest {
+    type Output = TestResponse;
+    type IntoFuture = Pin<Box<dyn Future<Output = TestResponse> + Send>>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        Box::pin(self.send())
+    }
+}
@@ -0,0 +1,110 @@
+use axum::body::{to_bytes, Bytes};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::Response;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// The response of a [`TestRequest`](crate::TestRequest), the body is read completely.
+///
+/// The `assert_*` methods panic with the body of the response when they fail and return the
+/// response so they can be chained.
+#[derive(Debug, Clone)]
+pub struct TestResponse {
+    status: StatusCode,
+    headers: HeaderMap,
+    body: Bytes,
+}
+
+impl TestResponse {
+    pub(crate) async fn from_response(response: Response) -> Self {
+        let (parts, body) = response.into_parts();
+        let body = to_bytes(body, usize::MAX)
+            .await
+            .expect("Failed to read the response body");
+
+        Self {
+            status: parts.status,
+            headers: parts.headers,
+            body,
+        }
+    }
+
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    pub fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+
+    /// The value of the header, `None` if it is missing or not visible ascii.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name).and_then(|value| value.to_str().ok())
+    }
+
+    pub fn bytes(&self) -> &Bytes {
+        &self.body
+    }
+
+    pub fn text(&self) -> String {
+        String::from_utf8_lossy(&self.body).into_owned()
+    }
+
+    /// # Panics
+    ///
+    /// Panics if the body is not a json `T`.
+    pub fn json<T: DeserializeOwned>(&self) -> T {
+        serde_json::from_slice(&self.body).unwrap_or_else(|error| {
+            panic!(
+                "The response body is not the expected json: {}\nbody: {}",
+                error,
+                self.text()
+            )
+        })
+    }
+
+    #[track_caller]
+    pub fn assert_status(self, status: StatusCode) -> Self {
+        assert_eq!(
+            self.status,
+            status,
+            "Unexpected response status, body: {}",
+            self.text()
+        );
+        self
+    }
+
+    #[track_caller]
+    pub fn assert_ok(self) -> Self {
+        self.assert_status(StatusCode::OK)
+    }
+
+    #[track_caller]
+    pub fn assert_header(self, name: &str, value: &str) -> Self {
+        assert_eq!(
+            self.header(name),
+            Some(value),
+            "Unexpected value of the response header `{}`",
+            name
+        );
+        self
+    }
+
+    #[track_caller]
+    pub fn assert_text(self, text: &str) -> Self {
+        assert_eq!(self.text(), text, "Unexpected response body");
+        self
+    }
+
+    /// Compare the body with the value as json, so the order of the keys does not matter.
+    #[track_caller]
+    pub fn assert_json<T: Serialize>(self, value: &T) -> Self {
+        let expected = serde_json::to_value(value).expect("Failed to serialize the expected json");
+        assert_eq!(
+            self.json::<serde_json::Value>(),
+            expected,
+            "Unexpected response json"
+        );
+        self
+    }
+}
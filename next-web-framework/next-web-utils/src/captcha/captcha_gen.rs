@@ -302,7 +302,10 @@ mod tests {
             println!("complexity: {}", captcha_result.complexity); // 打印验证码复杂度
 
             // 将图片数据保存为文件
-            std::fs::write("test.png", &captcha_result.image_data)?;
+            std::fs::write(
+                std::env::temp_dir().join("next-web-utils-captcha.png"),
+                &captcha_result.image_data,
+            )?;
             Ok(())
         })?
     }
@@ -1,10 +1,16 @@
-use chrono::{DateTime, Datelike, Duration, Local, Timelike};
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, NaiveDateTime, TimeZone, Timelike};
 use std::fmt;
 
+const MONTH_NAMES: [&str; 12] = [
+    "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+];
+const DAY_OF_WEEK_NAMES: [&str; 7] = ["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"];
+
 /// Cron 表达式结构
 /// Cron expression structure
 #[derive(Debug, Clone)]
 pub struct CronExpression {
+    pub second: Vec<u8>,      // 秒 (0-59)，5 个字段时为 0 / Seconds (0-59), 0 with 5 fields
     pub minute: Vec<u8>,      // 分钟 (0-59) / Minutes (0-59)
     pub hour: Vec<u8>,        // 小时 (0-23) / Hours (0-23)
     pub day_of_month: Vec<u8>, // 日期 (1-31) / Day of month (1-31)
@@ -13,6 +19,80 @@ pub struct CronExpression {
     pub expression: String,   // 原始表达式 / Original expression
 }
 
+impl CronExpression {
+    /// 计算指定时间之后的下一次执行时间，在 `from` 的时区中匹配
+    /// Next execution strictly after `from`, the fields are matched in the time zone of `from`
+    pub fn next_after<Tz: TimeZone>(&self, from: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+        let timezone = from.timezone();
+        let start = from.naive_local();
+        let mut candidate = start.with_nanosecond(0)? + Duration::seconds(1);
+
+        // 最多迭代 5 年，覆盖 2 月 29 日
+        // Iterate at most 5 years, enough for February 29th
+        let limit = start + Duration::days(366 * 5);
+
+        while candidate < limit {
+            if !self.month.contains(&(candidate.month() as u8)) {
+                let (year, month) = match candidate.month() {
+                    12 => (candidate.year() + 1, 1),
+                    month => (candidate.year(), month + 1),
+                };
+                candidate = Self::start_of_day(NaiveDate::from_ymd_opt(year, month, 1)?);
+                continue;
+            }
+
+            if !self.day_matches(candidate.date()) {
+                candidate = Self::start_of_day(candidate.date().succ_opt()?);
+                continue;
+            }
+
+            if !self.hour.contains(&(candidate.hour() as u8)) {
+                candidate = candidate.with_minute(0)?.with_second(0)? + Duration::hours(1);
+                continue;
+            }
+
+            if !self.minute.contains(&(candidate.minute() as u8)) {
+                candidate = candidate.with_second(0)? + Duration::minutes(1);
+                continue;
+            }
+
+            if !self.second.contains(&(candidate.second() as u8)) {
+                candidate += Duration::seconds(1);
+                continue;
+            }
+
+            // 夏令时跳过的本地时间不存在
+            // Local times skipped by daylight saving time don't exist
+            match timezone.from_local_datetime(&candidate).earliest() {
+                Some(next) if next > *from => return Some(next),
+                _ => candidate += Duration::seconds(1),
+            }
+        }
+
+        None
+    }
+
+    /// 日期和星期都有限制时满足其一即可，否则两者都要满足
+    /// When both day of month and day of week are restricted either one has to match,
+    /// otherwise both
+    fn day_matches(&self, date: NaiveDate) -> bool {
+        let day_of_month = self.day_of_month.contains(&(date.day() as u8));
+        let day_of_week = self
+            .day_of_week
+            .contains(&(date.weekday().num_days_from_sunday() as u8));
+
+        if self.day_of_month.len() < 31 && self.day_of_week.len() < 7 {
+            day_of_month || day_of_week
+        } else {
+            day_of_month && day_of_week
+        }
+    }
+
+    fn start_of_day(date: NaiveDate) -> NaiveDateTime {
+        date.and_hms_opt(0, 0, 0).unwrap()
+    }
+}
+
 /// Cron 表达式解析错误
 /// Cron expression parsing error
 #[derive(Debug)]
@@ -40,6 +120,10 @@ impl CronUtil {
     /// 解析 cron 表达式为结构体
     /// Parse cron expression into a struct
     /// 
+    /// 6 个字段时第一个字段为秒，月份和星期可以使用英文缩写，`?` 等同于 `*`
+    /// With 6 fields the first one is the seconds, months and days of week can be names
+    /// like `JAN` or `MON`, `?` is the same as `*`
+    /// 
     /// # 参数
     /// # Parameters
     /// * `expression` - cron 表达式，如 "0 0 * * *"
@@ -49,23 +133,47 @@ impl CronUtil {
     /// # Example
     /// ```
     /// let cron = CronUtil::parse("0 0 * * *").unwrap();
+    /// let cron = CronUtil::parse("30 0 2 * * MON-FRI").unwrap();
     /// ```
     pub fn parse(expression: &str) -> Result<CronExpression, CronError> {
-        let parts: Vec<&str> = expression.split_whitespace().collect();
-        
-        if parts.len() != 5 {
-            return Err(CronError::InvalidExpression(
-                format!("Expected to have 5 fields, actually there are {}", parts.len())
-            ));
-        }
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        
+        let (second, parts) = match fields.len() {
+            5 => (vec![0], &fields[..]),
+            6 => (Self::parse_field(fields[0], 0, 59)?, &fields[1..]),
+            7 => {
+                return Err(CronError::InvalidExpression(
+                    "Expected to have 5 or 6 fields, the year field is not supported".to_string()
+                ));
+            }
+            len => {
+                return Err(CronError::InvalidExpression(
+                    format!("Expected to have 5 or 6 fields, actually there are {}", len)
+                ));
+            }
+        };
         
         let minute = Self::parse_field(parts[0], 0, 59)?;
         let hour = Self::parse_field(parts[1], 0, 23)?;
-        let day_of_month = Self::parse_field(parts[2], 1, 31)?;
-        let month = Self::parse_field(parts[3], 1, 12)?;
-        let day_of_week = Self::parse_field(parts[4], 0, 6)?;
+        let day_of_month = Self::parse_field(&Self::supported(&parts[2].replace('?', "*"))?, 1, 31)?;
+        let month = Self::parse_field(&Self::replace_names(parts[3], &MONTH_NAMES, 1), 1, 12)?;
+        let mut day_of_week = Self::parse_field(
+            &Self::supported(&Self::replace_names(&parts[4].replace('?', "*"), &DAY_OF_WEEK_NAMES, 0))?,
+            0,
+            7,
+        )?;
+        
+        // 7 也表示星期日
+        // 7 is Sunday too
+        if day_of_week.contains(&7) {
+            day_of_week.retain(|day| *day != 7);
+            if !day_of_week.contains(&0) {
+                day_of_week.insert(0, 0);
+            }
+        }
         
         Ok(CronExpression {
+            second,
             minute,
             hour,
             day_of_month,
@@ -75,6 +183,28 @@ impl CronUtil {
         })
     }
     
+    /// 日期和星期不支持 `L`、`W` 和 `#`
+    /// The `L`, `W` and `#` syntax of the day fields is not supported
+    fn supported(field: &str) -> Result<&str, CronError> {
+        match field.to_ascii_uppercase().contains(['L', 'W', '#']) {
+            true => Err(CronError::InvalidField(format!(
+                "The `L`, `W` and `#` syntax is not supported: {}",
+                field
+            ))),
+            false => Ok(field),
+        }
+    }
+    
+    /// 将英文缩写替换为数字
+    /// Replace names like `JAN` or `MON` with their number
+    fn replace_names(field: &str, names: &[&str], first: u8) -> String {
+        let mut field = field.to_ascii_uppercase();
+        for (index, name) in names.iter().enumerate() {
+            field = field.replace(name, &(first + index as u8).to_string());
+        }
+        field
+    }
+    
     /// 解析 cron 字段
     /// Parse cron field
     fn parse_field(field: &str, min: u8, max: u8) -> Result<Vec<u8>, CronError> {
@@ -218,8 +348,8 @@ impl CronUtil {
         Self::next_execution_from(expression, now)
     }
     
-    /// 从指定时间计算下一次执行时间
-    /// Calculate next execution time from a specific time
+    /// 从指定时间计算下一次执行时间，按 `from` 的时区匹配
+    /// Calculate next execution time from a specific time, matched in the time zone of `from`
     /// 
     /// # 参数
     /// # Parameters
@@ -227,88 +357,13 @@ impl CronUtil {
     /// * `expression` - cron expression
     /// * `from` - 起始时间
     /// * `from` - start time
-    pub fn next_execution_from(expression: &str, from: DateTime<Local>) -> Result<DateTime<Local>, CronError> {
-        let cron = Self::parse(expression)?;
-        
-        let mut candidate = from + Duration::minutes(1);
-        candidate = candidate
-            .with_second(0)
-            .unwrap()
-            .with_nanosecond(0)
-            .unwrap();
-        
-        // 最多迭代 2 年，避免无限循环
-        // Iterate at most 2 years to avoid infinite loop
-        let limit = from + Duration::days(366 * 2);
-        
-        while candidate < limit {
-            let month = candidate.month() as u8;
-            if !cron.month.contains(&month) {
-                candidate = Self::add_months(candidate, 1).with_day(1).unwrap();
-                continue;
-            }
-            
-            let dom = candidate.day() as u8;
-            if !cron.day_of_month.contains(&dom) {
-                candidate = candidate + Duration::days(1);
-                candidate = candidate
-                    .with_hour(0)
-                    .unwrap()
-                    .with_minute(0)
-                    .unwrap();
-                continue;
-            }
-            
-            let dow = candidate.weekday().num_days_from_sunday() as u8;
-            if !cron.day_of_week.contains(&dow) && !cron.day_of_week.contains(&7) {
-                candidate = candidate + Duration::days(1);
-                candidate = candidate
-                    .with_hour(0)
-                    .unwrap()
-                    .with_minute(0)
-                    .unwrap();
-                continue;
-            }
-            
-            let hour = candidate.hour() as u8;
-            if !cron.hour.contains(&hour) {
-                candidate = candidate + Duration::hours(1);
-                candidate = candidate.with_minute(0).unwrap();
-                continue;
-            }
-            
-            let minute = candidate.minute() as u8;
-            if !cron.minute.contains(&minute) {
-                candidate = candidate + Duration::minutes(1);
-                continue;
-            }
-            
-            return Ok(candidate);
-        }
-        
-        Err(CronError::InvalidExpression("Cannot find next execution time, expression may be invalid or never executes".to_string()))
-    }
-    
-    /// 添加月数到日期
-    /// Add months to a date
-    fn add_months(dt: DateTime<Local>, months: i32) -> DateTime<Local> {
-        let mut year = dt.year();
-        let mut month = dt.month() as i32 + months;
-        
-        while month > 12 {
-            year += 1;
-            month -= 12;
-        }
-        
-        while month < 1 {
-            year -= 1;
-            month += 12;
-        }
-        
-        dt.with_year(year)
-          .unwrap()
-          .with_month(month as u32)
-          .unwrap()
+    pub fn next_execution_from<Tz: TimeZone>(
+        expression: &str,
+        from: DateTime<Tz>,
+    ) -> Result<DateTime<Tz>, CronError> {
+        Self::parse(expression)?.next_after(&from).ok_or_else(|| {
+            CronError::InvalidExpression("Cannot find next execution time, expression may be invalid or never executes".to_string())
+        })
     }
     
     /// 生成 cron 表达式的人类可读描述
@@ -502,7 +557,7 @@ impl CronUtil {
                         break;
                     }
                     count += 1;
-                    current = next + Duration::minutes(1);
+                    current = next;
                 },
                 Err(_) => break,
             }
@@ -531,7 +586,7 @@ impl CronUtil {
             match Self::next_execution_from(expression, current) {
                 Ok(next) => {
                     executions.push(next);
-                    current = next + Duration::minutes(1);
+                    current = next;
                 },
                 Err(e) => return Err(e),
             }
@@ -631,3 +686,62 @@ impl CronUtil {
 
 
 
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::*;
+
+    fn utc(value: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(value).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_parse() {
+        let cron = CronUtil::parse("0 0 * * *").unwrap();
+        assert_eq!(cron.second, vec![0]);
+
+        let cron = CronUtil::parse("*/15 30 2 ? JAN-MAR MON-FRI").unwrap();
+        assert_eq!(cron.second, vec![0, 15, 30, 45]);
+        assert_eq!(cron.day_of_month.len(), 31);
+        assert_eq!(cron.month, vec![1, 2, 3]);
+        assert_eq!(cron.day_of_week, vec![1, 2, 3, 4, 5]);
+
+        assert_eq!(CronUtil::parse("0 0 * * 7").unwrap().day_of_week, vec![0]);
+        assert!(CronUtil::parse("0 0 0 * *  * *").is_err());
+        assert!(CronUtil::parse("60 0 0 * * *").is_err());
+        assert!(CronUtil::parse("0 0 0 * * * 2030").is_err());
+        assert!(CronUtil::parse("0 0 0 L * *").is_err());
+        assert!(CronUtil::parse("0 0 0 ? * MON#2").is_err());
+        assert!(CronUtil::parse("0 0 0 15W * *").is_err());
+    }
+
+    #[test]
+    fn test_next_execution_from() {
+        let from = utc("2024-01-01T10:00:00Z");
+        assert_eq!(
+            CronUtil::next_execution_from("0 0 * * *", from).unwrap(),
+            utc("2024-01-02T00:00:00Z")
+        );
+        assert_eq!(
+            CronUtil::next_execution_from("*/10 * * * * *", from).unwrap(),
+            utc("2024-01-01T10:00:10Z")
+        );
+        assert_eq!(
+            CronUtil::next_execution_from("30 0 2 * * MON", from).unwrap(),
+            utc("2024-01-08T02:00:30Z")
+        );
+        assert_eq!(
+            CronUtil::next_execution_from("0 0 0 29 2 *", from).unwrap(),
+            utc("2024-02-29T00:00:00Z")
+        );
+    }
+
+    #[test]
+    fn test_next_execution_in_time_zone() {
+        let from = utc("2024-01-01T10:00:00Z").with_timezone(&chrono::FixedOffset::east_opt(8 * 3600).unwrap());
+        let next = CronUtil::next_execution_from("0 0 3 * * *", from).unwrap();
+        assert_eq!(next.with_timezone(&Utc), utc("2024-01-01T19:00:00Z"));
+    }
+}
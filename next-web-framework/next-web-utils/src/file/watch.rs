@@ -2,6 +2,8 @@ use std::time::Duration;
 
 use notify::{EventHandler, RecommendedWatcher, Watcher as MyWatcher};
 
+pub use notify::{Error, Event, EventKind};
+
 pub struct Watcher(RecommendedWatcher);
 
 impl Watcher {
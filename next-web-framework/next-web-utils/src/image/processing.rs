@@ -228,7 +228,11 @@ pub fn batch_process_image(
 mod tests {
     use super::*;
     use image::{ImageBuffer, Rgba};
-    use std::{fs, time::SystemTime};
+    use std::{
+        fs,
+        sync::atomic::{AtomicUsize, Ordering},
+        time::SystemTime,
+    };
 
     // 创建测试用的临时图片
     fn create_test_image(width: u32, height: u32) -> String {
@@ -245,14 +249,21 @@ mod tests {
         path
     }
 
+    // 测试输出写入临时目录，避免在工作区留下文件
     fn get_file_path() -> String {
-        format!(
-            "{}.png",
-            SystemTime::now()
-                .duration_since(SystemTime::UNIX_EPOCH)
-                .unwrap()
-                .as_millis()
-        )
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+        std::env::temp_dir()
+            .join(format!(
+                "next-web-utils-{}-{}-{}.png",
+                std::process::id(),
+                SystemTime::now()
+                    .duration_since(SystemTime::UNIX_EPOCH)
+                    .unwrap()
+                    .as_millis(),
+                COUNTER.fetch_add(1, Ordering::Relaxed)
+            ))
+            .to_string_lossy()
+            .into_owned()
     }
     #[test]
     fn test_resize_image() {
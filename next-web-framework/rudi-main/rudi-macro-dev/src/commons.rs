@@ -8,7 +8,10 @@ use syn::{
     PatType, Path, PathArguments, PathSegment, Stmt, Token, Type, TypePath, TypeReference,
 };
 
-use crate::{field_or_argument_attr::FieldOrArgumentAttr, value_attr::ValueAttr};
+use crate::{
+    field_or_argument_attr::FieldOrArgumentAttr, struct_or_function_attr::ClosureOrPath,
+    value_attr::ValueAttr,
+};
 
 pub(crate) fn generate_create_provider(scope: Scope, color: Color) -> TokenStream {
     match (scope, color) {
@@ -33,6 +36,49 @@ pub(crate) fn generate_create_provider(scope: Scope, color: Color) -> TokenStrea
     }
 }
 
+/// Combine the `condition` with the `on_*` conditions, all of them must be met.
+pub(crate) fn generate_condition(
+    path: &Path,
+    condition: Option<ClosureOrPath>,
+    on_property: Option<String>,
+    on_profile: Option<String>,
+    on_bean: Option<Type>,
+    on_missing_bean: Option<Type>,
+) -> TokenStream {
+    let conditions = quote!(#path::context::conditions::Conditions);
+
+    let mut checks = Vec::new();
+    if let Some(expression) = on_property {
+        checks.push(quote!(#conditions::on_property(cx, #expression)));
+    }
+    if let Some(expression) = on_profile {
+        checks.push(quote!(#conditions::on_profile(cx, #expression)));
+    }
+    if let Some(ty) = on_bean {
+        checks.push(quote!(#conditions::on_bean::<#ty>(cx)));
+    }
+    if let Some(ty) = on_missing_bean {
+        checks.push(quote!(#conditions::on_missing_bean::<#ty>(cx)));
+    }
+
+    match (condition, checks.is_empty()) {
+        (None, true) => quote!(None),
+        (Some(ClosureOrPath(expr)), true) => quote!(Some(#expr)),
+        (condition, false) => {
+            if let Some(ClosureOrPath(expr)) = condition {
+                checks.insert(0, quote!((#expr)(cx)));
+            }
+
+            quote! {
+                Some(
+                    (|cx: &#path::ApplicationContext| -> bool { #(#checks)&&* })
+                        as fn(&#path::ApplicationContext) -> bool
+                )
+            }
+        }
+    }
+}
+
 fn extract_ref_type(ty: &Type) -> syn::Result<&Type> {
     fn require_type_ref(ty: &Type) -> Option<&TypeReference> {
         match ty {
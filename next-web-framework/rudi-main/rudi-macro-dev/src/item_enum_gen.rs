@@ -8,7 +8,7 @@ use crate::{
     commons::{self, FieldResolveStmts, ResolvedFields},
     resource_attr::ResourceAttr,
     impl_fn_or_enum_variant_attr::ImplFnOrEnumVariantAttr,
-    struct_or_function_attr::{ClosureOrPath, StructOrFunctionAttr},
+    struct_or_function_attr::StructOrFunctionAttr,
 };
 
 pub(crate) fn generate(
@@ -30,6 +30,10 @@ pub(crate) fn generate(
         name,
         eager_create,
         condition,
+        on_property,
+        on_profile,
+        on_bean,
+        on_missing_bean,
         binds,
         async_,
         #[cfg(feature = "auto-register")]
@@ -50,9 +54,14 @@ pub(crate) fn generate(
         _ => Color::Sync,
     };
 
-    let condition = condition
-        .map(|ClosureOrPath(expr)| quote!(Some(#expr)))
-        .unwrap_or_else(|| quote!(None));
+    let condition = commons::generate_condition(
+        &path,
+        condition,
+        on_property,
+        on_profile,
+        on_bean,
+        on_missing_bean,
+    );
 
     let mut variant_spans = Vec::new();
 
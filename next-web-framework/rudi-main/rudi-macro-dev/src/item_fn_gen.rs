@@ -7,7 +7,7 @@ use syn::{GenericParam, ItemFn, ReturnType};
 use crate::{
     commons::{self, ArgumentResolveStmts},
     resource_attr::ResourceAttr,
-    struct_or_function_attr::{ClosureOrPath, StructOrFunctionAttr},
+    struct_or_function_attr::StructOrFunctionAttr,
 };
 
 // #[Singleton]
@@ -37,6 +37,10 @@ pub(crate) fn generate(
         name,
         eager_create,
         condition,
+        on_property,
+        on_profile,
+        on_bean,
+        on_missing_bean,
         binds,
         async_: _,
         #[cfg(feature = "auto-register")]
@@ -57,9 +61,14 @@ pub(crate) fn generate(
         None => Color::Sync,
     };
 
-    let condition = condition
-        .map(|ClosureOrPath(expr)| quote!(Some(#expr)))
-        .unwrap_or_else(|| quote!(None));
+    let condition = commons::generate_condition(
+        &path,
+        condition,
+        on_property,
+        on_profile,
+        on_bean,
+        on_missing_bean,
+    );
 
     let ArgumentResolveStmts {
         ref_mut_cx_stmts,
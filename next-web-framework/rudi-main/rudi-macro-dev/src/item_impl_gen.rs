@@ -11,7 +11,7 @@ use crate::{
     commons::{self, ArgumentResolveStmts},
     resource_attr::ResourceAttr,
     impl_fn_or_enum_variant_attr::ImplFnOrEnumVariantAttr,
-    struct_or_function_attr::{ClosureOrPath, StructOrFunctionAttr},
+    struct_or_function_attr::StructOrFunctionAttr,
 };
 
 // struct A {
@@ -132,6 +132,10 @@ fn generate_default_provider_impl<'a>(
         name,
         eager_create,
         condition,
+        on_property,
+        on_profile,
+        on_bean,
+        on_missing_bean,
         binds,
         async_: _,
         #[cfg(feature = "auto-register")]
@@ -182,9 +186,14 @@ fn generate_default_provider_impl<'a>(
         None => Color::Sync,
     };
 
-    let condition = condition
-        .map(|ClosureOrPath(expr)| quote!(Some(#expr)))
-        .unwrap_or_else(|| quote!(None));
+    let condition = commons::generate_condition(
+        &path,
+        condition,
+        on_property,
+        on_profile,
+        on_bean,
+        on_missing_bean,
+    );
 
     let ArgumentResolveStmts {
         ref_mut_cx_stmts,
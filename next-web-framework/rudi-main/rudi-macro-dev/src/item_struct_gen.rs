@@ -7,7 +7,7 @@ use syn::ItemStruct;
 use crate::{
     resource_attr::ResourceAttr,
     commons::{self, FieldResolveStmts, ResolvedFields},
-    struct_or_function_attr::{ClosureOrPath, StructOrFunctionAttr},
+    struct_or_function_attr::StructOrFunctionAttr,
 };
 
 pub(crate) fn generate(
@@ -25,6 +25,10 @@ pub(crate) fn generate(
         name,
         eager_create,
         condition,
+        on_property,
+        on_profile,
+        on_bean,
+        on_missing_bean,
         binds,
         async_,
         #[cfg(feature = "auto-register")]
@@ -46,9 +50,14 @@ pub(crate) fn generate(
     };
 
     // 获取单例的条件表达式 如果函数返回为 true 则创建单例
-    let condition = condition
-        .map(|ClosureOrPath(expr)| quote!(Some(#expr)))
-        .unwrap_or_else(|| quote!(None));
+    let condition = commons::generate_condition(
+        &path,
+        condition,
+        on_property,
+        on_profile,
+        on_bean,
+        on_missing_bean,
+    );
 
     // 生成字段解析语句
     // ref_mut_cx_stmts: 用于获取可变引用的上下文
@@ -45,6 +45,10 @@ fn generate(attr: TokenStream, item: TokenStream, scope: Scope) -> TokenStream {
 /// - `name`:           单例名称，默认使用结构体名称(首字符小写)生成, 例如: `TestData` -> `testData`
 /// - `eager_create`:   是否在初始化时立即创建实例（预加载）
 /// - `condition`:      可选条件闭包或路径，用于控制此单例的 Provider 是否插入 Context 中
+/// - `on_property`:    配置条件 `key` / `key=value` / `key!=value`, 例如: `next.data.redis.enabled!=false`
+/// - `on_profile`:     环境条件, 多个环境用 `,` 分隔, `!` 开头表示未激活, 例如: `dev,local` / `!prod`
+/// - `on_bean`:        容器中存在此类型的 Provider 或实例时才插入
+/// - `on_missing_bean`: 容器中不存在此类型的 Provider 或实例时才插入, 用于被用户提供的单例替换
 /// - `binds`:          绑定路径列表，一般来说这里可以填入结构体的动态实现
 /// - `async`:         可选异步标记路径值，指定是否为异步单例（使用 `#[async]` 属性重命名）
 /// - `auto_register`:  是否自动注册到容器（仅在启用 `auto-register` 特性时有效，默认为 `DEFAULT_AUTO_REGISTER`）
@@ -103,6 +107,10 @@ fn generate(attr: TokenStream, item: TokenStream, scope: Scope) -> TokenStream {
 /// - ` name `: singleton name, generated by default using the structure name (lowercase first character), for example: `TestData` -> `testData`
 /// - 'eager_create': Whether to immediately create an instance (preloaded) during initialization
 /// - ` condition `: Optional conditional closure or path, used to control whether the Provider of this singleton is inserted into the Context
+/// - ` on_property `: configuration condition `key` / `key=value` / `key!=value`, for example: `next.data.redis.enabled!=false`
+/// - ` on_profile `: profile condition, profiles separated by `,`, starting with `!` when not active, for example: `dev,local` / `!prod`
+/// - ` on_bean `: only inserted when a Provider or an instance of the type is in the Context
+/// - ` on_missing_bean `: only inserted when no Provider or instance of the type is in the Context, so it can be replaced by a user provided singleton
 /// - ` bindings `: a list of binding paths, usually where the dynamic implementation of the structure can be filled in
 /// - ` async `: Optional asynchronous tag path value, specifying whether it is an asynchronous singleton (rename using the ` # [async] ` property)
 /// - ` auto_register `: Whether it is automatically registered to the container (only valid when the ` auto register ` feature is enabled, default is ` DEFAULT_SUTD_RIGIST `)
@@ -1,5 +1,5 @@
 use from_attr::{ConvertParsed, FromAttr, PathValue};
-use syn::{parse_quote, spanned::Spanned, Expr, ExprPath};
+use syn::{parse_quote, spanned::Spanned, Expr, ExprPath, Type};
 
 #[derive(FromAttr)]
 #[attribute(idents = [resource])]
@@ -11,6 +11,14 @@ pub(crate) struct StructOrFunctionAttr {
 
     pub(crate) condition: Option<ClosureOrPath>,
 
+    pub(crate) on_property: Option<String>,
+
+    pub(crate) on_profile: Option<String>,
+
+    pub(crate) on_bean: Option<Type>,
+
+    pub(crate) on_missing_bean: Option<Type>,
+
     pub(crate) binds: Vec<ExprPath>,
 
     #[attribute(rename = "async")]